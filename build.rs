@@ -20,6 +20,11 @@ fn main() {
 
 /// Configure linker settings for Windows builds
 fn configure_linker() {
+    // The flags below are MSVC linker syntax; other toolchains keep their defaults.
+    if std::env::var("CARGO_CFG_TARGET_ENV").as_deref() != Ok("msvc") {
+        return;
+    }
+
     // Basic Configuration
     println!("cargo:rustc-link-arg=/MERGE:.rdata=.text");
     println!("cargo:rustc-link-arg=/STACK:0x800000");
@@ -0,0 +1,164 @@
+//! Scripted stand-in for OpenOCD, used by the end-to-end flow tests.
+//!
+//! It takes OpenOCD's `-f <file>` and `-c <commands>` arguments in order. Each
+//! `-f` file is replayed as a transcript instead of being run as a script:
+//!
+//! ```text
+//! # Comment
+//! <delay ms> out <text>                  write a stdout line after the delay
+//! <delay ms> err <text>                  write a stderr line after the delay
+//! <delay ms> repeat <count> out|err <text>
+//!                                        write <count> lines, each after the
+//!                                        delay, with `{n}` replaced by 0, 1, ...
+//! exit <code>                            stop and exit with <code>
+//! ```
+//!
+//! `{serial}` in a line is replaced by the `adapter serial` given with `-c`,
+//! or `*` when none was. `program <file>` fails the run when the file does
+//! not exist, as OpenOCD would. A transcript that ends without `exit` exits 0.
+
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::ExitCode;
+use std::thread;
+use std::time::Duration;
+
+enum Stream {
+    Out,
+    Err,
+}
+
+enum Step {
+    Line {
+        delay: Duration,
+        stream: Stream,
+        text: String,
+        count: Option<u32>,
+    },
+    Exit(u8),
+}
+
+#[derive(Default)]
+struct Session {
+    adapter_serial: Option<String>,
+}
+
+fn main() -> ExitCode {
+    let mut session = Session::default();
+    let mut args = std::env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        let Some(value) = args.next() else {
+            eprintln!("Error: option {arg} requires an argument");
+            return ExitCode::FAILURE;
+        };
+
+        let result = match arg.as_str() {
+            "-f" | "--file" => session.replay(Path::new(&value)),
+            "-c" | "--command" => session.run_commands(&value),
+            _ => Err(format!("unknown option {arg}")),
+        };
+        match result {
+            Ok(None) => {}
+            Ok(Some(code)) => return ExitCode::from(code),
+            Err(error) => {
+                eprintln!("Error: {error}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+impl Session {
+    fn run_commands(&mut self, commands: &str) -> Result<Option<u8>, String> {
+        for command in commands.split(';').map(str::trim) {
+            let mut words = command.split_whitespace();
+            match (words.next(), words.next(), words.next()) {
+                (Some("adapter"), Some("serial"), Some(serial)) => {
+                    self.adapter_serial = Some(serial.to_string());
+                }
+                (Some("program"), Some(file), _) if !Path::new(file).is_file() => {
+                    return Err(format!("couldn't open {file}"));
+                }
+                (Some("exit" | "shutdown"), _, _) => return Ok(Some(0)),
+                _ => {}
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn replay(&self, path: &Path) -> Result<Option<u8>, String> {
+        let transcript =
+            std::fs::read_to_string(path).map_err(|_| format!("Can't find {}", path.display()))?;
+        let serial = self.adapter_serial.as_deref().unwrap_or("*");
+
+        for (index, line) in transcript.lines().enumerate() {
+            let line = line.trim_end();
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+            let step = parse_step(line)
+                .ok_or_else(|| format!("{}:{}: malformed step", path.display(), index + 1))?;
+
+            match step {
+                Step::Exit(code) => return Ok(Some(code)),
+                Step::Line {
+                    delay,
+                    stream,
+                    text,
+                    count,
+                } => {
+                    let text = text.replace("{serial}", serial);
+                    for n in 0..count.unwrap_or(1) {
+                        thread::sleep(delay);
+                        emit(&stream, &text.replace("{n}", &n.to_string()));
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+fn parse_step(line: &str) -> Option<Step> {
+    let (first, rest) = line.split_once(' ').unwrap_or((line, ""));
+    if first == "exit" {
+        return rest.trim().parse().ok().map(Step::Exit);
+    }
+
+    let delay = Duration::from_millis(first.parse().ok()?);
+    let (mut keyword, mut rest) = rest.split_once(' ').unwrap_or((rest, ""));
+    let mut count = None;
+    if keyword == "repeat" {
+        let (times, remainder) = rest.split_once(' ')?;
+        count = Some(times.parse().ok()?);
+        (keyword, rest) = remainder.split_once(' ').unwrap_or((remainder, ""));
+    }
+    let stream = match keyword {
+        "out" => Stream::Out,
+        "err" => Stream::Err,
+        _ => return None,
+    };
+
+    Some(Step::Line {
+        delay,
+        stream,
+        text: rest.to_string(),
+        count,
+    })
+}
+
+// Flushed per line so the reader sees the transcript's timing.
+fn emit(stream: &Stream, text: &str) {
+    let _ = match stream {
+        Stream::Out => {
+            let mut stdout = io::stdout().lock();
+            writeln!(stdout, "{text}").and_then(|()| stdout.flush())
+        }
+        Stream::Err => writeln!(io::stderr(), "{text}"),
+    };
+}
@@ -1,6 +1,13 @@
-use crate::device_programmer::{FlashingManager, FlashingOption};
+use crate::device_programmer::{AdapterScanner, FlashingManager, FlashingOption};
+use crate::dna_allowlist::LicenseStatus;
+use crate::dna_history::DnaHistoryStore;
+use crate::pcileech_test::{TestProfile, TestProfileStore};
 use crate::utils::file_checker::FileChecker;
+use crate::utils::firmware_archive;
 use crate::utils::firmware_discovery::FirmwareManager;
+use crate::utils::firmware_locations::FirmwareLocations;
+use crate::utils::firmware_manifest::FirmwareMetadata;
+use crate::utils::firmware_watch::FirmwareWatcher;
 use crate::utils::logger::Logger;
 use std::path::PathBuf;
 use std::time::Instant;
@@ -42,28 +49,166 @@ impl FileCheckFlow {
 
 pub(super) struct FirmwareScanFlow {
     pub(super) manager: FirmwareManager,
+    pub(super) locations: FirmwareLocations,
+    pub(super) ignore_draft: String,
+    pub(super) locations_status: Option<Result<String, String>>,
+    pub(super) watcher: Option<FirmwareWatcher>,
     pub(super) last_scan: Instant,
     pub(super) scanning: bool,
 }
 
 impl FirmwareScanFlow {
-    pub(super) fn new() -> Self {
+    pub(super) fn new(locations: FirmwareLocations) -> Self {
+        let mut manager = FirmwareManager::new();
+        manager.set_locations(&locations);
         Self {
-            manager: FirmwareManager::new(),
+            manager,
+            locations,
+            ignore_draft: String::new(),
+            locations_status: None,
+            watcher: None,
             last_scan: Instant::now(),
             scanning: false,
         }
     }
 
+    /// Applies a change to the user's search locations. The new locations
+    /// are listed by the next `begin`.
+    pub(super) fn edit_locations<R>(
+        &mut self,
+        edit: impl FnOnce(&mut FirmwareLocations) -> R,
+    ) -> R {
+        let result = edit(&mut self.locations);
+        self.manager.set_locations(&self.locations);
+        result
+    }
+
+    /// Starts watching the search directories and lists them from scratch.
+    /// The watcher is started first so nothing added during the scan is missed.
+    pub(super) fn begin(&mut self, logger: &Logger) {
+        self.manager.remove_temp_firmware();
+        self.watcher = Some(FirmwareWatcher::start(
+            self.manager.search_roots(),
+            logger.clone(),
+        ));
+        self.manager.scan_firmware_files();
+        self.mark_scan_started();
+    }
+
+    /// Rescans the directories the watcher reports as changed. Returns whether
+    /// anything was rescanned.
+    pub(super) fn apply_changes(&mut self, logger: &Logger) -> bool {
+        let watcher = self.watcher.get_or_insert_with(|| {
+            FirmwareWatcher::start(self.manager.search_roots(), logger.clone())
+        });
+        let changed = watcher.changed_dirs();
+        if changed.is_empty() {
+            return false;
+        }
+        self.manager.rescan_dirs(&changed);
+        self.mark_scan_started();
+        true
+    }
+
+    pub(super) fn stop_watching(&mut self) {
+        self.watcher = None;
+    }
+
     pub(super) fn mark_scan_started(&mut self) {
         self.last_scan = Instant::now();
         self.scanning = true;
     }
 }
 
+pub(super) struct AdapterScanFlow {
+    pub(super) scanner: AdapterScanner,
+    pub(super) started: bool,
+}
+
+impl AdapterScanFlow {
+    pub(super) fn new() -> Self {
+        Self {
+            scanner: AdapterScanner::new(),
+            started: false,
+        }
+    }
+
+    pub(super) fn ensure_started(&mut self) {
+        if !self.started {
+            self.started = true;
+            self.scanner.start_scan();
+        }
+    }
+}
+
+pub(super) struct DnaHistoryFlow {
+    pub(super) store: DnaHistoryStore,
+    pub(super) query: String,
+    pub(super) selected: Option<String>,
+    pub(super) note_draft: String,
+    pub(super) status: Option<Result<String, String>>,
+}
+
+impl DnaHistoryFlow {
+    pub(super) fn new(store: DnaHistoryStore) -> Self {
+        Self {
+            store,
+            query: String::new(),
+            selected: None,
+            note_draft: String::new(),
+            status: None,
+        }
+    }
+
+    pub(super) fn select(&mut self, dna_value: String) {
+        self.note_draft = self
+            .store
+            .find(&dna_value)
+            .map(|record| record.note.clone())
+            .unwrap_or_default();
+        self.selected = Some(dna_value);
+        self.status = None;
+    }
+
+    /// Clears transient screen state so the next visit starts fresh.
+    pub(super) fn reset_view(&mut self) {
+        self.query.clear();
+        self.selected = None;
+        self.note_draft.clear();
+        self.status = None;
+    }
+}
+
+pub(super) struct PcileechProfileFlow {
+    pub(super) store: TestProfileStore,
+    pub(super) draft: TestProfile,
+    pub(super) editing: bool,
+    pub(super) status: Option<Result<String, String>>,
+}
+
+impl PcileechProfileFlow {
+    pub(super) fn new(store: TestProfileStore) -> Self {
+        let draft = store.selected().clone();
+        Self {
+            store,
+            draft,
+            editing: false,
+            status: None,
+        }
+    }
+
+    pub(super) fn select(&mut self, name: &str) {
+        self.store.select(name);
+        self.draft = self.store.selected().clone();
+        self.status = None;
+    }
+}
+
 pub(super) struct OperationFlow {
     pub(super) manager: FlashingManager,
     pub(super) selected_firmware: Option<PathBuf>,
+    /// What the manifests declare about the selected firmware.
+    pub(super) firmware_metadata: Option<FirmwareMetadata>,
     pub(super) selected_option: Option<FlashingOption>,
     pub(super) dna_started_at: Option<Instant>,
     pub(super) dna_in_progress: bool,
@@ -72,6 +217,10 @@ pub(super) struct OperationFlow {
     pub(super) retry_cooldown_started_at: Option<Instant>,
     pub(super) cleanup_retry_attempt: u32,
     pub(super) cleanup_retry_ready_at: Option<Instant>,
+    /// Allowlist outcome of the last successful DNA read.
+    pub(super) dna_license: Option<LicenseStatus>,
+    /// Where the DNA on the result screen was exported, or why it failed.
+    pub(super) dna_export_status: Option<Result<String, String>>,
 }
 
 impl OperationFlow {
@@ -79,6 +228,7 @@ impl OperationFlow {
         Self {
             manager: FlashingManager::new_with_logger(logger),
             selected_firmware: None,
+            firmware_metadata: None,
             selected_option: None,
             dna_started_at: None,
             dna_in_progress: false,
@@ -87,14 +237,15 @@ impl OperationFlow {
             retry_cooldown_started_at: None,
             cleanup_retry_attempt: 0,
             cleanup_retry_ready_at: None,
+            dna_license: None,
+            dna_export_status: None,
         }
     }
 
     pub(super) fn replace_manager(&mut self, logger: Logger) -> Result<(), String> {
-        let cleanup_enabled = self.manager.cleanup_enabled();
+        let config = self.manager.config().clone();
         self.manager.retire_for_restart()?;
-        self.manager = FlashingManager::new_with_logger(logger);
-        self.manager.set_cleanup_enabled(cleanup_enabled);
+        self.manager = FlashingManager::with_config(logger, config);
         self.reset_cleanup_retry();
         Ok(())
     }
@@ -103,6 +254,27 @@ impl OperationFlow {
         self.manager.set_cleanup_enabled(enabled);
     }
 
+    /// Selects the firmware to flash along with its manifest metadata, whose
+    /// declared digest the flash is checked against.
+    pub(super) fn select_firmware(
+        &mut self,
+        firmware: Option<PathBuf>,
+        metadata: Option<FirmwareMetadata>,
+    ) {
+        self.manager.set_expected_sha256(
+            metadata
+                .as_ref()
+                .and_then(|metadata| metadata.sha256.clone()),
+        );
+        self.manager.set_manifest_error(
+            metadata
+                .as_ref()
+                .and_then(|metadata| metadata.manifest_error.clone()),
+        );
+        self.selected_firmware = firmware;
+        self.firmware_metadata = metadata;
+    }
+
     pub(super) fn retry_plan(&self) -> RetryPlan {
         let Some(option) = self.selected_option.clone() else {
             return RetryPlan::NothingSelected;
@@ -113,10 +285,12 @@ impl OperationFlow {
         }
 
         match self.selected_firmware.as_ref() {
-            Some(firmware) if firmware.is_file() => RetryPlan::Flash {
-                option,
-                firmware: firmware.clone(),
-            },
+            Some(firmware) if firmware_archive::backing_file(firmware).is_file() => {
+                RetryPlan::Flash {
+                    option,
+                    firmware: firmware.clone(),
+                }
+            }
             _ => RetryPlan::ReselectFirmware,
         }
     }
@@ -133,7 +307,7 @@ impl OperationFlow {
     }
 
     pub(super) fn clear_selection(&mut self) {
-        self.selected_firmware = None;
+        self.select_firmware(None, None);
         self.selected_option = None;
         self.dna_started_at = None;
         self.dna_in_progress = false;
@@ -146,16 +320,10 @@ impl OperationFlow {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::device_programmer::{AdapterSelection, DnaLockPolicy};
+    use crate::utils::temp_dir::TempDir;
     use std::fs;
-    use std::time::{Instant, SystemTime, UNIX_EPOCH};
-
-    fn temporary_firmware_path() -> PathBuf {
-        let nonce = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_nanos();
-        std::env::temp_dir().join(format!("dma-tools-flow-{nonce}.bin"))
-    }
+    use std::time::Instant;
 
     #[test]
     fn file_check_reset_clears_transient_state() {
@@ -206,7 +374,7 @@ mod tests {
         assert_eq!(flow.retry_attempt, 0);
         assert_eq!(flow.cleanup_retry_attempt, 0);
         assert!(flow.cleanup_retry_ready_at.is_none());
-        assert!(!flow.manager.cleanup_enabled());
+        assert!(!flow.manager.config().cleanup_enabled);
     }
 
     #[test]
@@ -217,7 +385,63 @@ mod tests {
         flow.replace_manager(Logger::new("ReplacementManagerTest"))
             .unwrap();
 
-        assert!(flow.manager.cleanup_enabled());
+        assert!(flow.manager.config().cleanup_enabled);
+    }
+
+    #[test]
+    fn manager_replacement_preserves_adapter_selection() {
+        let mut flow = OperationFlow::new(Logger::new("OperationFlowTest"));
+        let selection = AdapterSelection {
+            ch347: None,
+            ftdi: Some("FT4ABC12".to_string()),
+        };
+        flow.manager.set_adapter_selection(selection.clone());
+
+        flow.replace_manager(Logger::new("ReplacementManagerTest"))
+            .unwrap();
+
+        assert_eq!(flow.manager.adapter_selection(), &selection);
+    }
+
+    #[test]
+    fn manager_replacement_preserves_every_setting() {
+        let mut flow = OperationFlow::new(Logger::new("OperationFlowTest"));
+        flow.manager.set_jtag_speed_khz(Some(3000));
+        flow.manager.set_dna_lock_policy(DnaLockPolicy::Block);
+        flow.manager
+            .set_session_dir(Some(PathBuf::from("sessions")));
+
+        flow.replace_manager(Logger::new("ReplacementManagerTest"))
+            .unwrap();
+
+        let config = flow.manager.config();
+        assert_eq!(config.jtag_speed_khz, Some(3000));
+        assert_eq!(config.dna_lock_policy, DnaLockPolicy::Block);
+        assert_eq!(config.session_dir, Some(PathBuf::from("sessions")));
+    }
+
+    #[test]
+    fn manager_replacement_preserves_the_manifest_digest() {
+        let mut flow = OperationFlow::new(Logger::new("OperationFlowTest"));
+        let digest = "ab".repeat(32);
+        flow.select_firmware(
+            Some(PathBuf::from("firmware.bin")),
+            Some(FirmwareMetadata {
+                sha256: Some(digest.clone()),
+                ..FirmwareMetadata::default()
+            }),
+        );
+
+        flow.replace_manager(Logger::new("ReplacementManagerTest"))
+            .unwrap();
+        assert_eq!(
+            flow.manager.config().expected_sha256.as_deref(),
+            Some(digest.as_str())
+        );
+
+        flow.clear_selection();
+        assert!(flow.firmware_metadata.is_none());
+        assert!(flow.manager.config().expected_sha256.as_deref().is_none());
     }
 
     #[test]
@@ -234,7 +458,7 @@ mod tests {
 
         assert!(error.contains("synthetic unconfirmed cleanup"));
         assert!(!flow.manager.snapshot().safe_to_restart);
-        assert!(flow.manager.cleanup_enabled());
+        assert!(flow.manager.config().cleanup_enabled);
         assert_eq!(flow.selected_option, Some(FlashingOption::DnaCH347));
     }
 
@@ -242,14 +466,15 @@ mod tests {
     fn retry_plan_requires_reselection_after_cleanup_deleted_firmware() {
         let mut flow = OperationFlow::new(Logger::new("OperationFlowTest"));
         flow.selected_option = Some(FlashingOption::CH347_35T);
-        flow.selected_firmware = Some(temporary_firmware_path());
+        flow.selected_firmware = Some(TempDir::new("flow").join("firmware.bin"));
 
         assert_eq!(flow.retry_plan(), RetryPlan::ReselectFirmware);
     }
 
     #[test]
     fn retry_plan_preserves_available_flash_and_dna_operations() {
-        let path = temporary_firmware_path();
+        let dir = TempDir::new("flow");
+        let path = dir.join("firmware.bin");
         fs::write(&path, b"firmware").unwrap();
 
         let mut flow = OperationFlow::new(Logger::new("OperationFlowTest"));
@@ -268,7 +493,5 @@ mod tests {
             flow.retry_plan(),
             RetryPlan::ReadDna(FlashingOption::DnaCH347)
         );
-
-        fs::remove_file(path).unwrap();
     }
 }
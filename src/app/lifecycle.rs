@@ -1,17 +1,22 @@
 use super::flows::RetryPlan;
 use super::{AppState, FirmwareToolApp};
-use crate::device_programmer::{CompletionStatus, FinalizationOutcome, OperationSnapshot};
+use crate::device_programmer::{
+    CompletionStatus, DeviceDna, FinalizationOutcome, OperationSnapshot, SignaturePolicy,
+};
+use crate::dna_history::{DnaRead, ExportFormat};
 use crate::ui::status::ResultAction;
 use crate::utils::file_checker::{CheckStatus, SUCCESS_TRANSITION_DELAY};
+use crate::utils::localization::{TextKey, format_translation, translate};
+use crate::utils::settings::Settings;
 use eframe::egui;
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 const ANIMATION_FRAME_RATE_MS: u64 = 16;
 const INITIAL_CHECK_DELAY_MS: u128 = 100;
 const FIRST_FIRMWARE_SCAN_INTERVAL_MS: u64 = 100;
-const SUBSEQUENT_FIRMWARE_SCAN_INTERVAL_MS: u64 = 3000;
 const FIRMWARE_SCAN_INDICATOR_DURATION_MS: u128 = 500;
+const FIRMWARE_HASH_POLL_INTERVAL: Duration = Duration::from_millis(100);
 const DNA_MIN_DISPLAY_TIME_MS: u64 = 100;
 
 const MAX_AUTO_RETRIES: u32 = 10;
@@ -79,7 +84,7 @@ impl FirmwareToolApp {
         if !self.file_check.started
             && self.file_check.app_started_at.elapsed().as_millis() > INITIAL_CHECK_DELAY_MS
         {
-            self.file_check.checker.start_check();
+            self.file_check.checker.start_check(self.logger.clone());
             self.file_check.started = true;
         }
     }
@@ -87,6 +92,8 @@ impl FirmwareToolApp {
     fn maybe_update_firmware_scan(&mut self, ctx: &egui::Context) {
         if self.state == AppState::FirmwareSelection {
             self.handle_firmware_scanning(ctx);
+        } else {
+            self.firmware_scan.stop_watching();
         }
     }
 
@@ -111,11 +118,13 @@ impl FirmwareToolApp {
         }
 
         if operation_completed && min_display_time_elapsed {
+            self.operation.manager.save_session(&snapshot);
             if self.maybe_auto_retry_flash(&snapshot) {
                 return;
             }
 
             self.operation.manager.stop_monitor_thread();
+            self.record_dna_history(&snapshot);
             self.transition_to_result();
         } else if operation_completed && !self.operation.waiting_message_logged {
             self.logger
@@ -239,6 +248,123 @@ impl FirmwareToolApp {
         false
     }
 
+    fn record_dna_history(&mut self, snapshot: &OperationSnapshot) {
+        self.operation.dna_license = None;
+        self.operation.dna_export_status = None;
+        let (CompletionStatus::DnaReadCompleted(info), Some(option)) =
+            (&snapshot.status, &snapshot.option)
+        else {
+            return;
+        };
+
+        // Without any allowlist every board would read as unknown, which is
+        // noise rather than a finding.
+        let license = (!self.dna_allowlist.is_empty()).then(|| self.dna_allowlist.check(&info.dna));
+        if let Some(license) = &license {
+            self.logger.info(format!("DNA {} is {license}", info.dna));
+        }
+        self.operation.dna_license = license.clone();
+
+        let read_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        self.dna_history.store.record_read(
+            DnaRead::new(
+                info.clone(),
+                option,
+                snapshot.adapter_serial.clone(),
+                license,
+            ),
+            read_at,
+        );
+        self.sync_known_dnas();
+    }
+
+    /// Hands every DNA from the history and allowlists to the firmware lock
+    /// check, so images bound to another known board are recognized.
+    pub(super) fn sync_known_dnas(&mut self) {
+        let mut dnas: Vec<DeviceDna> = self
+            .dna_history
+            .store
+            .dnas()
+            .chain(self.dna_allowlist.dnas())
+            .collect();
+        dnas.sort_by_key(DeviceDna::value);
+        dnas.dedup();
+        self.operation.manager.set_known_dnas(dnas);
+    }
+
+    /// Restores the preferences saved by the previous session.
+    pub(super) fn apply_settings(&mut self) {
+        let settings = self.settings.settings().clone();
+        self.language = settings.language;
+        self.log_expanded = settings.log_expanded;
+        self.logger.set_debug_mode(settings.debug_mode);
+        self.firmware_scan
+            .manager
+            .set_cleanup_enabled(settings.cleanup_enabled);
+        self.firmware_scan
+            .manager
+            .set_preferred(settings.last_firmware);
+        self.operation
+            .manager
+            .set_jtag_speed_khz(settings.jtag_speed_khz);
+        self.operation
+            .manager
+            .set_watchdog_config(settings.watchdog);
+        let signature_policy = settings.signature_policy.unwrap_or_else(|| {
+            SignaturePolicy::default_for(&self.operation.manager.config().trusted_keys)
+        });
+        self.operation
+            .manager
+            .set_signature_policy(signature_policy);
+        if let Some(name) = &settings.pcileech_profile
+            && !self.pcileech_profiles.store.select(name)
+        {
+            self.logger.warning(format!(
+                "Saved PCILeech test profile {name} no longer exists"
+            ));
+        }
+    }
+
+    /// Saves the preferences whenever one of them changed. The last profile
+    /// and firmware are kept once the selection is cleared.
+    pub(super) fn save_settings(&mut self) {
+        let previous = self.settings.settings();
+        let settings = Settings {
+            language: self.language,
+            last_option: self
+                .operation
+                .selected_option
+                .clone()
+                .or_else(|| previous.last_option.clone()),
+            last_firmware: self
+                .operation
+                .selected_firmware
+                .clone()
+                .or_else(|| previous.last_firmware.clone()),
+            cleanup_enabled: self.firmware_scan.manager.get_cleanup_enabled(),
+            debug_mode: self.logger.debug_mode(),
+            log_expanded: self.log_expanded,
+            jtag_speed_khz: self.operation.manager.jtag_speed_khz(),
+            pcileech_profile: Some(self.pcileech_profiles.store.selected().name.clone()),
+            signature_policy: self.chosen_signature_policy(previous.signature_policy),
+            watchdog: self.operation.manager.config().watchdog,
+        };
+        self.settings.update(settings);
+    }
+
+    /// The signature policy to save: kept unset while it is the default for
+    /// the installed keys, and left alone while the installation pins it.
+    fn chosen_signature_policy(&self, saved: Option<SignaturePolicy>) -> Option<SignaturePolicy> {
+        let config = self.operation.manager.config();
+        if config.signature_policy_pinned {
+            return saved;
+        }
+        let default = SignaturePolicy::default_for(&config.trusted_keys);
+        (saved.is_some() || config.signature_policy != default).then_some(config.signature_policy)
+    }
+
     fn transition_to_result(&mut self) {
         self.logger
             .debug("State changing to Result after all conditions met");
@@ -275,20 +401,23 @@ impl FirmwareToolApp {
     }
 
     fn handle_firmware_scanning(&mut self, ctx: &egui::Context) {
-        let scan_interval = if self.firmware_scan.manager.get_scan_count() <= 1 {
-            FIRST_FIRMWARE_SCAN_INTERVAL_MS
+        // The first listing is repeated once shortly after entering the screen;
+        // after that only directories the watcher reports are rescanned.
+        let scan_count = self.firmware_scan.manager.get_scan_count();
+        let scanned = if scan_count <= 1 {
+            let should_scan = !self.firmware_scan.scanning
+                && (scan_count == 0
+                    || self.firmware_scan.last_scan.elapsed().as_millis()
+                        >= FIRST_FIRMWARE_SCAN_INTERVAL_MS as u128);
+            if should_scan {
+                self.firmware_scan.begin(&self.logger);
+            }
+            should_scan
         } else {
-            SUBSEQUENT_FIRMWARE_SCAN_INTERVAL_MS
+            self.firmware_scan.apply_changes(&self.logger)
         };
 
-        let should_scan = !self.firmware_scan.scanning
-            && (self.firmware_scan.manager.get_scan_count() == 0
-                || self.firmware_scan.last_scan.elapsed().as_millis() >= scan_interval as u128);
-
-        if should_scan {
-            self.firmware_scan.manager.scan_firmware_files();
-            self.firmware_scan.mark_scan_started();
-
+        if scanned {
             let ctx = ctx.clone();
             thread::spawn(move || {
                 thread::sleep(Duration::from_millis(
@@ -298,6 +427,13 @@ impl FirmwareToolApp {
             });
         }
 
+        // Hashing runs on a worker thread; identical copies are grouped as
+        // the hashes arrive.
+        self.firmware_scan.manager.receive_hashes();
+        if self.firmware_scan.manager.is_hashing() {
+            ctx.request_repaint_after(FIRMWARE_HASH_POLL_INTERVAL);
+        }
+
         if self.firmware_scan.scanning
             && self.firmware_scan.last_scan.elapsed().as_millis()
                 > FIRMWARE_SCAN_INDICATOR_DURATION_MS
@@ -356,10 +492,9 @@ impl FirmwareToolApp {
                         self.logger.info(
                             "The previous firmware was cleaned up; select a firmware file before retrying.",
                         );
-                        self.operation.selected_firmware = None;
+                        self.operation.select_firmware(None, None);
                         self.state = AppState::FirmwareSelection;
-                        self.firmware_scan.manager.scan_firmware_files();
-                        self.firmware_scan.mark_scan_started();
+                        self.firmware_scan.begin(&self.logger);
                     }
                     RetryPlan::NothingSelected => {
                         if !self.retire_result_operation("operation selection") {
@@ -373,9 +508,50 @@ impl FirmwareToolApp {
                     }
                 }
             }
+            ResultAction::ExportDna(format) => self.export_dna_result(format),
         }
     }
 
+    fn export_dna_result(&mut self, format: ExportFormat) {
+        let snapshot = self.operation.manager.snapshot();
+        let CompletionStatus::DnaReadCompleted(info) = &snapshot.status else {
+            return;
+        };
+
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name(format.dna_file_name(&info.dna))
+            .add_filter(format.extension().to_uppercase(), &[format.extension()])
+            .save_file()
+        else {
+            return;
+        };
+
+        self.operation.dna_export_status = Some(
+            match format.write_dna(
+                &info.dna,
+                &info.device_type,
+                self.operation.dna_license.as_ref(),
+                &path,
+            ) {
+                Ok(()) => {
+                    self.logger
+                        .success(format!("DNA exported to {}", path.display()));
+                    Ok(format_translation(
+                        translate(TextKey::DnaHistoryExported, &self.language),
+                        &[&path.display().to_string()],
+                    ))
+                }
+                Err(error) => {
+                    self.logger.error(format!("DNA export failed: {error}"));
+                    Err(format_translation(
+                        translate(TextKey::DnaHistoryExportFailed, &self.language),
+                        &[&error],
+                    ))
+                }
+            },
+        );
+    }
+
     fn retire_result_operation(&mut self, destination: &str) -> bool {
         match self.operation.manager.retire_for_restart() {
             Ok(()) => true,
@@ -392,7 +568,10 @@ impl FirmwareToolApp {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::device_programmer::FlashingOption;
+    use crate::device_programmer::{DnaInfo, DnaLockPolicy, FlashingOption};
+    use crate::dna_allowlist::LicenseStatus;
+    use crate::utils::temp_dir::TempDir;
+    use std::fs;
 
     fn test_app() -> FirmwareToolApp {
         let logger = crate::utils::logger::Logger::new("LifecycleRestartSafetyTest");
@@ -400,8 +579,19 @@ mod tests {
             window_manager: crate::utils::window::WindowManager::new(),
             state: AppState::Result,
             file_check: crate::app::flows::FileCheckFlow::new(),
-            firmware_scan: crate::app::flows::FirmwareScanFlow::new(),
+            firmware_scan: crate::app::flows::FirmwareScanFlow::new(
+                crate::utils::firmware_locations::FirmwareLocations::in_memory(logger.clone()),
+            ),
+            adapter_scan: crate::app::flows::AdapterScanFlow::new(),
             operation: crate::app::flows::OperationFlow::new(logger.clone()),
+            dna_allowlist: crate::dna_allowlist::DnaAllowlist::default(),
+            dna_history: crate::app::flows::DnaHistoryFlow::new(
+                crate::dna_history::DnaHistoryStore::in_memory(logger.clone()),
+            ),
+            pcileech_profiles: crate::app::flows::PcileechProfileFlow::new(
+                crate::pcileech_test::TestProfileStore::in_memory(logger.clone()),
+            ),
+            settings: crate::utils::settings::SettingsStore::in_memory(logger.clone()),
             logger,
             previous_log_state: false,
             log_expanded: false,
@@ -433,7 +623,7 @@ mod tests {
         )));
         assert!(is_terminal_status(&CompletionStatus::Completed));
         assert!(is_terminal_status(&CompletionStatus::Failed(
-            "failed".to_string()
+            "failed".into()
         )));
     }
 
@@ -473,4 +663,170 @@ mod tests {
             Some(FlashingOption::CH347_35T)
         );
     }
+
+    #[test]
+    fn saved_settings_keep_the_last_selection_once_it_is_cleared() {
+        let mut app = test_app();
+        app.operation.selected_option = Some(FlashingOption::RS232_75T);
+        app.operation.selected_firmware = Some(std::path::PathBuf::from("card.bin"));
+        app.operation.manager.set_jtag_speed_khz(Some(4000));
+        app.save_settings();
+
+        app.operation.selected_option = None;
+        app.operation.selected_firmware = None;
+        app.save_settings();
+
+        let settings = app.settings.settings();
+        assert_eq!(settings.last_option, Some(FlashingOption::RS232_75T));
+        assert_eq!(
+            settings.last_firmware,
+            Some(std::path::PathBuf::from("card.bin"))
+        );
+        assert_eq!(settings.jtag_speed_khz, Some(4000));
+    }
+
+    #[test]
+    fn chosen_signature_policy_is_saved_unless_the_installation_pins_it() {
+        let mut app = test_app();
+        app.save_settings();
+        assert_eq!(app.settings.settings().signature_policy, None);
+
+        app.operation
+            .manager
+            .set_signature_policy(SignaturePolicy::Block);
+        app.save_settings();
+        assert_eq!(
+            app.settings.settings().signature_policy,
+            Some(SignaturePolicy::Block)
+        );
+
+        app.operation
+            .manager
+            .pin_signature_policy(SignaturePolicy::Warn);
+        app.operation
+            .manager
+            .set_signature_policy(SignaturePolicy::Off);
+        app.save_settings();
+        assert_eq!(
+            app.operation.manager.signature_policy(),
+            SignaturePolicy::Warn
+        );
+        assert_eq!(
+            app.settings.settings().signature_policy,
+            Some(SignaturePolicy::Block)
+        );
+    }
+
+    #[test]
+    fn only_successful_dna_reads_are_recorded_in_history() {
+        let mut app = test_app();
+        let mut snapshot = app.operation.manager.snapshot();
+        snapshot.option = Some(FlashingOption::DnaRS232_75T);
+        snapshot.adapter_serial = Some("FT4ABC12".to_string());
+
+        snapshot.status = CompletionStatus::Failed("failed".into());
+        app.record_dna_history(&snapshot);
+        assert!(app.dna_history.store.records().is_empty());
+
+        snapshot.status = CompletionStatus::DnaReadCompleted(DnaInfo {
+            dna: DeviceDna::from_value(0x0040A1B2C3D4E5F6).unwrap(),
+            device_type: "FTDI".to_string(),
+        });
+        app.record_dna_history(&snapshot);
+        app.record_dna_history(&snapshot);
+
+        let records = app.dna_history.store.records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].read_count, 2);
+        assert_eq!(records[0].density.as_deref(), Some("75T"));
+        assert_eq!(records[0].adapter_serial.as_deref(), Some("FT4ABC12"));
+        assert_eq!(records[0].license, None);
+        assert_eq!(app.operation.dna_license, None);
+    }
+
+    #[test]
+    fn dna_reads_are_checked_against_the_allowlist() {
+        let mut app = test_app();
+        app.dna_allowlist
+            .add_source("customers.csv", "0x0040A1B2C3D4E5F6,Acme Labs,revoked\n");
+        let mut snapshot = app.operation.manager.snapshot();
+        snapshot.option = Some(FlashingOption::DnaCH347);
+        snapshot.status = CompletionStatus::DnaReadCompleted(DnaInfo {
+            dna: DeviceDna::from_value(0x0040A1B2C3D4E5F6).unwrap(),
+            device_type: "CH347".to_string(),
+        });
+
+        app.record_dna_history(&snapshot);
+
+        let revoked = Some(LicenseStatus::Revoked("Acme Labs".to_string()));
+        assert_eq!(app.operation.dna_license, revoked);
+        assert_eq!(app.dna_history.store.records()[0].license, revoked);
+
+        snapshot.status = CompletionStatus::Failed("failed".into());
+        app.record_dna_history(&snapshot);
+        assert_eq!(app.operation.dna_license, None);
+    }
+
+    #[test]
+    fn recorded_and_allowlisted_dnas_feed_the_firmware_lock_check() {
+        let mut app = test_app();
+        app.dna_allowlist
+            .add_source("customers.csv", "0x11223344556677,Acme Labs\n");
+        let read = DeviceDna::from_value(0x0040A1B2C3D4E5F6).unwrap();
+        let mut snapshot = app.operation.manager.snapshot();
+        snapshot.option = Some(FlashingOption::DnaCH347);
+        snapshot.status = CompletionStatus::DnaReadCompleted(DnaInfo {
+            dna: read,
+            device_type: "CH347".to_string(),
+        });
+
+        app.record_dna_history(&snapshot);
+        app.operation
+            .manager
+            .set_dna_lock_policy(DnaLockPolicy::Block);
+        app.operation.replace_manager(app.logger.clone()).unwrap();
+
+        let allowlisted = DeviceDna::from_value(0x11223344556677).unwrap();
+        assert_eq!(
+            app.operation.manager.config().known_dnas,
+            [allowlisted, read]
+        );
+        assert_eq!(
+            app.operation.manager.dna_lock_policy(),
+            DnaLockPolicy::Block
+        );
+    }
+
+    #[test]
+    fn unstable_flash_is_retried_automatically_until_it_succeeds() {
+        use crate::device_programmer::{FlashAssessment, fake_openocd};
+
+        let _workspace = fake_openocd::lock_workspace();
+        let dir = TempDir::new("auto-retry");
+        let firmware = dir.join("firmware.bin");
+        fs::write(&firmware, b"firmware").unwrap();
+        let mut app = test_app();
+        app.operation
+            .manager
+            .set_command_args(fake_openocd::commands(&["flash_unstable", "flash_good"]));
+        app.operation.selected_option = Some(FlashingOption::CH347_35T);
+        app.operation.selected_firmware = Some(firmware.clone());
+        app.state = AppState::Flashing;
+        app.operation
+            .manager
+            .execute_flash(&firmware, &FlashingOption::CH347_35T, &app.language);
+
+        let deadline = Instant::now() + Duration::from_secs(20);
+        while app.state == AppState::Flashing {
+            assert!(Instant::now() < deadline, "flash did not reach a result");
+            app.maybe_transition_completed_operation();
+            thread::sleep(Duration::from_millis(20));
+        }
+
+        let snapshot = app.operation.manager.snapshot();
+        assert!(app.state == AppState::Result);
+        assert_eq!(app.operation.retry_attempt, 1);
+        assert_eq!(snapshot.status, CompletionStatus::Completed);
+        assert_eq!(snapshot.assessment, FlashAssessment::Success);
+    }
 }
@@ -3,9 +3,11 @@ use crate::APP_TITLE;
 #[cfg(feature = "branding")]
 use crate::branding::BrandingManager;
 
-use crate::pcileech_test::PcileechTestController;
+use crate::pcileech_test::{OutputRules, PcileechTestController, TestProfileStore};
+use crate::utils::firmware_locations::FirmwareLocations;
 use crate::utils::localization::Language;
 use crate::utils::logger::Logger;
+use crate::utils::settings::SettingsStore;
 use crate::utils::window::WindowManager;
 use eframe::egui;
 use std::time::Instant;
@@ -17,14 +19,23 @@ mod screens;
 mod state;
 
 use self::state::AppState;
-use flows::{FileCheckFlow, FirmwareScanFlow, OperationFlow};
+use crate::device_programmer::{SignaturePolicy, TrustedKeys, default_session_dir};
+use crate::dna_allowlist::DnaAllowlist;
+use crate::dna_history::DnaHistoryStore;
+use flows::{
+    AdapterScanFlow, DnaHistoryFlow, FileCheckFlow, FirmwareScanFlow, OperationFlow,
+    PcileechProfileFlow,
+};
 
 pub struct FirmwareToolApp {
     window_manager: WindowManager,
     state: AppState,
     file_check: FileCheckFlow,
     firmware_scan: FirmwareScanFlow,
+    adapter_scan: AdapterScanFlow,
     operation: OperationFlow,
+    dna_allowlist: DnaAllowlist,
+    dna_history: DnaHistoryFlow,
     logger: Logger,
     previous_log_state: bool,
     log_expanded: bool,
@@ -34,6 +45,8 @@ pub struct FirmwareToolApp {
     icon_manager: crate::assets::IconManager,
     language: Language,
     pcileech_test: PcileechTestController,
+    pcileech_profiles: PcileechProfileFlow,
+    settings: SettingsStore,
 }
 
 impl FirmwareToolApp {
@@ -47,12 +60,19 @@ impl FirmwareToolApp {
         let mut icon_manager = crate::assets::IconManager::new();
         icon_manager.ensure_loaded(&cc.egui_ctx);
 
-        Self {
+        let pcileech_profiles =
+            PcileechProfileFlow::new(TestProfileStore::open_default(logger.clone()));
+        let settings = SettingsStore::open_default(logger.clone());
+
+        let mut app = Self {
             window_manager,
             state: AppState::FileCheck,
             file_check: FileCheckFlow::new(),
-            firmware_scan: FirmwareScanFlow::new(),
+            firmware_scan: FirmwareScanFlow::new(FirmwareLocations::open_default(logger.clone())),
+            adapter_scan: AdapterScanFlow::new(),
             operation: OperationFlow::new(logger.clone()),
+            dna_allowlist: DnaAllowlist::load_default(&logger),
+            dna_history: DnaHistoryFlow::new(DnaHistoryStore::open_default(logger.clone())),
             logger,
             previous_log_state: false,
             log_expanded: false,
@@ -62,7 +82,24 @@ impl FirmwareToolApp {
             icon_manager,
             language: Language::English,
             pcileech_test: PcileechTestController::new(),
+            pcileech_profiles,
+            settings,
+        };
+        let trusted_keys = TrustedKeys::load_default(&app.logger);
+        app.operation.manager.set_trusted_keys(trusted_keys);
+        app.apply_settings();
+        if let Some(policy) = SignaturePolicy::load_pinned(&app.logger) {
+            app.operation.manager.pin_signature_policy(policy);
         }
+        app.sync_known_dnas();
+        app.pcileech_test
+            .set_profile(app.pcileech_profiles.store.selected().clone());
+        app.pcileech_test
+            .set_rules(OutputRules::load_default(&app.logger));
+        app.operation
+            .manager
+            .set_session_dir(Some(default_session_dir()));
+        app
     }
 }
 
@@ -76,6 +113,7 @@ impl eframe::App for FirmwareToolApp {
         self.setup_ui_and_animation(ctx);
         self.update_window_size(ctx);
         self.handle_state_specific_logic(ctx);
+        self.save_settings();
     }
 
     fn ui(&mut self, ui: &mut egui::Ui, _frame: &mut eframe::Frame) {
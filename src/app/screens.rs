@@ -1,14 +1,19 @@
 use super::{AppState, FirmwareToolApp};
-use crate::device_programmer::FlashingOption;
+use crate::device_programmer::{AdapterScanStatus, FlashingOption};
+use crate::dna_history::ExportFormat;
 use crate::pcileech_test::PcileechTestSnapshot;
 use crate::ui;
-use crate::ui::file_select::FileCheckRenderContext;
-use crate::ui::pcileech_test::PcileechAction;
+use crate::ui::dna_history::{DnaHistoryAction, DnaHistoryView};
+use crate::ui::file_select::{
+    FileCheckRenderContext, FirmwareSource, LocationAction, LocationsView,
+};
+use crate::ui::pcileech_test::{PcileechAction, PcileechProfileView};
 use crate::ui::status::ResultAction;
 use crate::utils::file_checker::CheckStatus;
+use crate::utils::localization::{TextKey, format_translation, translate};
 use eframe::egui;
-use std::path::PathBuf;
-use std::time::Instant;
+use std::path::{Path, PathBuf};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 const TOP_PADDING: f32 = 8.0;
 const BOTTOM_PADDING: f32 = 18.0;
@@ -51,11 +56,13 @@ impl FirmwareToolApp {
             AppState::FileCheck => self.render_file_check_state(ui),
             AppState::OperationSelection => self.render_operation_selection(ui),
             AppState::FirmwareSelection => self.render_firmware_selection(ui),
+            AppState::FirmwareLocations => self.render_firmware_locations(ui),
             AppState::FlashingOptions => self.render_flashing_options(ui),
             AppState::Flashing => self.render_flashing(ui),
             AppState::Result => self.render_result(ui),
             AppState::Drivers => self.render_drivers(ui),
             AppState::PcileechTest => self.render_pcileech_test(ui),
+            AppState::DnaHistory => self.render_dna_history(ui),
         }
     }
 
@@ -93,13 +100,16 @@ impl FirmwareToolApp {
             ui::operation::OperationType::FlashFirmware => {
                 self.state = AppState::FirmwareSelection;
 
-                self.firmware_scan.manager.scan_firmware_files();
-                self.firmware_scan.mark_scan_started();
+                self.firmware_scan.begin(&self.logger);
             }
             ui::operation::OperationType::ReadDNA => {
                 self.state = AppState::FlashingOptions;
                 self.operation.selected_option = Some(FlashingOption::DnaCH347);
             }
+            ui::operation::OperationType::DnaHistory => {
+                self.dna_history.reset_view();
+                self.state = AppState::DnaHistory;
+            }
             ui::operation::OperationType::Drivers => {
                 self.state = AppState::Drivers;
             }
@@ -127,11 +137,23 @@ impl FirmwareToolApp {
         self.pcileech_test.ensure_started();
         let test_snapshot: PcileechTestSnapshot = self.pcileech_test.snapshot();
 
-        match crate::ui::pcileech_test::render_pcileech_test(
+        let profiles = &mut self.pcileech_profiles;
+        let selected = profiles.store.selected().name.clone();
+        let mut view = PcileechProfileView {
+            profiles: profiles.store.profiles(),
+            selected: &selected,
+            draft: &mut profiles.draft,
+            editing: &mut profiles.editing,
+            status: profiles.status.as_ref(),
+        };
+        let action = crate::ui::pcileech_test::render_pcileech_test(
             ui,
-            &test_snapshot.state,
+            &test_snapshot,
+            &mut view,
             &self.language,
-        ) {
+        );
+
+        match action {
             Some(PcileechAction::Back) => {
                 self.pcileech_test.request_back();
                 if self.pcileech_test.acknowledge_back_if_ready() {
@@ -139,13 +161,143 @@ impl FirmwareToolApp {
                 }
             }
             Some(PcileechAction::Retry) => self.pcileech_test.retry(),
+            Some(PcileechAction::Stop) => self.pcileech_test.stop(),
+            Some(PcileechAction::SelectProfile(name)) => {
+                self.pcileech_profiles.select(&name);
+                self.restart_pcileech_test();
+            }
+            Some(PcileechAction::SaveProfile) => self.save_pcileech_profile(),
+            Some(PcileechAction::DeleteProfile) => self.delete_pcileech_profile(),
+            // A soak can run for a long time, so a new mode waits for the
+            // retry button instead of starting a run straight away.
+            Some(PcileechAction::SetMode(mode)) => self.pcileech_test.set_mode(mode),
+            None => {}
+        }
+    }
+
+    fn save_pcileech_profile(&mut self) {
+        let profiles = &mut self.pcileech_profiles;
+        let name = profiles.draft.name.trim().to_string();
+        profiles.status = Some(match profiles.store.save_profile(&profiles.draft) {
+            Ok(()) => {
+                self.logger
+                    .info(format!("Saved PCILeech test profile {name}"));
+                profiles.draft = profiles.store.selected().clone();
+                profiles.editing = false;
+                Ok(format_translation(
+                    translate(TextKey::PcileechProfileSaved, &self.language),
+                    &[&name],
+                ))
+            }
+            Err(error) => Err(translate(error.text_key(), &self.language).to_string()),
+        });
+        if !self.pcileech_profiles.editing {
+            self.restart_pcileech_test();
+        }
+    }
+
+    fn delete_pcileech_profile(&mut self) {
+        let profiles = &mut self.pcileech_profiles;
+        let name = profiles.store.selected().name.clone();
+        if !profiles.store.remove(&name) {
+            return;
+        }
+
+        self.logger
+            .info(format!("Deleted PCILeech test profile {name}"));
+        profiles.draft = profiles.store.selected().clone();
+        profiles.status = Some(Ok(format_translation(
+            translate(TextKey::PcileechProfileDeleted, &self.language),
+            &[&name],
+        )));
+        self.restart_pcileech_test();
+    }
+
+    /// Reruns the test against the newly selected profile.
+    fn restart_pcileech_test(&mut self) {
+        self.pcileech_test
+            .set_profile(self.pcileech_profiles.store.selected().clone());
+        self.pcileech_test.retry();
+    }
+
+    fn render_dna_history(&mut self, ui: &mut egui::Ui) {
+        let history = &mut self.dna_history;
+        let records = history.store.search(&history.query);
+        let mut view = DnaHistoryView {
+            records: &records,
+            total_records: history.store.records().len(),
+            query: &mut history.query,
+            selected: history.selected.as_deref(),
+            note_draft: &mut history.note_draft,
+            status: history.status.as_ref(),
+        };
+        let action = ui::dna_history::render_dna_history(ui, &mut view, &self.language);
+
+        match action {
+            Some(DnaHistoryAction::Back) => self.state = AppState::OperationSelection,
+            Some(DnaHistoryAction::Select(dna_value)) => self.dna_history.select(dna_value),
+            Some(DnaHistoryAction::SaveNote) => self.save_dna_history_note(),
+            Some(DnaHistoryAction::Export(format)) => self.export_dna_history(format),
             None => {}
         }
     }
 
+    fn save_dna_history_note(&mut self) {
+        let history = &mut self.dna_history;
+        let Some(selected) = history.selected.as_deref() else {
+            return;
+        };
+        if history.store.set_note(selected, &history.note_draft) {
+            history.status = Some(Ok(
+                translate(TextKey::DnaHistoryNoteSaved, &self.language).to_string()
+            ));
+        }
+    }
+
+    fn export_dna_history(&mut self, format: ExportFormat) {
+        let history = &mut self.dna_history;
+        let records = history.store.search(&history.query);
+        let exported_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+
+        history.status = Some(match format.write(&records, Path::new("."), exported_at) {
+            Ok(path) => {
+                let path = path.canonicalize().unwrap_or(path);
+                self.logger
+                    .success(format!("DNA history exported to {}", path.display()));
+                Ok(format_translation(
+                    translate(TextKey::DnaHistoryExported, &self.language),
+                    &[&path.display().to_string()],
+                ))
+            }
+            Err(error) => {
+                self.logger
+                    .error(format!("DNA history export failed: {error}"));
+                Err(format_translation(
+                    translate(TextKey::DnaHistoryExportFailed, &self.language),
+                    &[&error],
+                ))
+            }
+        });
+    }
+
     fn render_firmware_selection(&mut self, ui: &mut egui::Ui) {
         let mut selected_file = None;
         let mut go_back = false;
+        let mut source = None;
+
+        let dropped: Vec<PathBuf> = ui.ctx().input(|input| {
+            input
+                .raw
+                .dropped_files
+                .iter()
+                .map(|file| file.path().to_path_buf())
+                .collect()
+        });
+        for path in dropped {
+            self.add_dropped_firmware(path);
+        }
 
         let mut select_callback = |selected: Option<PathBuf>| {
             selected_file = Some(selected);
@@ -158,25 +310,151 @@ impl FirmwareToolApp {
             go_back = true;
         };
 
+        let mut source_callback = |picked: FirmwareSource| {
+            source = Some(picked);
+        };
+
         ui::file_select::render_firmware_selection(
             ui,
             &mut self.firmware_scan.manager,
             &mut select_callback,
             &mut back_callback,
+            &mut source_callback,
             is_scanning,
             &self.language,
         );
 
         if go_back {
             self.state = AppState::OperationSelection;
+        } else if let Some(source) = source {
+            self.open_firmware_source(source);
         } else if let Some(selected) = selected_file {
             let cleanup_enabled = self.firmware_scan.manager.get_cleanup_enabled();
-            self.operation.selected_firmware = selected;
+            let metadata = selected
+                .as_deref()
+                .and_then(|path| self.firmware_scan.manager.metadata_for(path))
+                .cloned();
+            self.operation.select_firmware(selected, metadata);
             self.state = AppState::FlashingOptions;
             self.operation.set_cleanup_enabled(cleanup_enabled);
         }
     }
 
+    fn open_firmware_source(&mut self, source: FirmwareSource) {
+        match source {
+            FirmwareSource::Browse => {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("Firmware", &["bin", "zip", "gz"])
+                    .pick_file()
+                {
+                    self.add_picked_firmware(path);
+                }
+            }
+            FirmwareSource::EditLocations => {
+                let scan = &mut self.firmware_scan;
+                scan.ignore_draft = scan.locations.ignore().join("\n");
+                scan.locations_status = None;
+                self.state = AppState::FirmwareLocations;
+            }
+        }
+    }
+
+    /// A dropped folder becomes a search location; a dropped file is listed
+    /// like one picked in the file dialog.
+    fn add_dropped_firmware(&mut self, path: PathBuf) {
+        if path.is_dir() {
+            if self
+                .firmware_scan
+                .edit_locations(|locations| locations.add_root(path.clone()))
+            {
+                self.logger
+                    .info(format!("Added firmware search location {}", path.display()));
+                self.firmware_scan.begin(&self.logger);
+            }
+        } else {
+            self.add_picked_firmware(path);
+        }
+    }
+
+    fn add_picked_firmware(&mut self, path: PathBuf) {
+        if self.firmware_scan.manager.add_file(path.clone()) {
+            self.logger
+                .info(format!("Added firmware file {}", path.display()));
+        } else {
+            self.logger.warning(format_translation(
+                translate(TextKey::PickedFileNotFirmware, &self.language),
+                &[&path.display().to_string()],
+            ));
+        }
+    }
+
+    fn render_firmware_locations(&mut self, ui: &mut egui::Ui) {
+        let scan = &mut self.firmware_scan;
+        let mut view = LocationsView {
+            roots: scan.locations.roots(),
+            ignore_draft: &mut scan.ignore_draft,
+            status: scan.locations_status.as_ref(),
+        };
+        let action = ui::file_select::render_firmware_locations(ui, &mut view, &self.language);
+
+        match action {
+            Some(LocationAction::Back) => {
+                self.state = AppState::FirmwareSelection;
+                self.firmware_scan.begin(&self.logger);
+            }
+            Some(LocationAction::AddFolder) => {
+                if let Some(path) = rfd::FileDialog::new().pick_folder() {
+                    self.add_firmware_location(path);
+                }
+            }
+            Some(LocationAction::Remove(index)) => {
+                self.firmware_scan
+                    .edit_locations(|locations| locations.remove_root(index));
+                self.firmware_scan.locations_status = None;
+            }
+            Some(LocationAction::SetRecursive {
+                index,
+                recursive,
+                max_depth,
+            }) => {
+                self.firmware_scan.edit_locations(|locations| {
+                    locations.set_recursive(index, recursive, max_depth)
+                });
+            }
+            Some(LocationAction::SaveIgnore) => {
+                let scan = &mut self.firmware_scan;
+                let draft = scan.ignore_draft.clone();
+                scan.edit_locations(|locations| locations.set_ignore(&draft));
+                scan.locations_status =
+                    Some(Ok(
+                        translate(TextKey::IgnorePatternsSaved, &self.language).to_string()
+                    ));
+            }
+            None => {}
+        }
+    }
+
+    fn add_firmware_location(&mut self, path: PathBuf) {
+        let display = path.display().to_string();
+        let added = self
+            .firmware_scan
+            .edit_locations(|locations| locations.add_root(path));
+        let status = if added {
+            self.logger
+                .info(format!("Added firmware search location {display}"));
+            Ok(format_translation(
+                translate(TextKey::FirmwareFolderAdded, &self.language),
+                &[&display],
+            ))
+        } else {
+            Err(format_translation(
+                translate(TextKey::FirmwareFolderAlreadyListed, &self.language),
+                &[&display],
+            ))
+        };
+        self.firmware_scan.locations_status = Some(status);
+    }
+
     fn render_flashing_options(&mut self, ui: &mut egui::Ui) {
         let app_state = &mut self.state;
         let selected_option = &mut self.operation.selected_option;
@@ -189,6 +467,21 @@ impl FirmwareToolApp {
         let language = &self.language;
         let mut go_back = false;
 
+        self.adapter_scan.ensure_started();
+        let adapter_scan = self.adapter_scan.scanner.status();
+        if let AdapterScanStatus::Complete(adapters) = &adapter_scan {
+            let mut selection = flashing_manager.adapter_selection().clone();
+            selection.retain_connected(adapters);
+            flashing_manager.set_adapter_selection(selection);
+        }
+        let mut adapter_selection = flashing_manager.adapter_selection().clone();
+        let mut dna_lock_policy = flashing_manager.dna_lock_policy();
+        let mut signature_policy = flashing_manager.signature_policy();
+        let signature_pinned = flashing_manager.config().signature_policy_pinned;
+        let mut jtag_speed_khz = flashing_manager.jtag_speed_khz();
+        let mut refresh_adapters = false;
+        let last_used = self.settings.settings().last_option.clone();
+
         let mut option_callback = |option: FlashingOption| {
             *selected_option = Some(option.clone());
 
@@ -212,17 +505,55 @@ impl FirmwareToolApp {
             go_back = true;
         };
 
+        let mut refresh_callback = || {
+            refresh_adapters = true;
+        };
+        let mut adapter_picker = ui::options::AdapterPicker {
+            scan: &adapter_scan,
+            selection: &mut adapter_selection,
+            jtag_speed_khz: &mut jtag_speed_khz,
+            on_refresh: &mut refresh_callback,
+        };
+
         if selected_firmware.is_some() {
-            ui::options::render_flash_options(ui, &mut option_callback, &self.language);
+            ui::options::render_flash_options(
+                ui,
+                &mut option_callback,
+                &mut adapter_picker,
+                &mut dna_lock_policy,
+                &mut ui::options::SignaturePicker {
+                    policy: &mut signature_policy,
+                    pinned: signature_pinned,
+                },
+                &ui::options::ProfileChoices {
+                    firmware: self.operation.firmware_metadata.as_ref(),
+                    last_used: last_used.as_ref(),
+                },
+                &self.language,
+            );
         } else {
             ui::options::render_dna_read_options(
                 ui,
                 &mut option_callback,
                 &mut back_callback,
+                &mut adapter_picker,
+                last_used.as_ref(),
                 &self.language,
             );
         }
 
+        self.operation
+            .manager
+            .set_adapter_selection(adapter_selection);
+        self.operation.manager.set_dna_lock_policy(dna_lock_policy);
+        self.operation.manager.set_jtag_speed_khz(jtag_speed_khz);
+        self.operation
+            .manager
+            .set_signature_policy(signature_policy);
+        if refresh_adapters {
+            self.adapter_scan.scanner.start_scan();
+        }
+
         if go_back {
             *app_state = AppState::OperationSelection;
             *selected_option = None;
@@ -243,7 +574,14 @@ impl FirmwareToolApp {
             };
 
             let snapshot = self.operation.manager.snapshot();
-            ui::status::render_result_screen(ui, &snapshot, &mut action_callback, &self.language);
+            ui::status::render_result_screen(
+                ui,
+                &snapshot,
+                self.operation.dna_license.as_ref(),
+                self.operation.dna_export_status.as_ref(),
+                &mut action_callback,
+                &self.language,
+            );
         }
 
         if let Some(action) = action_to_take {
@@ -257,11 +595,6 @@ impl FirmwareToolApp {
         if show_log != self.previous_log_state {
             self.previous_log_state = show_log;
             ctx.request_repaint();
-
-            // Always start collapsed on the next screen that shows a log.
-            if !show_log {
-                self.log_expanded = false;
-            }
         }
 
         if show_log {
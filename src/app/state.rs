@@ -7,11 +7,13 @@ pub(super) enum AppState {
     FileCheck,
     OperationSelection,
     FirmwareSelection,
+    FirmwareLocations,
     FlashingOptions,
     Flashing,
     Result,
     Drivers,
     PcileechTest,
+    DnaHistory,
 }
 
 impl FirmwareToolApp {
@@ -41,6 +43,7 @@ impl FirmwareToolApp {
             }
             AppState::OperationSelection => WindowSizeType::OperationSelection,
             AppState::FirmwareSelection => WindowSizeType::FileSelection,
+            AppState::FirmwareLocations => WindowSizeType::FirmwareLocations,
             AppState::FlashingOptions => {
                 match (self.is_dna_read_operation(), self.is_flash_operation()) {
                     (true, _) => WindowSizeType::ReadOptionSelection,
@@ -51,11 +54,15 @@ impl FirmwareToolApp {
             AppState::Flashing => WindowSizeType::FlashingProgress {
                 log_expanded: self.log_expanded,
             },
+            AppState::Result if self.is_dna_read_operation() => WindowSizeType::DnaResult {
+                log_expanded: self.log_expanded,
+            },
             AppState::Result => WindowSizeType::OperationResult {
                 log_expanded: self.log_expanded,
             },
             AppState::Drivers => WindowSizeType::Drivers,
             AppState::PcileechTest => WindowSizeType::PcileechTest,
+            AppState::DnaHistory => WindowSizeType::DnaHistory,
         }
     }
 
@@ -0,0 +1,370 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AdapterKind {
+    Ch347,
+    Ftdi,
+}
+
+impl AdapterKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Ch347 => "CH347",
+            Self::Ftdi => "FTDI",
+        }
+    }
+
+    /// The OpenOCD `adapter driver` that talks to this family.
+    pub fn openocd_driver(&self) -> &'static str {
+        match self {
+            Self::Ch347 => "ch347",
+            Self::Ftdi => "ftdi",
+        }
+    }
+
+    pub fn from_openocd_driver(driver: &str) -> Option<Self> {
+        [Self::Ch347, Self::Ftdi]
+            .into_iter()
+            .find(|kind| kind.openocd_driver() == driver)
+    }
+
+    pub fn is_known_usb_id(&self, vendor_id: u16, product_id: u16) -> bool {
+        KNOWN_ADAPTERS.contains(&(*self, vendor_id, product_id))
+    }
+}
+
+// The RS232 DNA profiles drive an FT4232H (6011) while the RS232 flash
+// profiles drive an FT232H (6014); both are listed so either cable shows up.
+const KNOWN_ADAPTERS: &[(AdapterKind, u16, u16)] = &[
+    (AdapterKind::Ch347, 0x1a86, 0x55dd),
+    (AdapterKind::Ftdi, 0x0403, 0x6011),
+    (AdapterKind::Ftdi, 0x0403, 0x6014),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsbAdapter {
+    pub kind: AdapterKind,
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub serial: Option<String>,
+}
+
+impl fmt::Display for UsbAdapter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({:04x}:{:04x})",
+            self.kind.label(),
+            self.vendor_id,
+            self.product_id
+        )?;
+        if let Some(serial) = &self.serial {
+            write!(f, " - {serial}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Serial chosen per adapter family. `None` lets OpenOCD pick the first match.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AdapterSelection {
+    pub ch347: Option<String>,
+    pub ftdi: Option<String>,
+}
+
+impl AdapterSelection {
+    pub fn serial_for(&self, kind: AdapterKind) -> Option<&str> {
+        match kind {
+            AdapterKind::Ch347 => self.ch347.as_deref(),
+            AdapterKind::Ftdi => self.ftdi.as_deref(),
+        }
+    }
+
+    pub fn set_serial(&mut self, kind: AdapterKind, serial: Option<String>) {
+        match kind {
+            AdapterKind::Ch347 => self.ch347 = serial,
+            AdapterKind::Ftdi => self.ftdi = serial,
+        }
+    }
+
+    /// Drops selections whose adapter is no longer connected.
+    pub fn retain_connected(&mut self, adapters: &[UsbAdapter]) {
+        for kind in [AdapterKind::Ch347, AdapterKind::Ftdi] {
+            let connected = self.serial_for(kind).is_none_or(|serial| {
+                adapters.iter().any(|adapter| {
+                    adapter.kind == kind && adapter.serial.as_deref() == Some(serial)
+                })
+            });
+            if !connected {
+                self.set_serial(kind, None);
+            }
+        }
+    }
+}
+
+/// OpenOCD arguments that pin the adapter to `serial`. They must precede the
+/// profile script because every profile calls `init` itself.
+pub(crate) fn adapter_serial_args(serial: Option<&str>) -> Vec<String> {
+    serial
+        .filter(|serial| is_valid_serial(serial))
+        .map(|serial| vec!["-c".to_string(), format!("adapter serial {serial}")])
+        .unwrap_or_default()
+}
+
+// Serials are interpolated into a Tcl command, so only plain descriptor
+// characters are accepted.
+fn is_valid_serial(serial: &str) -> bool {
+    !serial.is_empty()
+        && serial
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'))
+}
+
+fn known_kind(vendor_id: u16, product_id: u16) -> Option<AdapterKind> {
+    KNOWN_ADAPTERS
+        .iter()
+        .find(|(_, vid, pid)| *vid == vendor_id && *pid == product_id)
+        .map(|(kind, _, _)| *kind)
+}
+
+fn push_unique(adapters: &mut Vec<UsbAdapter>, adapter: UsbAdapter) {
+    if !adapters.contains(&adapter) {
+        adapters.push(adapter);
+    }
+}
+
+/// Extracts known adapters from Windows PnP instance IDs such as
+/// `USB\VID_0403&PID_6011\FT4ABC12`. Interface children (`&MI_xx`) are skipped,
+/// and a generated instance suffix (containing `&`) means no serial descriptor.
+#[cfg(any(windows, test))]
+fn parse_pnp_instance_ids(output: &str) -> Vec<UsbAdapter> {
+    let mut adapters = Vec::new();
+
+    for token in output.split_whitespace() {
+        let mut parts = token.split('\\');
+        let (Some(bus), Some(hardware), Some(instance)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        if !bus.eq_ignore_ascii_case("USB") {
+            continue;
+        }
+
+        let hardware = hardware.to_ascii_uppercase();
+        let mut fields = hardware.split('&');
+        let (Some(vid), Some(pid), None) = (fields.next(), fields.next(), fields.next()) else {
+            continue;
+        };
+        let (Some(vid), Some(pid)) = (vid.strip_prefix("VID_"), pid.strip_prefix("PID_")) else {
+            continue;
+        };
+        let (Ok(vendor_id), Ok(product_id)) =
+            (u16::from_str_radix(vid, 16), u16::from_str_radix(pid, 16))
+        else {
+            continue;
+        };
+        let Some(kind) = known_kind(vendor_id, product_id) else {
+            continue;
+        };
+
+        let serial =
+            (!instance.contains('&') && is_valid_serial(instance)).then(|| instance.to_string());
+        push_unique(
+            &mut adapters,
+            UsbAdapter {
+                kind,
+                vendor_id,
+                product_id,
+                serial,
+            },
+        );
+    }
+
+    adapters
+}
+
+#[cfg(windows)]
+fn list_usb_adapters() -> Result<Vec<UsbAdapter>, String> {
+    use crate::utils::process_job::hide_console;
+    use std::process::Command;
+
+    let mut command = Command::new("powershell.exe");
+    command.args([
+        "-NoProfile",
+        "-NonInteractive",
+        "-Command",
+        "Get-PnpDevice -PresentOnly | ForEach-Object { $_.InstanceId }",
+    ]);
+    hide_console(&mut command);
+    let output = command
+        .output()
+        .map_err(|error| format!("Failed to query USB devices: {error}"))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "USB device query exited with code {:?}",
+            output.status.code()
+        ));
+    }
+
+    Ok(parse_pnp_instance_ids(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+#[cfg(not(windows))]
+fn list_usb_adapters() -> Result<Vec<UsbAdapter>, String> {
+    use std::fs;
+    use std::path::Path;
+
+    let read_attribute = |device: &Path, name: &str| {
+        fs::read_to_string(device.join(name))
+            .ok()
+            .map(|value| value.trim().to_string())
+    };
+
+    let entries = fs::read_dir("/sys/bus/usb/devices")
+        .map_err(|error| format!("Failed to list USB devices: {error}"))?;
+    let mut adapters = Vec::new();
+
+    for entry in entries.flatten() {
+        let device = entry.path();
+        let (Some(vid), Some(pid)) = (
+            read_attribute(&device, "idVendor"),
+            read_attribute(&device, "idProduct"),
+        ) else {
+            continue;
+        };
+        let (Ok(vendor_id), Ok(product_id)) =
+            (u16::from_str_radix(&vid, 16), u16::from_str_radix(&pid, 16))
+        else {
+            continue;
+        };
+        let Some(kind) = known_kind(vendor_id, product_id) else {
+            continue;
+        };
+
+        let serial = read_attribute(&device, "serial").filter(|serial| is_valid_serial(serial));
+        push_unique(
+            &mut adapters,
+            UsbAdapter {
+                kind,
+                vendor_id,
+                product_id,
+                serial,
+            },
+        );
+    }
+
+    Ok(adapters)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdapterScanStatus {
+    NotStarted,
+    Scanning,
+    Complete(Vec<UsbAdapter>),
+    Failed(String),
+}
+
+/// Enumerates connected adapters on a background thread.
+pub struct AdapterScanner {
+    status: Arc<Mutex<AdapterScanStatus>>,
+}
+
+impl AdapterScanner {
+    pub fn new() -> Self {
+        Self {
+            status: Arc::new(Mutex::new(AdapterScanStatus::NotStarted)),
+        }
+    }
+
+    pub fn status(&self) -> AdapterScanStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    pub fn start_scan(&self) {
+        {
+            let mut status = self.status.lock().unwrap();
+            if *status == AdapterScanStatus::Scanning {
+                return;
+            }
+            *status = AdapterScanStatus::Scanning;
+        }
+
+        let status = Arc::clone(&self.status);
+        thread::spawn(move || {
+            let result = match list_usb_adapters() {
+                Ok(adapters) => AdapterScanStatus::Complete(adapters),
+                Err(error) => AdapterScanStatus::Failed(error),
+            };
+            *status.lock().unwrap() = result;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_serials_from_pnp_instance_ids() {
+        let output = "\
+            USB\\VID_0403&PID_6011\\FT4ABC12\r\n\
+            USB\\VID_0403&PID_6011&MI_00\\6&2A1B3C&0&0000\r\n\
+            FTDIBUS\\VID_0403+PID_6011+FT4ABC12A\\0000\r\n\
+            USB\\VID_1A86&PID_55DD\\5&1C2F3A&0&2\r\n\
+            USB\\VID_046D&PID_C52B\\5&11AA&0&1\r\n";
+
+        assert_eq!(
+            parse_pnp_instance_ids(output),
+            vec![
+                UsbAdapter {
+                    kind: AdapterKind::Ftdi,
+                    vendor_id: 0x0403,
+                    product_id: 0x6011,
+                    serial: Some("FT4ABC12".to_string()),
+                },
+                UsbAdapter {
+                    kind: AdapterKind::Ch347,
+                    vendor_id: 0x1a86,
+                    product_id: 0x55dd,
+                    serial: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn serial_args_precede_profile_and_reject_tcl_syntax() {
+        assert_eq!(
+            adapter_serial_args(Some("FT4ABC12")),
+            vec!["-c".to_string(), "adapter serial FT4ABC12".to_string()]
+        );
+        assert!(adapter_serial_args(Some("x; shutdown")).is_empty());
+        assert!(adapter_serial_args(Some("[exit]")).is_empty());
+        assert!(adapter_serial_args(None).is_empty());
+    }
+
+    #[test]
+    fn disconnected_selection_falls_back_to_auto() {
+        let mut selection = AdapterSelection {
+            ch347: Some("CH1".to_string()),
+            ftdi: Some("FT1".to_string()),
+        };
+        let connected = [UsbAdapter {
+            kind: AdapterKind::Ftdi,
+            vendor_id: 0x0403,
+            product_id: 0x6011,
+            serial: Some("FT1".to_string()),
+        }];
+
+        selection.retain_connected(&connected);
+
+        assert_eq!(selection.serial_for(AdapterKind::Ch347), None);
+        assert_eq!(selection.serial_for(AdapterKind::Ftdi), Some("FT1"));
+    }
+}
@@ -0,0 +1,211 @@
+use super::value::DeviceDna;
+use std::collections::HashMap;
+use std::fmt;
+
+const PATTERN_LEN: usize = 8;
+
+// Short values occur by chance in any multi-megabyte image, so they cannot
+// identify a board. Real DNA values fill the upper bits.
+const MIN_SIGNIFICANT_BITS: u32 = 32;
+
+/// Byte layouts firmware authors commonly use to embed a DNA value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DnaEncoding {
+    BigEndian,
+    LittleEndian,
+    /// The 57 bits in JTAG shift order, stored big-endian.
+    BitReversed,
+}
+
+impl DnaEncoding {
+    pub const ALL: [Self; 3] = [Self::BigEndian, Self::LittleEndian, Self::BitReversed];
+
+    fn pattern(&self, dna: &DeviceDna) -> [u8; PATTERN_LEN] {
+        match self {
+            Self::BigEndian => dna.value().to_be_bytes(),
+            Self::LittleEndian => dna.value().to_le_bytes(),
+            // Reversing the left-aligned register leaves the 57 bits right-aligned.
+            Self::BitReversed => dna.register().reverse_bits().to_be_bytes(),
+        }
+    }
+}
+
+impl fmt::Display for DnaEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::BigEndian => "big-endian",
+            Self::LittleEndian => "little-endian",
+            Self::BitReversed => "bit-reversed",
+        })
+    }
+}
+
+/// One occurrence of a known DNA inside a firmware image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmbeddedDna {
+    pub dna: DeviceDna,
+    pub encoding: DnaEncoding,
+    pub offset: usize,
+}
+
+impl fmt::Display for EmbeddedDna {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}) at offset 0x{:X}",
+            self.dna, self.encoding, self.offset
+        )
+    }
+}
+
+/// Whether a firmware image is bound to the board about to be flashed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DnaLock {
+    /// The board's own DNA is embedded.
+    MatchesBoard(EmbeddedDna),
+    /// Another known board's DNA is embedded and the board's is not.
+    LockedToOther(EmbeddedDna),
+    /// Neither the board's DNA nor any other known one is embedded. The image
+    /// may be unlocked or locked to a board that was never seen.
+    BoardNotFound,
+}
+
+impl DnaLock {
+    /// Searches `image` for `board` first and then for every other known DNA.
+    pub fn assess(image: &[u8], board: &DeviceDna, known: &[DeviceDna]) -> Self {
+        let mut candidates = vec![*board];
+        candidates.extend(known.iter().filter(|dna| *dna != board));
+        let found = find_embedded(image, &candidates);
+
+        if let Some(own) = found.iter().find(|embedded| embedded.dna == *board) {
+            Self::MatchesBoard(*own)
+        } else if let Some(other) = found.first() {
+            Self::LockedToOther(*other)
+        } else {
+            Self::BoardNotFound
+        }
+    }
+}
+
+/// Returns every occurrence of `candidates` in `image`, ordered by offset.
+/// Values too short to be distinctive are skipped.
+fn find_embedded(image: &[u8], candidates: &[DeviceDna]) -> Vec<EmbeddedDna> {
+    let mut patterns = HashMap::new();
+    for dna in candidates
+        .iter()
+        .filter(|dna| dna.value() >> MIN_SIGNIFICANT_BITS != 0)
+    {
+        for encoding in DnaEncoding::ALL {
+            patterns
+                .entry(encoding.pattern(dna))
+                .or_insert((*dna, encoding));
+        }
+    }
+    if patterns.is_empty() {
+        return Vec::new();
+    }
+
+    image
+        .windows(PATTERN_LEN)
+        .enumerate()
+        .filter_map(|(offset, window)| {
+            let (dna, encoding) = patterns.get(window)?;
+            Some(EmbeddedDna {
+                dna: *dna,
+                encoding: *encoding,
+                offset,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARD: u64 = 0x00641CC26AE96854;
+    const OTHER: u64 = 0x0040A1B2C3D4E5F6;
+
+    fn dna(value: u64) -> DeviceDna {
+        DeviceDna::from_value(value).unwrap()
+    }
+
+    fn image_with(pattern: [u8; PATTERN_LEN], offset: usize) -> Vec<u8> {
+        let mut image = vec![0xFF; 4096];
+        image[offset..offset + PATTERN_LEN].copy_from_slice(&pattern);
+        image
+    }
+
+    #[test]
+    fn finds_every_supported_encoding() {
+        let board = dna(BOARD);
+        let expected = [
+            (
+                DnaEncoding::BigEndian,
+                [0x00, 0x64, 0x1C, 0xC2, 0x6A, 0xE9, 0x68, 0x54],
+            ),
+            (
+                DnaEncoding::LittleEndian,
+                [0x54, 0x68, 0xE9, 0x6A, 0xC2, 0x1C, 0x64, 0x00],
+            ),
+            (
+                DnaEncoding::BitReversed,
+                [0x00, 0x54, 0x2D, 0x2E, 0xAC, 0x86, 0x70, 0x4C],
+            ),
+        ];
+
+        for (encoding, pattern) in expected {
+            assert_eq!(encoding.pattern(&board), pattern, "{encoding}");
+            let image = image_with(pattern, 0x123);
+            assert_eq!(
+                find_embedded(&image, &[board]),
+                vec![EmbeddedDna {
+                    dna: board,
+                    encoding,
+                    offset: 0x123
+                }]
+            );
+        }
+    }
+
+    #[test]
+    fn image_locked_to_another_board_reports_the_owner_and_offset() {
+        let board = dna(BOARD);
+        let other = dna(OTHER);
+        let image = image_with(DnaEncoding::LittleEndian.pattern(&other), 0x800);
+
+        assert_eq!(
+            DnaLock::assess(&image, &board, &[other]),
+            DnaLock::LockedToOther(EmbeddedDna {
+                dna: other,
+                encoding: DnaEncoding::LittleEndian,
+                offset: 0x800
+            })
+        );
+    }
+
+    #[test]
+    fn board_match_wins_over_other_known_values() {
+        let board = dna(BOARD);
+        let other = dna(OTHER);
+        let mut image = image_with(DnaEncoding::BigEndian.pattern(&other), 0x10);
+        image[0x400..0x408].copy_from_slice(&DnaEncoding::BitReversed.pattern(&board));
+
+        assert!(matches!(
+            DnaLock::assess(&image, &board, &[other, board]),
+            DnaLock::MatchesBoard(EmbeddedDna { offset: 0x400, .. })
+        ));
+    }
+
+    #[test]
+    fn short_values_and_unlocked_images_are_not_reported() {
+        let short = dna(0x3);
+        let image = image_with(DnaEncoding::BigEndian.pattern(&short), 0);
+
+        assert!(find_embedded(&image, &[short]).is_empty());
+        assert_eq!(
+            DnaLock::assess(&image, &dna(BOARD), &[short, dna(OTHER)]),
+            DnaLock::BoardNotFound
+        );
+    }
+}
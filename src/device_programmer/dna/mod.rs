@@ -1,5 +1,9 @@
+mod lock;
 mod output;
 mod parser;
 mod reader;
+mod value;
 
+pub use lock::DnaLock;
 pub use reader::DnaReader;
+pub use value::{DeviceDna, DnaFormat};
@@ -1,3 +1,4 @@
+use super::value::{DeviceDna, DnaFormat};
 use crate::device_programmer::DnaInfo;
 use std::fmt;
 
@@ -40,12 +41,12 @@ pub fn parse_dna_output(contents: &str) -> Result<DnaInfo, DnaParseError> {
         })
         .ok_or(DnaParseError::InformationNotFound)?;
 
-    let binary_value =
-        u64::from_str_radix(binary_part, 2).map_err(|_| DnaParseError::InformationNotFound)?;
-    let hexadecimal_digits = hexadecimal_part
-        .strip_prefix("0x")
-        .ok_or(DnaParseError::InformationNotFound)?;
-    let hexadecimal_value = u64::from_str_radix(hexadecimal_digits, 16)
+    if !hexadecimal_part.starts_with("0x") {
+        return Err(DnaParseError::InformationNotFound);
+    }
+    let binary_value = DeviceDna::parse(binary_part, DnaFormat::Binary)
+        .map_err(|_| DnaParseError::InformationNotFound)?;
+    let hexadecimal_value = DeviceDna::parse(hexadecimal_part, DnaFormat::Hex)
         .map_err(|_| DnaParseError::InformationNotFound)?;
 
     if binary_value != hexadecimal_value {
@@ -53,8 +54,7 @@ pub fn parse_dna_output(contents: &str) -> Result<DnaInfo, DnaParseError> {
     }
 
     Ok(DnaInfo {
-        dna_value: hexadecimal_part.to_string(),
-        dna_raw_value: binary_part.to_string(),
+        dna: binary_value,
         device_type: device_type.to_string(),
     })
 }
@@ -71,9 +71,9 @@ CH347 Open Succ
 DNA = 001100100000111001100001001101010111010010110100001010100 (0x00641CC26AE96854)
 ";
         let info = parse_dna_output(contents).unwrap();
-        assert_eq!(info.dna_value, "0x00641CC26AE96854");
+        assert_eq!(info.dna.value(), 0x00641CC26AE96854);
         assert_eq!(
-            info.dna_raw_value,
+            info.dna.format(DnaFormat::Binary),
             "001100100000111001100001001101010111010010110100001010100"
         );
         assert_eq!(info.device_type, "CH347");
@@ -84,7 +84,7 @@ DNA = 001100100000111001100001001101010111010010110100001010100 (0x00641CC26AE96
         let contents = "Open On-Chip Debugger\nInfo : ftdi: initialized\nDNA = 1101 (0xD)\n";
         let info = parse_dna_output(contents).unwrap();
         assert_eq!(info.device_type, "FTDI");
-        assert_eq!(info.dna_value, "0xD");
+        assert_eq!(info.dna.value(), 0xD);
     }
 
     #[test]
@@ -97,8 +97,8 @@ DNA = 001100100000111001100001001101010111010010110100001010100 (0x00641CC26AE96
     fn accepts_equivalent_values_with_leading_zeroes() {
         let info = parse_dna_output("DNA = 000000001101 (0x000D)\n").unwrap();
 
-        assert_eq!(info.dna_raw_value, "000000001101");
-        assert_eq!(info.dna_value, "0x000D");
+        assert_eq!(info.dna.value(), 0xD);
+        assert_eq!(info.dna.format(DnaFormat::Hex), "0x000000000000000D");
     }
 
     #[test]
@@ -107,6 +107,7 @@ DNA = 001100100000111001100001001101010111010010110100001010100 (0x00641CC26AE96
             "DNA = 0011 (0x4)\n",
             "DNA = 11111111111111111111111111111111111111111111111111111111111111111 (0x1)\n",
             "DNA = 1 (0x10000000000000000)\n",
+            "DNA = 1000000000000000000000000000000000000000000000000000000000 (0x200000000000000)\n",
         ] {
             assert_eq!(
                 parse_dna_output(contents),
@@ -1,16 +1,11 @@
-use super::output::{self, OutputGeneration, OutputWaitError};
+use super::output::OutputCapture;
 use super::parser::DnaParseError;
-use crate::device_programmer::process::{CommandOptions, ProcessExecutor};
-use crate::device_programmer::{CompletionStatus, DNA_OUTPUT_FILE, FlashingOption, SCRIPT_DIR};
+use crate::device_programmer::process::{CommandOptions, LaunchSettings, ProcessExecutor};
+use crate::device_programmer::{CompletionStatus, FlashingOption};
 use crate::utils::localization::{Language, TextKey, format_translation, translate};
 use crate::utils::logger::Logger;
-use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::Duration;
-
-const DNA_RETRY_WAIT: Duration = Duration::from_millis(200);
-const DNA_MAX_ATTEMPTS: usize = 5;
 
 pub struct DnaReader {
     logger: Logger,
@@ -25,11 +20,17 @@ impl DnaReader {
         }
     }
 
-    pub fn execute(&self, option: &FlashingOption, executor: &ProcessExecutor, lang: &Language) {
+    pub fn execute(
+        &self,
+        option: &FlashingOption,
+        executor: &ProcessExecutor,
+        launch: LaunchSettings,
+        lang: &Language,
+    ) {
         if !option.is_dna_read() {
             let message = translate(TextKey::DnaInvalidOption, lang);
             self.logger.error(message);
-            executor.set_completion_status(CompletionStatus::Failed(message.to_string()));
+            executor.set_completion_status(CompletionStatus::Failed(message.into()));
             return;
         }
 
@@ -37,54 +38,45 @@ impl DnaReader {
             translate(TextKey::Initializing, lang).to_string(),
         ));
 
-        let output_path = Path::new(DNA_OUTPUT_FILE);
-        let output_generation = match output::prepare_output_file(output_path, &self.logger) {
-            Ok(generation) => generation,
-            Err(error) => {
-                let native_message = format!(
-                    "Failed to prepare DNA output file at {}: {error}",
-                    output_path.display()
-                );
-                let message = localized_file_error(output_path, &error, lang);
-                self.logger.error(native_message);
-                executor.set_completion_status(CompletionStatus::Failed(message));
-                return;
-            }
-        };
+        let capture = OutputCapture::new();
+        let parse_callback = self.create_parse_callback(executor, lang, capture.clone());
 
-        let (command, config) = option.get_command_args();
-        let executable_path = format!("{SCRIPT_DIR}/{command}");
-        let config_path = format!("{SCRIPT_DIR}/{config}");
-        let parse_callback = self.create_parse_callback(executor, lang, output_generation);
-
-        if !self.run_command(&executable_path, &config_path, executor, parse_callback) {
+        if !self.run_command(option, executor, launch, capture, parse_callback) {
             self.stop_output_parsing();
             executor.set_completion_status(CompletionStatus::Failed(
-                translate(TextKey::DnaCommandFailed, lang).to_string(),
+                translate(TextKey::DnaCommandFailed, lang).into(),
             ));
         }
     }
 
     fn run_command(
         &self,
-        executable_path: &str,
-        config_path: &str,
+        option: &FlashingOption,
         executor: &ProcessExecutor,
+        launch: LaunchSettings,
+        capture: OutputCapture,
         parse_callback: Box<dyn FnOnce(bool) + Send + 'static>,
     ) -> bool {
-        let command =
-            ProcessExecutor::prepare_command(executable_path, &["-f", config_path, "-c", "exit"]);
+        let (executable_path, config_path, profile_copy) = launch.resolve(option, &self.logger);
+        let args = launch.openocd_args(&config_path, "exit");
+        let command = ProcessExecutor::prepare_command(
+            &executable_path,
+            &args.iter().map(String::as_str).collect::<Vec<&str>>(),
+        );
         self.logger
             .debug(format!("Executing DNA read command: {command:?}"));
 
         executor
             .execute_command(
                 command,
-                None,
+                Some(Box::new(move |line| capture.push_line(line))),
                 CommandOptions {
                     log_duration: true,
                     cleanup_temp_files: false,
                     duration_target: None,
+                    watchdog: launch.watchdog,
+                    session: launch.session,
+                    profile_copy,
                     on_complete: Some(parse_callback),
                 },
             )
@@ -100,11 +92,12 @@ impl DnaReader {
         self.logger.debug("DNA output parsing stop requested");
     }
 
+    /// Parses the captured output once both streams have been drained.
     fn create_parse_callback(
         &self,
         executor: &ProcessExecutor,
         lang: &Language,
-        output_generation: OutputGeneration,
+        capture: OutputCapture,
     ) -> Box<dyn FnOnce(bool) + Send + 'static> {
         let language = *lang;
         let logger = self.logger.clone();
@@ -116,7 +109,7 @@ impl DnaReader {
             if !command_succeeded {
                 logger.warning("DNA command failed before output parsing could run.");
                 *completion_status.lock().unwrap() = CompletionStatus::Failed(
-                    translate(TextKey::DnaCommandFailed, &language).to_string(),
+                    translate(TextKey::DnaCommandFailed, &language).into(),
                 );
                 parse_enabled.store(false, Ordering::SeqCst);
                 return;
@@ -125,7 +118,7 @@ impl DnaReader {
             if !parse_enabled.load(Ordering::SeqCst) {
                 logger.warning("DNA output parsing was stopped before processing");
                 *completion_status.lock().unwrap() = CompletionStatus::Failed(
-                    translate(TextKey::DnaCommandFailed, &language).to_string(),
+                    translate(TextKey::DnaCommandFailed, &language).into(),
                 );
                 return;
             }
@@ -134,28 +127,20 @@ impl DnaReader {
                 translate(TextKey::DnaRetrieving, &language).to_string(),
             );
 
-            let output_path = Path::new(DNA_OUTPUT_FILE);
-            match output::wait_for_parsed_output(
-                output_path,
-                output_generation,
-                DNA_MAX_ATTEMPTS,
-                DNA_RETRY_WAIT,
-                &logger,
-            ) {
+            if capture.was_truncated() {
+                logger.warning("DNA output exceeded the capture limit; later lines were dropped");
+            }
+
+            match capture.parse() {
                 Ok(dna_info) => {
-                    logger.info(format!(
-                        "DNA read completed successfully: {}",
-                        dna_info.dna_value
-                    ));
+                    logger.info(format!("DNA read completed successfully: {}", dna_info.dna));
                     *completion_status.lock().unwrap() =
                         CompletionStatus::DnaReadCompleted(dna_info);
                 }
                 Err(error) => {
-                    let message = localized_output_error(output_path, &error, &language);
-                    logger.error(format!(
-                        "Failed to obtain valid DNA output after {DNA_MAX_ATTEMPTS} attempts: {error}"
-                    ));
-                    *completion_status.lock().unwrap() = CompletionStatus::Failed(message);
+                    logger.error(format!("Failed to parse DNA from OpenOCD output: {error}"));
+                    *completion_status.lock().unwrap() =
+                        CompletionStatus::Failed(localized_output_error(error, &language).into());
                 }
             }
 
@@ -170,133 +155,86 @@ fn localized_parse_error(error: DnaParseError, lang: &Language) -> String {
     }
 }
 
-fn localized_output_error(path: &Path, error: &OutputWaitError, lang: &Language) -> String {
-    match error {
-        OutputWaitError::Unavailable => {
-            let attempts = DNA_MAX_ATTEMPTS.to_string();
-            format_translation(translate(TextKey::DnaFileNotFound, lang), &[&attempts])
-        }
-        OutputWaitError::Malformed(error) => {
-            let detail = localized_parse_error(*error, lang);
-            format_translation(translate(TextKey::DnaExtractFailed, lang), &[&detail])
-        }
-        OutputWaitError::Read(error) => localized_file_error(path, error, lang),
-        OutputWaitError::Stale => localized_file_error(path, error, lang),
-    }
-}
-
-fn localized_file_error(path: &Path, error: &dyn std::fmt::Display, lang: &Language) -> String {
-    format_file_error(
-        translate(TextKey::DnaFileReadError, lang),
-        &path.to_string_lossy(),
-        &error.to_string(),
-    )
-}
-
-fn format_file_error(template: &str, path: &str, error: &str) -> String {
-    format_translation(template, &[path, error])
+fn localized_output_error(error: DnaParseError, lang: &Language) -> String {
+    let detail = localized_parse_error(error, lang);
+    format_translation(translate(TextKey::DnaExtractFailed, lang), &[&detail])
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::{SystemTime, UNIX_EPOCH};
 
     #[test]
-    fn file_error_formatting_replaces_path_and_error_independently() {
-        let message = format_file_error(
-            "Failed to read DNA output file at {}: {}",
-            "OpenOCD/openocd_output.log",
-            "access denied",
-        );
+    fn arabic_malformed_error_contains_parse_detail() {
+        let detail = localized_parse_error(DnaParseError::InformationNotFound, &Language::Arabic);
+        let message = localized_output_error(DnaParseError::InformationNotFound, &Language::Arabic);
 
-        assert_eq!(
-            message,
-            "Failed to read DNA output file at OpenOCD/openocd_output.log: access denied"
-        );
+        assert!(message.contains(&detail));
+        assert!(!message.contains("{}"));
+        assert!(!message.contains("}{"));
     }
 
     #[test]
-    fn localized_file_error_contains_both_details() {
-        let error = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "access denied");
-        let message = localized_file_error(
-            Path::new("OpenOCD/openocd_output.log"),
-            &error,
-            &Language::English,
-        );
+    fn localized_template_does_not_reparse_braces_inside_inserted_values() {
+        let message = format_translation("first {} second {}", &["a{}b", "error"]);
 
-        assert!(message.contains("OpenOCD/openocd_output.log"));
-        assert!(message.contains("access denied"));
+        assert_eq!(message, "first a{}b second error");
     }
 
     #[test]
-    fn arabic_file_error_contains_path_and_native_error() {
-        let error = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "access denied");
-        let message = localized_file_error(
-            Path::new("OpenOCD/openocd_output.log"),
-            &error,
-            &Language::Arabic,
-        );
+    fn command_failure_callback_sets_localized_status() {
+        let logger = Logger::new("DnaReaderTest");
+        let reader = DnaReader::new(logger.clone());
+        let executor = ProcessExecutor::new(logger);
+        let callback =
+            reader.create_parse_callback(&executor, &Language::German, OutputCapture::new());
 
-        assert!(message.contains("OpenOCD/openocd_output.log"));
-        assert!(message.contains("access denied"));
-        assert!(!message.contains("{}"));
-        assert!(!message.contains("}{"));
-    }
+        callback(false);
 
-    #[test]
-    fn arabic_unavailable_error_contains_attempt_count() {
-        let message = localized_output_error(
-            Path::new("OpenOCD/openocd_output.log"),
-            &OutputWaitError::Unavailable,
-            &Language::Arabic,
+        assert_eq!(
+            executor.get_completion_status(),
+            CompletionStatus::Failed(
+                translate(TextKey::DnaCommandFailed, &Language::German).into()
+            )
         );
-
-        assert!(message.contains(&DNA_MAX_ATTEMPTS.to_string()));
-        assert!(!message.contains("{}"));
-        assert!(!message.contains("}{"));
     }
 
     #[test]
-    fn arabic_malformed_error_contains_parse_detail() {
-        let detail = localized_parse_error(DnaParseError::InformationNotFound, &Language::Arabic);
-        let message = localized_output_error(
-            Path::new("OpenOCD/openocd_output.log"),
-            &OutputWaitError::Malformed(DnaParseError::InformationNotFound),
-            &Language::Arabic,
-        );
+    fn successful_command_parses_the_captured_output() {
+        let logger = Logger::new("DnaReaderTest");
+        let reader = DnaReader::new(logger.clone());
+        let executor = ProcessExecutor::new(logger);
+        let capture = OutputCapture::new();
+        capture.push_line("CH347 Open Succ");
+        capture.push_line("DNA = 0011 (0x3)");
+        let callback = reader.create_parse_callback(&executor, &Language::English, capture);
 
-        assert!(message.contains(&detail));
-        assert!(!message.contains("{}"));
-        assert!(!message.contains("}{"));
-    }
+        callback(true);
 
-    #[test]
-    fn localized_template_does_not_reparse_braces_inside_inserted_values() {
-        let message = format_translation("first {} second {}", &["a{}b", "error"]);
-
-        assert_eq!(message, "first a{}b second error");
+        match executor.get_completion_status() {
+            CompletionStatus::DnaReadCompleted(info) => {
+                assert_eq!(info.dna.value(), 0x3);
+                assert_eq!(info.device_type, "CH347");
+            }
+            status => panic!("expected a DNA result, got {status:?}"),
+        }
     }
 
     #[test]
-    fn command_failure_callback_sets_localized_status() {
+    fn output_without_dna_fails_with_localized_detail() {
         let logger = Logger::new("DnaReaderTest");
         let reader = DnaReader::new(logger.clone());
         let executor = ProcessExecutor::new(logger);
-        let nonce = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_nanos();
-        let path = std::env::temp_dir().join(format!("dma-tools-dna-callback-{nonce}.log"));
-        let generation = output::prepare_output_file(&path, &Logger::new("DnaReaderTest")).unwrap();
-        let callback = reader.create_parse_callback(&executor, &Language::German, generation);
+        let callback =
+            reader.create_parse_callback(&executor, &Language::English, OutputCapture::new());
 
-        callback(false);
+        callback(true);
 
         assert_eq!(
             executor.get_completion_status(),
             CompletionStatus::Failed(
-                translate(TextKey::DnaCommandFailed, &Language::German).to_string()
+                localized_output_error(DnaParseError::InformationNotFound, &Language::English)
+                    .into()
             )
         );
     }
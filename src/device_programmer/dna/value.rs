@@ -0,0 +1,218 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use std::fmt;
+
+const DNA_BITS: u32 = 57;
+// `xilinx_get_dna` returns the 57 DNA bits left-aligned in a 64-bit register,
+// leaving the low 7 bits zero (matching the FUSE_DNA layout).
+const REGISTER_PADDING_BITS: u32 = u64::BITS - DNA_BITS;
+const REGISTER_PADDING_MASK: u64 = (1 << REGISTER_PADDING_BITS) - 1;
+const MAX_VALUE: u64 = (1 << DNA_BITS) - 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DnaValueError {
+    Empty,
+    InvalidDigits,
+    TooWide,
+    NonZeroPadding,
+}
+
+impl fmt::Display for DnaValueError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => formatter.write_str("DNA value is empty"),
+            Self::InvalidDigits => formatter.write_str("DNA value contains invalid digits"),
+            Self::TooWide => formatter.write_str("DNA value is wider than 57 bits"),
+            Self::NonZeroPadding => {
+                formatter.write_str("DNA register has non-zero bits below the 57-bit value")
+            }
+        }
+    }
+}
+
+/// Text encodings accepted by license tools.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DnaFormat {
+    /// `0x`-prefixed 16-digit hex of the 57-bit value, as OpenOCD prints it.
+    Hex,
+    /// Bare 15-digit hex of the 57-bit value.
+    Hex57,
+    /// 57 binary digits, most significant first.
+    Binary,
+    /// The 64-bit register with its bytes reversed, as `0x`-prefixed hex.
+    ByteReversed,
+    /// Base64 of the big-endian 64-bit register.
+    Base64,
+}
+
+impl DnaFormat {
+    pub const ALL: [Self; 5] = [
+        Self::Hex,
+        Self::Hex57,
+        Self::Binary,
+        Self::ByteReversed,
+        Self::Base64,
+    ];
+
+    /// Field name used in CSV and JSON exports.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Hex => "hex",
+            Self::Hex57 => "hex57",
+            Self::Binary => "binary",
+            Self::ByteReversed => "byte_reversed",
+            Self::Base64 => "base64",
+        }
+    }
+}
+
+/// 57-bit Series-7 device DNA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DeviceDna(u64);
+
+impl DeviceDna {
+    /// Builds a DNA from the right-aligned 57-bit value.
+    pub fn from_value(value: u64) -> Result<Self, DnaValueError> {
+        if value > MAX_VALUE {
+            return Err(DnaValueError::TooWide);
+        }
+        Ok(Self(value))
+    }
+
+    /// Builds a DNA from the 64-bit register returned by `xilinx_get_dna`.
+    pub fn from_register(register: u64) -> Result<Self, DnaValueError> {
+        if register & REGISTER_PADDING_MASK != 0 {
+            return Err(DnaValueError::NonZeroPadding);
+        }
+        Ok(Self(register >> REGISTER_PADDING_BITS))
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    pub fn register(&self) -> u64 {
+        self.0 << REGISTER_PADDING_BITS
+    }
+
+    pub fn format(&self, format: DnaFormat) -> String {
+        match format {
+            DnaFormat::Hex => format!("0x{:016X}", self.0),
+            DnaFormat::Hex57 => format!("{:015X}", self.0),
+            DnaFormat::Binary => format!("{:057b}", self.0),
+            DnaFormat::ByteReversed => format!("0x{:016X}", self.register().swap_bytes()),
+            DnaFormat::Base64 => BASE64.encode(self.register().to_be_bytes()),
+        }
+    }
+
+    pub fn parse(text: &str, format: DnaFormat) -> Result<Self, DnaValueError> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(DnaValueError::Empty);
+        }
+
+        match format {
+            DnaFormat::Hex => Self::from_value(parse_radix(strip_hex_prefix(text), 16)?),
+            DnaFormat::Hex57 => Self::from_value(parse_radix(text, 16)?),
+            DnaFormat::Binary => Self::from_value(parse_radix(text, 2)?),
+            DnaFormat::ByteReversed => {
+                Self::from_register(parse_radix(strip_hex_prefix(text), 16)?.swap_bytes())
+            }
+            DnaFormat::Base64 => {
+                let bytes = BASE64
+                    .decode(text)
+                    .map_err(|_| DnaValueError::InvalidDigits)?;
+                let bytes: [u8; 8] = bytes.try_into().map_err(|_| DnaValueError::TooWide)?;
+                Self::from_register(u64::from_be_bytes(bytes))
+            }
+        }
+    }
+}
+
+impl fmt::Display for DeviceDna {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.format(DnaFormat::Hex))
+    }
+}
+
+fn strip_hex_prefix(text: &str) -> &str {
+    text.strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .unwrap_or(text)
+}
+
+fn parse_radix(digits: &str, radix: u32) -> Result<u64, DnaValueError> {
+    if digits.is_empty() || !digits.chars().all(|ch| ch.is_digit(radix)) {
+        return Err(DnaValueError::InvalidDigits);
+    }
+    u64::from_str_radix(digits, radix).map_err(|_| DnaValueError::TooWide)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: u64 = 0x00641CC26AE96854;
+
+    #[test]
+    fn every_format_round_trips() {
+        let dna = DeviceDna::from_value(SAMPLE).unwrap();
+
+        for format in DnaFormat::ALL {
+            let text = dna.format(format);
+            assert_eq!(
+                DeviceDna::parse(&text, format),
+                Ok(dna),
+                "{format:?}: {text}"
+            );
+        }
+    }
+
+    #[test]
+    fn formats_match_the_series7_layout() {
+        let dna = DeviceDna::from_value(SAMPLE).unwrap();
+
+        assert_eq!(dna.register(), SAMPLE << 7);
+        assert_eq!(dna.format(DnaFormat::Hex), "0x00641CC26AE96854");
+        assert_eq!(dna.format(DnaFormat::Hex57), "0641CC26AE96854");
+        assert_eq!(
+            dna.format(DnaFormat::Binary),
+            "001100100000111001100001001101010111010010110100001010100"
+        );
+        assert_eq!(dna.format(DnaFormat::ByteReversed), "0x002AB47435610E32");
+        assert_eq!(dna.format(DnaFormat::Base64), "Mg5hNXS0KgA=");
+    }
+
+    #[test]
+    fn register_padding_bits_must_be_zero() {
+        assert_eq!(
+            DeviceDna::from_register((SAMPLE << 7) | 1),
+            Err(DnaValueError::NonZeroPadding)
+        );
+        assert_eq!(
+            DeviceDna::from_register(SAMPLE << 7).map(|dna| dna.value()),
+            Ok(SAMPLE)
+        );
+    }
+
+    #[test]
+    fn rejects_values_wider_than_57_bits_and_bad_digits() {
+        assert_eq!(DeviceDna::from_value(1 << 57), Err(DnaValueError::TooWide));
+        assert_eq!(
+            DeviceDna::parse("0x0200000000000000", DnaFormat::Hex),
+            Err(DnaValueError::TooWide)
+        );
+        assert_eq!(
+            DeviceDna::parse("0xNOTHEX", DnaFormat::Hex),
+            Err(DnaValueError::InvalidDigits)
+        );
+        assert_eq!(
+            DeviceDna::parse("+101", DnaFormat::Binary),
+            Err(DnaValueError::InvalidDigits)
+        );
+        assert_eq!(
+            DeviceDna::parse("  ", DnaFormat::Base64),
+            Err(DnaValueError::Empty)
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! Runs the flows against `examples/fake_openocd.rs`, which replays the
+//! transcripts in `tests/openocd` in place of the bundled OpenOCD.
+
+use super::types::CommandArgsSource;
+use super::{CompletionStatus, FlashingManager, OperationSnapshot};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const OPERATION_DEADLINE: Duration = Duration::from_secs(10);
+
+/// Flash runs share `FIRMWARE.bin` in the working directory.
+static WORKSPACE: Mutex<()> = Mutex::new(());
+
+pub(crate) fn lock_workspace() -> MutexGuard<'static, ()> {
+    WORKSPACE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Replays the named transcripts one per launch, repeating the last one once
+/// they run out. Clones share the count, so it carries across a replaced
+/// manager.
+pub(crate) fn commands(transcripts: &[&str]) -> CommandArgsSource {
+    assert!(!transcripts.is_empty());
+    let executable = executable();
+    let transcripts: Vec<String> = transcripts
+        .iter()
+        .map(|name| {
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/openocd")
+                .join(format!("{name}.txt"));
+            assert!(path.is_file(), "missing transcript {}", path.display());
+            path.display().to_string()
+        })
+        .collect();
+    let launches = AtomicUsize::new(0);
+
+    CommandArgsSource::replaced_with(move |_| {
+        let launch = launches
+            .fetch_add(1, Ordering::SeqCst)
+            .min(transcripts.len() - 1);
+        (executable.clone(), transcripts[launch].clone())
+    })
+}
+
+pub(crate) fn wait_for_terminal(manager: &FlashingManager) -> OperationSnapshot {
+    let deadline = Instant::now() + OPERATION_DEADLINE;
+    loop {
+        let snapshot = manager.snapshot();
+        if matches!(
+            snapshot.status,
+            CompletionStatus::Completed
+                | CompletionStatus::DnaReadCompleted(_)
+                | CompletionStatus::Failed(_)
+        ) {
+            return snapshot;
+        }
+        assert!(
+            Instant::now() < deadline,
+            "operation did not finish: {:?}",
+            snapshot.status
+        );
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// The stand-in is an example, which `cargo test` builds next to the test
+/// binary's directory.
+fn executable() -> String {
+    let path = std::env::current_exe()
+        .ok()
+        .and_then(|test_binary| Some(test_binary.parent()?.parent()?.to_path_buf()))
+        .unwrap_or_default()
+        .join(format!(
+            "examples/fake_openocd{}",
+            std::env::consts::EXE_SUFFIX
+        ));
+    assert!(
+        path.is_file(),
+        "{} is missing; run the tests through `cargo test`",
+        path.display()
+    );
+    path.display().to_string()
+}
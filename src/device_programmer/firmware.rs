@@ -1,12 +1,24 @@
+use crate::device_programmer::jtag_speed::ProfileCopy;
 use crate::device_programmer::monitor::OperationMonitor;
-use crate::device_programmer::process::{CommandOptions, ProcessExecutor};
-use crate::device_programmer::{CompletionStatus, FlashingOption, SCRIPT_DIR, TEMP_FIRMWARE_FILE};
+use crate::device_programmer::process::{CommandOptions, LaunchSettings, ProcessExecutor};
+use crate::device_programmer::signature::SignatureCheck;
+use crate::device_programmer::{CompletionStatus, FlashingOption, TEMP_FIRMWARE_FILE};
+use crate::utils::firmware_archive;
+use crate::utils::firmware_manifest::sha256_file;
 use crate::utils::logger::Logger;
 use std::fs;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+/// The image to flash, the SHA-256 its manifest declares and the signature
+/// check it must pass, if any.
+pub struct FirmwareImage<'a> {
+    pub path: &'a Path,
+    pub expected_sha256: Option<&'a str>,
+    pub signature: Option<&'a SignatureCheck>,
+}
+
 pub struct FirmwareFlasher {
     logger: Logger,
 }
@@ -18,40 +30,56 @@ impl FirmwareFlasher {
 
     pub fn execute(
         &self,
-        firmware_path: &Path,
+        image: &FirmwareImage<'_>,
         option: &FlashingOption,
         monitor: &OperationMonitor,
         executor: &ProcessExecutor,
         duration: Arc<Mutex<Option<Duration>>>,
+        launch: LaunchSettings,
     ) -> Result<(), String> {
         // Copy firmware to temporary location
-        self.copy_firmware_to_temp(firmware_path, executor)?;
+        self.copy_firmware_to_temp(image.path, executor)?;
+
+        // Verify the copy that is flashed, not the original the user can still change
+        if let Some(expected) = image.expected_sha256 {
+            self.verify_temp_firmware(expected, executor)?;
+        }
+        if let Some(check) = image.signature {
+            self.verify_temp_signature(check, image.path, executor)?;
+        }
 
         // Create the command
-        let (command, command_str) = self.create_flash_command(option);
+        let (command, command_str, profile_copy) = self.create_flash_command(option, &launch);
 
         // Log operation information
-        self.log_flash_operation(firmware_path, option, &command_str);
+        self.log_flash_operation(image.path, option, &command_str);
 
         // Execute and track the operation
-        self.run_flash_operation(command, monitor, executor, duration)
+        self.run_flash_operation(command, profile_copy, monitor, executor, duration, launch)
     }
 
-    fn create_flash_command(&self, option: &FlashingOption) -> (std::process::Command, String) {
-        let (exe_path, command_str, args) = self.prepare_flash_command(option);
+    fn create_flash_command(
+        &self,
+        option: &FlashingOption,
+        launch: &LaunchSettings,
+    ) -> (std::process::Command, String, Option<ProfileCopy>) {
+        let (exe_path, command_str, args, profile_copy) =
+            self.prepare_flash_command(option, launch);
         let command = ProcessExecutor::prepare_command(
             &exe_path,
             &args.iter().map(|s| s.as_str()).collect::<Vec<&str>>(),
         );
-        (command, command_str)
+        (command, command_str, profile_copy)
     }
 
     fn run_flash_operation(
         &self,
         command: std::process::Command,
+        profile_copy: Option<ProfileCopy>,
         monitor: &OperationMonitor,
         executor: &ProcessExecutor,
         duration: Arc<Mutex<Option<Duration>>>,
+        launch: LaunchSettings,
     ) -> Result<(), String> {
         // Create a monitor callback (only pass the logger)
         let monitor_callback =
@@ -65,6 +93,9 @@ impl FirmwareFlasher {
                 log_duration: true,
                 cleanup_temp_files: true,
                 duration_target: Some(Arc::clone(&duration)),
+                watchdog: launch.watchdog,
+                session: launch.session,
+                profile_copy,
                 on_complete: None,
             },
         ) {
@@ -92,23 +123,71 @@ impl FirmwareFlasher {
         self.logger.command(format!("Executing: {command_str}"));
     }
 
-    fn prepare_flash_command(&self, option: &FlashingOption) -> (String, String, Vec<String>) {
-        let (cmd, config) = option.get_command_args();
-        let exe_path = format!("{SCRIPT_DIR}/{cmd}");
-        let config_path = format!("{SCRIPT_DIR}/{config}");
+    fn prepare_flash_command(
+        &self,
+        option: &FlashingOption,
+        launch: &LaunchSettings,
+    ) -> (String, String, Vec<String>, Option<ProfileCopy>) {
+        let (exe_path, config_path, profile_copy) = launch.resolve(option, &self.logger);
 
         let program_arg = format!("program {TEMP_FIRMWARE_FILE}; exit");
-        let args = vec![
-            "-f".to_string(),
-            config_path.clone(),
-            "-c".to_string(),
-            program_arg.clone(),
-        ];
+        let args = launch.openocd_args(&config_path, &program_arg);
 
         // For logging purposes
-        let command_str = format!("{exe_path} -f {config_path} -c \"{program_arg}\"");
+        let adapter_arg = launch
+            .adapter_serial
+            .as_deref()
+            .map(|serial| format!(" -c \"adapter serial {serial}\""))
+            .unwrap_or_default();
+        let command_str = format!("{exe_path}{adapter_arg} -f {config_path} -c \"{program_arg}\"");
+
+        (exe_path, command_str, args, profile_copy)
+    }
 
-        (exe_path, command_str, args)
+    fn verify_temp_firmware(
+        &self,
+        expected: &str,
+        executor: &ProcessExecutor,
+    ) -> Result<(), String> {
+        let error = match sha256_file(Path::new(TEMP_FIRMWARE_FILE)) {
+            Ok(actual) if actual == expected => {
+                self.logger
+                    .success(format!("Firmware SHA-256 matches its manifest: {actual}"));
+                return Ok(());
+            }
+            Ok(actual) => format!(
+                "Firmware SHA-256 {actual} does not match the {expected} declared by its manifest"
+            ),
+            Err(e) => format!("Failed to hash firmware file: {e}"),
+        };
+
+        let _ = fs::remove_file(TEMP_FIRMWARE_FILE);
+        self.logger.error(&error);
+        executor.set_completion_status(CompletionStatus::Failed(error.clone().into()));
+        Err(error)
+    }
+
+    fn verify_temp_signature(
+        &self,
+        check: &SignatureCheck,
+        firmware_path: &Path,
+        executor: &ProcessExecutor,
+    ) -> Result<(), String> {
+        let error = match fs::read(TEMP_FIRMWARE_FILE) {
+            Ok(image) => match check.check(&image, firmware_path, &self.logger) {
+                Ok(()) => return Ok(()),
+                Err(error) => error,
+            },
+            Err(e) => {
+                let error = format!("Failed to read firmware file: {e}");
+                self.logger.error(&error);
+                error
+            }
+        };
+
+        let _ = fs::remove_file(TEMP_FIRMWARE_FILE);
+        executor.set_completion_status(CompletionStatus::Failed(error.clone().into()));
+        Err(error)
     }
 
     fn copy_firmware_to_temp(
@@ -116,14 +195,14 @@ impl FirmwareFlasher {
         firmware_path: &Path,
         executor: &ProcessExecutor,
     ) -> Result<(), String> {
-        fs::copy(firmware_path, TEMP_FIRMWARE_FILE)
-            .map(|_| ())
-            .map_err(|e| {
-                let error_msg = format!("Failed to prepare firmware file: {e}");
-                self.logger.error(&error_msg);
-                executor.set_completion_status(CompletionStatus::Failed(error_msg.clone()));
-                error_msg
-            })
+        // Images from archives are decompressed here, so OpenOCD always
+        // programs a plain .bin.
+        firmware_archive::extract_image(firmware_path, Path::new(TEMP_FIRMWARE_FILE)).map_err(|e| {
+            let error_msg = format!("Failed to prepare firmware file: {e}");
+            self.logger.error(&error_msg);
+            executor.set_completion_status(CompletionStatus::Failed(error_msg.clone().into()));
+            error_msg
+        })
     }
 }
 
@@ -131,12 +210,37 @@ impl FirmwareFlasher {
 mod tests {
     use super::*;
 
+    use crate::device_programmer::WatchdogBudget;
+    use crate::device_programmer::types::CommandArgsSource;
+
+    fn launch(adapter_serial: Option<&str>) -> LaunchSettings {
+        LaunchSettings {
+            watchdog: WatchdogBudget::unlimited(),
+            adapter_serial: adapter_serial.map(str::to_string),
+            command_args: CommandArgsSource::default(),
+            jtag_speed_khz: None,
+            session: None,
+        }
+    }
+
     #[test]
     fn flash_command_programs_prepared_temp_firmware() {
         let flasher = FirmwareFlasher::new(Logger::new("FirmwareFlasherTest"));
-        let (_, command_str, args) = flasher.prepare_flash_command(&FlashingOption::CH347_35T);
+        let (_, command_str, args, _) =
+            flasher.prepare_flash_command(&FlashingOption::CH347_35T, &launch(None));
 
         assert_eq!(args[3], format!("program {TEMP_FIRMWARE_FILE}; exit"));
         assert!(command_str.contains(&format!("program {TEMP_FIRMWARE_FILE}; exit")));
     }
+
+    #[test]
+    fn selected_adapter_serial_is_set_before_the_profile_runs_init() {
+        let flasher = FirmwareFlasher::new(Logger::new("FirmwareFlasherTest"));
+        let (_, command_str, args, _) =
+            flasher.prepare_flash_command(&FlashingOption::RS232_75T, &launch(Some("FT4ABC12")));
+
+        assert_eq!(args[..3], ["-c", "adapter serial FT4ABC12", "-f"]);
+        assert_eq!(args[5], format!("program {TEMP_FIRMWARE_FILE}; exit"));
+        assert!(command_str.contains("-c \"adapter serial FT4ABC12\" -f"));
+    }
 }
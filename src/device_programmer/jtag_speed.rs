@@ -0,0 +1,111 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Speeds offered in place of the profile's own `adapter speed`, in kHz.
+pub const JTAG_SPEEDS_KHZ: [u32; 5] = [10000, 6000, 4000, 2000, 1000];
+
+static NEXT_COPY: AtomicUsize = AtomicUsize::new(0);
+
+/// A copy of a profile that sets another `adapter speed`, deleted when
+/// dropped. Each operation gets its own file, so instances running at the
+/// same time cannot overwrite each other's copy.
+pub(crate) struct ProfileCopy {
+    path: PathBuf,
+}
+
+impl ProfileCopy {
+    /// Writes a copy of the profile at `config` that sets `khz` instead of
+    /// its own speed. The copy is placed in the temp directory; the paths it
+    /// names are still relative to the working directory OpenOCD runs in.
+    pub(crate) fn with_adapter_speed(config: &Path, khz: u32) -> io::Result<Self> {
+        let text = fs::read_to_string(config)?;
+        let Some(rewritten) = replace_adapter_speed(&text, khz) else {
+            return Err(io::Error::other(format!(
+                "{} sets no adapter speed",
+                config.display()
+            )));
+        };
+        let stem = config
+            .file_stem()
+            .map_or_else(|| "profile".into(), |stem| stem.to_string_lossy());
+        let id = NEXT_COPY.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!(
+            "dma-tools-{}-{id}-{stem}-{khz}khz.cfg",
+            std::process::id()
+        ));
+        fs::write(&path, rewritten)?;
+        Ok(Self { path })
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ProfileCopy {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Replaces every `adapter speed` and `adapter_khz` line, or returns `None`
+/// when the script sets no speed.
+fn replace_adapter_speed(text: &str, khz: u32) -> Option<String> {
+    let mut replaced = false;
+    let lines: Vec<String> = text
+        .lines()
+        .map(|line| {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            match words.as_slice() {
+                ["adapter", "speed", _] | ["adapter_khz", _] => {
+                    replaced = true;
+                    let indent = &line[..line.len() - line.trim_start().len()];
+                    format!("{indent}adapter speed {khz}")
+                }
+                _ => line.to_string(),
+            }
+        })
+        .collect();
+    replaced.then(|| lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::temp_dir::TempDir;
+
+    #[test]
+    fn only_the_speed_lines_are_replaced() {
+        let script = "adapter driver ch347\nadapter speed 10000\n# adapter speed 1\n  adapter_khz 500\ninit\n";
+
+        assert_eq!(
+            replace_adapter_speed(script, 4000).as_deref(),
+            Some(
+                "adapter driver ch347\nadapter speed 4000\n# adapter speed 1\n  adapter speed 4000\ninit\n"
+            )
+        );
+        assert_eq!(replace_adapter_speed("init\nexit\n", 4000), None);
+    }
+
+    #[test]
+    fn each_copy_has_its_own_file_and_is_deleted_when_dropped() {
+        let dir = TempDir::new("jtag-speed");
+        let config = dir.join("xc7a35T.cfg");
+        fs::write(&config, "adapter speed 10000\ninit\n").unwrap();
+
+        let first = ProfileCopy::with_adapter_speed(&config, 4000).unwrap();
+        let second = ProfileCopy::with_adapter_speed(&config, 4000).unwrap();
+        assert_ne!(first.path(), second.path());
+        assert_eq!(
+            fs::read_to_string(first.path()).unwrap(),
+            "adapter speed 4000\ninit\n"
+        );
+
+        let path = first.path().to_path_buf();
+        drop(first);
+        assert!(!path.exists());
+        assert!(second.path().exists());
+    }
+}
@@ -1,50 +1,106 @@
+mod adapter;
 pub mod dna;
+#[cfg(test)]
+pub(crate) mod fake_openocd;
 mod firmware;
+mod jtag_speed;
 mod monitor;
 mod operation;
+mod preflight;
 mod process;
+mod session;
+mod signature;
 pub mod types;
+mod watchdog;
 
 use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
 
 // Re-export the main types and functionality
-pub use dna::DnaReader;
-pub use firmware::FirmwareFlasher;
+pub use adapter::{AdapterKind, AdapterScanStatus, AdapterScanner, AdapterSelection, UsbAdapter};
+pub use dna::{DeviceDna, DnaFormat, DnaReader};
+pub use firmware::{FirmwareFlasher, FirmwareImage};
+pub use jtag_speed::JTAG_SPEEDS_KHZ;
 pub use operation::{FinalizationOutcome, FlashAssessment, OperationSnapshot, OperationStage};
+pub use preflight::DnaLockPolicy;
 pub use process::ProcessExecutor;
-pub use types::{CompletionStatus, DnaInfo, FlashingOption};
+pub use session::{SessionFile, default_session_dir, replay_session};
+pub use signature::{SignatureOutcome, SignaturePolicy, TrustedKeys};
+pub use types::{CompletionStatus, DnaInfo, FailureReason, FlashingOption};
+pub use watchdog::{WatchdogBudget, WatchdogConfig, WatchdogTimeout};
 
+use crate::utils::firmware_archive;
 use crate::utils::localization::Language;
 use crate::utils::localization::{TextKey, translate};
 use crate::utils::logger::Logger;
 use monitor::OperationMonitor;
+use preflight::{DnaPreflight, PreflightCancel};
+use process::LaunchSettings;
+use session::SessionRecorder;
+use signature::SignatureCheck;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use types::CommandArgsSource;
 
-// Windows-specific and configuration constants
-pub const CREATE_NO_WINDOW: u32 = 0x08000000;
+// Configuration constants
 pub const TEMP_FIRMWARE_FILE: &str = "FIRMWARE.bin";
-pub const DNA_OUTPUT_FILE: &str = "OpenOCD/openocd_output.log";
 pub const SCRIPT_DIR: &str = ".";
 
+/// User-chosen settings of a manager, carried over in one piece when the
+/// manager is rebuilt for the next operation.
+#[derive(Clone, Default)]
+pub struct FlashingConfig {
+    pub cleanup_enabled: bool,
+    pub watchdog: WatchdogConfig,
+    pub adapter_selection: AdapterSelection,
+    pub dna_lock_policy: DnaLockPolicy,
+    /// DNA values of other boards, searched for when the lock check runs.
+    pub known_dnas: Vec<DeviceDna>,
+    pub command_args: CommandArgsSource,
+    /// JTAG clock used instead of the profiles' own. `None` keeps theirs.
+    pub jtag_speed_khz: Option<u32>,
+    /// Where session transcripts are saved. `None` turns recording off.
+    pub session_dir: Option<PathBuf>,
+    /// Digest the firmware manifest declares for the image; a flash of an
+    /// image that hashes differently is refused.
+    pub expected_sha256: Option<String>,
+    /// Why the image's manifest could not be read; the flash is refused
+    /// while this is set.
+    pub manifest_error: Option<String>,
+    pub signature_policy: SignaturePolicy,
+    /// Set when the installation pins the signature policy; it then cannot
+    /// be changed.
+    pub signature_policy_pinned: bool,
+    /// Public keys a signed image is verified against.
+    pub trusted_keys: Arc<TrustedKeys>,
+}
+
 /// Main manager class for flashing operations
 pub struct FlashingManager {
     duration: Arc<Mutex<Option<Duration>>>,
     current_option: Option<FlashingOption>,
     logger: Logger,
-    monitor: OperationMonitor,
-    process_executor: ProcessExecutor,
+    monitor: Arc<OperationMonitor>,
+    process_executor: Arc<ProcessExecutor>,
     dna_reader: DnaReader,
-    firmware_flasher: FirmwareFlasher,
-    cleanup_enabled: bool,
+    firmware_flasher: Arc<FirmwareFlasher>,
+    config: FlashingConfig,
     original_firmware_path: Option<PathBuf>,
     cleanup_done: Arc<AtomicBool>,
+    current_adapter_serial: Option<String>,
+    preflight_cancel: PreflightCancel,
+    session: Option<SessionRecorder>,
+    signature_outcome: Arc<Mutex<Option<SignatureOutcome>>>,
 }
 
 impl FlashingManager {
     pub fn new_with_logger(logger: Logger) -> Self {
+        Self::with_config(logger, FlashingConfig::default())
+    }
+
+    /// A manager for a new operation that keeps the settings of the last one.
+    pub fn with_config(logger: Logger, config: FlashingConfig) -> Self {
         let monitor = OperationMonitor::new(logger.clone());
         let process_executor = ProcessExecutor::new(logger.clone());
 
@@ -52,22 +108,109 @@ impl FlashingManager {
             duration: Arc::new(Mutex::new(None)),
             current_option: None,
             logger: logger.clone(),
-            monitor,
-            process_executor,
+            monitor: Arc::new(monitor),
+            process_executor: Arc::new(process_executor),
             dna_reader: DnaReader::new(logger.clone()),
-            firmware_flasher: FirmwareFlasher::new(logger),
-            cleanup_enabled: false,
+            firmware_flasher: Arc::new(FirmwareFlasher::new(logger)),
+            config,
             original_firmware_path: None,
             cleanup_done: Arc::new(AtomicBool::new(false)),
+            current_adapter_serial: None,
+            preflight_cancel: PreflightCancel::default(),
+            session: None,
+            signature_outcome: Arc::default(),
         }
     }
 
+    pub fn config(&self) -> &FlashingConfig {
+        &self.config
+    }
+
     pub fn set_cleanup_enabled(&mut self, enabled: bool) {
-        self.cleanup_enabled = enabled;
+        self.config.cleanup_enabled = enabled;
+    }
+
+    pub fn set_watchdog_config(&mut self, config: WatchdogConfig) {
+        self.config.watchdog = config;
+    }
+
+    pub fn set_adapter_selection(&mut self, selection: AdapterSelection) {
+        self.config.adapter_selection = selection;
+    }
+
+    pub fn adapter_selection(&self) -> &AdapterSelection {
+        &self.config.adapter_selection
+    }
+
+    pub fn set_dna_lock_policy(&mut self, policy: DnaLockPolicy) {
+        self.config.dna_lock_policy = policy;
+    }
+
+    pub fn dna_lock_policy(&self) -> DnaLockPolicy {
+        self.config.dna_lock_policy
+    }
+
+    /// Changes the signature policy unless the installation pins it.
+    pub fn set_signature_policy(&mut self, policy: SignaturePolicy) {
+        if !self.config.signature_policy_pinned {
+            self.config.signature_policy = policy;
+        }
+    }
+
+    pub fn pin_signature_policy(&mut self, policy: SignaturePolicy) {
+        self.config.signature_policy = policy;
+        self.config.signature_policy_pinned = true;
+    }
+
+    pub fn signature_policy(&self) -> SignaturePolicy {
+        self.config.signature_policy
+    }
+
+    pub fn set_trusted_keys(&mut self, keys: TrustedKeys) {
+        self.config.trusted_keys = Arc::new(keys);
+    }
+
+    pub fn set_known_dnas(&mut self, dnas: Vec<DeviceDna>) {
+        self.config.known_dnas = dnas;
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_command_args(&mut self, command_args: CommandArgsSource) {
+        self.config.command_args = command_args;
+    }
+
+    pub fn set_jtag_speed_khz(&mut self, khz: Option<u32>) {
+        self.config.jtag_speed_khz = khz;
+    }
+
+    pub fn jtag_speed_khz(&self) -> Option<u32> {
+        self.config.jtag_speed_khz
+    }
+
+    pub fn set_expected_sha256(&mut self, digest: Option<String>) {
+        self.config.expected_sha256 = digest;
+    }
+
+    pub fn set_manifest_error(&mut self, error: Option<String>) {
+        self.config.manifest_error = error;
     }
 
-    pub fn cleanup_enabled(&self) -> bool {
-        self.cleanup_enabled
+    pub fn set_session_dir(&mut self, dir: Option<PathBuf>) {
+        self.config.session_dir = dir;
+    }
+
+    /// Saves the operation's transcript once it has finished. Later calls for
+    /// the same operation do nothing.
+    pub fn save_session(&mut self, snapshot: &OperationSnapshot) {
+        let (Some(session), Some(dir)) = (self.session.take(), &self.config.session_dir) else {
+            return;
+        };
+        match session.save(snapshot, dir) {
+            Ok(path) => self
+                .logger
+                .info(format!("Session transcript saved to {}", path.display())),
+            Err(error) => self.logger.warning(error),
+        }
     }
 
     pub fn execute_flash(
@@ -80,33 +223,118 @@ impl FlashingManager {
             let error = format!("Failed to initialize firmware operation: {error}");
             self.logger.error(&error);
             self.process_executor
-                .set_completion_status(CompletionStatus::Failed(error));
+                .set_completion_status(CompletionStatus::Failed(error.into()));
+            return;
+        }
+        if let Some(error) = &self.config.manifest_error {
+            let error = format!("Refusing to flash an image whose manifest is unreadable: {error}");
+            self.logger.error(&error);
+            self.process_executor
+                .set_completion_status(CompletionStatus::Failed(error.into()));
             return;
         }
         self.original_firmware_path = Some(firmware_path.to_path_buf());
 
-        if let Err(e) = self.firmware_flasher.execute(
-            firmware_path,
-            option,
-            &self.monitor,
-            &self.process_executor,
-            Arc::clone(&self.duration),
-        ) {
-            self.process_executor
-                .set_completion_status(CompletionStatus::Failed(e));
+        let dna_option = option.dna_read_option();
+        match dna_option {
+            Some(dna_option) if self.config.dna_lock_policy != DnaLockPolicy::Off => {
+                self.process_executor
+                    .set_completion_status(CompletionStatus::InProgress(
+                        translate(TextKey::DnaLockChecking, lang).to_string(),
+                    ));
+                self.start_with_dna_preflight(firmware_path, option, dna_option, lang);
+            }
+            _ => {
+                let signature = self.signature_check(lang);
+                let image = FirmwareImage {
+                    path: firmware_path,
+                    expected_sha256: self.config.expected_sha256.as_deref(),
+                    signature: signature.as_ref(),
+                };
+                if let Err(e) = self.firmware_flasher.execute(
+                    &image,
+                    option,
+                    &self.monitor,
+                    &self.process_executor,
+                    Arc::clone(&self.duration),
+                    self.launch_settings(option),
+                ) {
+                    self.process_executor
+                        .set_completion_status(CompletionStatus::Failed(e.into()));
+                }
+            }
         }
     }
 
+    /// Reads the board DNA and checks the image on a background thread, then
+    /// starts the flash unless the policy blocks it.
+    fn start_with_dna_preflight(
+        &self,
+        firmware_path: &Path,
+        option: &FlashingOption,
+        dna_option: FlashingOption,
+        lang: &Language,
+    ) {
+        let preflight = DnaPreflight {
+            policy: self.config.dna_lock_policy,
+            launch: self.launch_settings(&dna_option),
+            dna_option,
+            known: self.config.known_dnas.clone(),
+            logger: self.logger.clone(),
+            language: *lang,
+        };
+        let firmware_path = firmware_path.to_path_buf();
+        let expected_sha256 = self.config.expected_sha256.clone();
+        let signature = self.signature_check(lang);
+        let option = option.clone();
+        let launch = self.launch_settings(&option);
+        let cancel = Arc::clone(&self.preflight_cancel);
+        let monitor = Arc::clone(&self.monitor);
+        let executor = Arc::clone(&self.process_executor);
+        let flasher = Arc::clone(&self.firmware_flasher);
+        let duration = Arc::clone(&self.duration);
+
+        std::thread::spawn(move || {
+            let Some(board) = preflight.read_board(&cancel) else {
+                preflight
+                    .logger
+                    .debug("DNA lock check abandoned after operation reset");
+                return;
+            };
+            let verdict = preflight.check_image(board, &firmware_path);
+
+            let cancelled = cancel.lock().unwrap();
+            if *cancelled {
+                return;
+            }
+            let result = verdict.and_then(|()| {
+                let image = FirmwareImage {
+                    path: &firmware_path,
+                    expected_sha256: expected_sha256.as_deref(),
+                    signature: signature.as_ref(),
+                };
+                flasher.execute(&image, &option, &monitor, &executor, duration, launch)
+            });
+            if let Err(error) = result {
+                executor.set_completion_status(CompletionStatus::Failed(error.into()));
+            }
+        });
+    }
+
     pub fn execute_dna_read(&mut self, option: &FlashingOption, lang: &Language) {
         if let Err(error) = self.initialize_operation(option.clone(), lang) {
             let error = format!("Failed to initialize DNA operation: {error}");
             self.logger.error(&error);
             self.process_executor
-                .set_completion_status(CompletionStatus::Failed(error));
+                .set_completion_status(CompletionStatus::Failed(error.into()));
             return;
         }
-        self.dna_reader
-            .execute(option, &self.process_executor, lang);
+        self.dna_reader.execute(
+            option,
+            &self.process_executor,
+            self.launch_settings(option),
+            lang,
+        );
     }
 
     pub fn get_duration(&self) -> Option<Duration> {
@@ -118,6 +346,7 @@ impl FlashingManager {
     }
 
     pub fn retire_for_restart(&mut self) -> Result<(), String> {
+        self.cancel_preflight();
         self.monitor.stop_monitor_thread();
         self.process_executor.retire_for_restart()
     }
@@ -128,14 +357,52 @@ impl FlashingManager {
     }
 
     // Private methods
+    fn launch_settings(&self, option: &FlashingOption) -> LaunchSettings {
+        LaunchSettings {
+            watchdog: self.config.watchdog.budget_for(option),
+            adapter_serial: self.current_adapter_serial.clone(),
+            command_args: self.config.command_args.clone(),
+            jtag_speed_khz: self.config.jtag_speed_khz,
+            session: self.session.clone(),
+        }
+    }
+
+    fn signature_check(&self, lang: &Language) -> Option<SignatureCheck> {
+        (self.config.signature_policy != SignaturePolicy::Off).then(|| SignatureCheck {
+            policy: self.config.signature_policy,
+            keys: Arc::clone(&self.config.trusted_keys),
+            outcome: Arc::clone(&self.signature_outcome),
+            language: *lang,
+        })
+    }
+
+    fn cancel_preflight(&mut self) {
+        *self.preflight_cancel.lock().unwrap() = true;
+        self.preflight_cancel = PreflightCancel::default();
+    }
+
     fn initialize_operation(
         &mut self,
         option: FlashingOption,
         lang: &Language,
     ) -> Result<(), String> {
+        self.cancel_preflight();
         self.monitor.stop_monitor_thread();
         *self.duration.lock().unwrap() = None;
+        // A fresh slot, so a check still running for a retired operation
+        // cannot report into this one.
+        self.signature_outcome = Arc::default();
         self.current_option = Some(option.clone());
+        self.session = self
+            .config
+            .session_dir
+            .as_ref()
+            .map(|_| SessionRecorder::new());
+        self.current_adapter_serial = self
+            .config
+            .adapter_selection
+            .serial_for(option.adapter_kind())
+            .map(str::to_string);
         self.monitor.reset_counters();
         self.cleanup_done.store(false, AtomicOrdering::SeqCst);
 
@@ -181,12 +448,14 @@ impl FlashingManager {
             status,
             safe_to_restart,
             option: self.current_option.clone(),
+            adapter_serial: self.current_adapter_serial.clone(),
             stage: progress.stage,
             current_sector: progress.current_sector,
             sector_stats: progress.sector_stats,
             duration: self.get_duration(),
             assessment,
             terminated_early,
+            signature: self.signature_outcome.lock().unwrap().clone(),
         }
     }
 
@@ -211,7 +480,7 @@ impl FlashingManager {
             return FinalizationOutcome::NotApplicable;
         }
 
-        if !self.cleanup_enabled {
+        if !self.config.cleanup_enabled {
             return FinalizationOutcome::CleanupNotRequested {
                 assessment: snapshot.assessment,
             };
@@ -229,6 +498,17 @@ impl FlashingManager {
             };
         };
 
+        // The archive usually holds other images and a readme, so it is kept.
+        if firmware_archive::is_archive_member(&path) {
+            self.logger.info(format!(
+                "Firmware came from archive {}; the archive was kept",
+                firmware_archive::backing_file(&path).display()
+            ));
+            return FinalizationOutcome::SourcePreserved {
+                assessment: snapshot.assessment,
+            };
+        }
+
         // Claim cleanup atomically so concurrent finalization calls cannot race.
         // A transient removal failure releases the claim for a later retry.
         if self
@@ -271,18 +551,9 @@ impl FlashingManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::firmware_manifest::{FirmwareMetadata, FolderManifest, sha256_file};
+    use crate::utils::temp_dir::TempDir;
     use std::fs;
-    use std::fs::OpenOptions;
-    use std::os::windows::fs::OpenOptionsExt;
-    use std::time::{SystemTime, UNIX_EPOCH};
-
-    fn temporary_firmware_path(name: &str) -> PathBuf {
-        let nonce = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_nanos();
-        std::env::temp_dir().join(format!("dma-tools-{name}-{nonce}.bin"))
-    }
 
     fn record_normal_sectors(manager: &FlashingManager, count: usize) {
         let callback = manager.monitor.create_line_monitor(
@@ -297,7 +568,8 @@ mod tests {
 
     #[test]
     fn successful_finalization_cleans_source_exactly_once() {
-        let path = temporary_firmware_path("cleanup-success");
+        let dir = TempDir::new("cleanup-success");
+        let path = dir.join("firmware.bin");
         fs::write(&path, b"firmware").unwrap();
 
         let mut manager = FlashingManager::new_with_logger(Logger::new("FinalizationTest"));
@@ -322,9 +594,15 @@ mod tests {
         assert!(manager.cleanup_done.load(AtomicOrdering::SeqCst));
     }
 
+    // Relies on Windows share modes to make the removal fail.
+    #[cfg(windows)]
     #[test]
     fn finalization_retries_after_transient_removal_failure() {
-        let path = temporary_firmware_path("cleanup-retry");
+        use std::fs::OpenOptions;
+        use std::os::windows::fs::OpenOptionsExt;
+
+        let dir = TempDir::new("cleanup-retry");
+        let path = dir.join("firmware.bin");
         fs::write(&path, b"firmware").unwrap();
         let locked_file = OpenOptions::new()
             .read(true)
@@ -360,7 +638,8 @@ mod tests {
 
     #[test]
     fn missing_source_is_treated_as_completed_cleanup() {
-        let path = temporary_firmware_path("cleanup-missing");
+        let dir = TempDir::new("cleanup-missing");
+        let path = dir.join("firmware.bin");
         let mut manager = FlashingManager::new_with_logger(Logger::new("FinalizationTest"));
         manager.set_cleanup_enabled(true);
         manager.current_option = Some(FlashingOption::CH347_35T);
@@ -380,7 +659,8 @@ mod tests {
 
     #[test]
     fn failed_or_incomplete_finalization_preserves_source() {
-        let path = temporary_firmware_path("cleanup-preserved");
+        let dir = TempDir::new("cleanup-preserved");
+        let path = dir.join("firmware.bin");
         fs::write(&path, b"firmware").unwrap();
 
         let mut manager = FlashingManager::new_with_logger(Logger::new("FinalizationTest"));
@@ -398,22 +678,22 @@ mod tests {
 
         manager
             .process_executor
-            .set_completion_status(CompletionStatus::Failed("failed".to_string()));
+            .set_completion_status(CompletionStatus::Failed("failed".into()));
         assert_eq!(
             manager.finalize_completed_operation(),
             FinalizationOutcome::SourcePreserved {
-                assessment: FlashAssessment::Failed("failed".to_string())
+                assessment: FlashAssessment::Failed("failed".into())
             }
         );
 
         assert!(path.exists());
         assert!(!manager.cleanup_done.load(AtomicOrdering::SeqCst));
-        fs::remove_file(path).unwrap();
     }
 
     #[test]
     fn indeterminate_completion_preserves_requested_cleanup_source() {
-        let path = temporary_firmware_path("cleanup-indeterminate");
+        let dir = TempDir::new("cleanup-indeterminate");
+        let path = dir.join("firmware.bin");
         fs::write(&path, b"firmware").unwrap();
 
         let mut manager = FlashingManager::new_with_logger(Logger::new("FinalizationTest"));
@@ -432,19 +712,19 @@ mod tests {
         );
         assert!(path.exists());
         assert!(!manager.cleanup_done.load(AtomicOrdering::SeqCst));
-        fs::remove_file(path).unwrap();
     }
 
     #[test]
     fn unstable_connection_preserves_source_and_raw_process_failure() {
-        let path = temporary_firmware_path("cleanup-unstable");
+        let dir = TempDir::new("cleanup-unstable");
+        let path = dir.join("firmware.bin");
         fs::write(&path, b"firmware").unwrap();
 
         let mut manager = FlashingManager::new_with_logger(Logger::new("FinalizationTest"));
         manager.set_cleanup_enabled(true);
         manager.current_option = Some(FlashingOption::CH347_35T);
         manager.original_firmware_path = Some(path.clone());
-        let process_failure = CompletionStatus::Failed("owned process terminated".to_string());
+        let process_failure = CompletionStatus::Failed("owned process terminated".into());
         manager
             .process_executor
             .set_completion_status(process_failure.clone());
@@ -473,12 +753,12 @@ mod tests {
         );
         assert!(path.exists());
         assert!(!manager.cleanup_done.load(AtomicOrdering::SeqCst));
-        fs::remove_file(path).unwrap();
     }
 
     #[test]
     fn dna_completion_never_cleans_a_previous_firmware_source() {
-        let path = temporary_firmware_path("cleanup-dna");
+        let dir = TempDir::new("cleanup-dna");
+        let path = dir.join("firmware.bin");
         fs::write(&path, b"firmware").unwrap();
 
         let mut manager = FlashingManager::new_with_logger(Logger::new("FinalizationTest"));
@@ -496,6 +776,190 @@ mod tests {
 
         assert!(path.exists());
         assert!(!manager.cleanup_done.load(AtomicOrdering::SeqCst));
-        fs::remove_file(path).unwrap();
+    }
+
+    fn scripted_manager(transcripts: &[&str]) -> FlashingManager {
+        let mut manager = FlashingManager::new_with_logger(Logger::new("ScriptedFlowTest"));
+        manager.set_command_args(fake_openocd::commands(transcripts));
+        manager
+    }
+
+    #[test]
+    fn scripted_flash_over_a_healthy_link_succeeds() {
+        let _workspace = fake_openocd::lock_workspace();
+        let dir = TempDir::new("scripted-good");
+        let path = dir.join("firmware.bin");
+        fs::write(&path, b"firmware").unwrap();
+        let mut manager = scripted_manager(&["flash_good"]);
+
+        manager.execute_flash(&path, &FlashingOption::CH347_35T, &Language::English);
+        let snapshot = fake_openocd::wait_for_terminal(&manager);
+
+        assert_eq!(snapshot.status, CompletionStatus::Completed);
+        assert_eq!(snapshot.assessment, FlashAssessment::Success);
+        assert_eq!(snapshot.sector_stats.total, 12);
+        assert_eq!(snapshot.stage, OperationStage::WritingSector(11));
+        assert!(!snapshot.terminated_early);
+        assert!(!Path::new(TEMP_FIRMWARE_FILE).exists());
+    }
+
+    #[test]
+    fn scripted_flash_over_an_unstable_link_is_stopped_early() {
+        let _workspace = fake_openocd::lock_workspace();
+        let dir = TempDir::new("scripted-unstable");
+        let path = dir.join("firmware.bin");
+        fs::write(&path, b"firmware").unwrap();
+        let mut manager = scripted_manager(&["flash_unstable"]);
+
+        let started_at = std::time::Instant::now();
+        manager.execute_flash(&path, &FlashingOption::CH347_35T, &Language::English);
+        let snapshot = fake_openocd::wait_for_terminal(&manager);
+
+        assert!(started_at.elapsed() < Duration::from_secs(5));
+        assert!(snapshot.terminated_early);
+        assert!(matches!(
+            snapshot.assessment,
+            FlashAssessment::ConnectionUnstable {
+                normal_writes: 0,
+                ..
+            }
+        ));
+        assert!(snapshot.safe_to_restart);
+    }
+
+    #[test]
+    fn scripted_flash_without_the_adapter_fails() {
+        let _workspace = fake_openocd::lock_workspace();
+        let dir = TempDir::new("scripted-missing-adapter");
+        let path = dir.join("firmware.bin");
+        fs::write(&path, b"firmware").unwrap();
+        let mut manager = scripted_manager(&["adapter_missing"]);
+
+        manager.execute_flash(&path, &FlashingOption::RS232_75T, &Language::English);
+        let snapshot = fake_openocd::wait_for_terminal(&manager);
+
+        assert!(matches!(snapshot.status, CompletionStatus::Failed(_)));
+        assert!(matches!(snapshot.assessment, FlashAssessment::Failed(_)));
+        assert_eq!(snapshot.sector_stats.total, 0);
+    }
+
+    #[test]
+    fn firmware_that_does_not_match_its_manifest_is_not_flashed() {
+        let _workspace = fake_openocd::lock_workspace();
+        let dir = TempDir::new("scripted-digest");
+        let path = dir.join("firmware.bin");
+        fs::write(&path, b"firmware").unwrap();
+        let mut manager = scripted_manager(&["flash_good"]);
+        manager.set_expected_sha256(Some("00".repeat(32)));
+
+        manager.execute_flash(&path, &FlashingOption::CH347_35T, &Language::English);
+        let snapshot = fake_openocd::wait_for_terminal(&manager);
+
+        let CompletionStatus::Failed(reason) = &snapshot.status else {
+            panic!("expected a failure, got {:?}", snapshot.status);
+        };
+        assert!(reason.to_string().contains("does not match"), "{reason}");
+        assert_eq!(snapshot.sector_stats.total, 0);
+        assert!(!Path::new(TEMP_FIRMWARE_FILE).exists());
+
+        let mut manager = scripted_manager(&["flash_good"]);
+        manager.set_expected_sha256(Some(sha256_file(&path).unwrap()));
+        manager.execute_flash(&path, &FlashingOption::CH347_35T, &Language::English);
+
+        assert_eq!(
+            fake_openocd::wait_for_terminal(&manager).status,
+            CompletionStatus::Completed
+        );
+    }
+
+    #[test]
+    fn firmware_with_a_malformed_sidecar_is_not_flashed() {
+        let _workspace = fake_openocd::lock_workspace();
+        let dir = TempDir::new("scripted-bad-sidecar");
+        let path = dir.join("firmware.bin");
+        fs::write(&path, b"firmware").unwrap();
+        fs::write(dir.join("firmware.bin.json"), r#"{"sha256": "#).unwrap();
+        let mut warnings = Vec::new();
+        let metadata =
+            FirmwareMetadata::for_image(&path, &FolderManifest::default(), &mut warnings).unwrap();
+        assert_eq!(metadata.sha256, None);
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+
+        let mut manager = scripted_manager(&["flash_good"]);
+        manager.set_manifest_error(metadata.manifest_error);
+        manager.execute_flash(&path, &FlashingOption::CH347_35T, &Language::English);
+        let snapshot = fake_openocd::wait_for_terminal(&manager);
+
+        let CompletionStatus::Failed(reason) = &snapshot.status else {
+            panic!("expected a failure, got {:?}", snapshot.status);
+        };
+        assert!(reason.to_string().contains("firmware.bin.json"), "{reason}");
+        assert_eq!(snapshot.sector_stats.total, 0);
+        assert!(!Path::new(TEMP_FIRMWARE_FILE).exists());
+    }
+
+    #[test]
+    fn scripted_dna_read_reports_the_board_dna() {
+        let mut manager = scripted_manager(&["dna_ch347"]);
+
+        manager.execute_dna_read(&FlashingOption::DnaCH347, &Language::English);
+        let snapshot = fake_openocd::wait_for_terminal(&manager);
+
+        let CompletionStatus::DnaReadCompleted(info) = snapshot.status else {
+            panic!("expected a DNA result, got {:?}", snapshot.status);
+        };
+        assert_eq!(info.dna.value(), 0x00641CC26AE96854);
+        assert_eq!(info.device_type, "CH347");
+        assert_eq!(snapshot.assessment, FlashAssessment::NotApplicable);
+    }
+
+    #[test]
+    fn recorded_flash_session_replays_to_the_same_result() {
+        let _workspace = fake_openocd::lock_workspace();
+        let dir = TempDir::new("scripted-session");
+        let path = dir.join("firmware.bin");
+        let session_dir = dir.join("sessions");
+        fs::write(&path, b"firmware").unwrap();
+        let mut manager = scripted_manager(&["flash_good"]);
+        manager.set_session_dir(Some(session_dir.clone()));
+
+        manager.execute_flash(&path, &FlashingOption::CH347_35T, &Language::English);
+        let snapshot = fake_openocd::wait_for_terminal(&manager);
+        manager.save_session(&snapshot);
+        manager.save_session(&snapshot);
+
+        let saved: Vec<PathBuf> = fs::read_dir(&session_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(saved.len(), 1);
+        let session = SessionFile::load(&saved[0]).unwrap();
+        let run = session.runs.last().unwrap();
+        assert!(
+            run.args
+                .contains(&format!("program {TEMP_FIRMWARE_FILE}; exit"))
+        );
+        assert!(
+            run.exit
+                .as_deref()
+                .is_some_and(|exit| exit.ends_with(": 0"))
+        );
+        let sector_lines = run
+            .lines
+            .iter()
+            .filter(|line| line.stream == session::OutputStream::Stderr)
+            .filter(|line| line.text.contains("took 25 ms"))
+            .count();
+        assert_eq!(sector_lines, 12);
+        assert!(
+            run.lines
+                .windows(2)
+                .all(|pair| pair[0].at_ms <= pair[1].at_ms)
+        );
+
+        let recorded = session.snapshot.as_ref().unwrap();
+        assert_eq!(recorded.sectors_total, 12);
+        let report = replay_session(&session, &Logger::new("ScriptedFlowTest")).unwrap();
+        assert_eq!(report.differences(recorded), Vec::<String>::new());
     }
 }
@@ -1,4 +1,4 @@
-use super::{CompletionStatus, FlashingOption};
+use super::{CompletionStatus, FailureReason, FlashingOption, SignatureOutcome};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
@@ -49,7 +49,7 @@ pub enum FlashAssessment {
         total_sectors: usize,
     },
     Indeterminate,
-    Failed(String),
+    Failed(FailureReason),
     UnexpectedDnaResult,
 }
 
@@ -77,12 +77,15 @@ pub struct OperationSnapshot {
     pub status: CompletionStatus,
     pub safe_to_restart: bool,
     pub option: Option<FlashingOption>,
+    pub adapter_serial: Option<String>,
     pub stage: OperationStage,
     pub current_sector: Option<u32>,
     pub sector_stats: SectorStats,
     pub duration: Option<Duration>,
     pub assessment: FlashAssessment,
     pub terminated_early: bool,
+    /// Set once the flash has checked the image's signature.
+    pub signature: Option<SignatureOutcome>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -421,14 +424,14 @@ mod tests {
         );
         assert_eq!(
             assess_flash(
-                &CompletionStatus::Failed("boom".to_string()),
+                &CompletionStatus::Failed("boom".into()),
                 SectorStats {
                     total: 10,
                     normal: 10,
                 },
                 false,
             ),
-            FlashAssessment::Failed("boom".to_string())
+            FlashAssessment::Failed("boom".into())
         );
     }
 
@@ -441,7 +444,7 @@ mod tests {
 
         for status in [
             CompletionStatus::Completed,
-            CompletionStatus::Failed("forced process termination".to_string()),
+            CompletionStatus::Failed("forced process termination".into()),
         ] {
             assert_eq!(
                 assess_flash(&status, stats, true),
@@ -476,7 +479,7 @@ mod tests {
                 normal_writes: 4,
                 total_sectors: 10,
             },
-            FlashAssessment::Failed("failed".to_string()),
+            FlashAssessment::Failed("failed".into()),
             FlashAssessment::Pending,
         ] {
             assert!(!assessment.allows_source_cleanup());
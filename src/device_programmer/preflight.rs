@@ -0,0 +1,262 @@
+use crate::device_programmer::dna::{DeviceDna, DnaLock, DnaReader};
+use crate::device_programmer::process::{LaunchSettings, ProcessExecutor};
+use crate::device_programmer::{CompletionStatus, FlashingOption};
+use crate::utils::localization::{Language, TextKey, format_translation, translate};
+use crate::utils::logger::Logger;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+const DNA_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How a flash reacts to firmware that embeds another board's DNA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DnaLockPolicy {
+    /// Flash without reading the board first. A check that runs anyway only
+    /// logs its findings, as under `Warn`.
+    #[default]
+    Off,
+    /// Log the finding and flash anyway.
+    Warn,
+    /// Refuse to flash an image that does not embed the board's DNA, or one
+    /// whose board could not be read.
+    Block,
+}
+
+impl DnaLockPolicy {
+    pub const ALL: [Self; 3] = [Self::Off, Self::Warn, Self::Block];
+
+    pub fn label_key(&self) -> TextKey {
+        match self {
+            Self::Off => TextKey::DnaLockOff,
+            Self::Warn => TextKey::DnaLockWarn,
+            Self::Block => TextKey::DnaLockBlock,
+        }
+    }
+}
+
+/// Set once an operation is retired. Holding the lock while launching the
+/// flash keeps a retirement from slipping in between the check and the spawn.
+pub(crate) type PreflightCancel = Arc<Mutex<bool>>;
+
+/// Everything needed to read the board and judge the image off the UI thread.
+pub(crate) struct DnaPreflight {
+    pub policy: DnaLockPolicy,
+    pub dna_option: FlashingOption,
+    pub launch: LaunchSettings,
+    pub known: Vec<DeviceDna>,
+    pub logger: Logger,
+    pub language: Language,
+}
+
+impl DnaPreflight {
+    /// Reads the board DNA with a dedicated executor so the flash executor
+    /// never reports the read as the operation result. Returns `None` when the
+    /// operation was retired while the read was running.
+    pub fn read_board(&self, cancel: &PreflightCancel) -> Option<Result<DeviceDna, String>> {
+        let executor = ProcessExecutor::new(self.logger.clone());
+        if let Err(error) = executor.reset() {
+            return Some(Err(error));
+        }
+        DnaReader::new(self.logger.clone()).execute(
+            &self.dna_option,
+            &executor,
+            self.launch.clone(),
+            &self.language,
+        );
+
+        // Dropping the executor on cancellation terminates the read.
+        loop {
+            if *cancel.lock().unwrap() {
+                return None;
+            }
+            match executor.completion_snapshot().0 {
+                CompletionStatus::DnaReadCompleted(info) => return Some(Ok(info.dna)),
+                CompletionStatus::Failed(reason) => return Some(Err(reason.to_string())),
+                CompletionStatus::Completed => {
+                    return Some(Err(
+                        translate(TextKey::DnaInfoNotFound, &self.language).to_string()
+                    ));
+                }
+                CompletionStatus::NotCompleted | CompletionStatus::InProgress(_) => {
+                    thread::sleep(DNA_POLL_INTERVAL);
+                }
+            }
+        }
+    }
+
+    /// Judges the image against the board. `Err` carries the localized reason
+    /// the flash must not start.
+    pub fn check_image(
+        &self,
+        board: Result<DeviceDna, String>,
+        firmware_path: &Path,
+    ) -> Result<(), String> {
+        let lang = &self.language;
+        let board = match board {
+            Ok(board) => board,
+            Err(error) => {
+                let message =
+                    format_translation(translate(TextKey::DnaLockReadFailed, lang), &[&error]);
+                return self.finding(message);
+            }
+        };
+
+        let image = match crate::utils::firmware_archive::read_image(firmware_path) {
+            Ok(image) => image,
+            Err(error) => {
+                return Err(format!(
+                    "Failed to read firmware {}: {error}",
+                    firmware_path.display()
+                ));
+            }
+        };
+
+        match DnaLock::assess(&image, &board, &self.known) {
+            DnaLock::MatchesBoard(embedded) => {
+                self.logger
+                    .success(format!("Firmware is locked to this board: {embedded}"));
+                Ok(())
+            }
+            DnaLock::BoardNotFound => {
+                let message = format_translation(
+                    translate(TextKey::DnaLockBoardNotFound, lang),
+                    &[&board.to_string()],
+                );
+                self.finding(message)
+            }
+            DnaLock::LockedToOther(embedded) => {
+                let message = format_translation(
+                    translate(TextKey::DnaLockMismatch, lang),
+                    &[
+                        &embedded.dna.to_string(),
+                        &embedded.encoding.to_string(),
+                        &format!("0x{:X}", embedded.offset),
+                        &board.to_string(),
+                    ],
+                );
+                self.finding(message)
+            }
+        }
+    }
+
+    fn finding(&self, message: String) -> Result<(), String> {
+        match self.policy {
+            DnaLockPolicy::Block => {
+                self.logger.error(&message);
+                Err(message)
+            }
+            DnaLockPolicy::Off | DnaLockPolicy::Warn => {
+                self.logger.warning(message);
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device_programmer::WatchdogConfig;
+    use crate::device_programmer::types::CommandArgsSource;
+    use crate::utils::temp_dir::TempDir;
+    use std::fs;
+    use std::path::PathBuf;
+
+    const BOARD: u64 = 0x00641CC26AE96854;
+    const OTHER: u64 = 0x0040A1B2C3D4E5F6;
+
+    fn preflight(policy: DnaLockPolicy) -> DnaPreflight {
+        let dna_option = FlashingOption::DnaCH347;
+        DnaPreflight {
+            policy,
+            launch: LaunchSettings {
+                watchdog: WatchdogConfig::default().budget_for(&dna_option),
+                adapter_serial: None,
+                command_args: CommandArgsSource::default(),
+                jtag_speed_khz: None,
+                session: None,
+            },
+            dna_option,
+            known: vec![DeviceDna::from_value(OTHER).unwrap()],
+            logger: Logger::new("DnaPreflightTest"),
+            language: Language::English,
+        }
+    }
+
+    fn firmware_locked_to(dir: &TempDir, value: u64) -> PathBuf {
+        let path = dir.join(format!("locked-{value:x}.bin"));
+        let mut image = vec![0xFF; 0x1000];
+        image[0x200..0x208].copy_from_slice(&value.to_le_bytes());
+        fs::write(&path, image).unwrap();
+        path
+    }
+
+    #[test]
+    fn block_policy_refuses_firmware_locked_to_another_board() {
+        let dir = TempDir::new("dna-lock");
+        let path = firmware_locked_to(&dir, OTHER);
+        let board = DeviceDna::from_value(BOARD).unwrap();
+
+        let error = preflight(DnaLockPolicy::Block)
+            .check_image(Ok(board), &path)
+            .unwrap_err();
+
+        assert!(error.contains("0x0040A1B2C3D4E5F6"), "{error}");
+        assert!(error.contains("little-endian"), "{error}");
+        assert!(error.contains("0x200"), "{error}");
+        assert!(
+            preflight(DnaLockPolicy::Warn)
+                .check_image(Ok(board), &path)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn firmware_locked_to_this_board_is_flashed() {
+        let dir = TempDir::new("dna-lock");
+        let path = firmware_locked_to(&dir, BOARD);
+        let board = DeviceDna::from_value(BOARD).unwrap();
+
+        assert!(
+            preflight(DnaLockPolicy::Block)
+                .check_image(Ok(board), &path)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn firmware_without_the_board_dna_blocks_only_under_block_policy() {
+        let dir = TempDir::new("dna-lock");
+        let unknown_board = firmware_locked_to(&dir, 0x0123456789ABCDEF);
+        let board = DeviceDna::from_value(BOARD).unwrap();
+
+        let error = preflight(DnaLockPolicy::Block)
+            .check_image(Ok(board), &unknown_board)
+            .unwrap_err();
+        assert!(error.contains("0x00641CC26AE96854"), "{error}");
+        assert!(
+            preflight(DnaLockPolicy::Warn)
+                .check_image(Ok(board), &unknown_board)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn unreadable_board_blocks_only_under_block_policy() {
+        let dir = TempDir::new("dna-lock");
+        let path = firmware_locked_to(&dir, BOARD);
+        let failure = || Err("no JTAG response".to_string());
+
+        let error = preflight(DnaLockPolicy::Block)
+            .check_image(failure(), &path)
+            .unwrap_err();
+        assert!(error.contains("no JTAG response"), "{error}");
+        assert!(
+            preflight(DnaLockPolicy::Warn)
+                .check_image(failure(), &path)
+                .is_ok()
+        );
+    }
+}
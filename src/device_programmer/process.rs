@@ -1,13 +1,20 @@
-use crate::device_programmer::{CREATE_NO_WINDOW, CompletionStatus, TEMP_FIRMWARE_FILE};
+use crate::device_programmer::adapter::adapter_serial_args;
+use crate::device_programmer::jtag_speed::ProfileCopy;
+use crate::device_programmer::session::{OutputStream, RunRecorder, SessionRecorder};
+use crate::device_programmer::types::CommandArgsSource;
+use crate::device_programmer::watchdog::Watchdog;
+use crate::device_programmer::{
+    CompletionStatus, FlashingOption, TEMP_FIRMWARE_FILE, WatchdogBudget, WatchdogTimeout,
+};
 use crate::utils::logger::Logger;
-use crate::utils::process_job::{CREATE_SUSPENDED, ProcessJob};
+use crate::utils::process_job::{ProcessJob, contain_command};
 use std::fs;
 use std::io::{BufRead, BufReader};
-use std::os::windows::process::CommandExt;
+use std::path::Path;
 use std::process::{Child, Command, ExitStatus, Stdio};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, Weak};
 use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 
@@ -17,12 +24,24 @@ pub(crate) type ProcessTerminator = Arc<dyn Fn() -> Result<(), String> + Send +
 const READER_DRAIN_TIMEOUT: Duration = Duration::from_secs(2);
 const PROCESS_TERMINATION_TIMEOUT: Duration = Duration::from_secs(2);
 const PROCESS_TERMINATION_POLL_INTERVAL: Duration = Duration::from_millis(25);
+const WATCHDOG_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 enum ProcessWaitOutcome {
     Exited(ExitStatus),
+    TimedOut(WatchdogTimeout),
     Failed(String),
 }
 
+impl ProcessWaitOutcome {
+    fn describe(&self) -> String {
+        match self {
+            Self::Exited(status) => status.to_string(),
+            Self::TimedOut(timeout) => timeout.to_string(),
+            Self::Failed(error) => error.clone(),
+        }
+    }
+}
+
 #[derive(Default)]
 struct OperationState {
     generation: u64,
@@ -108,10 +127,67 @@ pub struct ProcessExecutor {
     operation_state: Arc<Mutex<OperationState>>,
 }
 
+/// Per-run settings resolved by `FlashingManager` before OpenOCD is launched.
+#[derive(Clone)]
+pub(crate) struct LaunchSettings {
+    pub watchdog: WatchdogBudget,
+    pub adapter_serial: Option<String>,
+    pub command_args: CommandArgsSource,
+    /// Replaces the profile's `adapter speed` when set.
+    pub jtag_speed_khz: Option<u32>,
+    pub session: Option<SessionRecorder>,
+}
+
+impl LaunchSettings {
+    /// Executable and profile for `option`. With a JTAG speed set, the
+    /// profile is a copy that uses it, which must be kept until OpenOCD has
+    /// finished; if the copy cannot be made the profile runs at its own speed.
+    pub(crate) fn resolve(
+        &self,
+        option: &FlashingOption,
+        logger: &Logger,
+    ) -> (String, String, Option<ProfileCopy>) {
+        let (executable, config) = self.command_args.resolve(option);
+        let Some(khz) = self.jtag_speed_khz else {
+            return (executable, config, None);
+        };
+        match ProfileCopy::with_adapter_speed(Path::new(&config), khz) {
+            Ok(copy) => {
+                logger.info(format!("Running {config} at {khz} kHz"));
+                let path = copy.path().to_string_lossy().into_owned();
+                (executable, path, Some(copy))
+            }
+            Err(error) => {
+                logger.warning(format!(
+                    "Could not apply the {khz} kHz JTAG speed ({error}); using the profile's own"
+                ));
+                (executable, config, None)
+            }
+        }
+    }
+
+    /// OpenOCD arguments for `config_path`, with any adapter pin placed first.
+    pub(crate) fn openocd_args(&self, config_path: &str, command: &str) -> Vec<String> {
+        let mut args = adapter_serial_args(self.adapter_serial.as_deref());
+        args.extend([
+            "-f".to_string(),
+            config_path.to_string(),
+            "-c".to_string(),
+            command.to_string(),
+        ]);
+        args
+    }
+}
+
 pub struct CommandOptions {
     pub log_duration: bool,
     pub cleanup_temp_files: bool,
     pub duration_target: Option<Arc<Mutex<Option<Duration>>>>,
+    pub watchdog: WatchdogBudget,
+    /// Records the run into the operation's session transcript.
+    pub session: Option<SessionRecorder>,
+    /// The profile copy the process runs, deleted once it has finished.
+    pub profile_copy: Option<ProfileCopy>,
     pub on_complete: CompletionCallback,
 }
 
@@ -210,7 +286,7 @@ impl ProcessExecutor {
         command.args(args);
         command.stdout(Stdio::piped());
         command.stderr(Stdio::piped());
-        command.creation_flags(CREATE_NO_WINDOW | CREATE_SUSPENDED);
+        contain_command(&mut command);
         command
     }
 
@@ -229,10 +305,10 @@ impl ProcessExecutor {
         on_line_callback: LineCallback,
         options: CommandOptions,
     ) -> Result<(), String> {
-        // Enforce suspended creation even for commands that did not originate
-        // from `prepare_command`; otherwise they could execute before Job Object
-        // assignment completes.
-        command.creation_flags(CREATE_NO_WINDOW | CREATE_SUSPENDED);
+        // Enforce containment even for commands that did not originate from
+        // `prepare_command`; otherwise they could execute before the process
+        // tree is owned.
+        contain_command(&mut command);
 
         let mut operation_state = self.operation_state.lock().unwrap();
         if operation_state.active {
@@ -252,11 +328,15 @@ impl ProcessExecutor {
                 Self::cleanup_temp_firmware_if_requested(options.cleanup_temp_files, &self.logger);
                 self.logger.error(&error_msg);
                 *self.completion_status.lock().unwrap() =
-                    CompletionStatus::Failed(error_msg.clone());
+                    CompletionStatus::Failed(error_msg.clone().into());
                 return Err(error_msg);
             }
         };
         operation_state.active = true;
+        let run = options
+            .session
+            .as_ref()
+            .map(|session| session.begin_run(&command));
 
         match command.spawn() {
             Ok(mut child) => {
@@ -278,18 +358,27 @@ impl ProcessExecutor {
                     if direct_cleanup_result.is_err() || job_cleanup_result.is_err() {
                         operation_state.restart_blocked = Some(error_msg.clone());
                     }
+                    if let Some(run) = &run {
+                        run.record_exit(&error_msg);
+                    }
                     Self::cleanup_temp_firmware_if_requested(
                         options.cleanup_temp_files,
                         &self.logger,
                     );
                     self.logger.error(&error_msg);
                     *self.completion_status.lock().unwrap() =
-                        CompletionStatus::Failed(error_msg.clone());
+                        CompletionStatus::Failed(error_msg.clone().into());
                     operation_state.active = false;
                     return Err(error_msg);
                 }
 
-                let reader_threads = self.attach_readers(&mut child, on_line_callback);
+                let watchdog = Arc::new(Watchdog::new(options.watchdog, Instant::now()));
+                let reader_threads = self.attach_readers(
+                    &mut child,
+                    on_line_callback,
+                    Arc::clone(&watchdog),
+                    run.clone(),
+                );
 
                 // Wait in a separate thread for the process to complete
                 let logger = self.logger.clone();
@@ -305,7 +394,8 @@ impl ProcessExecutor {
 
                 thread::spawn(move || {
                     let mut options = options;
-                    let mut wait_result = Self::wait_for_process(&mut child);
+                    let mut wait_result =
+                        Self::wait_for_process(&mut child, &watchdog, &process_job, &logger);
                     let mut restart_blocked = None;
                     let elapsed = start_time.lock().unwrap().map(|start| start.elapsed());
 
@@ -341,12 +431,21 @@ impl ProcessExecutor {
                             ProcessWaitOutcome::Exited(_) => {
                                 ProcessWaitOutcome::Failed(reader_error)
                             }
+                            // The stall is the root cause; descendants holding the
+                            // pipes open are a consequence of the forced stop.
+                            ProcessWaitOutcome::TimedOut(timeout) => {
+                                ProcessWaitOutcome::TimedOut(timeout)
+                            }
                             ProcessWaitOutcome::Failed(error) => {
                                 ProcessWaitOutcome::Failed(format!("{error}; {reader_error}"))
                             }
                         };
                     }
 
+                    if let Some(run) = &run {
+                        run.record_exit(wait_result.describe());
+                    }
+
                     let mut operation_state = worker_operation_state.lock().unwrap();
                     if operation_state.generation != operation_generation {
                         logger.debug("Discarding stale process finalization after operation reset");
@@ -363,6 +462,7 @@ impl ProcessExecutor {
                     // Remove the shared input before publishing any terminal status so an
                     // older worker can never delete a replacement operation's input file.
                     Self::cleanup_temp_firmware_if_requested(options.cleanup_temp_files, &logger);
+                    drop(options.profile_copy.take());
 
                     match wait_result {
                         ProcessWaitOutcome::Exited(exit_status) => {
@@ -425,6 +525,20 @@ impl ProcessExecutor {
                                 );
                             }
                         }
+                        ProcessWaitOutcome::TimedOut(timeout) => {
+                            if let Some(on_complete) = options.on_complete.take() {
+                                on_complete(false);
+                            }
+
+                            logger.error(timeout.to_string());
+
+                            // A stall is more specific than the generic failure a
+                            // completion callback reports for a non-zero exit.
+                            let mut status = completion_status.lock().unwrap();
+                            if !matches!(*status, CompletionStatus::DnaReadCompleted(_)) {
+                                *status = CompletionStatus::Failed(timeout.into());
+                            }
+                        }
                         ProcessWaitOutcome::Failed(error_msg) => {
                             if let Some(on_complete) = options.on_complete.take() {
                                 on_complete(false);
@@ -444,10 +558,13 @@ impl ProcessExecutor {
             }
             Err(e) => {
                 let error_msg = format!("Failed to start process: {e}");
+                if let Some(run) = &run {
+                    run.record_exit(&error_msg);
+                }
                 Self::cleanup_temp_firmware_if_requested(options.cleanup_temp_files, &self.logger);
                 self.logger.error(&error_msg);
                 *self.completion_status.lock().unwrap() =
-                    CompletionStatus::Failed(error_msg.clone());
+                    CompletionStatus::Failed(error_msg.clone().into());
                 operation_state.active = false;
 
                 Err(error_msg)
@@ -455,12 +572,61 @@ impl ProcessExecutor {
         }
     }
 
-    fn wait_for_process(child: &mut Child) -> ProcessWaitOutcome {
-        match child.wait() {
-            Ok(status) => ProcessWaitOutcome::Exited(status),
-            Err(error) => {
-                ProcessWaitOutcome::Failed(format!("Failed to wait for process: {error}"))
+    fn wait_for_process(
+        child: &mut Child,
+        watchdog: &Watchdog,
+        process_job: &Weak<ProcessJob>,
+        logger: &Logger,
+    ) -> ProcessWaitOutcome {
+        if watchdog.is_unlimited() {
+            return match child.wait() {
+                Ok(status) => ProcessWaitOutcome::Exited(status),
+                Err(error) => {
+                    ProcessWaitOutcome::Failed(format!("Failed to wait for process: {error}"))
+                }
+            };
+        }
+
+        loop {
+            match child.try_wait() {
+                Ok(Some(status)) => return ProcessWaitOutcome::Exited(status),
+                Ok(None) => {}
+                Err(error) => {
+                    return ProcessWaitOutcome::Failed(format!(
+                        "Failed to wait for process: {error}"
+                    ));
+                }
+            }
+
+            if let Some(timeout) = watchdog.check(Instant::now()) {
+                Self::stop_stalled_process(child, process_job, logger);
+                return ProcessWaitOutcome::TimedOut(timeout);
             }
+
+            thread::sleep(WATCHDOG_POLL_INTERVAL);
+        }
+    }
+
+    fn stop_stalled_process(child: &mut Child, process_job: &Weak<ProcessJob>, logger: &Logger) {
+        // Stop the whole tree, not just the direct child: a hung adapter call is
+        // usually inside the OpenOCD process, but helpers share the same Job.
+        if let Some(process_job) = process_job.upgrade()
+            && let Err(error) = process_job.terminate_and_wait(
+                PROCESS_TERMINATION_TIMEOUT,
+                PROCESS_TERMINATION_POLL_INTERVAL,
+            )
+        {
+            logger.error(format!("Failed to stop stalled process tree: {error}"));
+        }
+
+        // The final Job retirement decides restart safety; this only makes sure
+        // the direct child is reaped without an unbounded wait.
+        if let Err(error) = Self::terminate_child_bounded(
+            child,
+            PROCESS_TERMINATION_TIMEOUT,
+            PROCESS_TERMINATION_POLL_INTERVAL,
+        ) {
+            logger.error(format!("Failed to stop stalled process: {error}"));
         }
     }
 
@@ -526,7 +692,7 @@ impl ProcessExecutor {
             *status,
             CompletionStatus::DnaReadCompleted(_) | CompletionStatus::Failed(_)
         ) {
-            *status = CompletionStatus::Failed(error_message);
+            *status = CompletionStatus::Failed(error_message.into());
         }
     }
 
@@ -568,7 +734,13 @@ impl ProcessExecutor {
         }
     }
 
-    fn attach_readers(&self, child: &mut Child, line_callback: LineCallback) -> ReaderThreads {
+    fn attach_readers(
+        &self,
+        child: &mut Child,
+        line_callback: LineCallback,
+        watchdog: Arc<Watchdog>,
+        run: Option<RunRecorder>,
+    ) -> ReaderThreads {
         // Wrap the callback in an Arc for sharing between threads
         let callback_arc = Arc::new(line_callback);
         let callbacks_enabled = Arc::new(AtomicBool::new(true));
@@ -582,11 +754,17 @@ impl ProcessExecutor {
             let callback_opt = Arc::clone(&callback_arc);
             let callbacks_enabled = Arc::clone(&callbacks_enabled);
             let completion_tx = completion_tx.clone();
+            let watchdog = Arc::clone(&watchdog);
+            let run = run.clone();
             expected_completions += 1;
 
             reader_threads.push(thread::spawn(move || {
                 let reader = BufReader::new(stdout);
                 let result = Self::read_output_lines(reader, "stdout", |line| {
+                    watchdog.record_output(Instant::now());
+                    if let Some(run) = &run {
+                        run.record_line(OutputStream::Stdout, line);
+                    }
                     stdout_logger.output(line);
 
                     // Forward every line to the callback
@@ -615,6 +793,10 @@ impl ProcessExecutor {
             reader_threads.push(thread::spawn(move || {
                 let reader = BufReader::new(stderr);
                 let result = Self::read_output_lines(reader, "stderr", |line| {
+                    watchdog.record_output(Instant::now());
+                    if let Some(run) = &run {
+                        run.record_line(OutputStream::Stderr, line);
+                    }
                     stderr_logger.error(line);
 
                     // Forward all stderr output so domain parsers can observe both
@@ -671,23 +853,58 @@ impl ProcessExecutor {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::device_programmer::DnaInfo;
+    use crate::device_programmer::{DeviceDna, DnaInfo};
+    use crate::utils::process_job::hide_console;
+    use crate::utils::temp_dir::TempDir;
     use std::io::Cursor;
     use std::sync::atomic::{AtomicBool, Ordering};
-    use std::time::{SystemTime, UNIX_EPOCH};
 
     const TREE_STARTED_MARKER_ENV: &str = "DMA_TOOLS_TREE_STARTED_MARKER";
     const TREE_COMPLETED_MARKER_ENV: &str = "DMA_TOOLS_TREE_COMPLETED_MARKER";
 
-    fn unique_marker_path(label: &str) -> std::path::PathBuf {
-        let nonce = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_nanos();
-        std::env::temp_dir().join(format!(
-            "dma-tools-process-test-{}-{label}-{nonce}",
-            std::process::id()
-        ))
+    #[cfg(windows)]
+    fn exit_command(code: i32) -> Command {
+        ProcessExecutor::prepare_command("cmd", &["/C", &format!("exit /B {code}")])
+    }
+
+    #[cfg(unix)]
+    fn exit_command(code: i32) -> Command {
+        ProcessExecutor::prepare_command("sh", &["-c", &format!("exit {code}")])
+    }
+
+    #[cfg(windows)]
+    fn echo_command(text: &str) -> Command {
+        ProcessExecutor::prepare_command("cmd", &["/C", &format!("echo {text}")])
+    }
+
+    #[cfg(unix)]
+    fn echo_command(text: &str) -> Command {
+        ProcessExecutor::prepare_command("echo", &[text])
+    }
+
+    #[cfg(windows)]
+    fn sleep_args(duration: Duration) -> (&'static str, Vec<String>) {
+        let script = format!("Start-Sleep -Milliseconds {}", duration.as_millis());
+        (
+            "powershell.exe",
+            vec![
+                "-NoProfile".to_string(),
+                "-NonInteractive".to_string(),
+                "-Command".to_string(),
+                script,
+            ],
+        )
+    }
+
+    #[cfg(unix)]
+    fn sleep_args(duration: Duration) -> (&'static str, Vec<String>) {
+        ("sleep", vec![format!("{:.3}", duration.as_secs_f64())])
+    }
+
+    fn sleep_command(duration: Duration) -> Command {
+        let (program, args) = sleep_args(duration);
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        ProcessExecutor::prepare_command(program, &args)
     }
 
     fn wait_for_terminal_status(executor: &ProcessExecutor) -> CompletionStatus {
@@ -779,12 +996,11 @@ mod tests {
             // while holding the operation-state lock across both writes.
             let mut operation_state = executor.operation_state.lock().unwrap();
             operation_state.restart_blocked = Some(reason.to_string());
-            *executor.completion_status.lock().unwrap() =
-                CompletionStatus::Failed(reason.to_string());
+            *executor.completion_status.lock().unwrap() = CompletionStatus::Failed(reason.into());
         }
 
         let (status, safe_to_restart) = executor.completion_snapshot();
-        assert_eq!(status, CompletionStatus::Failed(reason.to_string()));
+        assert_eq!(status, CompletionStatus::Failed(reason.into()));
         assert!(!safe_to_restart);
 
         let error = executor
@@ -799,7 +1015,7 @@ mod tests {
         executor.reset().unwrap();
 
         let completion_status = executor.get_completion_status_arc();
-        let command = ProcessExecutor::prepare_command("cmd", &["/C", "exit /B 0"]);
+        let command = exit_command(0);
 
         executor
             .execute_command(
@@ -809,12 +1025,14 @@ mod tests {
                     log_duration: false,
                     cleanup_temp_files: false,
                     duration_target: None,
+                    watchdog: WatchdogBudget::unlimited(),
+                    session: None,
+                    profile_copy: None,
                     on_complete: Some(Box::new(move |command_succeeded| {
                         assert!(command_succeeded);
                         *completion_status.lock().unwrap() =
                             CompletionStatus::DnaReadCompleted(DnaInfo {
-                                dna_value: "0x1".to_string(),
-                                dna_raw_value: "1".to_string(),
+                                dna: DeviceDna::from_value(0x1).unwrap(),
                                 device_type: "test".to_string(),
                             });
                     })),
@@ -824,7 +1042,7 @@ mod tests {
 
         match wait_for_terminal_status(&executor) {
             CompletionStatus::DnaReadCompleted(info) => {
-                assert_eq!(info.dna_value, "0x1");
+                assert_eq!(info.dna.value(), 0x1);
             }
             status => panic!("expected DNA completion from callback, got {status:?}"),
         }
@@ -837,7 +1055,7 @@ mod tests {
 
         let callback_called = Arc::new(AtomicBool::new(false));
         let callback_called_clone = Arc::clone(&callback_called);
-        let command = ProcessExecutor::prepare_command("cmd", &["/C", "exit /B 7"]);
+        let command = exit_command(7);
 
         executor
             .execute_command(
@@ -847,6 +1065,9 @@ mod tests {
                     log_duration: false,
                     cleanup_temp_files: false,
                     duration_target: None,
+                    watchdog: WatchdogBudget::unlimited(),
+                    session: None,
+                    profile_copy: None,
                     on_complete: Some(Box::new(move |command_succeeded| {
                         assert!(!command_succeeded);
                         callback_called_clone.store(true, Ordering::SeqCst);
@@ -857,7 +1078,7 @@ mod tests {
 
         match wait_for_terminal_status(&executor) {
             CompletionStatus::Failed(error) => {
-                assert!(error.contains("exit code"));
+                assert!(error.to_string().contains("exit code"));
                 assert!(callback_called.load(Ordering::SeqCst));
             }
             status => panic!("expected failed completion, got {status:?}"),
@@ -871,7 +1092,7 @@ mod tests {
 
         let callback_finished = Arc::new(AtomicBool::new(false));
         let callback_finished_clone = Arc::clone(&callback_finished);
-        let command = ProcessExecutor::prepare_command("cmd", &["/C", "echo final-line"]);
+        let command = echo_command("final-line");
 
         executor
             .execute_command(
@@ -886,6 +1107,9 @@ mod tests {
                     log_duration: false,
                     cleanup_temp_files: false,
                     duration_target: None,
+                    watchdog: WatchdogBudget::unlimited(),
+                    session: None,
+                    profile_copy: None,
                     on_complete: None,
                 },
             )
@@ -907,7 +1131,7 @@ mod tests {
         executor.reset().unwrap();
 
         let completion_status = executor.get_completion_status_arc();
-        let command = ProcessExecutor::prepare_command("cmd", &["/C", "exit /B 9"]);
+        let command = exit_command(9);
 
         executor
             .execute_command(
@@ -917,10 +1141,13 @@ mod tests {
                     log_duration: false,
                     cleanup_temp_files: false,
                     duration_target: None,
+                    watchdog: WatchdogBudget::unlimited(),
+                    session: None,
+                    profile_copy: None,
                     on_complete: Some(Box::new(move |command_succeeded| {
                         assert!(!command_succeeded);
                         *completion_status.lock().unwrap() =
-                            CompletionStatus::Failed("localized command failure".to_string());
+                            CompletionStatus::Failed("localized command failure".into());
                     })),
                 },
             )
@@ -928,7 +1155,7 @@ mod tests {
 
         assert_eq!(
             wait_for_terminal_status(&executor),
-            CompletionStatus::Failed("localized command failure".to_string())
+            CompletionStatus::Failed("localized command failure".into())
         );
     }
 
@@ -937,15 +1164,7 @@ mod tests {
         let executor = ProcessExecutor::new(Logger::new("ProcessExecutorTest"));
         executor.reset().unwrap();
         let completion_status = executor.get_completion_status_arc();
-        let command = ProcessExecutor::prepare_command(
-            "powershell.exe",
-            &[
-                "-NoProfile",
-                "-NonInteractive",
-                "-Command",
-                "Start-Sleep -Seconds 30",
-            ],
-        );
+        let command = sleep_command(Duration::from_secs(30));
 
         executor
             .execute_command(
@@ -955,6 +1174,9 @@ mod tests {
                     log_duration: false,
                     cleanup_temp_files: false,
                     duration_target: None,
+                    watchdog: WatchdogBudget::unlimited(),
+                    session: None,
+                    profile_copy: None,
                     on_complete: None,
                 },
             )
@@ -970,6 +1192,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn silent_process_is_stopped_by_the_no_output_watchdog() {
+        let executor = ProcessExecutor::new(Logger::new("ProcessWatchdogTest"));
+        executor.reset().unwrap();
+        let command = sleep_command(Duration::from_secs(30));
+        let no_output = Duration::from_millis(500);
+
+        let started_at = Instant::now();
+        executor
+            .execute_command(
+                command,
+                None,
+                CommandOptions {
+                    log_duration: false,
+                    cleanup_temp_files: false,
+                    duration_target: None,
+                    watchdog: WatchdogBudget {
+                        no_output: Some(no_output),
+                        total_runtime: None,
+                    },
+                    session: None,
+                    profile_copy: None,
+                    on_complete: Some(Box::new(|command_succeeded| {
+                        assert!(!command_succeeded);
+                    })),
+                },
+            )
+            .expect("silent test command should start");
+
+        assert_eq!(
+            wait_for_terminal_status(&executor),
+            CompletionStatus::Failed(WatchdogTimeout::NoOutput(no_output).into())
+        );
+        assert!(started_at.elapsed() < Duration::from_secs(5));
+        assert!(executor.completion_snapshot().1);
+    }
+
     #[test]
     fn stale_terminator_cannot_stop_a_replacement_operation() {
         let executor = ProcessExecutor::new(Logger::new("ProcessGenerationTest"));
@@ -977,15 +1236,7 @@ mod tests {
         let stale_terminator = executor.process_terminator();
 
         executor.reset().unwrap();
-        let command = ProcessExecutor::prepare_command(
-            "powershell.exe",
-            &[
-                "-NoProfile",
-                "-NonInteractive",
-                "-Command",
-                "Start-Sleep -Milliseconds 150",
-            ],
-        );
+        let command = sleep_command(Duration::from_millis(150));
         executor
             .execute_command(
                 command,
@@ -994,6 +1245,9 @@ mod tests {
                     log_duration: false,
                     cleanup_temp_files: false,
                     duration_target: None,
+                    watchdog: WatchdogBudget::unlimited(),
+                    session: None,
+                    profile_copy: None,
                     on_complete: None,
                 },
             )
@@ -1013,15 +1267,7 @@ mod tests {
         executor.reset().unwrap();
         let stale_callback_called = Arc::new(AtomicBool::new(false));
         let callback_flag = Arc::clone(&stale_callback_called);
-        let long_command = ProcessExecutor::prepare_command(
-            "powershell.exe",
-            &[
-                "-NoProfile",
-                "-NonInteractive",
-                "-Command",
-                "Start-Sleep -Seconds 30",
-            ],
-        );
+        let long_command = sleep_command(Duration::from_secs(30));
         executor
             .execute_command(
                 long_command,
@@ -1030,6 +1276,9 @@ mod tests {
                     log_duration: false,
                     cleanup_temp_files: false,
                     duration_target: None,
+                    watchdog: WatchdogBudget::unlimited(),
+                    session: None,
+                    profile_copy: None,
                     on_complete: Some(Box::new(move |_| {
                         callback_flag.store(true, Ordering::SeqCst);
                     })),
@@ -1046,7 +1295,7 @@ mod tests {
             "reset must not wait for the command's 30-second natural exit"
         );
 
-        let replacement = ProcessExecutor::prepare_command("cmd", &["/C", "exit /B 0"]);
+        let replacement = exit_command(0);
         executor
             .execute_command(
                 replacement,
@@ -1055,6 +1304,9 @@ mod tests {
                     log_duration: false,
                     cleanup_temp_files: false,
                     duration_target: None,
+                    watchdog: WatchdogBudget::unlimited(),
+                    session: None,
+                    profile_copy: None,
                     on_complete: None,
                 },
             )
@@ -1077,14 +1329,11 @@ mod tests {
 
     #[test]
     fn bounded_child_termination_does_not_wait_for_natural_exit() {
-        let mut child = Command::new("powershell.exe")
-            .args([
-                "-NoProfile",
-                "-NonInteractive",
-                "-Command",
-                "Start-Sleep -Seconds 30",
-            ])
-            .creation_flags(CREATE_NO_WINDOW)
+        let (program, args) = sleep_args(Duration::from_secs(30));
+        let mut command = Command::new(program);
+        command.args(args);
+        hide_console(&mut command);
+        let mut child = command
             .spawn()
             .expect("long-running test process should start");
 
@@ -1104,8 +1353,9 @@ mod tests {
 
     #[test]
     fn terminal_status_retires_descendants_that_outlive_the_direct_child() {
-        let started_marker = unique_marker_path("started");
-        let completed_marker = unique_marker_path("completed");
+        let markers = TempDir::new("process-tree");
+        let started_marker = markers.join("started");
+        let completed_marker = markers.join("completed");
         let current_exe = std::env::current_exe().expect("test executable path should be known");
         let current_exe = current_exe
             .to_str()
@@ -1134,6 +1384,9 @@ mod tests {
                     log_duration: false,
                     cleanup_temp_files: false,
                     duration_target: None,
+                    watchdog: WatchdogBudget::unlimited(),
+                    session: None,
+                    profile_copy: None,
                     on_complete: None,
                 },
             )
@@ -1154,9 +1407,6 @@ mod tests {
             !completed_marker.exists(),
             "terminal status must not leave the descendant alive to finish later"
         );
-
-        let _ = fs::remove_file(started_marker);
-        let _ = fs::remove_file(completed_marker);
     }
 
     #[test]
@@ -0,0 +1,518 @@
+//! Session transcripts: every OpenOCD run of an operation, recorded so a
+//! customer can send one file that replays the failure exactly.
+
+use crate::device_programmer::monitor::OperationMonitor;
+use crate::device_programmer::operation::{OperationSnapshot, ProgressSnapshot};
+use crate::device_programmer::process::ProcessTerminator;
+use crate::utils::app_dirs;
+use crate::utils::logger::Logger;
+use serde::{Deserialize, Serialize};
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const SESSION_DIR: &str = "sessions";
+const SESSION_VERSION: u32 = 1;
+const MAX_SESSION_FILES: usize = 50;
+const MAX_RUN_LINES: usize = 50_000;
+/// Lets the monitor finish its last observations after the final line, as it
+/// does while the real process is being reaped.
+const REPLAY_SETTLE_TIME: Duration = Duration::from_millis(200);
+
+/// Variables that change where OpenOCD finds its scripts and libraries.
+/// Transcripts leave the machine, so the rest of the environment is not kept
+/// and `PATH` is cut down to the entries that hold the OpenOCD binary.
+const RECORDED_ENV: &[&str] = &["PATH", "OPENOCD_SCRIPTS", "LD_LIBRARY_PATH"];
+
+/// Sessions are kept in the per-user data directory, or in the working
+/// directory when there is none.
+pub fn default_session_dir() -> PathBuf {
+    app_dirs::data_dir()
+        .map(|dir| dir.join(SESSION_DIR))
+        .unwrap_or_else(|| PathBuf::from(SESSION_DIR))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptLine {
+    /// Milliseconds since the session started.
+    pub at_ms: u64,
+    pub stream: OutputStream,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessRun {
+    pub program: String,
+    pub args: Vec<String>,
+    pub working_dir: Option<String>,
+    pub env: Vec<(String, String)>,
+    pub started_at_ms: u64,
+    pub lines: Vec<TranscriptLine>,
+    pub lines_truncated: bool,
+    /// How the process ended, as reported to the user.
+    pub exit: Option<String>,
+}
+
+/// The operation result as the app saw it when the session ended.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotRecord {
+    pub status: String,
+    pub option: Option<String>,
+    pub adapter_serial: Option<String>,
+    pub stage: String,
+    pub sectors_total: usize,
+    pub sectors_normal: usize,
+    pub duration_ms: Option<u64>,
+    pub assessment: String,
+    pub terminated_early: bool,
+}
+
+impl From<&OperationSnapshot> for SnapshotRecord {
+    fn from(snapshot: &OperationSnapshot) -> Self {
+        Self {
+            status: format!("{:?}", snapshot.status),
+            option: snapshot
+                .option
+                .as_ref()
+                .map(|option| option.get_display_name().to_string()),
+            adapter_serial: snapshot.adapter_serial.clone(),
+            stage: format!("{:?}", snapshot.stage),
+            sectors_total: snapshot.sector_stats.total,
+            sectors_normal: snapshot.sector_stats.normal,
+            duration_ms: snapshot
+                .duration
+                .map(|duration| u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)),
+            assessment: format!("{:?}", snapshot.assessment),
+            terminated_early: snapshot.terminated_early,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionFile {
+    pub version: u32,
+    pub tool_version: String,
+    /// Wall-clock start, in milliseconds since the Unix epoch.
+    pub started_at: u64,
+    pub runs: Vec<ProcessRun>,
+    pub snapshot: Option<SnapshotRecord>,
+}
+
+impl SessionFile {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|error| format!("Failed to read session {}: {error}", path.display()))?;
+        let session: Self = serde_json::from_str(&contents)
+            .map_err(|error| format!("Failed to parse session {}: {error}", path.display()))?;
+        if session.version > SESSION_VERSION {
+            return Err(format!(
+                "Session {} was written by a newer version (format {})",
+                path.display(),
+                session.version
+            ));
+        }
+        Ok(session)
+    }
+}
+
+struct SessionState {
+    started: Instant,
+    file: SessionFile,
+}
+
+/// Collects the runs of one operation. Clones share the same session.
+#[derive(Clone)]
+pub struct SessionRecorder {
+    state: Arc<Mutex<SessionState>>,
+}
+
+impl SessionRecorder {
+    pub fn new() -> Self {
+        let started_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| {
+                u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+            });
+        Self {
+            state: Arc::new(Mutex::new(SessionState {
+                started: Instant::now(),
+                file: SessionFile {
+                    version: SESSION_VERSION,
+                    tool_version: env!("CARGO_PKG_VERSION").to_string(),
+                    started_at,
+                    runs: Vec::new(),
+                    snapshot: None,
+                },
+            })),
+        }
+    }
+
+    /// Starts recording a run of `command`, which is about to be spawned.
+    pub fn begin_run(&self, command: &Command) -> RunRecorder {
+        let mut env: Vec<(String, String)> = RECORDED_ENV
+            .iter()
+            .filter_map(|name| Some((name.to_string(), std::env::var(name).ok()?)))
+            .collect();
+        for (name, value) in command.get_envs() {
+            let name = name.to_string_lossy().into_owned();
+            env.retain(|(recorded, _)| *recorded != name);
+            if let Some(value) = value {
+                env.push((name, value.to_string_lossy().into_owned()));
+            }
+        }
+        let program = command.get_program();
+        env = env
+            .into_iter()
+            .filter_map(|(name, value)| {
+                if name != "PATH" {
+                    return Some((name, value));
+                }
+                let entries = path_entries_holding(&value, program)?;
+                Some((name, entries))
+            })
+            .collect();
+
+        let mut state = self.state.lock().unwrap();
+        let started_at_ms = elapsed_ms(state.started);
+        state.file.runs.push(ProcessRun {
+            program: command.get_program().to_string_lossy().into_owned(),
+            args: command
+                .get_args()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect(),
+            working_dir: command
+                .get_current_dir()
+                .map(Path::to_path_buf)
+                .or_else(|| std::env::current_dir().ok())
+                .map(|dir| dir.display().to_string()),
+            env,
+            started_at_ms,
+            lines: Vec::new(),
+            lines_truncated: false,
+            exit: None,
+        });
+
+        RunRecorder {
+            state: Arc::clone(&self.state),
+            index: state.file.runs.len() - 1,
+        }
+    }
+
+    /// Stores the final snapshot and writes the session into `dir`, removing
+    /// the oldest sessions beyond the retention limit.
+    pub fn save(&self, snapshot: &OperationSnapshot, dir: &Path) -> Result<PathBuf, String> {
+        let mut state = self.state.lock().unwrap();
+        state.file.snapshot = Some(SnapshotRecord::from(snapshot));
+
+        fs::create_dir_all(dir).map_err(|error| {
+            format!(
+                "Failed to create session directory {}: {error}",
+                dir.display()
+            )
+        })?;
+        let path = dir.join(session_file_name(state.file.started_at));
+        let contents = serde_json::to_string_pretty(&state.file)
+            .map_err(|error| format!("Failed to serialize session: {error}"))?;
+        fs::write(&path, contents)
+            .map_err(|error| format!("Failed to write session {}: {error}", path.display()))?;
+        drop(state);
+
+        prune_sessions(dir);
+        Ok(path)
+    }
+}
+
+/// Appends to one run of a session from the process reader threads.
+#[derive(Clone)]
+pub struct RunRecorder {
+    state: Arc<Mutex<SessionState>>,
+    index: usize,
+}
+
+impl RunRecorder {
+    pub fn record_line(&self, stream: OutputStream, text: &str) {
+        let mut state = self.state.lock().unwrap();
+        let at_ms = elapsed_ms(state.started);
+        let run = &mut state.file.runs[self.index];
+        if run.lines.len() >= MAX_RUN_LINES {
+            run.lines_truncated = true;
+            return;
+        }
+        run.lines.push(TranscriptLine {
+            at_ms,
+            stream,
+            text: text.to_string(),
+        });
+    }
+
+    pub fn record_exit(&self, outcome: impl Into<String>) {
+        self.state.lock().unwrap().file.runs[self.index].exit = Some(outcome.into());
+    }
+}
+
+/// The `PATH` entries that hold `program`, the only ones that decide which
+/// binary runs. `None` when there are none, e.g. for a program given by path.
+fn path_entries_holding(path: &str, program: &OsStr) -> Option<String> {
+    let program = Path::new(program);
+    if program.components().count() != 1 {
+        return None;
+    }
+    let names = [
+        program.to_path_buf(),
+        program.with_extension(std::env::consts::EXE_EXTENSION),
+    ];
+    let entries: Vec<PathBuf> = std::env::split_paths(path)
+        .filter(|dir| names.iter().any(|name| dir.join(name).is_file()))
+        .collect();
+    if entries.is_empty() {
+        return None;
+    }
+    let joined = std::env::join_paths(entries).ok()?;
+    Some(joined.to_string_lossy().into_owned())
+}
+
+fn elapsed_ms(started: Instant) -> u64 {
+    u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Zero-padded so a directory listing shows the sessions in order.
+fn session_file_name(started_at: u64) -> String {
+    format!("session-{started_at:020}.json")
+}
+
+/// The start time in a session file name.
+fn session_started_at(path: &Path) -> Option<u64> {
+    path.file_name()?
+        .to_str()?
+        .strip_prefix("session-")?
+        .strip_suffix(".json")?
+        .parse()
+        .ok()
+}
+
+fn prune_sessions(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut sessions: Vec<(u64, PathBuf)> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter_map(|path| Some((session_started_at(&path)?, path)))
+        .collect();
+    if sessions.len() <= MAX_SESSION_FILES {
+        return;
+    }
+
+    sessions.sort();
+    let excess = sessions.len() - MAX_SESSION_FILES;
+    for (_, path) in sessions.into_iter().take(excess) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// What the line monitor concluded from a replayed run.
+#[derive(Debug, Clone, Copy)]
+pub struct ReplayReport {
+    pub progress: ProgressSnapshot,
+    pub terminated_early: bool,
+}
+
+impl ReplayReport {
+    /// Where the replay disagrees with the recorded result.
+    pub fn differences(&self, recorded: &SnapshotRecord) -> Vec<String> {
+        let mut differences = Vec::new();
+        let stage = format!("{:?}", self.progress.stage);
+        if stage != recorded.stage {
+            differences.push(format!("stage {stage}, recorded {}", recorded.stage));
+        }
+        let stats = self.progress.sector_stats;
+        if (stats.total, stats.normal) != (recorded.sectors_total, recorded.sectors_normal) {
+            differences.push(format!(
+                "{}/{} normal sector writes, recorded {}/{}",
+                stats.normal, stats.total, recorded.sectors_normal, recorded.sectors_total
+            ));
+        }
+        if self.terminated_early != recorded.terminated_early {
+            differences.push(format!(
+                "terminated early: {}, recorded {}",
+                self.terminated_early, recorded.terminated_early
+            ));
+        }
+        differences
+    }
+}
+
+/// Feeds the last run of `session`, the one its snapshot describes, back
+/// through the line monitor with the recorded timing.
+pub fn replay_session(session: &SessionFile, logger: &Logger) -> Result<ReplayReport, String> {
+    let run = session
+        .runs
+        .last()
+        .ok_or_else(|| "Session contains no OpenOCD run".to_string())?;
+
+    let monitor = OperationMonitor::new(logger.clone());
+    let stopped = Arc::new(AtomicBool::new(false));
+    let terminator: ProcessTerminator = {
+        let stopped = Arc::clone(&stopped);
+        Arc::new(move || {
+            stopped.store(true, Ordering::SeqCst);
+            Ok(())
+        })
+    };
+    let on_line = monitor.create_line_monitor(logger.clone(), terminator);
+
+    let replay_started = Instant::now();
+    for line in &run.lines {
+        // The real process is gone once the monitor stops it.
+        if stopped.load(Ordering::SeqCst) {
+            break;
+        }
+        let due = Duration::from_millis(line.at_ms.saturating_sub(run.started_at_ms));
+        thread::sleep(due.saturating_sub(replay_started.elapsed()));
+        on_line(&line.text);
+    }
+    if !stopped.load(Ordering::SeqCst) {
+        thread::sleep(REPLAY_SETTLE_TIME);
+    }
+    monitor.stop_monitor_thread();
+
+    Ok(ReplayReport {
+        progress: monitor.progress_snapshot(),
+        terminated_early: monitor.was_terminated_early(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::temp_dir::TempDir;
+
+    fn run_with_sectors(elapsed_ms: u32, spacing_ms: u64) -> ProcessRun {
+        ProcessRun {
+            program: "openocd".to_string(),
+            args: Vec::new(),
+            working_dir: None,
+            env: Vec::new(),
+            started_at_ms: 1000,
+            lines: (0..12)
+                .map(|sector| TranscriptLine {
+                    at_ms: 1000 + spacing_ms * (sector + 1),
+                    stream: OutputStream::Stderr,
+                    text: format!("Info : sector {sector} took {elapsed_ms} ms"),
+                })
+                .collect(),
+            lines_truncated: false,
+            exit: Some("exit status: 0".to_string()),
+        }
+    }
+
+    fn session(runs: Vec<ProcessRun>) -> SessionFile {
+        SessionFile {
+            version: SESSION_VERSION,
+            tool_version: "test".to_string(),
+            started_at: 0,
+            runs,
+            snapshot: None,
+        }
+    }
+
+    #[test]
+    fn replaying_dropped_writes_stops_the_run_like_the_live_monitor() {
+        let report = replay_session(
+            &session(vec![run_with_sectors(1, 5)]),
+            &Logger::new("SessionReplayTest"),
+        )
+        .unwrap();
+
+        assert!(report.terminated_early);
+        assert!(report.progress.sector_stats.total >= 10);
+        assert_eq!(report.progress.sector_stats.normal, 0);
+    }
+
+    #[test]
+    fn replay_uses_the_last_run_and_reports_mismatches() {
+        let preflight = ProcessRun {
+            lines: Vec::new(),
+            ..run_with_sectors(1, 5)
+        };
+        let report = replay_session(
+            &session(vec![preflight, run_with_sectors(25, 1)]),
+            &Logger::new("SessionReplayTest"),
+        )
+        .unwrap();
+        let recorded = SnapshotRecord {
+            status: "Completed".to_string(),
+            option: None,
+            adapter_serial: None,
+            stage: "WritingSector(11)".to_string(),
+            sectors_total: 12,
+            sectors_normal: 12,
+            duration_ms: None,
+            assessment: "Success".to_string(),
+            terminated_early: true,
+        };
+
+        assert!(!report.terminated_early);
+        assert_eq!(
+            report.differences(&recorded),
+            ["terminated early: false, recorded true"]
+        );
+    }
+
+    #[test]
+    fn only_path_entries_holding_the_program_are_recorded() {
+        let dir = TempDir::new("session-path");
+        let (tools, home) = (dir.join("tools"), dir.join("home"));
+        fs::create_dir_all(&tools).unwrap();
+        fs::create_dir_all(&home).unwrap();
+        let binary = format!("openocd{}", std::env::consts::EXE_SUFFIX);
+        fs::write(tools.join(binary), "").unwrap();
+        let path = std::env::join_paths([&home, &tools]).unwrap();
+
+        let recorded_path = |program: &Path| {
+            let mut command = Command::new(program);
+            command.env("PATH", &path);
+            let session = SessionRecorder::new();
+            session.begin_run(&command);
+            let state = session.state.lock().unwrap();
+            let run = &state.file.runs[0];
+            run.env
+                .iter()
+                .find(|(name, _)| name == "PATH")
+                .map(|(_, value)| value.clone())
+        };
+
+        assert_eq!(
+            recorded_path(Path::new("openocd")),
+            Some(tools.display().to_string())
+        );
+        assert_eq!(recorded_path(&tools.join("openocd")), None);
+    }
+
+    #[test]
+    fn sessions_beyond_the_retention_limit_are_pruned_oldest_first() {
+        let dir = TempDir::new("sessions");
+        for started_at in 1..MAX_SESSION_FILES as u64 + 2 {
+            fs::write(dir.join(session_file_name(started_at)), "{}").unwrap();
+        }
+        fs::write(dir.join("notes.txt"), "kept").unwrap();
+
+        prune_sessions(dir.path());
+
+        assert!(!dir.join(session_file_name(1)).exists());
+        assert!(dir.join(session_file_name(2)).exists());
+        assert!(dir.join("notes.txt").exists());
+    }
+}
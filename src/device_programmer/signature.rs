@@ -0,0 +1,489 @@
+use crate::utils::app_dirs;
+use crate::utils::firmware_archive;
+use crate::utils::localization::{Language, TextKey, format_translation, translate};
+use crate::utils::logger::Logger;
+use ed25519_dalek::pkcs8::DecodePublicKey;
+use ed25519_dalek::{PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH, Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Folder of public keys shipped next to the tool, like `OpenOCD/`.
+const BUNDLED_KEYS_DIR: &str = "keys";
+const KEY_EXTENSIONS: [&str; 2] = ["pub", "pem"];
+/// File in the bundled `keys/` folder naming the policy a deployment enforces.
+const PINNED_POLICY_FILE: &str = "signature_policy.txt";
+/// Bytes of the key's SHA-256 shown as its ID.
+const KEY_ID_BYTES: usize = 8;
+
+/// How a flash reacts to firmware without a valid signature from a trusted key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SignaturePolicy {
+    /// Flash without looking for a signature.
+    #[default]
+    Off,
+    /// Verify, flag unsigned or invalid images and flash anyway.
+    Warn,
+    /// Refuse to flash an image that is unsigned or fails verification.
+    Block,
+}
+
+impl SignaturePolicy {
+    pub const ALL: [Self; 3] = [Self::Off, Self::Warn, Self::Block];
+
+    /// Verification is on by default once trusted keys are installed.
+    pub fn default_for(keys: &TrustedKeys) -> Self {
+        if keys.is_empty() {
+            Self::Off
+        } else {
+            Self::Warn
+        }
+    }
+
+    /// The policy pinned by `keys/signature_policy.txt` beside the executable,
+    /// which the options screen then cannot change.
+    pub fn load_pinned(logger: &Logger) -> Option<Self> {
+        Self::read_pinned(
+            &Path::new(BUNDLED_KEYS_DIR).join(PINNED_POLICY_FILE),
+            logger,
+        )
+    }
+
+    /// Reads a pin holding `Off`, `Warn` or `Block`. A pin that cannot be
+    /// read or names no policy enforces `Block`.
+    fn read_pinned(path: &Path, logger: &Logger) -> Option<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return None,
+            Err(error) => {
+                logger.error(format!(
+                    "Failed to read {}: {error}; enforcing Block",
+                    path.display()
+                ));
+                return Some(Self::Block);
+            }
+        };
+        let policy = match contents.trim().to_ascii_lowercase().as_str() {
+            "off" => Self::Off,
+            "warn" => Self::Warn,
+            "block" => Self::Block,
+            other => {
+                logger.error(format!(
+                    "{} names no signature policy (`{other}`); enforcing Block",
+                    path.display()
+                ));
+                Self::Block
+            }
+        };
+        logger.info(format!(
+            "Firmware signature policy pinned to {policy:?} by {}",
+            path.display()
+        ));
+        Some(policy)
+    }
+
+    pub fn label_key(&self) -> TextKey {
+        match self {
+            Self::Off => TextKey::SignatureOff,
+            Self::Warn => TextKey::SignatureWarn,
+            Self::Block => TextKey::SignatureBlock,
+        }
+    }
+}
+
+/// An Ed25519 public key firmware may be signed with. The ID is derived from
+/// the key itself, so a renamed key file cannot pass for another key.
+#[derive(Debug, Clone)]
+pub struct TrustedKey {
+    pub name: String,
+    pub id: String,
+    key: VerifyingKey,
+}
+
+impl TrustedKey {
+    pub fn new(name: impl Into<String>, key: VerifyingKey) -> Self {
+        let digest = Sha256::digest(key.as_bytes());
+        Self {
+            name: name.into(),
+            id: digest[..KEY_ID_BYTES]
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect(),
+            key,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TrustedKeys {
+    keys: Vec<TrustedKey>,
+}
+
+impl TrustedKeys {
+    /// Loads the keys bundled in `keys/` and those installed in the `keys`
+    /// folder of the per-user data directory.
+    pub fn load_default(logger: &Logger) -> Self {
+        let mut dirs = vec![PathBuf::from(BUNDLED_KEYS_DIR)];
+        dirs.extend(app_dirs::data_dir().map(|dir| dir.join(BUNDLED_KEYS_DIR)));
+        Self::load_dirs(&dirs, logger)
+    }
+
+    /// Reads every `.pub`/`.pem` file in `dirs`. Each holds a PEM public key
+    /// or the 32 key bytes in hex; the file name is the key's name.
+    pub fn load_dirs(dirs: &[PathBuf], logger: &Logger) -> Self {
+        let mut trusted = Self::default();
+        for dir in dirs {
+            let Ok(entries) = fs::read_dir(dir) else {
+                continue;
+            };
+            let mut paths: Vec<PathBuf> = entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.extension().is_some_and(|extension| {
+                        KEY_EXTENSIONS
+                            .iter()
+                            .any(|known| extension.eq_ignore_ascii_case(known))
+                    })
+                })
+                .collect();
+            paths.sort();
+
+            for path in paths {
+                match read_public_key(&path) {
+                    Ok(key) => {
+                        let name = path
+                            .file_stem()
+                            .unwrap_or_default()
+                            .to_string_lossy()
+                            .into_owned();
+                        let key = TrustedKey::new(name, key);
+                        logger.info(format!(
+                            "Trusting firmware signing key {} ({}) from {}",
+                            key.name,
+                            key.id,
+                            path.display()
+                        ));
+                        trusted.add(key);
+                    }
+                    Err(error) => logger.warning(format!(
+                        "Ignoring firmware signing key {}: {error}",
+                        path.display()
+                    )),
+                }
+            }
+        }
+        trusted
+    }
+
+    pub fn add(&mut self, key: TrustedKey) {
+        if !self.keys.iter().any(|known| known.id == key.id) {
+            self.keys.push(key);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    fn signer_of(&self, image: &[u8], signature: &Signature) -> Option<&TrustedKey> {
+        self.keys
+            .iter()
+            .find(|trusted| trusted.key.verify_strict(image, signature).is_ok())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureOutcome {
+    Verified { key_name: String, key_id: String },
+    Unsigned,
+    Invalid(InvalidSignature),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidSignature {
+    Unreadable(String),
+    Malformed,
+    NoTrustedKeys,
+    NoMatchingKey,
+}
+
+impl SignatureOutcome {
+    pub fn is_verified(&self) -> bool {
+        matches!(self, Self::Verified { .. })
+    }
+
+    pub fn describe(&self, lang: &Language) -> String {
+        match self {
+            Self::Verified { key_name, key_id } => format_translation(
+                translate(TextKey::SignatureVerified, lang),
+                &[key_name, key_id],
+            ),
+            Self::Unsigned => translate(TextKey::SignatureMissing, lang).to_string(),
+            Self::Invalid(reason) => {
+                let reason = match reason {
+                    InvalidSignature::Unreadable(error) => error.clone(),
+                    InvalidSignature::Malformed => {
+                        translate(TextKey::SignatureMalformed, lang).to_string()
+                    }
+                    InvalidSignature::NoTrustedKeys => {
+                        translate(TextKey::SignatureNoTrustedKeys, lang).to_string()
+                    }
+                    InvalidSignature::NoMatchingKey => {
+                        translate(TextKey::SignatureNoMatchingKey, lang).to_string()
+                    }
+                };
+                format_translation(translate(TextKey::SignatureInvalid, lang), &[&reason])
+            }
+        }
+    }
+}
+
+/// What the flasher needs to verify an image's signature, and where it
+/// records the outcome for the result screen.
+#[derive(Debug, Clone)]
+pub struct SignatureCheck {
+    pub policy: SignaturePolicy,
+    pub keys: Arc<TrustedKeys>,
+    pub outcome: Arc<Mutex<Option<SignatureOutcome>>>,
+    pub language: Language,
+}
+
+impl SignatureCheck {
+    /// Verifies `image`, the bytes about to be flashed, against the detached
+    /// signature next to `source`. `Err` carries the localized reason the
+    /// flash must not start.
+    pub fn check(&self, image: &[u8], source: &Path, logger: &Logger) -> Result<(), String> {
+        let outcome = verify_image(image, source, &self.keys);
+        let message = outcome.describe(&self.language);
+        *self.outcome.lock().unwrap() = Some(outcome.clone());
+
+        if outcome.is_verified() {
+            logger.success(message);
+            return Ok(());
+        }
+        match self.policy {
+            SignaturePolicy::Block => {
+                logger.error(&message);
+                Err(message)
+            }
+            SignaturePolicy::Off | SignaturePolicy::Warn => {
+                logger.warning(message);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The detached signature of an image: `<image>.sig`, inside the same
+/// archive for images listed from one.
+pub fn signature_path(image: &Path) -> PathBuf {
+    let mut path = image.as_os_str().to_os_string();
+    path.push(".sig");
+    PathBuf::from(path)
+}
+
+pub fn verify_image(image: &[u8], source: &Path, keys: &TrustedKeys) -> SignatureOutcome {
+    let signature = match firmware_archive::read_image(&signature_path(source)) {
+        Ok(signature) => signature,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            return SignatureOutcome::Unsigned;
+        }
+        Err(error) => {
+            return SignatureOutcome::Invalid(InvalidSignature::Unreadable(error.to_string()));
+        }
+    };
+    let Some(signature) = parse_signature(&signature) else {
+        return SignatureOutcome::Invalid(InvalidSignature::Malformed);
+    };
+    if keys.is_empty() {
+        return SignatureOutcome::Invalid(InvalidSignature::NoTrustedKeys);
+    }
+
+    match keys.signer_of(image, &signature) {
+        Some(key) => SignatureOutcome::Verified {
+            key_name: key.name.clone(),
+            key_id: key.id.clone(),
+        },
+        None => SignatureOutcome::Invalid(InvalidSignature::NoMatchingKey),
+    }
+}
+
+/// Accepts the raw 64 signature bytes (as `openssl pkeyutl -sign -rawin`
+/// writes them) or the same bytes in hex.
+fn parse_signature(bytes: &[u8]) -> Option<Signature> {
+    if bytes.len() == SIGNATURE_LENGTH {
+        return Some(Signature::from_bytes(bytes.try_into().ok()?));
+    }
+    let decoded: [u8; SIGNATURE_LENGTH] = decode_hex(std::str::from_utf8(bytes).ok()?)?;
+    Some(Signature::from_bytes(&decoded))
+}
+
+fn read_public_key(path: &Path) -> Result<VerifyingKey, String> {
+    let text = fs::read_to_string(path).map_err(|error| error.to_string())?;
+    if text.contains("-----BEGIN") {
+        return VerifyingKey::from_public_key_pem(&text).map_err(|error| error.to_string());
+    }
+    let bytes: [u8; PUBLIC_KEY_LENGTH] = decode_hex(&text)
+        .ok_or_else(|| "expected a PEM public key or 64 hex digits".to_string())?;
+    VerifyingKey::from_bytes(&bytes).map_err(|error| error.to_string())
+}
+
+fn decode_hex<const N: usize>(text: &str) -> Option<[u8; N]> {
+    let text = text.trim();
+    if text.len() != N * 2 || !text.is_ascii() {
+        return None;
+    }
+    let mut bytes = [0u8; N];
+    for (byte, pair) in bytes.iter_mut().zip(text.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?;
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::temp_dir::TempDir;
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD as BASE64;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    const IMAGE: &[u8] = b"bitstream";
+
+    fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn trusted(seed: u8) -> TrustedKeys {
+        let mut keys = TrustedKeys::default();
+        keys.add(TrustedKey::new(
+            "release",
+            signing_key(seed).verifying_key(),
+        ));
+        keys
+    }
+
+    fn temporary_image(dir: &TempDir) -> PathBuf {
+        let path = dir.join("card.bin");
+        fs::write(&path, IMAGE).unwrap();
+        path
+    }
+
+    fn check(policy: SignaturePolicy, keys: TrustedKeys) -> SignatureCheck {
+        SignatureCheck {
+            policy,
+            keys: Arc::new(keys),
+            outcome: Arc::default(),
+            language: Language::English,
+        }
+    }
+
+    #[test]
+    fn image_signed_by_a_trusted_key_is_verified() {
+        let dir = TempDir::new("signed");
+        let image = temporary_image(&dir);
+        let signature = signing_key(1).sign(IMAGE).to_bytes();
+        fs::write(signature_path(&image), signature).unwrap();
+        let keys = trusted(1);
+        let key_id = keys.keys[0].id.clone();
+
+        assert_eq!(
+            verify_image(IMAGE, &image, &keys),
+            SignatureOutcome::Verified {
+                key_name: "release".into(),
+                key_id
+            }
+        );
+        // Hex-encoded signatures are accepted too.
+        let hex: String = signature.iter().map(|byte| format!("{byte:02x}")).collect();
+        fs::write(signature_path(&image), hex).unwrap();
+        assert!(verify_image(IMAGE, &image, &keys).is_verified());
+
+        assert_eq!(
+            verify_image(b"tampered", &image, &keys),
+            SignatureOutcome::Invalid(InvalidSignature::NoMatchingKey)
+        );
+        assert_eq!(
+            verify_image(IMAGE, &image, &trusted(2)),
+            SignatureOutcome::Invalid(InvalidSignature::NoMatchingKey)
+        );
+    }
+
+    #[test]
+    fn block_policy_refuses_unsigned_images_and_warn_flags_them() {
+        let dir = TempDir::new("signed");
+        let image = temporary_image(&dir);
+
+        let blocking = check(SignaturePolicy::Block, trusted(1));
+        let error = blocking
+            .check(IMAGE, &image, &Logger::new("SignatureTest"))
+            .unwrap_err();
+        assert!(error.contains("not signed"), "{error}");
+
+        let warning = check(SignaturePolicy::Warn, trusted(1));
+        assert!(
+            warning
+                .check(IMAGE, &image, &Logger::new("SignatureTest"))
+                .is_ok()
+        );
+        assert_eq!(
+            *warning.outcome.lock().unwrap(),
+            Some(SignatureOutcome::Unsigned)
+        );
+    }
+
+    #[test]
+    fn keys_are_read_from_pem_and_hex_files() {
+        let dir = TempDir::new("keys");
+        let key = signing_key(3).verifying_key();
+        // `openssl pkey -pubout` for the same key.
+        let der_prefix = [
+            0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+        ];
+        let pem = pem_encode(&[&der_prefix[..], key.as_bytes()].concat());
+        fs::write(dir.join("vendor.pem"), pem).unwrap();
+        let hex: String = signing_key(4)
+            .verifying_key()
+            .as_bytes()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        fs::write(dir.join("lab.pub"), hex).unwrap();
+        fs::write(dir.join("broken.pub"), "not a key").unwrap();
+        fs::write(dir.join("readme.txt"), "ignored").unwrap();
+
+        let keys =
+            TrustedKeys::load_dirs(&[dir.path().to_path_buf()], &Logger::new("SignatureTest"));
+        let names: Vec<&str> = keys.keys.iter().map(|key| key.name.as_str()).collect();
+        assert_eq!(names, ["lab", "vendor"]);
+        assert_eq!(keys.keys[1].id, TrustedKey::new("", key).id);
+    }
+
+    #[test]
+    fn pinned_policy_is_read_and_falls_back_to_block() {
+        let dir = TempDir::new("signature-pin");
+        let path = dir.join(PINNED_POLICY_FILE);
+        let logger = Logger::new("SignatureTest");
+
+        assert_eq!(SignaturePolicy::read_pinned(&path, &logger), None);
+        fs::write(&path, "warn\n").unwrap();
+        assert_eq!(
+            SignaturePolicy::read_pinned(&path, &logger),
+            Some(SignaturePolicy::Warn)
+        );
+        fs::write(&path, "strict").unwrap();
+        assert_eq!(
+            SignaturePolicy::read_pinned(&path, &logger),
+            Some(SignaturePolicy::Block)
+        );
+    }
+
+    fn pem_encode(der: &[u8]) -> String {
+        let encoded = BASE64.encode(der);
+        format!("-----BEGIN PUBLIC KEY-----\n{encoded}\n-----END PUBLIC KEY-----\n")
+    }
+}
@@ -1,11 +1,18 @@
+use super::SCRIPT_DIR;
+use super::adapter::AdapterKind;
+use super::dna::DeviceDna;
+use super::watchdog::WatchdogTimeout;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DnaInfo {
-    pub dna_value: String,     // Hex value (e.g., 0x...)
-    pub dna_raw_value: String, // Binary value (e.g., 0011...)
+    pub dna: DeviceDna,
     pub device_type: String,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FlashingOption {
     CH347_35T,
     CH347_75T,
@@ -20,10 +27,29 @@ pub enum FlashingOption {
 }
 
 // Constants for OpenOCD executable paths
+#[cfg(windows)]
 pub const OPENOCD_CH347_PATH: &str = "OpenOCD/openocd-347.exe";
+#[cfg(windows)]
 pub const OPENOCD_RS232_PATH: &str = "OpenOCD/openocd.exe";
+#[cfg(not(windows))]
+pub const OPENOCD_CH347_PATH: &str = "OpenOCD/openocd-347";
+#[cfg(not(windows))]
+pub const OPENOCD_RS232_PATH: &str = "OpenOCD/openocd";
 
 impl FlashingOption {
+    pub const ALL: [FlashingOption; 10] = [
+        FlashingOption::CH347_35T,
+        FlashingOption::CH347_75T,
+        FlashingOption::CH347_100T,
+        FlashingOption::RS232_35T,
+        FlashingOption::RS232_75T,
+        FlashingOption::RS232_100T,
+        FlashingOption::DnaCH347,
+        FlashingOption::DnaRS232_35T,
+        FlashingOption::DnaRS232_75T,
+        FlashingOption::DnaRS232_100T,
+    ];
+
     pub fn is_dna_read(&self) -> bool {
         matches!(
             self,
@@ -46,6 +72,20 @@ impl FlashingOption {
         )
     }
 
+    /// DNA read profile for the same adapter and density, used to identify
+    /// the board before a flash.
+    pub fn dna_read_option(&self) -> Option<FlashingOption> {
+        match self {
+            FlashingOption::CH347_35T | FlashingOption::CH347_75T | FlashingOption::CH347_100T => {
+                Some(FlashingOption::DnaCH347)
+            }
+            FlashingOption::RS232_35T => Some(FlashingOption::DnaRS232_35T),
+            FlashingOption::RS232_75T => Some(FlashingOption::DnaRS232_75T),
+            FlashingOption::RS232_100T => Some(FlashingOption::DnaRS232_100T),
+            _ => None,
+        }
+    }
+
     pub fn get_command_args(&self) -> (&'static str, &'static str) {
         match self {
             FlashingOption::CH347_35T => (OPENOCD_CH347_PATH, "OpenOCD/flash/xc7a35T.cfg"),
@@ -93,6 +133,73 @@ impl FlashingOption {
             | FlashingOption::DnaRS232_100T => "FTDI Driver",
         }
     }
+
+    /// FPGA density targeted by the profile. The CH347 DNA profile probes any
+    /// density, so it has none.
+    pub fn density(&self) -> Option<&'static str> {
+        match self {
+            FlashingOption::CH347_35T
+            | FlashingOption::RS232_35T
+            | FlashingOption::DnaRS232_35T => Some("35T"),
+            FlashingOption::CH347_75T
+            | FlashingOption::RS232_75T
+            | FlashingOption::DnaRS232_75T => Some("75T"),
+            FlashingOption::CH347_100T
+            | FlashingOption::RS232_100T
+            | FlashingOption::DnaRS232_100T => Some("100T"),
+            FlashingOption::DnaCH347 => None,
+        }
+    }
+
+    pub fn adapter_kind(&self) -> AdapterKind {
+        match self {
+            FlashingOption::CH347_35T
+            | FlashingOption::CH347_75T
+            | FlashingOption::CH347_100T
+            | FlashingOption::DnaCH347 => AdapterKind::Ch347,
+            FlashingOption::RS232_35T
+            | FlashingOption::RS232_75T
+            | FlashingOption::RS232_100T
+            | FlashingOption::DnaRS232_35T
+            | FlashingOption::DnaRS232_75T
+            | FlashingOption::DnaRS232_100T => AdapterKind::Ftdi,
+        }
+    }
+}
+
+type CommandArgsFn = dyn Fn(&FlashingOption) -> (String, String) + Send + Sync;
+
+/// Where an option's OpenOCD executable and profile come from. Runs use the
+/// bundled tree unless a replacement is installed, which is how the flows are
+/// driven against the scripted stand-in in `examples/fake_openocd.rs`.
+#[derive(Clone, Default)]
+pub struct CommandArgsSource {
+    replacement: Option<Arc<CommandArgsFn>>,
+}
+
+impl CommandArgsSource {
+    #[cfg(test)]
+    pub fn replaced_with(
+        resolve: impl Fn(&FlashingOption) -> (String, String) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            replacement: Some(Arc::new(resolve)),
+        }
+    }
+
+    /// Executable and profile paths, ready to launch.
+    pub fn resolve(&self, option: &FlashingOption) -> (String, String) {
+        match &self.replacement {
+            Some(replacement) => replacement(option),
+            None => {
+                let (command, config) = option.get_command_args();
+                (
+                    format!("{SCRIPT_DIR}/{command}"),
+                    format!("{SCRIPT_DIR}/{config}"),
+                )
+            }
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -101,7 +208,42 @@ pub enum CompletionStatus {
     InProgress(String),
     Completed,                 // Flashing
     DnaReadCompleted(DnaInfo), // DNA read
-    Failed(String),
+    Failed(FailureReason),
+}
+
+/// Why an operation failed. Watchdog expiry stays typed so the UI can
+/// distinguish a hung adapter from an OpenOCD error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FailureReason {
+    Error(String),
+    Timeout(WatchdogTimeout),
+}
+
+impl From<String> for FailureReason {
+    fn from(message: String) -> Self {
+        Self::Error(message)
+    }
+}
+
+impl From<&str> for FailureReason {
+    fn from(message: &str) -> Self {
+        Self::Error(message.to_string())
+    }
+}
+
+impl From<WatchdogTimeout> for FailureReason {
+    fn from(timeout: WatchdogTimeout) -> Self {
+        Self::Timeout(timeout)
+    }
+}
+
+impl fmt::Display for FailureReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error(message) => f.write_str(message),
+            Self::Timeout(timeout) => timeout.fmt(f),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -151,6 +293,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn flash_profiles_map_to_a_dna_profile_for_the_same_adapter() {
+        for opt in [
+            FlashingOption::CH347_35T,
+            FlashingOption::CH347_100T,
+            FlashingOption::RS232_35T,
+            FlashingOption::RS232_75T,
+            FlashingOption::RS232_100T,
+        ] {
+            let dna = opt.dna_read_option().unwrap();
+            assert!(dna.is_dna_read());
+            assert_eq!(dna.adapter_kind(), opt.adapter_kind());
+            assert!(
+                dna.density()
+                    .is_none_or(|density| Some(density) == opt.density())
+            );
+        }
+        assert_eq!(FlashingOption::DnaCH347.dna_read_option(), None);
+    }
+
     // ── Command args ──
 
     #[test]
@@ -256,8 +418,7 @@ mod tests {
             CompletionStatus::Completed,
             CompletionStatus::Failed("err".into()),
             CompletionStatus::DnaReadCompleted(DnaInfo {
-                dna_value: "0x1".into(),
-                dna_raw_value: "1".into(),
+                dna: DeviceDna::from_value(0x1).unwrap(),
                 device_type: "CH347".into(),
             }),
         ];
@@ -296,13 +457,12 @@ mod tests {
     #[test]
     fn dna_completed_preserves_info() {
         let info = DnaInfo {
-            dna_value: "0x00641CC26AE96854".into(),
-            dna_raw_value: "00110010".into(),
+            dna: DeviceDna::from_value(0x00641CC26AE96854).unwrap(),
             device_type: "CH347".into(),
         };
         let status = CompletionStatus::DnaReadCompleted(info);
         if let CompletionStatus::DnaReadCompleted(inner) = status {
-            assert_eq!(inner.dna_value, "0x00641CC26AE96854");
+            assert_eq!(inner.dna.value(), 0x00641CC26AE96854);
             assert_eq!(inner.device_type, "CH347");
         } else {
             panic!("Expected DnaReadCompleted");
@@ -313,7 +473,7 @@ mod tests {
     fn failed_preserves_message() {
         let status = CompletionStatus::Failed("cable disconnected".into());
         if let CompletionStatus::Failed(msg) = status {
-            assert_eq!(msg, "cable disconnected");
+            assert_eq!(msg.to_string(), "cable disconnected");
         } else {
             panic!("Expected Failed");
         }
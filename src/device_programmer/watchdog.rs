@@ -0,0 +1,236 @@
+use super::FlashingOption;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// OpenOCD reports every erased/written sector, but verification of a full
+// 100T image can stay quiet for a while, so the flash idle budget is generous.
+const FLASH_NO_OUTPUT_TIMEOUT: Duration = Duration::from_secs(120);
+const FLASH_TOTAL_RUNTIME_TIMEOUT: Duration = Duration::from_secs(20 * 60);
+const DNA_NO_OUTPUT_TIMEOUT: Duration = Duration::from_secs(20);
+const DNA_TOTAL_RUNTIME_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Limits applied to a single OpenOCD run. `None` disables that check.
+/// Saved as whole seconds, with `null` for no limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchdogBudget {
+    #[serde(rename = "no_output_secs", with = "seconds")]
+    pub no_output: Option<Duration>,
+    #[serde(rename = "total_runtime_secs", with = "seconds")]
+    pub total_runtime: Option<Duration>,
+}
+
+impl WatchdogBudget {
+    #[cfg(test)]
+    pub const fn unlimited() -> Self {
+        Self {
+            no_output: None,
+            total_runtime: None,
+        }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.no_output.is_none() && self.total_runtime.is_none()
+    }
+}
+
+/// Per-operation-type budgets used by `FlashingManager`. A budget missing
+/// from the settings keeps its default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchdogConfig {
+    pub flash: WatchdogBudget,
+    pub dna_read: WatchdogBudget,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            flash: WatchdogBudget {
+                no_output: Some(FLASH_NO_OUTPUT_TIMEOUT),
+                total_runtime: Some(FLASH_TOTAL_RUNTIME_TIMEOUT),
+            },
+            dna_read: WatchdogBudget {
+                no_output: Some(DNA_NO_OUTPUT_TIMEOUT),
+                total_runtime: Some(DNA_TOTAL_RUNTIME_TIMEOUT),
+            },
+        }
+    }
+}
+
+impl WatchdogConfig {
+    pub fn budget_for(&self, option: &FlashingOption) -> WatchdogBudget {
+        if option.is_dna_read() {
+            self.dna_read
+        } else {
+            self.flash
+        }
+    }
+}
+
+mod seconds {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(
+        limit: &Option<Duration>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        limit.map(|limit| limit.as_secs()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Duration>, D::Error> {
+        Ok(Option::<u64>::deserialize(deserializer)?.map(Duration::from_secs))
+    }
+}
+
+/// Which budget an operation exceeded, carrying the configured limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchdogTimeout {
+    NoOutput(Duration),
+    TotalRuntime(Duration),
+}
+
+impl WatchdogTimeout {
+    pub fn limit(&self) -> Duration {
+        match self {
+            Self::NoOutput(limit) | Self::TotalRuntime(limit) => *limit,
+        }
+    }
+}
+
+impl fmt::Display for WatchdogTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoOutput(limit) => write!(
+                f,
+                "Adapter stopped responding: no output for {}s",
+                limit.as_secs()
+            ),
+            Self::TotalRuntime(limit) => write!(
+                f,
+                "Adapter stopped responding: operation exceeded {}s",
+                limit.as_secs()
+            ),
+        }
+    }
+}
+
+/// Tracks output activity for one process and reports the first exceeded budget.
+pub(crate) struct Watchdog {
+    budget: WatchdogBudget,
+    started_at: Instant,
+    last_output_at: Mutex<Instant>,
+}
+
+impl Watchdog {
+    pub(crate) fn new(budget: WatchdogBudget, started_at: Instant) -> Self {
+        Self {
+            budget,
+            started_at,
+            last_output_at: Mutex::new(started_at),
+        }
+    }
+
+    pub(crate) fn is_unlimited(&self) -> bool {
+        self.budget.is_unlimited()
+    }
+
+    pub(crate) fn record_output(&self, at: Instant) {
+        let mut last_output_at = self.last_output_at.lock().unwrap();
+        if at > *last_output_at {
+            *last_output_at = at;
+        }
+    }
+
+    pub(crate) fn check(&self, now: Instant) -> Option<WatchdogTimeout> {
+        // Runtime is checked first so a process that is both silent and over its
+        // total budget reports the hard limit rather than the idle one.
+        if let Some(limit) = self.budget.total_runtime
+            && now.saturating_duration_since(self.started_at) >= limit
+        {
+            return Some(WatchdogTimeout::TotalRuntime(limit));
+        }
+
+        let last_output_at = *self.last_output_at.lock().unwrap();
+        if let Some(limit) = self.budget.no_output
+            && now.saturating_duration_since(last_output_at) >= limit
+        {
+            return Some(WatchdogTimeout::NoOutput(limit));
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budget(no_output: u64, total_runtime: u64) -> WatchdogBudget {
+        WatchdogBudget {
+            no_output: Some(Duration::from_secs(no_output)),
+            total_runtime: Some(Duration::from_secs(total_runtime)),
+        }
+    }
+
+    #[test]
+    fn output_resets_the_idle_budget() {
+        let start = Instant::now();
+        let watchdog = Watchdog::new(budget(5, 60), start);
+
+        watchdog.record_output(start + Duration::from_secs(4));
+        assert_eq!(watchdog.check(start + Duration::from_secs(8)), None);
+        assert_eq!(
+            watchdog.check(start + Duration::from_secs(9)),
+            Some(WatchdogTimeout::NoOutput(Duration::from_secs(5)))
+        );
+    }
+
+    #[test]
+    fn total_runtime_applies_even_with_continuous_output() {
+        let start = Instant::now();
+        let watchdog = Watchdog::new(budget(5, 10), start);
+
+        for second in 1..=10 {
+            watchdog.record_output(start + Duration::from_secs(second));
+        }
+
+        assert_eq!(
+            watchdog.check(start + Duration::from_secs(10)),
+            Some(WatchdogTimeout::TotalRuntime(Duration::from_secs(10)))
+        );
+    }
+
+    #[test]
+    fn unlimited_budget_never_expires() {
+        let start = Instant::now();
+        let watchdog = Watchdog::new(WatchdogBudget::unlimited(), start);
+
+        assert_eq!(watchdog.check(start + Duration::from_secs(86_400)), None);
+    }
+
+    #[test]
+    fn out_of_order_output_does_not_rewind_activity() {
+        let start = Instant::now();
+        let watchdog = Watchdog::new(budget(5, 60), start);
+
+        watchdog.record_output(start + Duration::from_secs(4));
+        watchdog.record_output(start + Duration::from_secs(1));
+        assert_eq!(watchdog.check(start + Duration::from_secs(8)), None);
+    }
+
+    #[test]
+    fn budgets_are_selected_by_operation_type() {
+        let config = WatchdogConfig::default();
+
+        assert_eq!(
+            config.budget_for(&FlashingOption::DnaCH347),
+            config.dna_read
+        );
+        assert_eq!(config.budget_for(&FlashingOption::RS232_75T), config.flash);
+    }
+}
@@ -0,0 +1,242 @@
+use super::status::LicenseStatus;
+use crate::device_programmer::{DeviceDna, DnaFormat};
+use crate::utils::app_dirs;
+use crate::utils::logger::Logger;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const ALLOWLIST_DIR: &str = "allowlists";
+const ALLOWLIST_EXTENSIONS: [&str; 2] = ["csv", "txt"];
+const REVOKED_MARKER: &str = "revoked";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    label: String,
+    revoked: bool,
+}
+
+/// DNA values mapped to license labels, merged from every allowlist file.
+///
+/// Each line reads `<dna>,<label>` or `<dna>,<label>,revoked`. Blank lines and
+/// lines starting with `#` are skipped. A revocation in any file wins over a
+/// license granted in another.
+#[derive(Debug, Default)]
+pub struct DnaAllowlist {
+    entries: HashMap<DeviceDna, Entry>,
+}
+
+impl DnaAllowlist {
+    /// Loads `allowlists/` next to the tool and in the per-user data directory.
+    pub fn load_default(logger: &Logger) -> Self {
+        let mut directories = vec![PathBuf::from(ALLOWLIST_DIR)];
+        directories.extend(app_dirs::data_dir().map(|dir| dir.join(ALLOWLIST_DIR)));
+
+        let mut allowlist = Self::default();
+        for directory in directories {
+            allowlist.load_directory(&directory, logger);
+        }
+        if !allowlist.is_empty() {
+            logger.info(format!(
+                "Loaded {} allowlisted DNA values",
+                allowlist.entries.len()
+            ));
+        }
+        allowlist
+    }
+
+    fn load_directory(&mut self, directory: &Path, logger: &Logger) {
+        let Ok(read_dir) = fs::read_dir(directory) else {
+            return;
+        };
+
+        let mut files: Vec<PathBuf> = read_dir
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && has_allowlist_extension(path))
+            .collect();
+        files.sort();
+
+        for path in files {
+            match fs::read_to_string(&path) {
+                Ok(contents) => {
+                    for warning in self.add_source(&path.display().to_string(), &contents) {
+                        logger.warning(warning);
+                    }
+                }
+                Err(error) => logger.warning(format!(
+                    "Failed to read allowlist {}: {error}",
+                    path.display()
+                )),
+            }
+        }
+    }
+
+    /// Merges one allowlist file. Returns a `source:line` warning for every
+    /// line that was skipped or conflicted with an earlier entry.
+    pub fn add_source(&mut self, source: &str, contents: &str) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let problem = match parse_line(line) {
+                Ok((dna, entry)) => self.insert(dna, entry),
+                Err(error) => Some(error),
+            };
+            if let Some(problem) = problem {
+                warnings.push(format!("{source}:{}: {problem}", index + 1));
+            }
+        }
+
+        warnings
+    }
+
+    fn insert(&mut self, dna: DeviceDna, entry: Entry) -> Option<String> {
+        let Some(existing) = self.entries.get_mut(&dna) else {
+            self.entries.insert(dna, entry);
+            return None;
+        };
+
+        if existing.revoked {
+            None
+        } else if entry.revoked {
+            *existing = entry;
+            None
+        } else if existing.label != entry.label {
+            Some(format!(
+                "{dna} is already licensed to {}; ignoring {}",
+                existing.label, entry.label
+            ))
+        } else {
+            None
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn dnas(&self) -> impl Iterator<Item = DeviceDna> + '_ {
+        self.entries.keys().copied()
+    }
+
+    pub fn check(&self, dna: &DeviceDna) -> LicenseStatus {
+        match self.entries.get(dna) {
+            Some(entry) if entry.revoked => LicenseStatus::Revoked(entry.label.clone()),
+            Some(entry) => LicenseStatus::Licensed(entry.label.clone()),
+            None => LicenseStatus::Unknown,
+        }
+    }
+}
+
+fn has_allowlist_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            ALLOWLIST_EXTENSIONS
+                .iter()
+                .any(|allowed| extension.eq_ignore_ascii_case(allowed))
+        })
+}
+
+fn parse_line(line: &str) -> Result<(DeviceDna, Entry), String> {
+    let (dna_text, rest) = line
+        .split_once(',')
+        .ok_or_else(|| "expected `<dna>,<label>`".to_string())?;
+    let dna_text = dna_text.trim();
+    let dna = DeviceDna::parse(dna_text, DnaFormat::Hex)
+        .map_err(|error| format!("{error}: {dna_text}"))?;
+
+    let rest = rest.trim();
+    let (label, revoked) = match rest.rsplit_once(',') {
+        Some((label, marker)) if marker.trim().eq_ignore_ascii_case(REVOKED_MARKER) => {
+            (label.trim(), true)
+        }
+        _ if rest.eq_ignore_ascii_case(REVOKED_MARKER) => ("", true),
+        _ => (rest, false),
+    };
+    if label.is_empty() && !revoked {
+        return Err(format!("missing license label for {dna_text}"));
+    }
+
+    Ok((
+        dna,
+        Entry {
+            label: label.to_string(),
+            revoked,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dna(value: u64) -> DeviceDna {
+        DeviceDna::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn reports_licensed_unknown_and_revoked_boards() {
+        let mut allowlist = DnaAllowlist::default();
+        let warnings = allowlist.add_source(
+            "customers.csv",
+            "# dna,label[,revoked]\n\
+             0x0040A1B2C3D4E5F6, Acme Labs\n\
+             \n\
+             11223344556677,Old Customer,REVOKED\n",
+        );
+
+        assert!(warnings.is_empty(), "{warnings:?}");
+        assert_eq!(
+            allowlist.check(&dna(0x0040A1B2C3D4E5F6)),
+            LicenseStatus::Licensed("Acme Labs".to_string())
+        );
+        assert_eq!(
+            allowlist.check(&dna(0x11223344556677)),
+            LicenseStatus::Revoked("Old Customer".to_string())
+        );
+        assert_eq!(allowlist.check(&dna(0x1)), LicenseStatus::Unknown);
+    }
+
+    #[test]
+    fn revocation_in_any_file_wins() {
+        for sources in [
+            ["0xAB,Acme", "0xab,revoked"],
+            ["0xab,revoked", "0x00AB,Acme"],
+        ] {
+            let mut allowlist = DnaAllowlist::default();
+            for (index, contents) in sources.iter().enumerate() {
+                allowlist.add_source(&format!("list{index}.csv"), contents);
+            }
+
+            assert!(matches!(
+                allowlist.check(&dna(0xAB)),
+                LicenseStatus::Revoked(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn malformed_and_conflicting_lines_are_reported_with_their_location() {
+        let mut allowlist = DnaAllowlist::default();
+        let warnings = allowlist.add_source(
+            "lab.txt",
+            "0xAB,Acme\n0xZZ,Broken\nno separator\n0xCD,\n0xAB,Other\n",
+        );
+
+        assert_eq!(warnings.len(), 4);
+        assert!(warnings[0].starts_with("lab.txt:2: "));
+        assert!(warnings[1].starts_with("lab.txt:3: "));
+        assert!(warnings[2].starts_with("lab.txt:4: "));
+        assert!(warnings[3].starts_with("lab.txt:5: "));
+        assert_eq!(
+            allowlist.check(&dna(0xAB)),
+            LicenseStatus::Licensed("Acme".to_string())
+        );
+    }
+}
@@ -0,0 +1,5 @@
+mod list;
+mod status;
+
+pub use list::DnaAllowlist;
+pub use status::LicenseStatus;
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Outcome of checking one DNA against the loaded allowlists.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", content = "label", rename_all = "snake_case")]
+pub enum LicenseStatus {
+    Licensed(String),
+    Unknown,
+    Revoked(String),
+}
+
+impl LicenseStatus {
+    /// Short machine-readable name used in exports.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Licensed(_) => "licensed",
+            Self::Unknown => "unknown",
+            Self::Revoked(_) => "revoked",
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            Self::Licensed(label) | Self::Revoked(label) => label,
+            Self::Unknown => "",
+        }
+    }
+}
+
+impl fmt::Display for LicenseStatus {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Licensed(label) => write!(formatter, "licensed to {label}"),
+            Self::Unknown => formatter.write_str("unknown board"),
+            Self::Revoked(label) if label.is_empty() => formatter.write_str("revoked"),
+            Self::Revoked(label) => write!(formatter, "revoked ({label})"),
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use super::record::{DnaRecord, format_timestamp};
+use crate::device_programmer::{DeviceDna, DnaFormat};
+use crate::dna_allowlist::LicenseStatus;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CSV_HEADER: &str = "dna_hex,dna_raw,density,adapter,adapter_serial,license_status,\
+     license_label,first_read_utc,last_read_utc,read_count,note";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+
+    pub fn render(&self, records: &[&DnaRecord]) -> Result<String, String> {
+        match self {
+            Self::Csv => Ok(render_csv(records)),
+            Self::Json => serde_json::to_string_pretty(records).map_err(|error| error.to_string()),
+        }
+    }
+
+    /// Writes `records` to `dna_history_<timestamp>.<ext>` inside `directory`.
+    pub fn write(
+        &self,
+        records: &[&DnaRecord],
+        directory: &Path,
+        exported_at: u64,
+    ) -> Result<PathBuf, String> {
+        let stamp = format_timestamp(exported_at)
+            .replace(['-', ':'], "")
+            .replace(' ', "_");
+        let path = directory.join(format!("dna_history_{stamp}.{}", self.extension()));
+        fs::write(&path, self.render(records)?)
+            .map_err(|error| format!("Failed to write {}: {error}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Renders one DNA in every representation, for license tools that
+    /// import a single device.
+    pub fn render_dna(
+        &self,
+        dna: &DeviceDna,
+        device_type: &str,
+        license: Option<&LicenseStatus>,
+    ) -> Result<String, String> {
+        match self {
+            Self::Csv => {
+                let mut header: Vec<&str> = DnaFormat::ALL.iter().map(DnaFormat::key).collect();
+                header.extend(["device_type", "license_status", "license_label"]);
+                let mut row: Vec<String> = DnaFormat::ALL
+                    .iter()
+                    .map(|format| csv_quote(&dna.format(*format)))
+                    .collect();
+                row.push(csv_field(device_type));
+                row.push(csv_field(license.map_or("", LicenseStatus::key)));
+                row.push(csv_field(license.map_or("", LicenseStatus::label)));
+                Ok(format!("{}\r\n{}\r\n", header.join(","), row.join(",")))
+            }
+            Self::Json => {
+                let mut object = serde_json::Map::new();
+                for format in DnaFormat::ALL {
+                    object.insert(format.key().to_string(), dna.format(format).into());
+                }
+                object.insert("device_type".to_string(), device_type.into());
+                let license = serde_json::to_value(license).map_err(|error| error.to_string())?;
+                object.insert("license".to_string(), license);
+                serde_json::to_string_pretty(&object).map_err(|error| error.to_string())
+            }
+        }
+    }
+
+    /// `dna_<57-bit hex>.<ext>`, offered as the name of a single DNA export.
+    pub fn dna_file_name(&self, dna: &DeviceDna) -> String {
+        format!("dna_{}.{}", dna.format(DnaFormat::Hex57), self.extension())
+    }
+
+    /// Writes one DNA to `path`.
+    pub fn write_dna(
+        &self,
+        dna: &DeviceDna,
+        device_type: &str,
+        license: Option<&LicenseStatus>,
+        path: &Path,
+    ) -> Result<(), String> {
+        fs::write(path, self.render_dna(dna, device_type, license)?)
+            .map_err(|error| format!("Failed to write {}: {error}", path.display()))
+    }
+}
+
+fn render_csv(records: &[&DnaRecord]) -> String {
+    let mut csv = String::from(CSV_HEADER);
+    csv.push_str("\r\n");
+
+    for record in records {
+        let read_count = record.read_count.to_string();
+        let fields = [
+            record.dna_value.as_str(),
+            record.dna_raw_value.as_str(),
+            record.density.as_deref().unwrap_or(""),
+            record.adapter.as_str(),
+            record.adapter_serial.as_deref().unwrap_or(""),
+            record.license.as_ref().map_or("", LicenseStatus::key),
+            record.license.as_ref().map_or("", LicenseStatus::label),
+            &format_timestamp(record.first_read_at),
+            &format_timestamp(record.last_read_at),
+            &read_count,
+            record.note.as_str(),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push_str("\r\n");
+    }
+
+    csv
+}
+
+fn csv_field(value: &str) -> String {
+    // Leading formula characters are neutralized so spreadsheet apps do not
+    // evaluate operator notes.
+    if value.starts_with(['=', '+', '-', '@']) {
+        csv_quote(&format!("'{value}"))
+    } else {
+        csv_quote(value)
+    }
+}
+
+// Generated DNA encodings are quoted but not neutralized: base64 may start
+// with `+`, and license tools need the exact text.
+fn csv_quote(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(note: &str) -> DnaRecord {
+        DnaRecord {
+            dna_value: "0x0040A1B2C3D4E5F6".to_string(),
+            dna_raw_value: "0101".to_string(),
+            density: Some("75T".to_string()),
+            adapter: "FTDI".to_string(),
+            adapter_serial: Some("FT4ABC12".to_string()),
+            license: Some(LicenseStatus::Licensed("Acme Labs".to_string())),
+            first_read_at: 0,
+            last_read_at: 60,
+            read_count: 3,
+            note: note.to_string(),
+        }
+    }
+
+    #[test]
+    fn csv_quotes_notes_and_neutralizes_formulas() {
+        let quoted = record("rack 2, \"left\"");
+        let formula = record("=HYPERLINK(\"x\")");
+
+        let csv = ExportFormat::Csv.render(&[&quoted, &formula]).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(
+            lines[1],
+            "0x0040A1B2C3D4E5F6,0101,75T,FTDI,FT4ABC12,licensed,Acme Labs,\
+             1970-01-01 00:00:00,1970-01-01 00:01:00,3,\"rack 2, \"\"left\"\"\""
+        );
+        assert!(lines[2].ends_with(",3,\"'=HYPERLINK(\"\"x\"\")\""));
+    }
+
+    #[test]
+    fn json_export_contains_every_record_field() {
+        let entry = record("bench");
+        let json = ExportFormat::Json.render(&[&entry]).unwrap();
+        let parsed: Vec<DnaRecord> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed, vec![entry]);
+    }
+
+    #[test]
+    fn single_dna_export_lists_every_representation() {
+        let dna = DeviceDna::from_value(0x00641CC26AE96854).unwrap();
+        let license = LicenseStatus::Revoked("Acme Labs".to_string());
+
+        let csv = ExportFormat::Csv
+            .render_dna(&dna, "CH347", Some(&license))
+            .unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "hex,hex57,binary,byte_reversed,base64,device_type,license_status,license_label"
+        );
+        assert!(lines[1].starts_with("0x00641CC26AE96854,0641CC26AE96854,0011"));
+        assert!(lines[1].ends_with(",0x002AB47435610E32,Mg5hNXS0KgA=,CH347,revoked,Acme Labs"));
+
+        let json = ExportFormat::Json
+            .render_dna(&dna, "CH347", Some(&license))
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        for format in DnaFormat::ALL {
+            assert_eq!(parsed[format.key()], dna.format(format));
+        }
+        assert_eq!(parsed["device_type"], "CH347");
+        assert_eq!(parsed["license"]["status"], "revoked");
+        assert_eq!(parsed["license"]["label"], "Acme Labs");
+    }
+}
@@ -0,0 +1,7 @@
+mod export;
+mod record;
+mod store;
+
+pub use export::ExportFormat;
+pub use record::{DnaRead, DnaRecord, format_timestamp};
+pub use store::DnaHistoryStore;
@@ -0,0 +1,133 @@
+use crate::device_programmer::{DnaInfo, FlashingOption};
+use crate::dna_allowlist::LicenseStatus;
+use serde::{Deserialize, Serialize};
+
+/// One device in the history. Repeated reads of the same DNA update this
+/// record instead of adding a new one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DnaRecord {
+    pub dna_value: String,
+    pub dna_raw_value: String,
+    pub density: Option<String>,
+    pub adapter: String,
+    #[serde(default)]
+    pub adapter_serial: Option<String>,
+    /// Allowlist outcome of the latest read; `None` when no allowlist was loaded.
+    #[serde(default)]
+    pub license: Option<LicenseStatus>,
+    pub first_read_at: u64,
+    pub last_read_at: u64,
+    pub read_count: u32,
+    #[serde(default)]
+    pub note: String,
+}
+
+impl DnaRecord {
+    pub(super) fn key(&self) -> String {
+        normalize_dna(&self.dna_value)
+    }
+}
+
+/// A successful read as reported by the device programmer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DnaRead {
+    pub info: DnaInfo,
+    pub density: Option<String>,
+    pub adapter: String,
+    pub adapter_serial: Option<String>,
+    pub license: Option<LicenseStatus>,
+}
+
+impl DnaRead {
+    pub fn new(
+        info: DnaInfo,
+        option: &FlashingOption,
+        adapter_serial: Option<String>,
+        license: Option<LicenseStatus>,
+    ) -> Self {
+        Self {
+            info,
+            density: option.density().map(str::to_string),
+            adapter: option.adapter_kind().label().to_string(),
+            adapter_serial,
+            license,
+        }
+    }
+}
+
+/// Lowercase hex digits of a DNA value without the `0x` prefix or separators.
+pub(super) fn hex_digits(value: &str) -> String {
+    let trimmed = value.trim();
+    let digits = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed);
+    digits
+        .chars()
+        .filter(|ch| !matches!(ch, '_' | ' ' | ':' | '-'))
+        .map(|ch| ch.to_ascii_lowercase())
+        .collect()
+}
+
+/// Dedup key for a DNA value, so `0x00AB_CD` and `abcd` name the same device.
+pub(super) fn normalize_dna(value: &str) -> String {
+    let digits = hex_digits(value);
+    let significant = digits.trim_start_matches('0');
+    if significant.is_empty() && !digits.is_empty() {
+        "0".to_string()
+    } else {
+        significant.to_string()
+    }
+}
+
+/// Formats Unix seconds as `YYYY-MM-DD HH:MM:SS` UTC.
+pub fn format_timestamp(unix_secs: u64) -> String {
+    let days = unix_secs / 86_400;
+    let seconds_of_day = unix_secs % 86_400;
+    let (year, month, day) = civil_from_days(days);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60
+    )
+}
+
+// Howard Hinnant's days-to-civil conversion, restricted to dates after 1970.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dna_values_normalize_regardless_of_prefix_case_and_padding() {
+        assert_eq!(normalize_dna("0x00AB_CD"), "abcd");
+        assert_eq!(normalize_dna("  ABCD "), "abcd");
+        assert_eq!(normalize_dna("0x0000"), "0");
+    }
+
+    #[test]
+    fn timestamps_format_as_utc_calendar_dates() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00:00");
+        assert_eq!(format_timestamp(1_767_225_599), "2025-12-31 23:59:59");
+    }
+}
@@ -0,0 +1,239 @@
+use super::record::{DnaRead, DnaRecord, hex_digits, normalize_dna};
+use crate::device_programmer::{DeviceDna, DnaFormat};
+use crate::utils::app_dirs;
+use crate::utils::json_store::JsonFormat;
+use crate::utils::logger::Logger;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+const HISTORY_FILE: &str = "dna_history.json";
+const HISTORY_FORMAT: JsonFormat = JsonFormat {
+    label: "DNA history",
+    version: 1,
+    fallback: "starting with an empty history",
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct HistoryFile {
+    records: Vec<DnaRecord>,
+}
+
+/// DNA reads kept across sessions, one record per device.
+pub struct DnaHistoryStore {
+    path: Option<PathBuf>,
+    records: Vec<DnaRecord>,
+    logger: Logger,
+}
+
+impl DnaHistoryStore {
+    /// Opens the history in the per-user data directory, falling back to the
+    /// working directory when no data directory is available.
+    pub fn open_default(logger: Logger) -> Self {
+        let path = app_dirs::data_dir()
+            .map(|dir| dir.join(HISTORY_FILE))
+            .unwrap_or_else(|| PathBuf::from(HISTORY_FILE));
+        Self::open(path, logger)
+    }
+
+    pub fn open(path: PathBuf, logger: Logger) -> Self {
+        let records = HISTORY_FORMAT
+            .load::<HistoryFile>(&path, &logger)
+            .map(|file| file.records)
+            .unwrap_or_default();
+        Self {
+            path: Some(path),
+            records,
+            logger,
+        }
+    }
+
+    /// A history that is never written to disk.
+    #[cfg(test)]
+    pub fn in_memory(logger: Logger) -> Self {
+        Self {
+            path: None,
+            records: Vec::new(),
+            logger,
+        }
+    }
+
+    /// Most recently read devices first.
+    pub fn records(&self) -> &[DnaRecord] {
+        &self.records
+    }
+
+    /// Every recorded DNA value.
+    pub fn dnas(&self) -> impl Iterator<Item = DeviceDna> + '_ {
+        self.records
+            .iter()
+            .filter_map(|record| DeviceDna::parse(&record.dna_value, DnaFormat::Hex).ok())
+    }
+
+    pub fn find(&self, dna_value: &str) -> Option<&DnaRecord> {
+        let key = normalize_dna(dna_value);
+        self.records.iter().find(|record| record.key() == key)
+    }
+
+    /// Records a successful read and persists the history.
+    pub fn record_read(&mut self, read: DnaRead, read_at: u64) {
+        let dna_value = read.info.dna.format(DnaFormat::Hex);
+        let dna_raw_value = read.info.dna.format(DnaFormat::Binary);
+        // Lowercase hex without leading zeros, the same form `normalize_dna` produces.
+        let key = format!("{:x}", read.info.dna.value());
+
+        match self.records.iter().position(|record| record.key() == key) {
+            Some(index) => {
+                let mut record = self.records.remove(index);
+                record.read_count = record.read_count.saturating_add(1);
+                record.last_read_at = read_at;
+                record.dna_raw_value = dna_raw_value;
+                record.adapter = read.adapter;
+                record.adapter_serial = read.adapter_serial;
+                record.license = read.license;
+                if read.density.is_some() {
+                    record.density = read.density;
+                }
+                self.records.insert(0, record);
+            }
+            None => self.records.insert(
+                0,
+                DnaRecord {
+                    dna_value,
+                    dna_raw_value,
+                    density: read.density,
+                    adapter: read.adapter,
+                    adapter_serial: read.adapter_serial,
+                    license: read.license,
+                    first_read_at: read_at,
+                    last_read_at: read_at,
+                    read_count: 1,
+                    note: String::new(),
+                },
+            ),
+        }
+
+        self.save();
+    }
+
+    /// Replaces the operator note for a device. Returns false if it is unknown.
+    pub fn set_note(&mut self, dna_value: &str, note: &str) -> bool {
+        let key = normalize_dna(dna_value);
+        let Some(record) = self.records.iter_mut().find(|record| record.key() == key) else {
+            return false;
+        };
+        record.note = note.trim().to_string();
+        self.save();
+        true
+    }
+
+    /// Records whose DNA contains `query` as hex digits. `0x`, case and
+    /// separators in the query are ignored; an empty query matches everything.
+    pub fn search(&self, query: &str) -> Vec<&DnaRecord> {
+        let needle = hex_digits(query);
+        self.records
+            .iter()
+            .filter(|record| needle.is_empty() || hex_digits(&record.dna_value).contains(&needle))
+            .collect()
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let file = HistoryFile {
+            records: self.records.clone(),
+        };
+        if let Err(error) = HISTORY_FORMAT.save(path, &file) {
+            self.logger.error(format!(
+                "Failed to save DNA history to {}: {error}",
+                path.display()
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device_programmer::{DeviceDna, DnaInfo, FlashingOption};
+    use crate::utils::temp_dir::TempDir;
+    use std::fs;
+
+    fn read(dna_value: &str, option: &FlashingOption) -> DnaRead {
+        DnaRead::new(
+            DnaInfo {
+                dna: DeviceDna::parse(dna_value, DnaFormat::Hex).unwrap(),
+                device_type: "FTDI".to_string(),
+            },
+            option,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn repeated_reads_update_one_record() {
+        let mut store = DnaHistoryStore::in_memory(Logger::new("DnaHistoryTest"));
+
+        store.record_read(read("0x00ABCDEF", &FlashingOption::DnaCH347), 100);
+        store.record_read(read("0x1234", &FlashingOption::DnaCH347), 150);
+        store.record_read(read("0xabcdef", &FlashingOption::DnaRS232_75T), 200);
+
+        assert_eq!(store.records().len(), 2);
+        let record = &store.records()[0];
+        assert_eq!(record.dna_value, "0x0000000000ABCDEF");
+        assert_eq!(record.read_count, 2);
+        assert_eq!(record.first_read_at, 100);
+        assert_eq!(record.last_read_at, 200);
+        assert_eq!(record.density.as_deref(), Some("75T"));
+        assert_eq!(record.adapter, "FTDI");
+    }
+
+    #[test]
+    fn search_matches_partial_hex_ignoring_prefix_and_case() {
+        let mut store = DnaHistoryStore::in_memory(Logger::new("DnaHistoryTest"));
+        store.record_read(read("0x0040A1B2C3D4E5F6", &FlashingOption::DnaCH347), 1);
+        store.record_read(read("0x0011223344556677", &FlashingOption::DnaCH347), 2);
+
+        let found = store.search("0xC3d4");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].dna_value, "0x0040A1B2C3D4E5F6");
+        assert_eq!(store.search("").len(), 2);
+        assert!(store.search("ffff").is_empty());
+    }
+
+    #[test]
+    fn history_round_trips_through_disk_with_notes() {
+        let dir = TempDir::new("dna-history");
+        let path = dir.join(HISTORY_FILE);
+        let mut store = DnaHistoryStore::open(path.clone(), Logger::new("DnaHistoryTest"));
+        store.record_read(read("0x1234", &FlashingOption::DnaRS232_35T), 10);
+        assert!(store.set_note("1234", "  bench board  "));
+
+        let reopened = DnaHistoryStore::open(path.clone(), Logger::new("DnaHistoryTest"));
+        assert_eq!(reopened.records(), store.records());
+        assert_eq!(reopened.records()[0].note, "bench board");
+    }
+
+    #[test]
+    fn corrupt_history_is_kept_aside_and_replaced() {
+        let dir = TempDir::new("dna-history-corrupt");
+        let path = dir.join(HISTORY_FILE);
+        fs::write(&path, "{ not json").unwrap();
+
+        let mut store = DnaHistoryStore::open(path.clone(), Logger::new("DnaHistoryTest"));
+        assert!(store.records().is_empty());
+        assert_eq!(
+            fs::read_to_string(path.with_extension("json.bak")).unwrap(),
+            "{ not json"
+        );
+
+        store.record_read(read("0x1234", &FlashingOption::DnaCH347), 10);
+        assert_eq!(
+            DnaHistoryStore::open(path.clone(), Logger::new("DnaHistoryTest"))
+                .records()
+                .len(),
+            1
+        );
+    }
+}
@@ -8,17 +8,26 @@ mod assets;
 #[rustfmt::skip]
 mod branding;
 mod device_programmer;
+mod dna_allowlist;
+mod dna_history;
 mod pcileech_test;
 mod ui;
 mod utils;
 
+use crate::device_programmer::{SessionFile, replay_session};
 use crate::utils::cleanup::perform_startup_cleanup;
+use crate::utils::file_checker::payload_root;
+use crate::utils::localization::Language;
 use crate::utils::logger::Logger;
+use crate::utils::openocd_lint::{LintRule, LintTarget, lint};
+use crate::utils::payload_manifest::{PAYLOAD_MANIFEST_FILE, PayloadManifest};
 use crate::utils::window::{WINDOW_HEIGHT_INITIAL, WINDOW_WIDTH};
 use eframe::egui;
+use std::path::{Path, PathBuf};
 
 const APP_TITLE: &str = env!("CARGO_PKG_NAME");
 const VERSION: &str = env!("CARGO_PKG_VERSION");
+const CLI_FLAGS: [&str; 3] = ["--replay", "--write-payload-manifest", "--lint-openocd"];
 
 fn main() -> Result<(), eframe::Error> {
     let logger = Logger::new("DMA-Tools");
@@ -29,6 +38,38 @@ fn main() -> Result<(), eframe::Error> {
         logger.info("Debug build detected - debug mode enabled");
     }
 
+    // Replaying a session touches no hardware, so it skips the startup cleanup
+    let mut args = std::env::args_os().skip(1).peekable();
+    if args
+        .peek()
+        .is_some_and(|arg| CLI_FLAGS.iter().any(|flag| arg == flag))
+    {
+        attach_parent_console();
+    }
+    match args.next() {
+        Some(arg) if arg == "--replay" => {
+            let code = match args.next() {
+                Some(path) => run_replay(Path::new(&path), &logger),
+                None => {
+                    logger.error("Usage: --replay <session.json>");
+                    2
+                }
+            };
+            std::process::exit(code);
+        }
+        Some(arg) if arg == "--write-payload-manifest" => {
+            let root = args
+                .next()
+                .map_or_else(|| PathBuf::from("."), PathBuf::from);
+            std::process::exit(write_payload_manifest(&root, &logger));
+        }
+        Some(arg) if arg == "--lint-openocd" => {
+            let scripts: Vec<String> = args.map(|arg| arg.to_string_lossy().into_owned()).collect();
+            std::process::exit(lint_openocd(&scripts, &logger));
+        }
+        _ => {}
+    }
+
     // Perform cleanup operations at startup
     perform_startup_cleanup(&logger);
 
@@ -52,6 +93,113 @@ fn main() -> Result<(), eframe::Error> {
     )
 }
 
+/// Feeds a recorded session back through the flash monitor. Exits with 0 when
+/// the replay reproduces the recorded result.
+fn run_replay(path: &Path, logger: &Logger) -> i32 {
+    let session = match SessionFile::load(path) {
+        Ok(session) => session,
+        Err(error) => {
+            logger.error(error);
+            return 2;
+        }
+    };
+    let report = match replay_session(&session, logger) {
+        Ok(report) => report,
+        Err(error) => {
+            logger.error(error);
+            return 2;
+        }
+    };
+
+    let stats = report.progress.sector_stats;
+    logger.info(format!(
+        "Replayed {}: stage {:?}, {}/{} normal sector writes, terminated early: {}",
+        path.display(),
+        report.progress.stage,
+        stats.normal,
+        stats.total,
+        report.terminated_early
+    ));
+
+    let Some(recorded) = &session.snapshot else {
+        logger.warning("The session has no recorded result to compare against");
+        return 0;
+    };
+    let differences = report.differences(recorded);
+    if differences.is_empty() {
+        logger.success("Replay matches the recorded result");
+        return 0;
+    }
+    for difference in differences {
+        logger.error(format!("Replay differs: {difference}"));
+    }
+    1
+}
+
+/// Records the size and SHA-256 of every file under `OpenOCD/` and `tools/`
+/// in `root`, for the startup check of a packaged release.
+fn write_payload_manifest(root: &Path, logger: &Logger) -> i32 {
+    let manifest = match PayloadManifest::generate(root) {
+        Ok(manifest) => manifest,
+        Err(error) => {
+            logger.error(format!(
+                "Failed to hash the payload in {}: {error}",
+                root.display()
+            ));
+            return 2;
+        }
+    };
+    let path = root.join(PAYLOAD_MANIFEST_FILE);
+    match manifest.save(&path) {
+        Ok(()) => {
+            logger.success(format!(
+                "Wrote {} entries to {}",
+                manifest.files.len(),
+                path.display()
+            ));
+            0
+        }
+        Err(error) => {
+            logger.error(format!("Failed to write {}: {error}", path.display()));
+            2
+        }
+    }
+}
+
+/// Lints the given OpenOCD configs, or every profile's config when none are
+/// given. Exits with 1 when anything is found.
+fn lint_openocd(scripts: &[String], logger: &Logger) -> i32 {
+    let (root, targets) = if scripts.is_empty() {
+        (payload_root(), LintTarget::bundled())
+    } else {
+        let targets = scripts.iter().map(|script| LintTarget::user(script));
+        (PathBuf::from("."), targets.collect())
+    };
+    let findings = lint(&root, &targets);
+    for finding in &findings {
+        logger.warning(format!(
+            "{}: {}",
+            finding.location,
+            finding.describe(&Language::English)
+        ));
+    }
+    let unreadable = findings
+        .iter()
+        .filter(|finding| finding.rule == LintRule::UnreadableScript)
+        .count();
+    let linted = targets.len() - unreadable;
+    if findings.is_empty() {
+        logger.success(format!("Linted {linted} config(s), no problems found"));
+        0
+    } else {
+        logger.error(format!(
+            "Linted {linted} config(s), {} problem(s) found",
+            findings.len()
+        ));
+        1
+    }
+}
+
 fn create_window_options() -> eframe::NativeOptions {
     let window_width = WINDOW_WIDTH;
     let window_height = WINDOW_HEIGHT_INITIAL;
@@ -70,6 +218,7 @@ fn create_window_options() -> eframe::NativeOptions {
         .with_decorations(true)
         .with_maximized(false)
         .with_fullscreen(false)
+        .with_drag_and_drop(true)
         .with_icon(icon_data.map(std::sync::Arc::new).unwrap_or_default());
 
     // Center the window on the screen
@@ -86,6 +235,7 @@ fn create_window_options() -> eframe::NativeOptions {
     }
 }
 
+#[cfg(windows)]
 fn get_primary_monitor_size() -> Option<egui::Vec2> {
     use winapi::um::winuser::{GetSystemMetrics, SM_CXSCREEN, SM_CYSCREEN};
 
@@ -102,6 +252,26 @@ fn get_primary_monitor_size() -> Option<egui::Vec2> {
     }
 }
 
+// Other platforms let the window manager place the window.
+#[cfg(not(windows))]
+fn get_primary_monitor_size() -> Option<egui::Vec2> {
+    None
+}
+
+// Release builds use the Windows subsystem, so the command-line modes have to
+// borrow the console of the shell that started them to show any output.
+#[cfg(windows)]
+fn attach_parent_console() {
+    utils::win_utils::attach_parent_console();
+}
+
+#[cfg(not(windows))]
+fn attach_parent_console() {}
+
+#[cfg(windows)]
 fn setup_window() {
     utils::win_utils::setup_window_controls();
 }
+
+#[cfg(not(windows))]
+fn setup_window() {}
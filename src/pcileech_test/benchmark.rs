@@ -0,0 +1,128 @@
+use std::sync::{Arc, Mutex};
+
+/// Benchmark lengths offered on the test screen.
+pub const BENCHMARK_DURATIONS: [u32; 4] = [10, 30, 60, 120];
+
+/// Read throughput samples in bytes per second, in the order the tool
+/// reported them.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Throughput {
+    pub samples: Vec<u64>,
+}
+
+impl Throughput {
+    pub fn min(&self) -> u64 {
+        self.samples.iter().copied().min().unwrap_or(0)
+    }
+
+    pub fn max(&self) -> u64 {
+        self.samples.iter().copied().max().unwrap_or(0)
+    }
+
+    pub fn average(&self) -> u64 {
+        let total: u128 = self.samples.iter().map(|sample| u128::from(*sample)).sum();
+        let count = self.samples.len().max(1) as u128;
+        u64::try_from(total / count).unwrap_or(u64::MAX)
+    }
+}
+
+/// Throughput in MB/s (10^6 bytes) with one decimal.
+pub fn format_mbps(bytes_per_second: u64) -> String {
+    format!("{:.1} MB/s", bytes_per_second as f64 / 1_000_000.0)
+}
+
+/// Samples shared between a running benchmark and the screen showing it.
+#[derive(Debug, Clone, Default)]
+pub(super) struct ThroughputRecorder {
+    samples: Arc<Mutex<Vec<u64>>>,
+}
+
+impl ThroughputRecorder {
+    pub(super) fn push(&self, bytes_per_second: u64) {
+        self.samples.lock().unwrap().push(bytes_per_second);
+    }
+
+    pub(super) fn throughput(&self) -> Throughput {
+        Throughput {
+            samples: self.samples.lock().unwrap().clone(),
+        }
+    }
+}
+
+/// Reads a rate such as `123.4 MB/s`, `87MiB/s` or `1048576 bytes/s` from a
+/// line, in bytes per second. The bundled memflow-base prints no rates, so
+/// this only sees output from a tool configured in the profile.
+pub(super) fn parse_throughput(line: &str) -> Option<u64> {
+    let words: Vec<&str> = line
+        .split_whitespace()
+        .map(|word| word.trim_matches([',', ';', '(', ')', '[', ']']))
+        .collect();
+
+    words.iter().enumerate().find_map(|(index, word)| {
+        let split = word
+            .find(|character: char| character.is_ascii_alphabetic())
+            .unwrap_or(word.len());
+        let (attached_number, unit) = word.split_at(split);
+        let multiplier = rate_multiplier(unit)?;
+        let number = if attached_number.is_empty() {
+            words.get(index.checked_sub(1)?)?
+        } else {
+            attached_number
+        };
+
+        let value: f64 = number.parse().ok()?;
+        (value.is_finite() && value >= 0.0).then(|| (value * multiplier).round() as u64)
+    })
+}
+
+fn rate_multiplier(unit: &str) -> Option<f64> {
+    let multiplier = match unit.to_ascii_lowercase().as_str() {
+        "b/s" | "bytes/s" => 1.0,
+        "kb/s" => 1e3,
+        "kib/s" => 1024.0,
+        "mb/s" => 1e6,
+        "mib/s" => 1024.0 * 1024.0,
+        "gb/s" => 1e9,
+        "gib/s" => 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some(multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_rates_in_common_units() {
+        assert_eq!(parse_throughput("read: 123.4 MB/s"), Some(123_400_000));
+        assert_eq!(parse_throughput("[bench] 64MiB/s"), Some(64 * 1024 * 1024));
+        assert_eq!(parse_throughput("1048576 bytes/s"), Some(1_048_576));
+        assert_eq!(parse_throughput("speed (2.5 GB/s)"), Some(2_500_000_000));
+    }
+
+    #[test]
+    fn ignores_lines_without_a_rate() {
+        for line in [
+            "memflow init",
+            "MB/s",
+            "read 4096 bytes",
+            "ntdll.dll base address: 0x7ffa0000",
+            "fast MB/s",
+        ] {
+            assert_eq!(parse_throughput(line), None, "accepted {line:?}");
+        }
+    }
+
+    #[test]
+    fn summarises_samples() {
+        let throughput = Throughput {
+            samples: vec![30_000_000, 10_000_000, 20_000_000],
+        };
+
+        assert_eq!(throughput.min(), 10_000_000);
+        assert_eq!(throughput.max(), 30_000_000);
+        assert_eq!(throughput.average(), 20_000_000);
+        assert_eq!(format_mbps(throughput.average()), "20.0 MB/s");
+    }
+}
@@ -1,9 +1,25 @@
-use super::PcileechTestState;
+use super::benchmark::{Throughput, ThroughputRecorder};
+use super::profile::TestProfile;
+use super::rules::OutputRules;
 use super::runner::{self, CancellationToken, RunOutcome};
+use super::soak::{self, SoakRecorder, SoakReport};
+use super::{PcileechTestState, TestMode};
 use std::sync::{Arc, Mutex};
 use std::thread;
-
-type TestRunner = dyn Fn(CancellationToken) -> RunOutcome + Send + Sync;
+use std::time::Duration;
+
+type TestRunner = dyn Fn(&TestRequest, CancellationToken) -> RunOutcome + Send + Sync;
+
+/// Everything one run needs, fixed when it is launched.
+struct TestRequest {
+    profile: TestProfile,
+    rules: Arc<OutputRules>,
+    mode: TestMode,
+    /// Benchmark samples as they arrive, shown while the run is in progress.
+    recorder: ThroughputRecorder,
+    /// Soak attempts as they finish, shown while the run is in progress.
+    soak: SoakRecorder,
+}
 
 #[derive(Debug)]
 struct ActiveRun {
@@ -19,11 +35,17 @@ struct ControllerState {
     requested_generation: Option<u64>,
     restart_blocked: Option<String>,
     back_requested: bool,
+    profile: TestProfile,
+    rules: Arc<OutputRules>,
+    mode: TestMode,
+    recorder: ThroughputRecorder,
+    soak: SoakRecorder,
 }
 
 struct PendingLaunch {
     generation: u64,
     cancellation: CancellationToken,
+    request: TestRequest,
 }
 
 pub struct PcileechTestController {
@@ -34,14 +56,33 @@ pub struct PcileechTestController {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PcileechTestSnapshot {
     pub state: PcileechTestState,
+    pub mode: TestMode,
+    /// Samples of the benchmark in progress, or of the last one.
+    pub throughput: Throughput,
+    /// Attempts of the soak test in progress, or of the last one.
+    pub soak: SoakReport,
+    /// Whether the profile has a read-loop mode for the benchmark to run.
+    pub benchmark_available: bool,
     pub back_pending: bool,
     pub can_go_back: bool,
 }
 
 impl PcileechTestController {
     pub fn new() -> Self {
-        Self::with_runner(Arc::new(|cancellation| {
-            runner::run_pcileech_test(&cancellation)
+        Self::with_runner(Arc::new(|request, cancellation| match request.mode {
+            TestMode::Check => {
+                runner::run_pcileech_test(&request.profile, &request.rules, &cancellation)
+            }
+            TestMode::Benchmark { seconds } => runner::run_benchmark(
+                &request.profile,
+                &request.rules,
+                Duration::from_secs(seconds.into()),
+                &request.recorder,
+                &cancellation,
+            ),
+            TestMode::Soak(plan) => soak::run_soak(&plan, &request.soak, &cancellation, || {
+                runner::run_pcileech_test(&request.profile, &request.rules, &cancellation)
+            }),
         }))
     }
 
@@ -52,6 +93,31 @@ impl PcileechTestController {
         }
     }
 
+    /// Sets the profile used by the next run; a run in progress keeps its own.
+    /// Falls back to the check when the profile cannot run the benchmark.
+    pub fn set_profile(&self, profile: TestProfile) {
+        let mut state = self.state.lock().unwrap();
+        if matches!(state.mode, TestMode::Benchmark { .. }) && !profile.supports_benchmark() {
+            state.mode = TestMode::Check;
+        }
+        state.profile = profile;
+    }
+
+    /// Sets how the output of the next run is judged.
+    pub fn set_rules(&self, rules: OutputRules) {
+        self.state.lock().unwrap().rules = Arc::new(rules);
+    }
+
+    /// Sets what the next run does; a run in progress keeps its own mode. The
+    /// benchmark is refused for a profile without benchmark arguments.
+    pub fn set_mode(&self, mode: TestMode) {
+        let mut state = self.state.lock().unwrap();
+        if matches!(mode, TestMode::Benchmark { .. }) && !state.profile.supports_benchmark() {
+            return;
+        }
+        state.mode = mode;
+    }
+
     pub fn ensure_started(&self) {
         let launch = {
             let mut state = self.state.lock().unwrap();
@@ -112,6 +178,15 @@ impl PcileechTestController {
         }
     }
 
+    /// Stops the run in progress and shows what it found so far, e.g. the
+    /// attempts a soak test finished.
+    pub fn stop(&self) {
+        let state = self.state.lock().unwrap();
+        if let Some(active_run) = &state.active_run {
+            active_run.cancellation.cancel();
+        }
+    }
+
     pub fn request_back(&self) {
         let mut state = self.state.lock().unwrap();
         if !state.back_requested {
@@ -140,6 +215,10 @@ impl PcileechTestController {
         let state = self.state.lock().unwrap();
         PcileechTestSnapshot {
             state: state.test_state.clone(),
+            mode: state.mode,
+            throughput: state.recorder.throughput(),
+            soak: state.soak.report(),
+            benchmark_available: state.profile.supports_benchmark(),
             back_pending: state.back_requested,
             can_go_back: back_ready(&state),
         }
@@ -175,9 +254,19 @@ fn prepare_launch(state: &mut ControllerState) -> Option<PendingLaunch> {
         cancellation: cancellation.clone(),
     });
 
+    // Fresh recorders per run keep a cancelled run's results off the screen.
+    state.recorder = ThroughputRecorder::default();
+    state.soak = SoakRecorder::default();
     Some(PendingLaunch {
         generation,
         cancellation,
+        request: TestRequest {
+            profile: state.profile.clone(),
+            rules: Arc::clone(&state.rules),
+            mode: state.mode,
+            recorder: state.recorder.clone(),
+            soak: state.soak.clone(),
+        },
     })
 }
 
@@ -191,7 +280,7 @@ fn launch_run(
     launch: PendingLaunch,
 ) {
     thread::spawn(move || {
-        let outcome = runner(launch.cancellation);
+        let outcome = runner(&launch.request, launch.cancellation);
         let next_launch = {
             let mut state = shared_state.lock().unwrap();
             let is_active_generation = state
@@ -240,6 +329,7 @@ fn restart_blocked_message(state: &PcileechTestState) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::pcileech_test::{SoakLimit, SoakPlan};
     use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
     use std::sync::mpsc;
     use std::time::Duration;
@@ -255,7 +345,7 @@ mod tests {
         let controller = PcileechTestController::new();
         {
             let mut state = controller.state.lock().unwrap();
-            state.test_state = PcileechTestState::Success("ok".into());
+            state.test_state = PcileechTestState::Success(Box::default());
         }
 
         controller.request_back();
@@ -267,7 +357,7 @@ mod tests {
 
     #[test]
     fn back_is_available_after_a_safe_reader_bookkeeping_failure() {
-        let runner = Arc::new(|_cancellation: CancellationToken| {
+        let runner = Arc::new(|_: &TestRequest, _cancellation: CancellationToken| {
             RunOutcome::safe(PcileechTestState::Failed(
                 "Test tool output reader panicked".into(),
             ))
@@ -303,7 +393,7 @@ mod tests {
             let maximum_workers = Arc::clone(&maximum_workers);
             let next_run = Arc::clone(&next_run);
             let allow_replacement_to_finish = Arc::clone(&allow_replacement_to_finish);
-            Arc::new(move |cancellation: CancellationToken| {
+            Arc::new(move |_: &TestRequest, cancellation: CancellationToken| {
                 let run = next_run.fetch_add(1, Ordering::SeqCst) + 1;
                 let active = active_workers.fetch_add(1, Ordering::SeqCst) + 1;
                 maximum_workers.fetch_max(active, Ordering::SeqCst);
@@ -370,7 +460,7 @@ mod tests {
         let (started_sender, started_receiver) = mpsc::channel();
         let runner = {
             let starts = Arc::clone(&starts);
-            Arc::new(move |cancellation: CancellationToken| {
+            Arc::new(move |_: &TestRequest, cancellation: CancellationToken| {
                 starts.fetch_add(1, Ordering::SeqCst);
                 started_sender.send(()).unwrap();
                 while !cancellation.is_cancelled() {
@@ -401,4 +491,129 @@ mod tests {
             matches!(controller.snapshot().state, PcileechTestState::Failed(error) if error.contains("Restart is blocked"))
         );
     }
+
+    #[test]
+    fn runs_use_the_profile_and_mode_set_before_they_start() {
+        let (request_sender, request_receiver) = mpsc::channel();
+        let runner = Arc::new(
+            move |request: &TestRequest, _cancellation: CancellationToken| {
+                request_sender
+                    .send((request.profile.connector.clone(), request.mode))
+                    .unwrap();
+                RunOutcome::safe(PcileechTestState::Failed("done".into()))
+            },
+        ) as Arc<TestRunner>;
+        let controller = PcileechTestController::with_runner(runner);
+
+        controller.set_profile(TestProfile {
+            connector: "qemu".into(),
+            benchmark_args: "--bench".into(),
+            ..TestProfile::default()
+        });
+        controller.set_mode(TestMode::Benchmark { seconds: 30 });
+        controller.ensure_started();
+
+        assert_eq!(
+            request_receiver
+                .recv_timeout(Duration::from_secs(1))
+                .unwrap(),
+            ("qemu".to_string(), TestMode::Benchmark { seconds: 30 })
+        );
+    }
+
+    #[test]
+    fn benchmark_samples_are_visible_while_it_runs() {
+        let (started_sender, started_receiver) = mpsc::channel();
+        let runner = Arc::new(
+            move |request: &TestRequest, cancellation: CancellationToken| {
+                request.recorder.push(40_000_000);
+                request.recorder.push(60_000_000);
+                started_sender.send(()).unwrap();
+                while !cancellation.is_cancelled() {
+                    thread::yield_now();
+                }
+                RunOutcome::safe(PcileechTestState::Failed("cancelled".into()))
+            },
+        ) as Arc<TestRunner>;
+        let controller = PcileechTestController::with_runner(runner);
+
+        controller.set_profile(TestProfile {
+            benchmark_args: "--bench".into(),
+            ..TestProfile::default()
+        });
+        controller.set_mode(TestMode::Benchmark { seconds: 10 });
+        controller.ensure_started();
+        started_receiver
+            .recv_timeout(Duration::from_secs(1))
+            .unwrap();
+
+        let snapshot = controller.snapshot();
+        assert_eq!(snapshot.state, PcileechTestState::Running);
+        assert_eq!(snapshot.throughput.samples, [40_000_000, 60_000_000]);
+    }
+
+    #[test]
+    fn benchmark_mode_needs_benchmark_args_in_the_profile() {
+        let controller = PcileechTestController::with_runner(Arc::new(|_, _| {
+            RunOutcome::safe(PcileechTestState::Failed("done".into()))
+        }));
+
+        controller.set_mode(TestMode::Benchmark { seconds: 10 });
+        assert_eq!(controller.snapshot().mode, TestMode::Check);
+        assert!(!controller.snapshot().benchmark_available);
+
+        controller.set_profile(TestProfile {
+            benchmark_args: "--bench".into(),
+            ..TestProfile::default()
+        });
+        controller.set_mode(TestMode::Benchmark { seconds: 10 });
+        assert_eq!(
+            controller.snapshot().mode,
+            TestMode::Benchmark { seconds: 10 }
+        );
+
+        controller.set_profile(TestProfile::default());
+        assert_eq!(controller.snapshot().mode, TestMode::Check);
+    }
+
+    #[test]
+    fn stopping_a_soak_shows_the_attempts_so_far() {
+        let runner = Arc::new(
+            move |request: &TestRequest, cancellation: CancellationToken| {
+                let TestMode::Soak(plan) = request.mode else {
+                    unreachable!("only soak runs are started");
+                };
+                soak::run_soak(&plan, &request.soak, &cancellation, || {
+                    RunOutcome::safe(PcileechTestState::Success(Box::default()))
+                })
+            },
+        ) as Arc<TestRunner>;
+        let controller = PcileechTestController::with_runner(runner);
+
+        controller.set_mode(TestMode::Soak(SoakPlan {
+            limit: SoakLimit::Runs(100),
+            gap_seconds: 10,
+        }));
+        controller.ensure_started();
+        // The first attempt finishes at once and the soak then waits out the gap.
+        let deadline = std::time::Instant::now() + Duration::from_secs(1);
+        while controller.snapshot().soak.attempts.is_empty() && std::time::Instant::now() < deadline
+        {
+            thread::yield_now();
+        }
+        controller.stop();
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(1);
+        while controller.snapshot().state == PcileechTestState::Running
+            && std::time::Instant::now() < deadline
+        {
+            thread::yield_now();
+        }
+
+        let PcileechTestState::Soaked(report) = controller.snapshot().state else {
+            panic!("expected a soak report");
+        };
+        assert!(report.cancelled);
+        assert_eq!(report.successes(), 1);
+    }
 }
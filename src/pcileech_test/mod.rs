@@ -1,14 +1,37 @@
+mod benchmark;
 mod controller;
 mod parser;
+mod profile;
+mod report;
+mod rules;
 mod runner;
+mod soak;
 
+pub use benchmark::{BENCHMARK_DURATIONS, Throughput, format_mbps};
 pub use controller::{PcileechTestController, PcileechTestSnapshot};
+pub use profile::{TestProfile, TestProfileStore};
+pub use report::PcileechReport;
+pub use rules::OutputRules;
+pub use soak::{MAX_FAILURE_REASONS, SOAK_GAPS, SOAK_LIMITS, SoakLimit, SoakPlan, SoakReport};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TestMode {
+    /// Single pass/fail connection check.
+    #[default]
+    Check,
+    /// Runs the tool's read loop and measures throughput.
+    Benchmark { seconds: u32 },
+    /// Repeats the connection check to find intermittent failures.
+    Soak(SoakPlan),
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum PcileechTestState {
     #[default]
     Idle,
     Running,
-    Success(String),
+    Success(Box<PcileechReport>),
+    Measured(Throughput),
+    Soaked(SoakReport),
     Failed(String),
 }
@@ -1,51 +1,48 @@
 use super::PcileechTestState;
+use super::report::PcileechReport;
+use super::rules::OutputRules;
 
-const SUCCESS_SIGNATURE: &str = "ntdll.dll base address:";
-
-pub fn find_success_line(output: &str) -> Option<String> {
-    output.lines().find_map(|line| {
-        let line = line.trim();
-        if is_error_line(line) {
-            return None;
-        }
-
-        let (_, address) = line.split_once(SUCCESS_SIGNATURE)?;
-        let address = address.trim();
-        let hexadecimal = address
-            .strip_prefix("0x")
-            .or_else(|| address.strip_prefix("0X"))?;
-
-        u64::from_str_radix(hexadecimal, 16)
-            .ok()
-            .filter(|address| *address != 0)
-            .map(|_| line.to_string())
-    })
-}
-
-pub fn find_error_message(output: &str) -> Option<String> {
+pub fn find_success_line(rules: &OutputRules, output: &str) -> Option<String> {
     output
         .lines()
-        .find(|line| line.contains("Error:"))
-        .or_else(|| output.lines().find(|line| is_error_line(line)))
-        .map(|line| line.trim().to_string())
+        .find_map(|line| rules.success_in_line(line.trim()))
 }
 
-fn is_error_line(line: &str) -> bool {
-    line.to_ascii_lowercase().contains("error")
+/// A non-zero `0x`-prefixed hexadecimal address.
+pub(super) fn parse_address(text: &str) -> Option<u64> {
+    let hexadecimal = text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))?;
+
+    u64::from_str_radix(hexadecimal, 16)
+        .ok()
+        .filter(|address| *address != 0)
+}
+
+pub fn find_error_message(rules: &OutputRules, output: &str) -> Option<String> {
+    rules.failure_in_output(output)
 }
 
+/// Success is only read from stdout; `diagnostics` holds both streams and is
+/// searched for the details shown alongside it.
 pub fn finalize_result(
+    rules: &OutputRules,
     output: &str,
+    diagnostics: &str,
     success_line: Option<String>,
     process_error: Option<String>,
 ) -> PcileechTestState {
-    if let Some(line) = success_line.or_else(|| find_success_line(output)) {
-        return PcileechTestState::Success(line);
+    if let Some(line) = success_line.or_else(|| find_success_line(rules, output)) {
+        return PcileechTestState::Success(Box::new(PcileechReport::parse(
+            rules,
+            &line,
+            diagnostics,
+        )));
     }
 
     PcileechTestState::Failed(
         process_error
-            .or_else(|| find_error_message(output))
+            .or_else(|| find_error_message(rules, output))
             .unwrap_or_else(|| "Unknown error".to_string()),
     )
 }
@@ -54,11 +51,15 @@ pub fn finalize_result(
 mod tests {
     use super::*;
 
+    fn rules() -> OutputRules {
+        OutputRules::default()
+    }
+
     #[test]
     fn detects_pcileech_success_signature() {
         let output = "memflow init\nntdll.dll base address: 0x7ffa0000\n";
         assert_eq!(
-            find_success_line(output),
+            find_success_line(&rules(), output),
             Some("ntdll.dll base address: 0x7ffa0000".to_string())
         );
     }
@@ -67,7 +68,7 @@ mod tests {
     fn extracts_first_error_line() {
         let output = "startup\nError: failed to initialize connector\nmore detail\n";
         assert_eq!(
-            find_error_message(output),
+            find_error_message(&rules(), output),
             Some("Error: failed to initialize connector".to_string())
         );
     }
@@ -76,7 +77,7 @@ mod tests {
     fn detects_lowercase_error_line() {
         let output = "connector error: device not found\n";
         assert_eq!(
-            find_error_message(output),
+            find_error_message(&rules(), output),
             Some("connector error: device not found".to_string())
         );
     }
@@ -86,11 +87,17 @@ mod tests {
         let output = "Error: transient connector warning\nntdll.dll base address: 0x7ffa0000\n";
         assert_eq!(
             finalize_result(
+                &rules(),
+                output,
                 output,
                 None,
                 Some("Error: transient connector warning".to_string())
             ),
-            PcileechTestState::Success("ntdll.dll base address: 0x7ffa0000".into())
+            PcileechTestState::Success(Box::new(PcileechReport::parse(
+                &rules(),
+                "ntdll.dll base address: 0x7ffa0000",
+                output
+            )))
         );
     }
 
@@ -98,9 +105,9 @@ mod tests {
     fn rejects_success_signature_on_error_line() {
         let output = "Error: missing ntdll.dll base address: 0x7ffa0000\n";
 
-        assert_eq!(find_success_line(output), None);
+        assert_eq!(find_success_line(&rules(), output), None);
         assert_eq!(
-            finalize_result(output, None, None),
+            finalize_result(&rules(), output, output, None, None),
             PcileechTestState::Failed(output.trim().into())
         );
     }
@@ -114,7 +121,11 @@ mod tests {
             "ntdll.dll base address: 0x0\n",
             "ntdll.dll base address: 0x7ffa0000 trailing\n",
         ] {
-            assert_eq!(find_success_line(output), None, "accepted {output:?}");
+            assert_eq!(
+                find_success_line(&rules(), output),
+                None,
+                "accepted {output:?}"
+            );
         }
     }
 
@@ -122,13 +133,22 @@ mod tests {
     fn accepts_valid_success_with_a_benign_prefix() {
         let output = "status: ntdll.dll base address: 0x7ffa0000\n";
 
-        assert_eq!(find_success_line(output), Some(output.trim().to_string()));
+        assert_eq!(
+            find_success_line(&rules(), output),
+            Some(output.trim().to_string())
+        );
     }
 
     #[test]
     fn preserves_process_error_without_success() {
         assert_eq!(
-            finalize_result("startup\n", None, Some("Error: failed".to_string())),
+            finalize_result(
+                &rules(),
+                "startup\n",
+                "startup\n",
+                None,
+                Some("Error: failed".to_string())
+            ),
             PcileechTestState::Failed("Error: failed".into())
         );
     }
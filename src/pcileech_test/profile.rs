@@ -0,0 +1,414 @@
+use crate::utils::app_dirs;
+use crate::utils::json_store::JsonFormat;
+use crate::utils::localization::TextKey;
+use crate::utils::logger::Logger;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[cfg(windows)]
+const PCILEECH_TOOL_PATH: &str = "tools\\memflow-base\\memflow-base.exe";
+#[cfg(not(windows))]
+const PCILEECH_TOOL_PATH: &str = "tools/memflow-base/memflow-base";
+const DEFAULT_PROFILE_NAME: &str = "Default";
+const DEFAULT_CONNECTOR: &str = "pcileech";
+const PROFILES_FILE: &str = "pcileech_profiles.json";
+const PROFILES_FORMAT: JsonFormat = JsonFormat {
+    label: "PCILeech test profiles",
+    version: 1,
+    fallback: "using the default profile",
+};
+
+/// A named test target: the memflow tool to run and the connector it should
+/// load.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestProfile {
+    pub name: String,
+    pub executable: String,
+    pub connector: String,
+    /// Connector arguments in memflow's `key=value` form. Only the pcileech
+    /// connector takes any, as `device=FPGA`, which the tool receives as
+    /// `--pcileech-device`.
+    #[serde(default)]
+    pub connector_args: String,
+    /// Appended to the tool arguments for the read-loop benchmark. The bundled
+    /// memflow-base has no read-loop mode, so this is empty by default and
+    /// benchmark mode is unavailable until a tool that has one is configured.
+    #[serde(default)]
+    pub benchmark_args: String,
+}
+
+impl Default for TestProfile {
+    fn default() -> Self {
+        Self {
+            name: DEFAULT_PROFILE_NAME.to_string(),
+            executable: PCILEECH_TOOL_PATH.to_string(),
+            connector: DEFAULT_CONNECTOR.to_string(),
+            connector_args: String::new(),
+            benchmark_args: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileError {
+    MissingName,
+    MissingExecutable,
+    InvalidConnector,
+    InvalidConnectorArgs,
+}
+
+impl ProfileError {
+    pub fn text_key(&self) -> TextKey {
+        match self {
+            Self::MissingName => TextKey::PcileechProfileMissingName,
+            Self::MissingExecutable => TextKey::PcileechProfileMissingExecutable,
+            Self::InvalidConnector => TextKey::PcileechProfileInvalidConnector,
+            Self::InvalidConnectorArgs => TextKey::PcileechProfileInvalidConnectorArgs,
+        }
+    }
+}
+
+impl TestProfile {
+    /// Tool arguments in the form memflow-base accepts: the connector, the
+    /// PCILeech device when one is set, and headless mode.
+    pub fn tool_args(&self) -> Vec<String> {
+        let mut args = vec!["-c".to_string(), self.connector.trim().to_string()];
+        if let Ok(Some(device)) = self.pcileech_device() {
+            args.extend(["--pcileech-device".to_string(), device.to_string()]);
+        }
+        args.push("--headless".to_string());
+        args
+    }
+
+    /// The device named in the connector arguments, either as `device=FPGA` or
+    /// as memflow's bare default argument. memflow-base has no way to pass
+    /// arguments to any other connector.
+    fn pcileech_device(&self) -> Result<Option<&str>, ProfileError> {
+        let connector_args = self.connector_args.trim();
+        if connector_args.is_empty() {
+            return Ok(None);
+        }
+        if !self
+            .connector
+            .trim()
+            .eq_ignore_ascii_case(DEFAULT_CONNECTOR)
+        {
+            return Err(ProfileError::InvalidConnectorArgs);
+        }
+
+        let device = connector_args
+            .strip_prefix("device=")
+            .unwrap_or(connector_args)
+            .trim();
+        if device.is_empty()
+            || device
+                .chars()
+                .any(|character| character.is_whitespace() || matches!(character, ',' | '='))
+        {
+            return Err(ProfileError::InvalidConnectorArgs);
+        }
+        Ok(Some(device))
+    }
+
+    /// Whether the profile names a read-loop mode for the benchmark to run.
+    pub fn supports_benchmark(&self) -> bool {
+        !self.benchmark_args.trim().is_empty()
+    }
+
+    /// Tool arguments for the read-loop benchmark, which runs until stopped.
+    pub fn benchmark_args(&self) -> Vec<String> {
+        let mut args = self.tool_args();
+        args.extend(self.benchmark_args.split_whitespace().map(str::to_string));
+        args
+    }
+
+    pub fn validate(&self) -> Result<(), ProfileError> {
+        if self.name.trim().is_empty() {
+            return Err(ProfileError::MissingName);
+        }
+        if self.executable.trim().is_empty() {
+            return Err(ProfileError::MissingExecutable);
+        }
+        // Arguments have their own field; a `:` here would start a second list.
+        let connector = self.connector.trim();
+        if connector.is_empty()
+            || connector
+                .chars()
+                .any(|character| character.is_whitespace() || character == ':')
+        {
+            return Err(ProfileError::InvalidConnector);
+        }
+        self.pcileech_device()?;
+        Ok(())
+    }
+
+    fn trimmed(&self) -> Self {
+        Self {
+            name: self.name.trim().to_string(),
+            executable: self.executable.trim().to_string(),
+            connector: self.connector.trim().to_string(),
+            connector_args: self.connector_args.trim().to_string(),
+            benchmark_args: self.benchmark_args.trim().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ProfilesFile {
+    selected: String,
+    profiles: Vec<TestProfile>,
+}
+
+/// Saved test profiles and the one the test screen runs. There is always at
+/// least one profile.
+pub struct TestProfileStore {
+    path: Option<PathBuf>,
+    profiles: Vec<TestProfile>,
+    selected: usize,
+    logger: Logger,
+}
+
+impl TestProfileStore {
+    /// Opens the profiles in the per-user data directory, falling back to the
+    /// working directory when no data directory is available.
+    pub fn open_default(logger: Logger) -> Self {
+        let path = app_dirs::data_dir()
+            .map(|dir| dir.join(PROFILES_FILE))
+            .unwrap_or_else(|| PathBuf::from(PROFILES_FILE));
+        Self::open(path, logger)
+    }
+
+    pub fn open(path: PathBuf, logger: Logger) -> Self {
+        let (profiles, selected) = match PROFILES_FORMAT.load::<ProfilesFile>(&path, &logger) {
+            Some(file) if !file.profiles.is_empty() => {
+                let selected = file
+                    .profiles
+                    .iter()
+                    .position(|profile| profile.name == file.selected)
+                    .unwrap_or(0);
+                (file.profiles, selected)
+            }
+            _ => (vec![TestProfile::default()], 0),
+        };
+        for profile in &profiles {
+            if profile.pcileech_device().is_err() {
+                logger.warning(format!(
+                    "PCILeech test profile {} has connector arguments the test tool cannot take; running it without them",
+                    profile.name
+                ));
+            }
+        }
+
+        Self {
+            path: Some(path),
+            profiles,
+            selected,
+            logger,
+        }
+    }
+
+    /// Profiles that are never written to disk.
+    #[cfg(test)]
+    pub fn in_memory(logger: Logger) -> Self {
+        Self {
+            path: None,
+            profiles: vec![TestProfile::default()],
+            selected: 0,
+            logger,
+        }
+    }
+
+    pub fn profiles(&self) -> &[TestProfile] {
+        &self.profiles
+    }
+
+    pub fn selected(&self) -> &TestProfile {
+        &self.profiles[self.selected]
+    }
+
+    pub fn select(&mut self, name: &str) -> bool {
+        let Some(index) = self.position(name) else {
+            return false;
+        };
+        self.selected = index;
+        self.save();
+        true
+    }
+
+    /// Adds the profile, or replaces the one with the same name, and selects it.
+    pub fn save_profile(&mut self, profile: &TestProfile) -> Result<(), ProfileError> {
+        profile.validate()?;
+        let profile = profile.trimmed();
+
+        self.selected = match self.position(&profile.name) {
+            Some(index) => {
+                self.profiles[index] = profile;
+                index
+            }
+            None => {
+                self.profiles.push(profile);
+                self.profiles.len() - 1
+            }
+        };
+        self.save();
+        Ok(())
+    }
+
+    /// Removes a profile unless it is the last one.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let Some(index) = self.position(name) else {
+            return false;
+        };
+        if self.profiles.len() == 1 {
+            return false;
+        }
+
+        self.profiles.remove(index);
+        if self.selected >= index && self.selected > 0 {
+            self.selected -= 1;
+        }
+        self.save();
+        true
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        let name = name.trim();
+        self.profiles
+            .iter()
+            .position(|profile| profile.name.eq_ignore_ascii_case(name))
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let file = ProfilesFile {
+            selected: self.selected().name.clone(),
+            profiles: self.profiles.clone(),
+        };
+        if let Err(error) = PROFILES_FORMAT.save(path, &file) {
+            self.logger.error(format!(
+                "Failed to save PCILeech test profiles to {}: {error}",
+                path.display()
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::temp_dir::TempDir;
+
+    fn profile(name: &str, connector: &str, connector_args: &str) -> TestProfile {
+        TestProfile {
+            name: name.to_string(),
+            connector: connector.to_string(),
+            connector_args: connector_args.to_string(),
+            ..TestProfile::default()
+        }
+    }
+
+    #[test]
+    fn default_profile_keeps_the_original_arguments() {
+        assert_eq!(
+            TestProfile::default().tool_args(),
+            ["-c", "pcileech", "--headless"]
+        );
+    }
+
+    #[test]
+    fn the_pcileech_device_is_passed_as_its_own_option() {
+        let expected = ["-c", "pcileech", "--pcileech-device", "FPGA", "--headless"];
+        assert_eq!(
+            profile("FT2232H", "pcileech", " device=FPGA ").tool_args(),
+            expected
+        );
+        assert_eq!(profile("FT601", "pcileech", "FPGA").tool_args(), expected);
+    }
+
+    #[test]
+    fn connector_args_the_tool_cannot_pass_are_rejected() {
+        for (connector, connector_args) in [
+            ("qemu", "name=win10"),
+            ("pcileech", "device=FPGA,memmap=auto"),
+            ("pcileech", "device="),
+        ] {
+            assert_eq!(
+                profile("Custom", connector, connector_args).validate(),
+                Err(ProfileError::InvalidConnectorArgs),
+                "{connector} {connector_args}"
+            );
+        }
+    }
+
+    #[test]
+    fn benchmark_args_come_from_the_profile() {
+        assert!(!TestProfile::default().supports_benchmark());
+        let profile = TestProfile {
+            benchmark_args: " --bench  --seconds=30 ".to_string(),
+            ..profile("Lab", "qemu", "")
+        };
+        assert_eq!(
+            profile.benchmark_args(),
+            ["-c", "qemu", "--headless", "--bench", "--seconds=30"]
+        );
+        assert!(profile.supports_benchmark());
+
+        let saved: TestProfile = serde_json::from_str(
+            r#"{ "name": "Old", "executable": "tool", "connector": "pcileech" }"#,
+        )
+        .unwrap();
+        assert!(!saved.supports_benchmark());
+    }
+
+    #[test]
+    fn connector_names_must_be_a_single_word() {
+        assert_eq!(
+            profile("Custom", "pcileech:device=FPGA", "").validate(),
+            Err(ProfileError::InvalidConnector)
+        );
+        assert_eq!(
+            profile("Custom", "", "").validate(),
+            Err(ProfileError::InvalidConnector)
+        );
+        assert_eq!(
+            profile(" ", "pcileech", "").validate(),
+            Err(ProfileError::MissingName)
+        );
+    }
+
+    #[test]
+    fn saved_profiles_and_selection_survive_a_restart() {
+        let dir = TempDir::new("pcileech-profiles");
+        let path = dir.join(PROFILES_FILE);
+        let logger = Logger::new("TestProfileStoreTest");
+
+        let mut store = TestProfileStore::open(path.clone(), logger.clone());
+        store
+            .save_profile(&profile("FT601", "pcileech", "device=FPGA"))
+            .unwrap();
+        store.save_profile(&profile("QEMU", "qemu", "")).unwrap();
+        assert!(store.select("ft601"));
+
+        let reopened = TestProfileStore::open(path.clone(), logger);
+        let names: Vec<&str> = reopened
+            .profiles()
+            .iter()
+            .map(|profile| profile.name.as_str())
+            .collect();
+        assert_eq!(names, ["Default", "FT601", "QEMU"]);
+        assert_eq!(reopened.selected().connector_args, "device=FPGA");
+    }
+
+    #[test]
+    fn the_last_profile_cannot_be_removed() {
+        let mut store = TestProfileStore::in_memory(Logger::new("TestProfileStoreTest"));
+        store.save_profile(&profile("QEMU", "qemu", "")).unwrap();
+
+        assert!(store.remove("QEMU"));
+        assert_eq!(store.selected().name, "Default");
+        assert!(!store.remove("Default"));
+        assert_eq!(store.profiles().len(), 1);
+    }
+}
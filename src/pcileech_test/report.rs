@@ -0,0 +1,303 @@
+use super::parser::parse_address;
+use super::rules::OutputRules;
+use crate::utils::localization::TextKey;
+
+/// What the test tool reported about the DMA card and the target it reached.
+/// Details the tool did not print stay `None`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PcileechReport {
+    /// The line that proved memory could be read.
+    pub summary: String,
+    pub fpga_device: Option<String>,
+    pub fpga_id: Option<String>,
+    pub fpga_version: Option<String>,
+    pub device_id: Option<String>,
+    pub pcie_link: Option<String>,
+    pub target_os: Option<String>,
+    pub os_build: Option<String>,
+    pub kernel_base: Option<u64>,
+    pub dtb: Option<u64>,
+    pub ntdll_base: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Field {
+    FpgaId,
+    FpgaVersion,
+    DeviceId,
+    PcieLink,
+    TargetOs,
+    OsBuild,
+    KernelBase,
+    Dtb,
+    NtdllBase,
+}
+
+// Labels as they appear once lowercased with `_` and `-` turned into spaces.
+// Longer labels come first so `os build` is not read as `os`.
+const LABELS: &[(&str, Field)] = &[
+    ("ntdll.dll base address", Field::NtdllBase),
+    ("directory table base", Field::Dtb),
+    ("bitstream version", Field::FpgaVersion),
+    ("fpga version", Field::FpgaVersion),
+    ("ntoskrnl base", Field::KernelBase),
+    ("kernel base", Field::KernelBase),
+    ("build number", Field::OsBuild),
+    ("link speed", Field::PcieLink),
+    ("pcie link", Field::PcieLink),
+    ("target os", Field::TargetOs),
+    ("device id", Field::DeviceId),
+    ("os version", Field::TargetOs),
+    ("os build", Field::OsBuild),
+    ("fpga id", Field::FpgaId),
+    ("os name", Field::TargetOs),
+    ("build", Field::OsBuild),
+    ("dtb", Field::Dtb),
+    ("os", Field::TargetOs),
+];
+
+impl PcileechReport {
+    /// Builds a report around the success line and fills in every detail
+    /// found in `output`, skipping lines the rules call failures. The first
+    /// value printed for a detail wins.
+    pub fn parse(rules: &OutputRules, summary: &str, output: &str) -> Self {
+        let mut report = Self {
+            summary: summary.trim().to_string(),
+            ..Self::default()
+        };
+        for line in summary.lines().chain(output.lines()) {
+            let line = line.trim();
+            if line.is_empty() || rules.is_failure(line) {
+                continue;
+            }
+            report.read_fpga_line(line);
+            report.read_labelled_values(line);
+        }
+        report
+    }
+
+    /// Label and display value of each detail that was found, in the order
+    /// the test screen shows them.
+    pub fn rows(&self) -> Vec<(TextKey, String)> {
+        let text = [
+            (TextKey::PcileechFpgaDevice, &self.fpga_device),
+            (TextKey::PcileechFpgaId, &self.fpga_id),
+            (TextKey::PcileechFpgaVersion, &self.fpga_version),
+            (TextKey::PcileechDeviceId, &self.device_id),
+            (TextKey::PcileechPcieLink, &self.pcie_link),
+            (TextKey::PcileechTargetOs, &self.target_os),
+            (TextKey::PcileechOsBuild, &self.os_build),
+        ];
+        let addresses = [
+            (TextKey::PcileechKernelBase, self.kernel_base),
+            (TextKey::PcileechDtb, self.dtb),
+            (TextKey::PcileechNtdllBase, self.ntdll_base),
+        ];
+
+        text.into_iter()
+            .filter_map(|(key, value)| Some((key, value.clone()?)))
+            .chain(
+                addresses
+                    .into_iter()
+                    .filter_map(|(key, value)| Some((key, format!("{:#x}", value?)))),
+            )
+            .collect()
+    }
+
+    /// LeechCore describes the card on one line, printed as
+    /// `DEVICE: FPGA: %s PCIe gen%i x%i [%i,%i,%i] [v%i.%i,%04x] [%s,%s%s]`,
+    /// where the hex field after the version is the FPGA ID.
+    fn read_fpga_line(&mut self, line: &str) {
+        let Some((_, description)) = line.split_once("FPGA:") else {
+            return;
+        };
+        let (words, brackets) = description
+            .split_once('[')
+            .map_or((description, ""), |(words, rest)| (words, rest));
+
+        let mut words = words.split_whitespace();
+        let device: Vec<&str> = words.by_ref().take_while(|word| *word != "PCIe").collect();
+        let link: Vec<&str> = words.collect();
+        if !device.is_empty() {
+            set_once(&mut self.fpga_device, device.join(" "));
+        }
+        if !link.is_empty() {
+            set_once(&mut self.pcie_link, format!("PCIe {}", link.join(" ")));
+        }
+
+        for bracket in brackets.split(['[', ']']).map(str::trim) {
+            let Some(version) = bracket.strip_prefix('v') else {
+                continue;
+            };
+            let (version, id) = version.split_once(',').unwrap_or((version, ""));
+            set_once(&mut self.fpga_version, version.trim().to_string());
+            let id = id.trim();
+            if !id.is_empty() {
+                set_once(&mut self.fpga_id, format!("0x{id}"));
+            }
+        }
+    }
+
+    fn read_labelled_values(&mut self, line: &str) {
+        let normalized = line.to_ascii_lowercase().replace(['_', '-'], " ");
+        let mut search_from = 0;
+
+        while search_from < normalized.len() {
+            let Some((label_end, field)) = next_label(&normalized, search_from) else {
+                return;
+            };
+            // `to_ascii_lowercase` and the replacements keep byte offsets.
+            let Some(value_start) = value_start(&normalized, label_end) else {
+                search_from = label_end;
+                continue;
+            };
+            let value = &line[value_start..];
+            self.set_field(field, value);
+            search_from = value_start;
+        }
+    }
+
+    fn set_field(&mut self, field: Field, value: &str) {
+        match field {
+            Field::KernelBase => set_address(&mut self.kernel_base, value),
+            Field::Dtb => set_address(&mut self.dtb, value),
+            Field::NtdllBase => set_address(&mut self.ntdll_base, value),
+            Field::FpgaId => set_text(&mut self.fpga_id, value),
+            Field::FpgaVersion => set_text(&mut self.fpga_version, value),
+            Field::DeviceId => set_text(&mut self.device_id, value),
+            Field::PcieLink => set_text(&mut self.pcie_link, value),
+            Field::TargetOs => set_text(&mut self.target_os, value),
+            Field::OsBuild => set_text(&mut self.os_build, value),
+        }
+    }
+}
+
+/// The earliest label at or after `from` that starts a word, returning where
+/// it ends.
+fn next_label(line: &str, from: usize) -> Option<(usize, Field)> {
+    LABELS
+        .iter()
+        .filter_map(|(label, field)| {
+            let start = line[from..]
+                .match_indices(label)
+                .map(|(offset, _)| from + offset)
+                .find(|start| {
+                    line[..*start]
+                        .chars()
+                        .next_back()
+                        .is_none_or(|previous| !previous.is_ascii_alphanumeric())
+                })?;
+            Some((start, start + label.len(), *field))
+        })
+        .min_by_key(|(start, _, _)| *start)
+        .map(|(_, end, field)| (end, field))
+}
+
+/// A label only counts when it is directly followed by `:` or `=`.
+fn value_start(line: &str, label_end: usize) -> Option<usize> {
+    let value = line[label_end..]
+        .trim_start()
+        .strip_prefix([':', '='])?
+        .trim_start();
+    Some(line.len() - value.len())
+}
+
+fn set_address(slot: &mut Option<u64>, value: &str) {
+    if slot.is_some() {
+        return;
+    }
+    let token = value
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches([',', ';', ')', '}']);
+    *slot = parse_address(token);
+}
+
+fn set_text(slot: &mut Option<String>, value: &str) {
+    // `key=value` pairs are usually listed on one line; stop at the next pair.
+    let value = value
+        .split([',', ';'])
+        .next()
+        .unwrap_or_default()
+        .split_whitespace()
+        .take_while(|word| !word.contains('='))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if !value.is_empty() {
+        set_once(slot, value);
+    }
+}
+
+fn set_once(slot: &mut Option<String>, value: String) {
+    if slot.is_none() {
+        *slot = Some(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUCCESS_LINE: &str = "ntdll.dll base address: 0x7ffa0000";
+
+    #[test]
+    fn reads_the_leechcore_fpga_line() {
+        let report = PcileechReport::parse(
+            &OutputRules::default(),
+            SUCCESS_LINE,
+            "DEVICE: FPGA: ScreamerM2 PCIe gen2 x1 [300,25,500] [v4.14,0003] [ASYNC,NORM]\n",
+        );
+
+        assert_eq!(report.fpga_device.as_deref(), Some("ScreamerM2"));
+        assert_eq!(report.pcie_link.as_deref(), Some("PCIe gen2 x1"));
+        assert_eq!(report.fpga_version.as_deref(), Some("4.14"));
+        assert_eq!(report.fpga_id.as_deref(), Some("0x0003"));
+        assert_eq!(report.ntdll_base, Some(0x7ffa_0000));
+    }
+
+    #[test]
+    fn reads_labelled_values_from_log_lines() {
+        let output = "\
+[INFO  memflow_pcileech] device_id: 10EE:0666
+[INFO  memflow_win32] kernel_base=0xfffff80412000000 dtb=0x1ad000
+[INFO  memflow_win32] OS: Windows 10 x64
+[INFO  memflow_win32] Build number: 19045
+";
+        let report = PcileechReport::parse(&OutputRules::default(), SUCCESS_LINE, output);
+
+        assert_eq!(report.device_id.as_deref(), Some("10EE:0666"));
+        assert_eq!(report.kernel_base, Some(0xffff_f804_1200_0000));
+        assert_eq!(report.dtb, Some(0x1a_d000));
+        assert_eq!(report.target_os.as_deref(), Some("Windows 10 x64"));
+        assert_eq!(report.os_build.as_deref(), Some("19045"));
+    }
+
+    #[test]
+    fn ignores_labels_inside_words_and_error_lines() {
+        let output = "\
+found ntoskrnl.exe at 0x1000
+Error: could not read dtb: 0x1ad000
+kernel base: unknown
+";
+        let report = PcileechReport::parse(&OutputRules::default(), SUCCESS_LINE, output);
+
+        assert_eq!(report.target_os, None);
+        assert_eq!(report.dtb, None);
+        assert_eq!(report.kernel_base, None);
+    }
+
+    #[test]
+    fn rows_list_only_found_details() {
+        let report =
+            PcileechReport::parse(&OutputRules::default(), SUCCESS_LINE, "OS build: 22631\n");
+
+        assert_eq!(
+            report.rows(),
+            [
+                (TextKey::PcileechOsBuild, "22631".to_string()),
+                (TextKey::PcileechNtdllBase, "0x7ffa0000".to_string()),
+            ]
+        );
+    }
+}
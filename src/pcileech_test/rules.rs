@@ -0,0 +1,261 @@
+use crate::utils::app_dirs;
+use crate::utils::logger::Logger;
+use regex::Regex;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const RULES_FILE: &str = "pcileech_rules.json";
+const RULES_VERSION: u32 = 1;
+
+// A non-zero `0x` address of at most 16 significant digits ends the line.
+const DEFAULT_SUCCESS: &[&str] =
+    &[r"ntdll\.dll base address:\s*0[xX]0*[1-9a-fA-F][0-9a-fA-F]{0,15}$"];
+// Lines with `Error:` are preferred as the message over other mentions.
+const DEFAULT_FAILURE: &[&str] = &["Error:", "(?i)error"];
+// memflow logs below error level, and error counters that are zero.
+const DEFAULT_IGNORE: &[&str] = &[
+    r"^\[(TRACE|DEBUG|INFO|WARN)\b",
+    r"(?i)\b0 errors?\b",
+    r"(?i)\berrors?\s*[:=]\s*(0|none)\b",
+];
+
+/// One line pattern. When the pattern has a capture group, the first group is
+/// what gets shown; otherwise the whole line is.
+#[derive(Debug, Clone)]
+struct OutputRule {
+    pattern: Regex,
+}
+
+impl OutputRule {
+    fn new(section: &str, pattern: &str) -> Result<Self, String> {
+        Regex::new(pattern)
+            .map(|pattern| Self { pattern })
+            .map_err(|error| format!("Invalid {section} rule `{pattern}`: {error}"))
+    }
+
+    fn find(&self, line: &str) -> Option<String> {
+        let captures = self.pattern.captures(line)?;
+        let shown = captures.get(1).map_or(line, |group| group.as_str()).trim();
+        Some(if shown.is_empty() { line } else { shown }.to_string())
+    }
+}
+
+/// How the test tool's output is judged: which lines prove success, which
+/// are fatal errors, and which merely mention an error and can be ignored.
+#[derive(Debug, Clone)]
+pub struct OutputRules {
+    success: Vec<OutputRule>,
+    failure: Vec<OutputRule>,
+    ignore: Vec<OutputRule>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RulesFile {
+    version: u32,
+    success: Option<Vec<String>>,
+    failure: Option<Vec<String>>,
+    ignore: Option<Vec<String>>,
+}
+
+impl Default for OutputRules {
+    fn default() -> Self {
+        Self::from_patterns(DEFAULT_SUCCESS, DEFAULT_FAILURE, DEFAULT_IGNORE)
+            .expect("built-in PCILeech output rules are valid")
+    }
+}
+
+impl OutputRules {
+    /// Loads the rules in the per-user data directory, falling back to the
+    /// working directory when no data directory is available.
+    pub fn load_default(logger: &Logger) -> Self {
+        let path = app_dirs::data_dir()
+            .map(|dir| dir.join(RULES_FILE))
+            .unwrap_or_else(|| PathBuf::from(RULES_FILE));
+        Self::load(&path, logger)
+    }
+
+    /// Reads rules from `path`. A missing file means the built-in rules; a
+    /// section left out of the file keeps its built-in patterns.
+    pub fn load(path: &Path, logger: &Logger) -> Self {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Self::default();
+            }
+            Err(error) => {
+                logger.error(format!(
+                    "Failed to read PCILeech output rules from {}: {error}",
+                    path.display()
+                ));
+                return Self::default();
+            }
+        };
+
+        match Self::parse(&contents) {
+            Ok(rules) => {
+                logger.info(format!(
+                    "Loaded PCILeech output rules from {}",
+                    path.display()
+                ));
+                rules
+            }
+            Err(error) => {
+                logger.warning(format!(
+                    "PCILeech output rules {} are not usable ({error}); using the built-in rules",
+                    path.display()
+                ));
+                Self::default()
+            }
+        }
+    }
+
+    fn parse(contents: &str) -> Result<Self, String> {
+        let file: RulesFile = serde_json::from_str(contents).map_err(|error| error.to_string())?;
+        if file.version > RULES_VERSION {
+            return Err(format!("newer format version {}", file.version));
+        }
+
+        let section = |patterns: &Option<Vec<String>>, defaults: &[&str]| {
+            patterns.as_ref().map_or_else(
+                || defaults.iter().map(|pattern| pattern.to_string()).collect(),
+                Clone::clone,
+            )
+        };
+        let success = section(&file.success, DEFAULT_SUCCESS);
+        if success.is_empty() {
+            return Err("at least one success rule is required".to_string());
+        }
+        Self::from_patterns(
+            &success,
+            &section(&file.failure, DEFAULT_FAILURE),
+            &section(&file.ignore, DEFAULT_IGNORE),
+        )
+    }
+
+    fn from_patterns(
+        success: &[impl AsRef<str>],
+        failure: &[impl AsRef<str>],
+        ignore: &[impl AsRef<str>],
+    ) -> Result<Self, String> {
+        Ok(Self {
+            success: compile("success", success)?,
+            failure: compile("failure", failure)?,
+            ignore: compile("ignore", ignore)?,
+        })
+    }
+
+    /// True when the line matches a failure rule and no ignore rule.
+    pub(super) fn is_failure(&self, line: &str) -> bool {
+        !self.is_ignored(line) && self.failure.iter().any(|rule| rule.pattern.is_match(line))
+    }
+
+    /// What a success rule shows for the line, unless the line is a failure.
+    pub(super) fn success_in_line(&self, line: &str) -> Option<String> {
+        if self.is_failure(line) {
+            return None;
+        }
+        self.success.iter().find_map(|rule| rule.find(line))
+    }
+
+    /// The message of the first line matching a failure rule. Rules are tried
+    /// in order, so an earlier rule wins over an earlier line.
+    pub(super) fn failure_in_output(&self, output: &str) -> Option<String> {
+        self.failure.iter().find_map(|rule| {
+            output
+                .lines()
+                .map(str::trim)
+                .filter(|line| !self.is_ignored(line))
+                .find_map(|line| rule.find(line))
+        })
+    }
+
+    fn is_ignored(&self, line: &str) -> bool {
+        self.ignore.iter().any(|rule| rule.pattern.is_match(line))
+    }
+}
+
+fn compile(section: &str, patterns: &[impl AsRef<str>]) -> Result<Vec<OutputRule>, String> {
+    patterns
+        .iter()
+        .map(|pattern| OutputRule::new(section, pattern.as_ref()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::temp_dir::TempDir;
+
+    #[test]
+    fn default_rules_ignore_benign_error_mentions() {
+        let rules = OutputRules::default();
+
+        for line in [
+            "[INFO  memflow::plugins] skipping error_handler symbol",
+            "[WARN  memflow_win32] retrying read after error",
+            "scan finished with 0 errors",
+            "errors: none",
+        ] {
+            assert!(!rules.is_failure(line), "failed on {line:?}");
+        }
+        assert!(rules.is_failure("[ERROR memflow] connector error"));
+        assert_eq!(
+            rules.failure_in_output("read error at 0x1000\nError: device not found\n"),
+            Some("Error: device not found".to_string())
+        );
+    }
+
+    #[test]
+    fn capture_groups_pick_the_text_to_show() {
+        let rules = OutputRules::parse(
+            r#"{
+                "version": 1,
+                "success": ["kernel found at (0x[0-9a-f]+)"],
+                "failure": ["^FATAL: (.*)$"]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            rules.success_in_line("kernel found at 0xfffff804"),
+            Some("0xfffff804".to_string())
+        );
+        assert_eq!(
+            rules.success_in_line("ntdll.dll base address: 0x7ffa0000"),
+            None
+        );
+        assert_eq!(
+            rules.failure_in_output("Error: ignored now\nFATAL: no FPGA\n"),
+            Some("no FPGA".to_string())
+        );
+        assert_eq!(rules.success_in_line("FATAL: kernel found at 0x1"), None);
+    }
+
+    #[test]
+    fn unusable_rules_are_rejected() {
+        for contents in [
+            r#"{"version": 1, "failure": ["(unclosed"]}"#,
+            r#"{"version": 1, "success": []}"#,
+            r#"{"version": 2}"#,
+            "not json",
+        ] {
+            assert!(OutputRules::parse(contents).is_err(), "accepted {contents}");
+        }
+    }
+
+    #[test]
+    fn an_invalid_file_falls_back_to_the_built_in_rules() {
+        let dir = TempDir::new("pcileech-rules");
+        let path = dir.join("pcileech_rules.json");
+        fs::write(&path, r#"{"version": 1, "success": ["[bad"]}"#).unwrap();
+
+        let rules = OutputRules::load(&path, &Logger::new("OutputRulesTest"));
+
+        assert!(
+            rules
+                .success_in_line("ntdll.dll base address: 0x7ffa0000")
+                .is_some()
+        );
+    }
+}
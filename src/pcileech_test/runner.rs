@@ -1,10 +1,11 @@
 use super::PcileechTestState;
+use super::benchmark::{ThroughputRecorder, parse_throughput};
 use super::parser::{finalize_result, find_error_message};
-use crate::device_programmer::CREATE_NO_WINDOW;
-use crate::utils::process_job::{CREATE_SUSPENDED, ProcessJob};
+use super::profile::TestProfile;
+use super::rules::OutputRules;
+use crate::utils::process_job::{ProcessJob, contain_command};
 use std::borrow::Cow;
 use std::ffi::OsString;
-use std::os::windows::process::CommandExt;
 use std::path::{Path, PathBuf};
 use std::process::{Child, ChildStderr, ChildStdout, Command, ExitStatus, Stdio};
 use std::sync::Arc;
@@ -13,7 +14,6 @@ use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TryRecvError
 use std::thread;
 use std::time::{Duration, Instant};
 
-const PCILEECH_TOOL_PATH: &str = "tools\\memflow-base\\memflow-base.exe";
 const PCILEECH_POLL_INTERVAL: Duration = Duration::from_millis(50);
 const PROCESS_TERMINATION_GRACE: Duration = Duration::from_secs(2);
 const STREAM_DRAIN_GRACE: Duration = Duration::from_secs(1);
@@ -70,44 +70,174 @@ struct RunConfig {
     termination_grace: Duration,
     stream_drain_grace: Duration,
     output_limit_grace: Duration,
+    /// Stops the tool once it has run this long.
+    deadline: Option<Duration>,
+    rules: Arc<OutputRules>,
 }
 
-impl Default for RunConfig {
-    fn default() -> Self {
+impl RunConfig {
+    fn for_profile(profile: &TestProfile, rules: &Arc<OutputRules>) -> Self {
+        Self::with_args(profile, profile.tool_args(), rules)
+    }
+
+    fn for_benchmark(profile: &TestProfile, duration: Duration, rules: &Arc<OutputRules>) -> Self {
         Self {
-            executable: PathBuf::from(PCILEECH_TOOL_PATH),
-            args: ["-c", "pcileech", "--headless"]
-                .into_iter()
-                .map(OsString::from)
-                .collect(),
+            deadline: Some(duration),
+            ..Self::with_args(profile, profile.benchmark_args(), rules)
+        }
+    }
+
+    fn with_args(profile: &TestProfile, args: Vec<String>, rules: &Arc<OutputRules>) -> Self {
+        Self {
+            executable: PathBuf::from(&profile.executable),
+            args: args.into_iter().map(OsString::from).collect(),
             poll_interval: PCILEECH_POLL_INTERVAL,
             termination_grace: PROCESS_TERMINATION_GRACE,
             stream_drain_grace: STREAM_DRAIN_GRACE,
             output_limit_grace: OUTPUT_LIMIT_GRACE,
+            deadline: None,
+            rules: Arc::clone(rules),
         }
     }
 }
 
-pub(super) fn run_pcileech_test(cancellation: &CancellationToken) -> RunOutcome {
-    run_with_config(&RunConfig::default(), cancellation)
+impl Default for RunConfig {
+    fn default() -> Self {
+        Self::for_profile(&TestProfile::default(), &Arc::default())
+    }
+}
+
+pub(super) fn run_pcileech_test(
+    profile: &TestProfile,
+    rules: &Arc<OutputRules>,
+    cancellation: &CancellationToken,
+) -> RunOutcome {
+    run_with_config(&RunConfig::for_profile(profile, rules), cancellation)
+}
+
+/// Runs the tool's read loop for `duration`, recording each rate it reports.
+pub(super) fn run_benchmark(
+    profile: &TestProfile,
+    rules: &Arc<OutputRules>,
+    duration: Duration,
+    recorder: &ThroughputRecorder,
+    cancellation: &CancellationToken,
+) -> RunOutcome {
+    run_benchmark_with_config(
+        &RunConfig::for_benchmark(profile, duration, rules),
+        recorder,
+        cancellation,
+    )
 }
 
 fn run_with_config(config: &RunConfig, cancellation: &CancellationToken) -> RunOutcome {
+    let run = match run_tool(config, cancellation, &mut |_| {}) {
+        Ok(run) => run,
+        Err(outcome) => return outcome,
+    };
+
+    let output = &run.output;
+    let final_stdout = output.stdout_text();
+    let final_diagnostics = output.diagnostic_text();
+    let observed_error = find_error_message(&config.rules, &final_diagnostics);
+    let truncation_error = output.truncation_error.clone();
+
+    let state = match run.stop_reason {
+        StopReason::Cancelled => {
+            PcileechTestState::Failed("PCILeech test was cancelled".to_string())
+        }
+        StopReason::OutputLimit(error) => finalize_result(
+            &config.rules,
+            &final_stdout,
+            &final_diagnostics,
+            None,
+            Some(error),
+        ),
+        StopReason::Error(error) => PcileechTestState::Failed(error),
+        StopReason::DeadlineReached => {
+            PcileechTestState::Failed("PCILeech test timed out".to_string())
+        }
+        StopReason::Exited(status) => {
+            let process_error = (!status.success())
+                .then(|| format!("PCILeech test exited with code: {:?}", status.code()));
+            finalize_result(
+                &config.rules,
+                &final_stdout,
+                &final_diagnostics,
+                None,
+                observed_error.or(process_error).or(truncation_error),
+            )
+        }
+    };
+
+    RunOutcome::safe(state)
+}
+
+fn run_benchmark_with_config(
+    config: &RunConfig,
+    recorder: &ThroughputRecorder,
+    cancellation: &CancellationToken,
+) -> RunOutcome {
+    let mut record_rate = |line: &str| {
+        if let Some(rate) = parse_throughput(line) {
+            recorder.push(rate);
+        }
+    };
+    let run = match run_tool(config, cancellation, &mut record_rate) {
+        Ok(run) => run,
+        Err(outcome) => return outcome,
+    };
+
+    let throughput = recorder.throughput();
+    let state = match run.stop_reason {
+        StopReason::Cancelled => {
+            PcileechTestState::Failed("PCILeech benchmark was cancelled".to_string())
+        }
+        StopReason::Error(error) => PcileechTestState::Failed(error),
+        // The tool only has to keep reading until the deadline; whatever it
+        // measured up to that point is the result.
+        _ if !throughput.samples.is_empty() => PcileechTestState::Measured(throughput),
+        StopReason::OutputLimit(error) => PcileechTestState::Failed(error),
+        StopReason::DeadlineReached | StopReason::Exited(_) => PcileechTestState::Failed(
+            find_error_message(&config.rules, &run.output.diagnostic_text())
+                .unwrap_or_else(|| "The test tool reported no read throughput".to_string()),
+        ),
+    };
+
+    RunOutcome::safe(state)
+}
+
+struct FinishedRun {
+    stop_reason: StopReason,
+    output: CollectedOutput,
+}
+
+/// Runs the tool until it exits, fails, is cancelled or reaches the deadline,
+/// handing each complete output line to `on_line` as it arrives. The process
+/// tree has been torn down by the time this returns `Ok`.
+fn run_tool(
+    config: &RunConfig,
+    cancellation: &CancellationToken,
+    on_line: &mut dyn FnMut(&str),
+) -> Result<FinishedRun, RunOutcome> {
     if cancellation.is_cancelled() {
-        return RunOutcome::safe(PcileechTestState::Failed(
+        return Err(RunOutcome::safe(PcileechTestState::Failed(
             "PCILeech test was cancelled".to_string(),
-        ));
+        )));
     }
 
-    let (mut child, process_job) = match spawn_pcileech_tool(config) {
-        Ok(process) => process,
-        Err(outcome) => return outcome,
-    };
+    let (mut child, process_job) = spawn_pcileech_tool(config)?;
+    let started_at = Instant::now();
 
     let (stdout, stderr) = match take_process_streams(&mut child) {
         Ok(streams) => streams,
         Err(error) => {
-            return finish_after_capture_failure(&mut child, &process_job, config, error);
+            return Err(finish_after_capture_failure(
+                &mut child,
+                &process_job,
+                config,
+                error,
+            ));
         }
     };
 
@@ -130,8 +260,10 @@ fn run_with_config(config: &RunConfig, cancellation: &CancellationToken) -> RunO
 
     let mut output = CollectedOutput::default();
     let mut output_limit_reached_at = None;
+    let mut forwarded = 0;
     let stop_reason = loop {
         let _ = drain_available_output(&output_receiver, &mut output);
+        forwarded = output.forward_lines(forwarded, on_line);
 
         // Capture failures are runner failures, not tool diagnostics. They must
         // never be hidden by a success signature in an earlier output chunk.
@@ -157,6 +289,13 @@ fn run_with_config(config: &RunConfig, cancellation: &CancellationToken) -> RunO
             break StopReason::Cancelled;
         }
 
+        if config
+            .deadline
+            .is_some_and(|deadline| started_at.elapsed() >= deadline)
+        {
+            break StopReason::DeadlineReached;
+        }
+
         match child.try_wait() {
             Ok(Some(status)) => break StopReason::Exited(status),
             Ok(None) => {}
@@ -201,32 +340,14 @@ fn run_with_config(config: &RunConfig, cancellation: &CancellationToken) -> RunO
     .err();
 
     if let Some(cleanup_failure) = cleanup_failure_outcome(termination_error, reader_error) {
-        return cleanup_failure;
+        return Err(cleanup_failure);
     }
+    output.forward_lines(forwarded, on_line);
 
-    let final_stdout = output.stdout_text();
-    let final_diagnostics = output.diagnostic_text();
-    let observed_error = find_error_message(&final_diagnostics);
-    let truncation_error = output.truncation_error.clone();
-
-    let state = match stop_reason {
-        StopReason::Cancelled => {
-            PcileechTestState::Failed("PCILeech test was cancelled".to_string())
-        }
-        StopReason::OutputLimit(error) => finalize_result(&final_stdout, None, Some(error)),
-        StopReason::Error(error) => PcileechTestState::Failed(error),
-        StopReason::Exited(status) => {
-            let process_error = (!status.success())
-                .then(|| format!("PCILeech test exited with code: {:?}", status.code()));
-            finalize_result(
-                &final_stdout,
-                None,
-                observed_error.or(process_error).or(truncation_error),
-            )
-        }
-    };
-
-    RunOutcome::safe(state)
+    Ok(FinishedRun {
+        stop_reason,
+        output,
+    })
 }
 
 fn finish_after_capture_failure(
@@ -255,8 +376,8 @@ fn spawn_pcileech_tool(config: &RunConfig) -> Result<(Child, ProcessJob), RunOut
     command
         .args(&config.args)
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .creation_flags(CREATE_NO_WINDOW | CREATE_SUSPENDED);
+        .stderr(Stdio::piped());
+    contain_command(&mut command);
     if let Some(runtime_directory) = executable
         .parent()
         .filter(|path| !path.as_os_str().is_empty())
@@ -538,6 +659,18 @@ impl CollectedOutput {
     fn diagnostic_text(&self) -> Cow<'_, str> {
         String::from_utf8_lossy(&self.diagnostics)
     }
+
+    /// Hands the lines completed since byte `from` of the diagnostics to
+    /// `on_line` and returns the offset to continue from.
+    fn forward_lines(&self, from: usize, on_line: &mut dyn FnMut(&str)) -> usize {
+        let end = self.diagnostics.len();
+        if end > from {
+            for line in String::from_utf8_lossy(&self.diagnostics[from..end]).lines() {
+                on_line(line);
+            }
+        }
+        end
+    }
 }
 
 fn spawn_output_reader<T>(
@@ -728,6 +861,7 @@ enum StopReason {
     Cancelled,
     OutputLimit(String),
     Error(String),
+    DeadlineReached,
     Exited(ExitStatus),
 }
 
@@ -751,6 +885,8 @@ mod tests {
             termination_grace: Duration::from_secs(1),
             stream_drain_grace: Duration::from_millis(500),
             output_limit_grace,
+            deadline: None,
+            rules: Arc::default(),
         }
     }
 
@@ -792,11 +928,14 @@ mod tests {
             b"ntdll.dll base address: 0x7",
         );
 
-        assert_eq!(find_success_line(&output.stdout_text()), None);
+        assert_eq!(
+            find_success_line(&OutputRules::default(), &output.stdout_text()),
+            None
+        );
 
         record_chunk(&mut output, OutputStream::Stdout, b"ffa0000\n");
         assert_eq!(
-            find_success_line(&output.stdout_text()),
+            find_success_line(&OutputRules::default(), &output.stdout_text()),
             Some("ntdll.dll base address: 0x7ffa0000".to_string())
         );
     }
@@ -810,10 +949,16 @@ mod tests {
             b"ntdll.dll base address: 0x7ffa0000",
         );
 
-        assert_eq!(find_success_line(&output.stdout_text()), None);
+        assert_eq!(
+            find_success_line(&OutputRules::default(), &output.stdout_text()),
+            None
+        );
 
         record_chunk(&mut output, OutputStream::Stdout, b" trailing diagnostic\n");
-        assert_eq!(find_success_line(&output.stdout_text()), None);
+        assert_eq!(
+            find_success_line(&OutputRules::default(), &output.stdout_text()),
+            None
+        );
     }
 
     #[test]
@@ -827,8 +972,14 @@ mod tests {
         record_chunk(&mut output, OutputStream::Stderr, b"7ffa0000\n");
         let _ = output.record(OutputEvent::Closed(OutputStream::Stdout));
 
-        assert_eq!(find_success_line(&output.stdout_text()), None);
-        assert_eq!(find_success_line(&output.diagnostic_text()), None);
+        assert_eq!(
+            find_success_line(&OutputRules::default(), &output.stdout_text()),
+            None
+        );
+        assert_eq!(
+            find_success_line(&OutputRules::default(), &output.diagnostic_text()),
+            None
+        );
     }
 
     #[test]
@@ -840,7 +991,10 @@ mod tests {
             b"ntdll.dll base address: 0x7ffa0000\n",
         );
 
-        assert_eq!(find_success_line(&output.stdout_text()), None);
+        assert_eq!(
+            find_success_line(&OutputRules::default(), &output.stdout_text()),
+            None
+        );
     }
 
     #[test]
@@ -851,20 +1005,36 @@ mod tests {
             OutputStream::Stdout,
             b"ntdll.dll base address: 0x7ffa0000",
         );
-        assert_eq!(find_success_line(&output.stdout_text()), None);
+        assert_eq!(
+            find_success_line(&OutputRules::default(), &output.stdout_text()),
+            None
+        );
 
         let _ = output.record(OutputEvent::Closed(OutputStream::Stdout));
-        assert!(find_success_line(&output.stdout_text()).is_some());
+        assert!(find_success_line(&OutputRules::default(), &output.stdout_text()).is_some());
     }
 
+    #[cfg(windows)]
+    const SHELL: [&str; 2] = ["cmd", "/C"];
+    #[cfg(not(windows))]
+    const SHELL: [&str; 2] = ["sh", "-c"];
+
+    #[cfg(windows)]
+    const LONG_RUNNING_COMMAND: &str = "ping 127.0.0.1 -n 10 > nul";
+    #[cfg(not(windows))]
+    const LONG_RUNNING_COMMAND: &str = "sleep 10";
+
     fn command_config(command: &str) -> RunConfig {
+        let [shell, flag] = SHELL;
         RunConfig {
-            executable: PathBuf::from("cmd"),
-            args: ["/C", command].into_iter().map(OsString::from).collect(),
+            executable: PathBuf::from(shell),
+            args: [flag, command].into_iter().map(OsString::from).collect(),
             poll_interval: Duration::from_millis(5),
             termination_grace: Duration::from_millis(200),
             stream_drain_grace: Duration::from_millis(50),
             output_limit_grace: Duration::from_millis(100),
+            deadline: None,
+            rules: Arc::default(),
         }
     }
 
@@ -884,15 +1054,28 @@ mod tests {
 
     #[test]
     fn detects_success_after_streams_complete() {
-        let config = command_config("echo memflow init & echo ntdll.dll base address: 0x7ffa0000");
+        let config = command_config("echo memflow init && echo ntdll.dll base address: 0x7ffa0000");
 
         let outcome = run_with_config(&config, &CancellationToken::default());
         assert!(outcome.safe_to_restart);
         assert!(
-            matches!(outcome.state, PcileechTestState::Success(line) if line.contains("0x7ffa0000"))
+            matches!(outcome.state, PcileechTestState::Success(report) if report.summary.contains("0x7ffa0000"))
         );
     }
 
+    #[test]
+    fn report_includes_details_printed_on_stderr() {
+        let config =
+            command_config("echo dtb: 0x1ad000 1>&2 && echo ntdll.dll base address: 0x7ffa0000");
+
+        let outcome = run_with_config(&config, &CancellationToken::default());
+        let PcileechTestState::Success(report) = outcome.state else {
+            panic!("expected success, got {:?}", outcome.state);
+        };
+        assert_eq!(report.dtb, Some(0x1a_d000));
+        assert_eq!(report.ntdll_base, Some(0x7ffa_0000));
+    }
+
     #[test]
     fn subprocess_runs_from_its_executable_directory() {
         let config = subprocess_config(
@@ -903,7 +1086,7 @@ mod tests {
         let outcome = run_with_config(&config, &CancellationToken::default());
 
         assert!(
-            matches!(outcome.state, PcileechTestState::Success(line) if line.contains("0x7ffa0000"))
+            matches!(outcome.state, PcileechTestState::Success(report) if report.summary.contains("0x7ffa0000"))
         );
     }
 
@@ -928,7 +1111,7 @@ mod tests {
     #[test]
     fn valid_success_wins_over_a_prior_output_error() {
         let config = command_config(
-            "echo ntdll.dll base address: 0x7ffa0000 & echo Error: connector failed 1>&2",
+            "echo ntdll.dll base address: 0x7ffa0000 && echo Error: connector failed 1>&2",
         );
 
         let outcome = run_with_config(&config, &CancellationToken::default());
@@ -979,9 +1162,45 @@ mod tests {
         thread::sleep(Duration::from_millis(250));
     }
 
+    #[test]
+    fn benchmark_keeps_the_rates_reported_before_the_deadline() {
+        let config = RunConfig {
+            deadline: Some(Duration::from_millis(300)),
+            ..command_config(&format!(
+                "echo read: 40.0 MB/s && echo read: 60.0 MB/s && {LONG_RUNNING_COMMAND}"
+            ))
+        };
+        let recorder = ThroughputRecorder::default();
+
+        let started_at = Instant::now();
+        let outcome = run_benchmark_with_config(&config, &recorder, &CancellationToken::default());
+
+        assert!(started_at.elapsed() < Duration::from_secs(5));
+        assert!(outcome.safe_to_restart);
+        let PcileechTestState::Measured(throughput) = outcome.state else {
+            panic!("expected a measurement, got {:?}", outcome.state);
+        };
+        assert_eq!(throughput.samples, [40_000_000, 60_000_000]);
+    }
+
+    #[test]
+    fn benchmark_without_rates_reports_the_tool_error() {
+        let config = command_config("echo Error: connector not found 1>&2");
+
+        let outcome = run_benchmark_with_config(
+            &config,
+            &ThroughputRecorder::default(),
+            &CancellationToken::default(),
+        );
+
+        assert!(
+            matches!(outcome.state, PcileechTestState::Failed(error) if error.contains("connector not found"))
+        );
+    }
+
     #[test]
     fn cancellation_terminates_a_running_process_promptly() {
-        let config = command_config("ping 127.0.0.1 -n 10 > nul");
+        let config = command_config(LONG_RUNNING_COMMAND);
         let cancellation = CancellationToken::default();
         let cancellation_worker = cancellation.clone();
         let started_at = Instant::now();
@@ -1015,7 +1234,7 @@ mod tests {
 
         assert!(outcome.safe_to_restart);
         assert!(
-            matches!(outcome.state, PcileechTestState::Success(line) if line.contains("0x7ffa0000"))
+            matches!(outcome.state, PcileechTestState::Success(report) if report.summary.contains("0x7ffa0000"))
         );
     }
 
@@ -1046,7 +1265,7 @@ mod tests {
 
         assert!(outcome.safe_to_restart);
         assert!(
-            matches!(outcome.state, PcileechTestState::Success(line) if line.contains("0x7ffa0000"))
+            matches!(outcome.state, PcileechTestState::Success(report) if report.summary.contains("0x7ffa0000"))
         );
     }
 
@@ -1084,6 +1303,8 @@ mod tests {
             termination_grace: Duration::from_secs(1),
             stream_drain_grace: Duration::from_millis(500),
             output_limit_grace: Duration::from_millis(100),
+            deadline: None,
+            rules: Arc::default(),
         };
 
         let outcome = run_with_config(&config, &CancellationToken::default());
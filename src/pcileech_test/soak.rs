@@ -0,0 +1,334 @@
+use super::PcileechTestState;
+use super::runner::{CancellationToken, RunOutcome};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Soak lengths offered on the test screen.
+pub const SOAK_LIMITS: [SoakLimit; 8] = [
+    SoakLimit::Runs(10),
+    SoakLimit::Runs(25),
+    SoakLimit::Runs(50),
+    SoakLimit::Runs(100),
+    SoakLimit::Minutes(5),
+    SoakLimit::Minutes(15),
+    SoakLimit::Minutes(30),
+    SoakLimit::Minutes(60),
+];
+/// Pauses between attempts offered on the test screen, in seconds.
+pub const SOAK_GAPS: [u32; 4] = [0, 1, 5, 10];
+
+const GAP_POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Most failure reasons listed in a report; the rest are counted together.
+pub const MAX_FAILURE_REASONS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoakLimit {
+    Runs(u32),
+    Minutes(u32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SoakPlan {
+    pub limit: SoakLimit,
+    pub gap_seconds: u32,
+}
+
+impl Default for SoakPlan {
+    fn default() -> Self {
+        Self {
+            limit: SOAK_LIMITS[0],
+            gap_seconds: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SoakAttempt {
+    pub duration: Duration,
+    /// `None` when the attempt succeeded.
+    pub failure: Option<String>,
+}
+
+/// Every attempt of a soak run, in order.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SoakReport {
+    pub attempts: Vec<SoakAttempt>,
+    /// Set when the soak was stopped before reaching its limit.
+    pub cancelled: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SuccessTimes {
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+impl SoakReport {
+    pub fn successes(&self) -> usize {
+        self.attempts
+            .iter()
+            .filter(|attempt| attempt.failure.is_none())
+            .count()
+    }
+
+    pub fn failures(&self) -> usize {
+        self.attempts.len() - self.successes()
+    }
+
+    /// Share of attempts that succeeded, in percent.
+    pub fn success_rate(&self) -> f64 {
+        if self.attempts.is_empty() {
+            return 0.0;
+        }
+        self.successes() as f64 * 100.0 / self.attempts.len() as f64
+    }
+
+    /// Failure messages with how often each occurred, most frequent first.
+    /// Ties keep the order in which the messages first appeared.
+    pub fn failure_reasons(&self) -> Vec<(&str, usize)> {
+        let mut reasons: Vec<(&str, usize)> = Vec::new();
+        for failure in self.attempts.iter().filter_map(|a| a.failure.as_deref()) {
+            match reasons.iter_mut().find(|(reason, _)| *reason == failure) {
+                Some((_, count)) => *count += 1,
+                None => reasons.push((failure, 1)),
+            }
+        }
+        reasons.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        reasons
+    }
+
+    /// Nearest-rank percentiles of how long successful attempts took.
+    pub fn success_times(&self) -> Option<SuccessTimes> {
+        let mut times: Vec<Duration> = self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.failure.is_none())
+            .map(|attempt| attempt.duration)
+            .collect();
+        times.sort();
+        let max = *times.last()?;
+
+        let percentile = |percent: usize| {
+            let rank = (percent * times.len()).div_ceil(100).max(1);
+            times[rank - 1]
+        };
+        Some(SuccessTimes {
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max,
+        })
+    }
+}
+
+/// Attempts shared between a running soak and the screen showing it.
+#[derive(Debug, Clone, Default)]
+pub(super) struct SoakRecorder {
+    report: Arc<Mutex<SoakReport>>,
+}
+
+impl SoakRecorder {
+    fn push(&self, attempt: SoakAttempt) {
+        self.report.lock().unwrap().attempts.push(attempt);
+    }
+
+    pub(super) fn report(&self) -> SoakReport {
+        self.report.lock().unwrap().clone()
+    }
+}
+
+/// Runs `check` until the plan's limit is reached, pausing between attempts.
+/// An attempt that leaves the tool possibly running ends the soak, since
+/// starting another would run two hardware tests at once. A cancelled soak
+/// still reports the attempts it finished.
+pub(super) fn run_soak(
+    plan: &SoakPlan,
+    recorder: &SoakRecorder,
+    cancellation: &CancellationToken,
+    mut check: impl FnMut() -> RunOutcome,
+) -> RunOutcome {
+    let started_at = Instant::now();
+    let mut attempts = 0;
+
+    loop {
+        let limit_reached = match plan.limit {
+            SoakLimit::Runs(runs) => attempts >= runs,
+            SoakLimit::Minutes(minutes) => {
+                started_at.elapsed() >= Duration::from_secs(u64::from(minutes) * 60)
+            }
+        };
+        if limit_reached {
+            break;
+        }
+        if attempts > 0 && !wait_for_gap(plan.gap_seconds, cancellation) {
+            break;
+        }
+        if cancellation.is_cancelled() {
+            break;
+        }
+
+        let attempt_started_at = Instant::now();
+        let outcome = check();
+        // A cancelled attempt says nothing about the hardware.
+        if cancellation.is_cancelled() {
+            if !outcome.safe_to_restart {
+                return outcome;
+            }
+            break;
+        }
+        attempts += 1;
+
+        let failure = match &outcome.state {
+            PcileechTestState::Success(_) => None,
+            PcileechTestState::Failed(message) => Some(message.clone()),
+            state => Some(format!("Unexpected test result: {state:?}")),
+        };
+        recorder.push(SoakAttempt {
+            duration: attempt_started_at.elapsed(),
+            failure,
+        });
+
+        if !outcome.safe_to_restart {
+            return outcome;
+        }
+    }
+
+    let mut report = recorder.report();
+    report.cancelled = cancellation.is_cancelled();
+    RunOutcome::safe(PcileechTestState::Soaked(report))
+}
+
+/// Returns false if the run was cancelled during the pause.
+fn wait_for_gap(gap_seconds: u32, cancellation: &CancellationToken) -> bool {
+    let resume_at = Instant::now() + Duration::from_secs(gap_seconds.into());
+    while Instant::now() < resume_at {
+        if cancellation.is_cancelled() {
+            return false;
+        }
+        thread::sleep(GAP_POLL_INTERVAL.min(resume_at - Instant::now()));
+    }
+    !cancellation.is_cancelled()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attempt(millis: u64, failure: Option<&str>) -> SoakAttempt {
+        SoakAttempt {
+            duration: Duration::from_millis(millis),
+            failure: failure.map(str::to_string),
+        }
+    }
+
+    fn plan(runs: u32) -> SoakPlan {
+        SoakPlan {
+            limit: SoakLimit::Runs(runs),
+            gap_seconds: 0,
+        }
+    }
+
+    #[test]
+    fn runs_the_planned_number_of_attempts() {
+        let recorder = SoakRecorder::default();
+        let mut calls = 0;
+
+        let outcome = run_soak(&plan(5), &recorder, &CancellationToken::default(), || {
+            calls += 1;
+            let state = if calls % 2 == 0 {
+                PcileechTestState::Failed("Error: device not found".into())
+            } else {
+                PcileechTestState::Success(Box::default())
+            };
+            RunOutcome::safe(state)
+        });
+
+        let PcileechTestState::Soaked(report) = outcome.state else {
+            panic!("expected a soak report, got {:?}", outcome.state);
+        };
+        assert_eq!(calls, 5);
+        assert_eq!(report.successes(), 3);
+        assert_eq!(report.failure_reasons(), [("Error: device not found", 2)]);
+    }
+
+    #[test]
+    fn an_unsafe_attempt_ends_the_soak() {
+        let recorder = SoakRecorder::default();
+        let mut calls = 0;
+
+        let outcome = run_soak(&plan(10), &recorder, &CancellationToken::default(), || {
+            calls += 1;
+            RunOutcome::unsafe_to_restart(PcileechTestState::Failed(
+                "Failed to terminate test tool".into(),
+            ))
+        });
+
+        assert_eq!(calls, 1);
+        assert!(!outcome.safe_to_restart);
+        assert_eq!(recorder.report().failures(), 1);
+    }
+
+    #[test]
+    fn cancellation_stops_between_attempts_and_keeps_the_attempts() {
+        let recorder = SoakRecorder::default();
+        let cancellation = CancellationToken::default();
+        let soak_plan = SoakPlan {
+            limit: SoakLimit::Runs(100),
+            gap_seconds: 10,
+        };
+        let canceller = {
+            let cancellation = cancellation.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(50));
+                cancellation.cancel();
+            })
+        };
+
+        let started_at = Instant::now();
+        let outcome = run_soak(&soak_plan, &recorder, &cancellation, || {
+            RunOutcome::safe(PcileechTestState::Success(Box::default()))
+        });
+        canceller.join().unwrap();
+
+        assert!(started_at.elapsed() < Duration::from_secs(5));
+        let PcileechTestState::Soaked(report) = outcome.state else {
+            panic!("expected a soak report, got {:?}", outcome.state);
+        };
+        assert!(report.cancelled);
+        assert_eq!(report.attempts, recorder.report().attempts);
+        assert_eq!(report.successes(), 1);
+    }
+
+    #[test]
+    fn reports_rates_reasons_and_percentiles() {
+        let mut report = SoakReport {
+            attempts: (1..=10).map(|second| attempt(second * 100, None)).collect(),
+            ..SoakReport::default()
+        };
+        report.attempts.extend([
+            attempt(50, Some("Error: timeout")),
+            attempt(50, Some("Error: no device")),
+            attempt(50, Some("Error: no device")),
+        ]);
+
+        assert_eq!(report.successes(), 10);
+        assert!((report.success_rate() - 76.923).abs() < 0.01);
+        assert_eq!(
+            report.failure_reasons(),
+            [("Error: no device", 2), ("Error: timeout", 1)]
+        );
+        assert_eq!(
+            report.success_times(),
+            Some(SuccessTimes {
+                p50: Duration::from_millis(500),
+                p90: Duration::from_millis(900),
+                p99: Duration::from_millis(1000),
+                max: Duration::from_millis(1000),
+            })
+        );
+        assert_eq!(SoakReport::default().success_times(), None);
+    }
+}
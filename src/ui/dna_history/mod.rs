@@ -0,0 +1,233 @@
+use crate::dna_history::{DnaRecord, ExportFormat, format_timestamp};
+use crate::ui::common::{self, palette};
+use crate::ui::status::license_text;
+use crate::utils::localization::{Language, TextKey, translate};
+use eframe::egui::{self, RichText, Ui};
+
+const SEARCH_WIDTH: f32 = 360.0;
+const TABLE_HEIGHT: f32 = 240.0;
+const DETAILS_MARGIN: i8 = 14;
+const DETAILS_ROUNDING: u8 = 8;
+const NOTE_WIDTH: f32 = 360.0;
+const BUTTON_HEIGHT: f32 = 32.0;
+const BUTTON_SPACING: f32 = 12.0;
+const SECTION_SPACING: f32 = 14.0;
+const STATUS_SIZE: f32 = 13.5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DnaHistoryAction {
+    Back,
+    Select(String),
+    SaveNote,
+    Export(ExportFormat),
+}
+
+/// Mutable view state owned by the app and edited in place by the screen.
+pub struct DnaHistoryView<'a> {
+    pub records: &'a [&'a DnaRecord],
+    pub total_records: usize,
+    pub query: &'a mut String,
+    pub selected: Option<&'a str>,
+    pub note_draft: &'a mut String,
+    pub status: Option<&'a Result<String, String>>,
+}
+
+pub fn render_dna_history(
+    ui: &mut Ui,
+    view: &mut DnaHistoryView<'_>,
+    lang: &Language,
+) -> Option<DnaHistoryAction> {
+    let mut action = None;
+
+    ui.vertical_centered(|ui| {
+        ui.heading(translate(TextKey::DnaHistory, lang));
+        ui.add_space(SECTION_SPACING);
+        ui.add(
+            egui::TextEdit::singleline(view.query)
+                .hint_text(translate(TextKey::DnaHistorySearchHint, lang))
+                .desired_width(SEARCH_WIDTH),
+        );
+    });
+    ui.add_space(SECTION_SPACING);
+
+    if view.records.is_empty() {
+        let message = if view.total_records == 0 {
+            TextKey::DnaHistoryEmpty
+        } else {
+            TextKey::DnaHistoryNoMatches
+        };
+        ui.vertical_centered(|ui| {
+            ui.label(RichText::new(translate(message, lang)).color(palette::TEXT_MUTED));
+        });
+    } else {
+        render_table(ui, view, lang, &mut action);
+    }
+
+    let selected_record = view.selected.and_then(|selected| {
+        view.records
+            .iter()
+            .find(|record| record.dna_value == selected)
+    });
+    if let Some(record) = selected_record {
+        ui.add_space(SECTION_SPACING);
+        render_details(ui, record, view.note_draft, lang, &mut action);
+    }
+
+    if let Some(status) = view.status {
+        ui.add_space(SECTION_SPACING);
+        let (text, color) = match status {
+            Ok(message) => (message.as_str(), palette::SUCCESS),
+            Err(message) => (message.as_str(), palette::ERROR),
+        };
+        ui.vertical_centered(|ui| {
+            ui.label(RichText::new(text).size(STATUS_SIZE).color(color));
+        });
+    }
+
+    ui.add_space(SECTION_SPACING);
+    ui.separator();
+    ui.add_space(SECTION_SPACING);
+    render_buttons(ui, !view.records.is_empty(), lang, &mut action);
+
+    action
+}
+
+fn render_table(
+    ui: &mut Ui,
+    view: &DnaHistoryView<'_>,
+    lang: &Language,
+    action: &mut Option<DnaHistoryAction>,
+) {
+    egui::ScrollArea::vertical()
+        .max_height(TABLE_HEIGHT)
+        .auto_shrink([false, true])
+        .show(ui, |ui| {
+            egui::Grid::new("dna_history_table")
+                .striped(true)
+                .num_columns(5)
+                .show(ui, |ui| {
+                    for key in [
+                        TextKey::DnaHistoryColDna,
+                        TextKey::DnaHistoryColDensity,
+                        TextKey::DnaHistoryColAdapter,
+                        TextKey::DnaHistoryColReads,
+                        TextKey::DnaHistoryColLastRead,
+                    ] {
+                        ui.label(RichText::new(translate(key, lang)).strong());
+                    }
+                    ui.end_row();
+
+                    for record in view.records {
+                        let is_selected = view
+                            .selected
+                            .is_some_and(|selected| selected == record.dna_value);
+                        if ui
+                            .selectable_label(
+                                is_selected,
+                                RichText::new(&record.dna_value).monospace(),
+                            )
+                            .clicked()
+                        {
+                            *action = Some(DnaHistoryAction::Select(record.dna_value.clone()));
+                        }
+                        ui.label(record.density.as_deref().unwrap_or("-"));
+                        ui.label(&record.adapter);
+                        ui.label(record.read_count.to_string());
+                        ui.label(format_timestamp(record.last_read_at));
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+fn render_details(
+    ui: &mut Ui,
+    record: &DnaRecord,
+    note_draft: &mut String,
+    lang: &Language,
+    action: &mut Option<DnaHistoryAction>,
+) {
+    egui::Frame::NONE
+        .fill(palette::SURFACE_RECESSED)
+        .corner_radius(egui::CornerRadius::same(DETAILS_ROUNDING))
+        .stroke(egui::Stroke::new(1.0, palette::STROKE_SUBTLE))
+        .inner_margin(egui::Margin::same(DETAILS_MARGIN))
+        .show(ui, |ui| {
+            ui.label(RichText::new(&record.dna_value).monospace().strong());
+            ui.label(
+                RichText::new(&record.dna_raw_value)
+                    .monospace()
+                    .size(11.0)
+                    .color(palette::TEXT_MUTED),
+            );
+            ui.label(format!(
+                "{} {}",
+                translate(TextKey::DnaHistoryFirstRead, lang),
+                format_timestamp(record.first_read_at)
+            ));
+            if let Some(license) = &record.license {
+                let (text, color) = license_text(license, lang);
+                ui.label(RichText::new(text).color(color));
+            }
+            if let Some(serial) = &record.adapter_serial {
+                ui.label(format!(
+                    "{} {} {serial}",
+                    translate(TextKey::AdapterLabel, lang),
+                    record.adapter
+                ));
+            }
+            ui.horizontal(|ui| {
+                ui.label(translate(TextKey::DnaHistoryNote, lang));
+                ui.add(egui::TextEdit::singleline(note_draft).desired_width(NOTE_WIDTH));
+                if ui
+                    .button(translate(TextKey::DnaHistorySaveNote, lang))
+                    .clicked()
+                {
+                    *action = Some(DnaHistoryAction::SaveNote);
+                }
+            });
+        });
+}
+
+fn render_buttons(
+    ui: &mut Ui,
+    can_export: bool,
+    lang: &Language,
+    action: &mut Option<DnaHistoryAction>,
+) {
+    ui.horizontal(|ui| {
+        let button_width = (ui.available_width() - 2.0 * BUTTON_SPACING) / 3.0;
+        let size = egui::vec2(button_width, BUTTON_HEIGHT);
+
+        if common::secondary_icon_button(
+            ui,
+            Some(egui_phosphor::regular::HOUSE),
+            translate(TextKey::MainMenu, lang),
+            size,
+        )
+        .clicked()
+        {
+            *action = Some(DnaHistoryAction::Back);
+        }
+
+        ui.add_space(BUTTON_SPACING);
+
+        for (format, key) in [
+            (ExportFormat::Csv, TextKey::DnaHistoryExportCsv),
+            (ExportFormat::Json, TextKey::DnaHistoryExportJson),
+        ] {
+            let icon = Some(egui_phosphor::regular::EXPORT);
+            let text = translate(key, lang);
+            if can_export {
+                if common::primary_icon_button(ui, icon, text, size).clicked() {
+                    *action = Some(DnaHistoryAction::Export(format));
+                }
+            } else {
+                common::disabled_primary_icon_button(ui, icon, text, size);
+            }
+            if format == ExportFormat::Csv {
+                ui.add_space(BUTTON_SPACING);
+            }
+        }
+    });
+}
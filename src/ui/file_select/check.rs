@@ -1,9 +1,13 @@
 use super::FileCheckRenderContext;
 use crate::APP_TITLE;
 use crate::ui::common::{self, palette};
-use crate::ui::file_select::components::render_missing_file;
-use crate::utils::file_checker::{CheckStatus, FileCheckResult, SUCCESS_TRANSITION_DELAY};
+use crate::ui::file_select::components::{render_lint_finding, render_missing_file};
+use crate::utils::file_checker::{
+    CheckStatus, FileCheckResult, FileProblem, SUCCESS_TRANSITION_DELAY,
+};
 use crate::utils::localization::{TextKey, format_translation, translate};
+use crate::utils::openocd_lint::LintFinding;
+use crate::utils::payload_manifest::{PAYLOAD_MANIFEST_FILE, PayloadState};
 use eframe::egui::{self, Color32, CornerRadius, Margin, RichText, Sense, Stroke, Ui, Vec2};
 
 // UI Constants
@@ -149,12 +153,23 @@ fn render_check_failed(
     on_rescan: &mut dyn FnMut(),
     lang: &crate::utils::localization::Language,
 ) {
+    let heading = if check_result.problems.is_empty() {
+        TextKey::ConfigWarnings
+    } else if check_result
+        .problems
+        .iter()
+        .all(|problem| problem.state == PayloadState::Missing)
+    {
+        TextKey::MissingFiles
+    } else {
+        TextKey::DamagedFiles
+    };
     ui.vertical_centered(|ui| {
         ui.colored_label(
             COLOR_WARNING,
             RichText::new(format!(
                 "{} {}",
-                translate(TextKey::MissingFiles, lang),
+                translate(heading, lang),
                 check_result.error_count
             ))
             .size(TEXT_SIZE_LARGE)
@@ -246,7 +261,23 @@ fn render_missing_files_list(
             egui::ScrollArea::vertical()
                 .max_height(MISSING_FILES_MAX_HEIGHT)
                 .show(ui, |ui| {
-                    render_file_groups(ui, &check_result.missing_files, lang);
+                    render_file_groups(ui, &check_result.problems, lang);
+                    if check_result
+                        .problems
+                        .iter()
+                        .any(|problem| problem.path == PAYLOAD_MANIFEST_FILE)
+                    {
+                        ui.label(
+                            RichText::new(translate(TextKey::PresenceOnlyNote, lang))
+                                .size(TEXT_SIZE_NORMAL)
+                                .italics()
+                                .color(palette::TEXT_MUTED),
+                        );
+                        ui.add_space(SPACING_MEDIUM);
+                    }
+                    if !check_result.lint.is_empty() {
+                        render_lint_group(ui, &check_result.lint, lang);
+                    }
                 });
         });
 }
@@ -314,18 +345,18 @@ fn render_warning_box(ui: &mut Ui, lang: &crate::utils::localization::Language)
 // File grouping functionality
 #[derive(Default)]
 struct FileGroups<'a> {
-    executables: Vec<&'a String>,
-    libraries: Vec<&'a String>,
-    bitstreams: Vec<&'a String>,
-    configs: Vec<&'a String>,
-    others: Vec<&'a String>,
+    executables: Vec<&'a FileProblem>,
+    libraries: Vec<&'a FileProblem>,
+    bitstreams: Vec<&'a FileProblem>,
+    configs: Vec<&'a FileProblem>,
+    others: Vec<&'a FileProblem>,
 }
 
-fn group_files(files: &[String]) -> FileGroups<'_> {
+fn group_files(files: &[FileProblem]) -> FileGroups<'_> {
     let mut groups = FileGroups::default();
 
     for file in files {
-        match file {
+        match file.path.to_ascii_lowercase() {
             f if f.ends_with(".exe") => groups.executables.push(file),
             f if f.ends_with(".dll") => groups.libraries.push(file),
             f if f.ends_with(".bit") => groups.bitstreams.push(file),
@@ -337,7 +368,11 @@ fn group_files(files: &[String]) -> FileGroups<'_> {
     groups
 }
 
-fn render_file_groups(ui: &mut Ui, files: &[String], lang: &crate::utils::localization::Language) {
+fn render_file_groups(
+    ui: &mut Ui,
+    files: &[FileProblem],
+    lang: &crate::utils::localization::Language,
+) {
     let groups = group_files(files);
 
     if !groups.executables.is_empty() {
@@ -345,6 +380,7 @@ fn render_file_groups(ui: &mut Ui, files: &[String], lang: &crate::utils::locali
             ui,
             translate(TextKey::GroupExecutables, lang),
             &groups.executables,
+            lang,
         );
     }
     if !groups.libraries.is_empty() {
@@ -352,6 +388,7 @@ fn render_file_groups(ui: &mut Ui, files: &[String], lang: &crate::utils::locali
             ui,
             translate(TextKey::GroupLibraries, lang),
             &groups.libraries,
+            lang,
         );
     }
     if !groups.bitstreams.is_empty() {
@@ -359,23 +396,56 @@ fn render_file_groups(ui: &mut Ui, files: &[String], lang: &crate::utils::locali
             ui,
             translate(TextKey::GroupBitstreams, lang),
             &groups.bitstreams,
+            lang,
         );
     }
     if !groups.configs.is_empty() {
-        render_file_group(ui, translate(TextKey::GroupConfigs, lang), &groups.configs);
+        render_file_group(
+            ui,
+            translate(TextKey::GroupConfigs, lang),
+            &groups.configs,
+            lang,
+        );
     }
     if !groups.others.is_empty() {
-        render_file_group(ui, translate(TextKey::GroupOther, lang), &groups.others);
+        render_file_group(
+            ui,
+            translate(TextKey::GroupOther, lang),
+            &groups.others,
+            lang,
+        );
     }
 }
 
-fn render_file_group(ui: &mut Ui, title: &str, files: &[&String]) {
+fn render_file_group(
+    ui: &mut Ui,
+    title: &str,
+    files: &[&FileProblem],
+    lang: &crate::utils::localization::Language,
+) {
     if !files.is_empty() {
         ui.label(RichText::new(title).size(TEXT_SIZE_MEDIUM).strong());
         ui.add_space(SPACING_SMALL);
         for file in files {
-            render_missing_file(ui, file, TEXT_SIZE_NORMAL);
+            render_missing_file(ui, file, TEXT_SIZE_NORMAL, lang);
         }
         ui.add_space(SPACING_MEDIUM);
     }
 }
+
+fn render_lint_group(
+    ui: &mut Ui,
+    findings: &[LintFinding],
+    lang: &crate::utils::localization::Language,
+) {
+    ui.label(
+        RichText::new(translate(TextKey::ConfigWarnings, lang))
+            .size(TEXT_SIZE_MEDIUM)
+            .strong(),
+    );
+    ui.add_space(SPACING_SMALL);
+    for finding in findings {
+        render_lint_finding(ui, finding, TEXT_SIZE_NORMAL, lang);
+    }
+    ui.add_space(SPACING_MEDIUM);
+}
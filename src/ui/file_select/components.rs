@@ -1,7 +1,43 @@
 use crate::ui::common::palette;
+use crate::utils::file_checker::FileProblem;
+use crate::utils::localization::{Language, TextKey, format_translation, translate};
+use crate::utils::openocd_lint::LintFinding;
+use crate::utils::payload_manifest::PayloadState;
 use eframe::egui::{RichText, Ui};
 
-pub fn render_missing_file(ui: &mut Ui, file: &str, font_size: f32) {
+const REFERENCE_SIZE_FACTOR: f32 = 0.8;
+
+pub fn render_missing_file(ui: &mut Ui, problem: &FileProblem, font_size: f32, lang: &Language) {
+    let (label, color) = match problem.state {
+        PayloadState::Corrupt => (TextKey::FileStateCorrupt, palette::ERROR),
+        PayloadState::Modified => (TextKey::FileStateModified, palette::ERROR),
+        PayloadState::Missing | PayloadState::Intact => {
+            (TextKey::FileStateMissing, palette::WARNING)
+        }
+    };
+    ui.horizontal(|ui| {
+        ui.label(RichText::new("•").size(font_size).strong().color(color));
+        ui.label(RichText::new(&problem.path).size(font_size).monospace());
+        ui.label(
+            RichText::new(translate(label, lang))
+                .size(font_size)
+                .color(color),
+        );
+    });
+    if let Some(location) = &problem.referenced_by {
+        ui.label(
+            RichText::new(format_translation(
+                translate(TextKey::ReferencedByScript, lang),
+                &[&location.to_string()],
+            ))
+            .size(font_size * REFERENCE_SIZE_FACTOR)
+            .monospace()
+            .color(palette::TEXT_MUTED),
+        );
+    }
+}
+
+pub fn render_lint_finding(ui: &mut Ui, finding: &LintFinding, font_size: f32, lang: &Language) {
     ui.horizontal(|ui| {
         ui.label(
             RichText::new("•")
@@ -9,6 +45,15 @@ pub fn render_missing_file(ui: &mut Ui, file: &str, font_size: f32) {
                 .strong()
                 .color(palette::WARNING),
         );
-        ui.label(RichText::new(file).size(font_size).monospace());
+        ui.label(
+            RichText::new(finding.location.to_string())
+                .size(font_size)
+                .monospace(),
+        );
     });
+    ui.label(
+        RichText::new(finding.describe(lang))
+            .size(font_size * REFERENCE_SIZE_FACTOR)
+            .color(palette::TEXT_MUTED),
+    );
 }
@@ -1,11 +1,13 @@
 use crate::ui::common;
 use crate::ui::common::palette;
+use crate::utils::firmware_archive;
 use crate::utils::firmware_discovery::FirmwareManager;
+use crate::utils::firmware_manifest::FirmwareMetadata;
 use crate::utils::localization::{TextKey, translate};
 use eframe::egui::{
     self, Color32, CornerRadius, Frame, Layout, Margin, RichText, Stroke, Ui, Vec2,
 };
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 const BORDER_COLOR: Color32 = palette::STROKE;
 const BORDER_WIDTH: f32 = 1.0;
@@ -19,11 +21,19 @@ const NORMAL_SIZE: f32 = 16.0;
 const SECONDARY_SIZE: f32 = 14.5;
 const SECONDARY_COLOR: Color32 = palette::TEXT_MUTED;
 
+/// Ways to bring in firmware from outside the listed folders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FirmwareSource {
+    Browse,
+    EditLocations,
+}
+
 pub fn render_firmware_selection(
     ui: &mut Ui,
     firmware_manager: &mut FirmwareManager,
     on_select: &mut dyn FnMut(Option<PathBuf>),
     on_back: &mut dyn FnMut(),
+    on_source: &mut dyn FnMut(FirmwareSource),
     is_scanning: bool,
     lang: &crate::utils::localization::Language,
 ) {
@@ -57,6 +67,56 @@ pub fn render_firmware_selection(
             lang,
         );
     }
+
+    render_source_buttons(ui, on_source, lang);
+}
+
+fn render_source_buttons(
+    ui: &mut Ui,
+    on_source: &mut dyn FnMut(FirmwareSource),
+    lang: &crate::utils::localization::Language,
+) {
+    ui.add_space(12.0);
+    ui.horizontal(|ui| {
+        let spacing = 12.0;
+        let button_width = (ui.available_width() - spacing) / 2.0;
+
+        for (source, icon, key) in [
+            (
+                FirmwareSource::Browse,
+                egui_phosphor::regular::FILE_ARROW_UP,
+                TextKey::BrowseFirmware,
+            ),
+            (
+                FirmwareSource::EditLocations,
+                egui_phosphor::regular::FOLDERS,
+                TextKey::FirmwareLocations,
+            ),
+        ] {
+            if common::secondary_icon_button(
+                ui,
+                Some(icon),
+                translate(key, lang),
+                Vec2::new(button_width, 28.0),
+            )
+            .clicked()
+            {
+                on_source(source);
+            }
+            if source == FirmwareSource::Browse {
+                ui.add_space(spacing);
+            }
+        }
+    });
+    ui.vertical_centered(|ui| {
+        ui.add_space(4.0);
+        ui.label(
+            RichText::new(translate(TextKey::DropFirmwareHint, lang))
+                .size(SECONDARY_SIZE)
+                .color(SECONDARY_COLOR)
+                .italics(),
+        );
+    });
 }
 
 fn render_firmware_status(
@@ -120,7 +180,7 @@ fn render_firmware_list(
 ) {
     ui.vertical_centered(|ui| {
         render_status_bar(ui, is_scanning, lang);
-        render_file_list(ui, files, firmware_manager);
+        render_file_list(ui, files, firmware_manager, lang);
 
         let mut cleanup_enabled = firmware_manager.get_cleanup_enabled();
         ui.horizontal(|ui| {
@@ -177,6 +237,7 @@ fn render_file_list(
     ui: &mut Ui,
     files: &[(usize, PathBuf, bool)],
     firmware_manager: &mut FirmwareManager,
+    lang: &crate::utils::localization::Language,
 ) {
     let file_list_frame = Frame::dark_canvas(ui.style())
         .stroke(Stroke::new(BORDER_WIDTH, BORDER_COLOR))
@@ -188,26 +249,80 @@ fn render_file_list(
             .max_height(SCROLL_HEIGHT)
             .show(ui, |ui| {
                 for (i, file, selected) in files {
-                    let file_name = file
-                        .file_name()
-                        .and_then(|n| n.to_str())
-                        .unwrap_or("Unknown");
-
-                    let text = format!("{}. {}", i + 1, file_name);
+                    let file_name = firmware_archive::display_name(file);
+                    let metadata = firmware_manager.metadata_for(file).cloned();
+                    let mut display_name = metadata
+                        .as_ref()
+                        .and_then(|metadata| metadata.name.as_deref())
+                        .map_or(file_name, str::to_string);
+                    if firmware_manager.shares_name(file) {
+                        display_name.push_str(&format!(" ({})", folder_label(file)));
+                    }
 
-                    let response =
-                        ui.selectable_label(*selected, RichText::new(text).size(NORMAL_SIZE));
+                    let response = ui
+                        .horizontal(|ui| {
+                            let text = format!("{}. {}", i + 1, display_name);
+                            let response = ui
+                                .selectable_label(*selected, RichText::new(text).size(NORMAL_SIZE));
+                            if let Some(metadata) = &metadata {
+                                ui.label(
+                                    RichText::new(metadata.summary())
+                                        .size(SECONDARY_SIZE)
+                                        .color(SECONDARY_COLOR),
+                                );
+                            }
+                            response
+                        })
+                        .inner;
 
                     if response.clicked() {
                         firmware_manager.select_firmware(*i);
                     }
 
-                    response.on_hover_text(file.to_string_lossy());
+                    response.on_hover_text(firmware_tooltip(
+                        file,
+                        metadata.as_ref(),
+                        firmware_manager.identical_copies(file),
+                        lang,
+                    ));
                 }
             });
     });
 }
 
+fn firmware_tooltip(
+    file: &Path,
+    metadata: Option<&FirmwareMetadata>,
+    copies: &[PathBuf],
+    lang: &crate::utils::localization::Language,
+) -> String {
+    let mut tooltip = file.to_string_lossy().into_owned();
+    if !copies.is_empty() {
+        tooltip.push_str("\n\n");
+        tooltip.push_str(translate(TextKey::IdenticalCopies, lang));
+        for copy in copies {
+            tooltip.push_str("\n  ");
+            tooltip.push_str(&copy.to_string_lossy());
+        }
+    }
+    if let Some(error) = metadata.and_then(|metadata| metadata.manifest_error.as_deref()) {
+        tooltip.push_str("\n\n");
+        tooltip.push_str(error);
+    }
+    if let Some(notes) = metadata.and_then(|metadata| metadata.notes.as_deref()) {
+        tooltip.push_str("\n\n");
+        tooltip.push_str(notes);
+    }
+    tooltip
+}
+
+/// The folder holding `file`, shown to tell same-named images apart.
+fn folder_label(file: &Path) -> String {
+    firmware_archive::backing_file(file)
+        .parent()
+        .map_or_else(String::new, |parent| parent.display().to_string())
+}
+
 fn render_continue_button(
     ui: &mut Ui,
     firmware_manager: &FirmwareManager,
@@ -0,0 +1,178 @@
+use crate::ui::common::{self, palette};
+use crate::utils::firmware_locations::{MAX_SCAN_DEPTH, SearchRoot};
+use crate::utils::localization::{Language, TextKey, translate};
+use eframe::egui::{self, RichText, Ui};
+
+const LIST_HEIGHT: f32 = 190.0;
+const IGNORE_ROWS: usize = 4;
+const BUTTON_HEIGHT: f32 = 32.0;
+const BUTTON_SPACING: f32 = 12.0;
+const SECTION_SPACING: f32 = 14.0;
+const STATUS_SIZE: f32 = 13.5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LocationAction {
+    Back,
+    AddFolder,
+    Remove(usize),
+    SetRecursive {
+        index: usize,
+        recursive: bool,
+        max_depth: usize,
+    },
+    SaveIgnore,
+}
+
+/// Mutable view state owned by the app and edited in place by the screen.
+pub struct LocationsView<'a> {
+    pub roots: &'a [SearchRoot],
+    pub ignore_draft: &'a mut String,
+    pub status: Option<&'a Result<String, String>>,
+}
+
+pub fn render_firmware_locations(
+    ui: &mut Ui,
+    view: &mut LocationsView<'_>,
+    lang: &Language,
+) -> Option<LocationAction> {
+    let mut action = None;
+
+    ui.vertical_centered(|ui| {
+        ui.heading(translate(TextKey::FirmwareLocations, lang));
+        ui.label(
+            RichText::new(translate(TextKey::FirmwareLocationsHint, lang))
+                .color(palette::TEXT_MUTED),
+        );
+    });
+    ui.add_space(SECTION_SPACING);
+
+    if view.roots.is_empty() {
+        ui.vertical_centered(|ui| {
+            ui.label(
+                RichText::new(translate(TextKey::FirmwareLocationsEmpty, lang))
+                    .color(palette::TEXT_MUTED),
+            );
+        });
+    } else {
+        render_roots(ui, view.roots, lang, &mut action);
+    }
+
+    ui.add_space(SECTION_SPACING);
+    ui.label(translate(TextKey::IgnorePatternsLabel, lang));
+    ui.add(
+        egui::TextEdit::multiline(view.ignore_draft)
+            .desired_rows(IGNORE_ROWS)
+            .desired_width(f32::INFINITY)
+            .font(egui::TextStyle::Monospace),
+    );
+    if ui
+        .button(translate(TextKey::SaveIgnorePatterns, lang))
+        .clicked()
+    {
+        action = Some(LocationAction::SaveIgnore);
+    }
+
+    if let Some(status) = view.status {
+        ui.add_space(SECTION_SPACING);
+        let (text, color) = match status {
+            Ok(message) => (message.as_str(), palette::SUCCESS),
+            Err(message) => (message.as_str(), palette::ERROR),
+        };
+        ui.vertical_centered(|ui| {
+            ui.label(RichText::new(text).size(STATUS_SIZE).color(color));
+        });
+    }
+
+    ui.add_space(SECTION_SPACING);
+    ui.separator();
+    ui.add_space(SECTION_SPACING);
+    render_buttons(ui, lang, &mut action);
+
+    action
+}
+
+fn render_roots(
+    ui: &mut Ui,
+    roots: &[SearchRoot],
+    lang: &Language,
+    action: &mut Option<LocationAction>,
+) {
+    egui::ScrollArea::vertical()
+        .max_height(LIST_HEIGHT)
+        .auto_shrink([false, true])
+        .show(ui, |ui| {
+            egui::Grid::new("firmware_locations")
+                .striped(true)
+                .num_columns(4)
+                .show(ui, |ui| {
+                    for (index, root) in roots.iter().enumerate() {
+                        ui.label(RichText::new(root.path.to_string_lossy()).monospace());
+
+                        let mut recursive = root.recursive;
+                        let mut max_depth = root.max_depth;
+                        let toggled = ui
+                            .checkbox(&mut recursive, translate(TextKey::IncludeSubfolders, lang))
+                            .changed();
+                        let deepened = ui
+                            .add_enabled_ui(recursive, |ui| {
+                                ui.horizontal(|ui| {
+                                    ui.label(translate(TextKey::ScanDepth, lang));
+                                    ui.add(
+                                        egui::DragValue::new(&mut max_depth)
+                                            .range(1..=MAX_SCAN_DEPTH),
+                                    )
+                                    .changed()
+                                })
+                                .inner
+                            })
+                            .inner;
+                        if toggled || deepened {
+                            *action = Some(LocationAction::SetRecursive {
+                                index,
+                                recursive,
+                                max_depth,
+                            });
+                        }
+
+                        if ui
+                            .button(translate(TextKey::RemoveFirmwareFolder, lang))
+                            .clicked()
+                        {
+                            *action = Some(LocationAction::Remove(index));
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+fn render_buttons(ui: &mut Ui, lang: &Language, action: &mut Option<LocationAction>) {
+    ui.horizontal(|ui| {
+        let button_width = (ui.available_width() - BUTTON_SPACING) / 2.0;
+        let size = egui::vec2(button_width, BUTTON_HEIGHT);
+
+        if common::secondary_icon_button(
+            ui,
+            Some(egui_phosphor::regular::ARROW_LEFT),
+            translate(TextKey::BackToFirmwareSelection, lang),
+            size,
+        )
+        .clicked()
+        {
+            *action = Some(LocationAction::Back);
+        }
+
+        ui.add_space(BUTTON_SPACING);
+
+        if common::primary_icon_button(
+            ui,
+            Some(egui_phosphor::regular::FOLDER_PLUS),
+            translate(TextKey::AddFirmwareFolder, lang),
+            size,
+        )
+        .clicked()
+        {
+            *action = Some(LocationAction::AddFolder);
+        }
+    });
+}
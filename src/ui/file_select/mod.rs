@@ -1,6 +1,7 @@
 mod check;
 mod components;
 mod firmware;
+mod locations;
 
 use crate::utils::file_checker::CheckStatus;
 use eframe::egui::Ui;
@@ -16,4 +17,5 @@ pub struct FileCheckRenderContext<'a> {
 
 // Re-export the main entry points
 pub use check::render_file_check;
-pub use firmware::render_firmware_selection;
+pub use firmware::{FirmwareSource, render_firmware_selection};
+pub use locations::{LocationAction, LocationsView, render_firmware_locations};
@@ -65,5 +65,19 @@ pub fn render_clear_button(
         {
             logger.clear();
         }
+
+        let mut debug = logger.debug_mode();
+        if ui
+            .checkbox(
+                &mut debug,
+                crate::utils::localization::translate(
+                    crate::utils::localization::TextKey::DebugLogging,
+                    lang,
+                ),
+            )
+            .changed()
+        {
+            logger.set_debug_mode(debug);
+        }
     });
 }
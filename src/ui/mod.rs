@@ -1,4 +1,5 @@
 pub mod common;
+pub mod dna_history;
 pub mod drivers;
 pub mod file_select;
 pub mod log_view;
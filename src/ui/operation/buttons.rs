@@ -20,6 +20,7 @@ mod colors {
 
     pub const FLASH_FIRMWARE: Color32 = super::palette::PRIMARY;
     pub const READ_DNA: Color32 = Color32::from_rgb(62, 118, 88);
+    pub const DNA_HISTORY: Color32 = Color32::from_rgb(48, 112, 120);
     pub const DRIVERS: Color32 = Color32::from_rgb(150, 100, 24);
     pub const TEST_PCILEECH: Color32 = Color32::from_rgb(110, 74, 138);
 }
@@ -42,6 +43,12 @@ pub fn create_operation_button(
             colors::READ_DNA,
             false,
         ),
+        OperationType::DnaHistory => (
+            translate(TextKey::DnaHistory, lang),
+            egui_phosphor::regular::CLOCK_COUNTER_CLOCKWISE,
+            colors::DNA_HISTORY,
+            false,
+        ),
         OperationType::Drivers => (
             translate(TextKey::Drivers, lang),
             egui_phosphor::regular::WRENCH,
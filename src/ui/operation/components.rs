@@ -45,6 +45,16 @@ pub fn render_operation_buttons(
 
         ui.add_space(SECTION_SPACING);
 
+        render_operation_option(
+            ui,
+            OperationType::DnaHistory,
+            translate(TextKey::DnaHistoryDesc, lang),
+            on_select,
+            lang,
+        );
+
+        ui.add_space(SECTION_SPACING);
+
         render_operation_option(
             ui,
             OperationType::Drivers,
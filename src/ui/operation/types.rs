@@ -2,6 +2,7 @@
 pub enum OperationType {
     FlashFirmware,
     ReadDNA,
+    DnaHistory,
     Drivers,
     TestPcileech,
 }
@@ -0,0 +1,162 @@
+use crate::device_programmer::{
+    AdapterKind, AdapterScanStatus, AdapterSelection, JTAG_SPEEDS_KHZ, UsbAdapter,
+};
+use crate::ui::common::{self, palette};
+use crate::utils::localization::{Language, TextKey, format_translation, translate};
+use eframe::egui::{self, RichText, Ui, Vec2};
+
+const PANEL_ROUNDING: u8 = 8;
+const PANEL_STROKE_WIDTH: f32 = 1.0;
+const PANEL_MARGIN: i8 = 12;
+const ROW_SPACE: f32 = 6.0;
+const KIND_LABEL_WIDTH: f32 = 60.0;
+const COMBO_WIDTH: f32 = 300.0;
+const REFRESH_BUTTON_WIDTH: f32 = 120.0;
+const REFRESH_BUTTON_HEIGHT: f32 = 26.0;
+const STATUS_SIZE: f32 = 13.0;
+
+/// State the options screens need to let the user pin a specific adapter.
+pub struct AdapterPicker<'a> {
+    pub scan: &'a AdapterScanStatus,
+    pub selection: &'a mut AdapterSelection,
+    /// `None` keeps each profile's own speed.
+    pub jtag_speed_khz: &'a mut Option<u32>,
+    pub on_refresh: &'a mut dyn FnMut(),
+}
+
+pub(super) fn render_adapter_picker(ui: &mut Ui, picker: &mut AdapterPicker<'_>, lang: &Language) {
+    egui::Frame::NONE
+        .fill(palette::SURFACE_RECESSED)
+        .corner_radius(egui::CornerRadius::same(PANEL_ROUNDING))
+        .stroke(egui::Stroke::new(
+            PANEL_STROKE_WIDTH,
+            palette::STROKE_SUBTLE,
+        ))
+        .inner_margin(egui::Margin::same(PANEL_MARGIN))
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(translate(TextKey::AdapterSectionTitle, lang)).strong());
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let refresh = common::secondary_icon_button(
+                        ui,
+                        Some(egui_phosphor::regular::ARROWS_CLOCKWISE),
+                        translate(TextKey::AdapterRefresh, lang),
+                        Vec2::new(REFRESH_BUTTON_WIDTH, REFRESH_BUTTON_HEIGHT),
+                    );
+                    if refresh.clicked() && *picker.scan != AdapterScanStatus::Scanning {
+                        (picker.on_refresh)();
+                    }
+                });
+            });
+            ui.add_space(ROW_SPACE);
+
+            let adapters = match picker.scan {
+                AdapterScanStatus::Complete(adapters) => adapters.as_slice(),
+                _ => &[],
+            };
+            for kind in [AdapterKind::Ch347, AdapterKind::Ftdi] {
+                render_kind_row(ui, kind, adapters, picker.selection, lang);
+                ui.add_space(ROW_SPACE);
+            }
+            render_speed_row(ui, picker.jtag_speed_khz, lang);
+            ui.add_space(ROW_SPACE);
+
+            if let Some(status) = scan_status_text(picker.scan, lang) {
+                ui.label(
+                    RichText::new(status)
+                        .size(STATUS_SIZE)
+                        .color(palette::TEXT_MUTED),
+                );
+            }
+        });
+}
+
+fn render_kind_row(
+    ui: &mut Ui,
+    kind: AdapterKind,
+    adapters: &[UsbAdapter],
+    selection: &mut AdapterSelection,
+    lang: &Language,
+) {
+    let auto = translate(TextKey::AdapterAuto, lang);
+    let mut selected = selection.serial_for(kind).map(str::to_string);
+    let selected_text = selected.clone().unwrap_or_else(|| auto.to_string());
+
+    ui.horizontal(|ui| {
+        ui.add_sized(
+            [KIND_LABEL_WIDTH, ui.spacing().interact_size.y],
+            egui::Label::new(kind.label()),
+        );
+        egui::ComboBox::from_id_salt(("adapter_serial", kind.label()))
+            .width(COMBO_WIDTH)
+            .selected_text(selected_text)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut selected, None, auto);
+                for adapter in adapters.iter().filter(|adapter| adapter.kind == kind) {
+                    match &adapter.serial {
+                        Some(serial) => {
+                            ui.selectable_value(
+                                &mut selected,
+                                Some(serial.clone()),
+                                adapter.to_string(),
+                            );
+                        }
+                        None => {
+                            ui.add_enabled(
+                                false,
+                                egui::Button::selectable(
+                                    false,
+                                    format!(
+                                        "{adapter} {}",
+                                        translate(TextKey::AdapterNoSerial, lang)
+                                    ),
+                                ),
+                            );
+                        }
+                    }
+                }
+            });
+    });
+
+    if selected.as_deref() != selection.serial_for(kind) {
+        selection.set_serial(kind, selected);
+    }
+}
+
+fn render_speed_row(ui: &mut Ui, jtag_speed_khz: &mut Option<u32>, lang: &Language) {
+    let profile_default = translate(TextKey::JtagSpeedProfileDefault, lang);
+    let speed_text = |speed: Option<u32>| {
+        speed.map_or_else(|| profile_default.to_string(), |khz| format!("{khz} kHz"))
+    };
+
+    ui.horizontal(|ui| {
+        ui.add_sized(
+            [KIND_LABEL_WIDTH, ui.spacing().interact_size.y],
+            egui::Label::new(translate(TextKey::JtagSpeed, lang)),
+        );
+        egui::ComboBox::from_id_salt("jtag_speed")
+            .width(COMBO_WIDTH)
+            .selected_text(speed_text(*jtag_speed_khz))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(jtag_speed_khz, None, speed_text(None));
+                for khz in JTAG_SPEEDS_KHZ {
+                    ui.selectable_value(jtag_speed_khz, Some(khz), speed_text(Some(khz)));
+                }
+            });
+    });
+}
+
+fn scan_status_text(scan: &AdapterScanStatus, lang: &Language) -> Option<String> {
+    match scan {
+        AdapterScanStatus::NotStarted => None,
+        AdapterScanStatus::Scanning => Some(translate(TextKey::AdapterScanning, lang).to_string()),
+        AdapterScanStatus::Complete(adapters) if adapters.is_empty() => {
+            Some(translate(TextKey::AdapterNoneFound, lang).to_string())
+        }
+        AdapterScanStatus::Complete(_) => None,
+        AdapterScanStatus::Failed(error) => Some(format_translation(
+            translate(TextKey::AdapterScanFailed, lang),
+            &[error],
+        )),
+    }
+}
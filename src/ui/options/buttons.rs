@@ -20,6 +20,8 @@ const ICON_LEFT_PADDING: f32 = 18.0;
 const TEXT_LEFT_PADDING: f32 = ICON_LEFT_PADDING + BUTTON_ICON_SIZE + BUTTON_ICON_GAP;
 const LABEL_Y_OFFSET: f32 = -10.0;
 const DESCRIPTION_Y_OFFSET: f32 = 12.0;
+const BADGE_FONT_SIZE: f32 = 11.5;
+const BADGE_PADDING: f32 = 6.0;
 
 pub fn render_colored_option_button(
     ui: &mut Ui,
@@ -29,7 +31,7 @@ pub fn render_colored_option_button(
     accent_color: egui::Color32,
     option_fn: impl FnOnce() -> FlashingOption,
     on_select: &mut dyn FnMut(FlashingOption),
-) {
+) -> egui::Response {
     let button = egui::Button::new("")
         .min_size(egui::vec2(ui.available_width(), BUTTON_HEIGHT))
         .fill(if ui.visuals().dark_mode {
@@ -53,6 +55,18 @@ pub fn render_colored_option_button(
     if response.clicked() {
         on_select(option_fn());
     }
+    response
+}
+
+/// Tags the top-right corner of an option button, e.g. with "last used".
+pub fn draw_last_used_badge(ui: &Ui, response: &egui::Response, text: &str) {
+    ui.painter().text(
+        response.rect.right_top() + egui::vec2(-BADGE_PADDING, BADGE_PADDING),
+        Align2::RIGHT_TOP,
+        text,
+        egui::FontId::proportional(BADGE_FONT_SIZE),
+        BUTTON_DESCRIPTION_COLOR,
+    );
 }
 
 fn draw_hover_fill(ui: &mut Ui, response: &egui::Response) {
@@ -0,0 +1,26 @@
+use crate::device_programmer::DnaLockPolicy;
+use crate::ui::common::palette;
+use crate::utils::localization::{Language, TextKey, translate};
+use eframe::egui::{self, RichText, Ui};
+
+const COMBO_WIDTH: f32 = 120.0;
+const DESCRIPTION_SIZE: f32 = 13.0;
+
+pub(super) fn render_dna_lock_picker(ui: &mut Ui, policy: &mut DnaLockPolicy, lang: &Language) {
+    ui.horizontal(|ui| {
+        ui.label(RichText::new(translate(TextKey::DnaLockCheck, lang)).strong());
+        egui::ComboBox::from_id_salt("dna_lock_policy")
+            .width(COMBO_WIDTH)
+            .selected_text(translate(policy.label_key(), lang))
+            .show_ui(ui, |ui| {
+                for option in DnaLockPolicy::ALL {
+                    ui.selectable_value(policy, option, translate(option.label_key(), lang));
+                }
+            });
+        ui.label(
+            RichText::new(translate(TextKey::DnaLockDescription, lang))
+                .size(DESCRIPTION_SIZE)
+                .color(palette::TEXT_MUTED),
+        );
+    });
+}
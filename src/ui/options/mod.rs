@@ -1,5 +1,10 @@
+mod adapters;
 mod buttons;
+mod dna_lock;
 mod panels;
 mod render;
+mod signature;
 
-pub use render::{render_dna_read_options, render_flash_options};
+pub use adapters::AdapterPicker;
+pub use render::{ProfileChoices, render_dna_read_options, render_flash_options};
+pub use signature::SignaturePicker;
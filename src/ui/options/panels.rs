@@ -1,4 +1,5 @@
-use super::buttons::render_colored_option_button;
+use super::buttons::{draw_last_used_badge, render_colored_option_button};
+use super::render::ProfileChoices;
 use crate::device_programmer::FlashingOption;
 use crate::ui::common::palette;
 use crate::utils::localization::{TextKey, translate};
@@ -18,73 +19,144 @@ const STROKE_COLOR: egui::Color32 = palette::STROKE_SUBTLE;
 const CH347_COLOR: egui::Color32 = palette::INFO;
 const RS232_COLOR: egui::Color32 = egui::Color32::from_rgb(150, 126, 214);
 
+const CH347_FLASH_OPTIONS: [(FlashingOption, TextKey, TextKey); 3] = [
+    (
+        FlashingOption::CH347_35T,
+        TextKey::Ch347_35T_Label,
+        TextKey::Ch347_35T_Desc,
+    ),
+    (
+        FlashingOption::CH347_75T,
+        TextKey::Ch347_75T_Label,
+        TextKey::Ch347_75T_Desc,
+    ),
+    (
+        FlashingOption::CH347_100T,
+        TextKey::Ch347_100T_Label,
+        TextKey::Ch347_100T_Desc,
+    ),
+];
+const DNA_OPTIONS: [(FlashingOption, TextKey, TextKey, egui::Color32); 4] = [
+    (
+        FlashingOption::DnaCH347,
+        TextKey::Dna_Ch347_Label,
+        TextKey::Dna_Ch347_Desc,
+        CH347_COLOR,
+    ),
+    (
+        FlashingOption::DnaRS232_35T,
+        TextKey::Dna_Rs232_35T_Label,
+        TextKey::Dna_Rs232_35T_Desc,
+        RS232_COLOR,
+    ),
+    (
+        FlashingOption::DnaRS232_75T,
+        TextKey::Dna_Rs232_75T_Label,
+        TextKey::Dna_Rs232_75T_Desc,
+        RS232_COLOR,
+    ),
+    (
+        FlashingOption::DnaRS232_100T,
+        TextKey::Dna_Rs232_100T_Label,
+        TextKey::Dna_Rs232_100T_Desc,
+        RS232_COLOR,
+    ),
+];
+const RS232_FLASH_OPTIONS: [(FlashingOption, TextKey, TextKey); 3] = [
+    (
+        FlashingOption::RS232_35T,
+        TextKey::Rs232_35T_Label,
+        TextKey::Rs232_35T_Desc,
+    ),
+    (
+        FlashingOption::RS232_75T,
+        TextKey::Rs232_75T_Label,
+        TextKey::Rs232_75T_Desc,
+    ),
+    (
+        FlashingOption::RS232_100T,
+        TextKey::Rs232_100T_Label,
+        TextKey::Rs232_100T_Desc,
+    ),
+];
+
 pub fn render_flash_section(
     ui: &mut Ui,
     on_select: &mut dyn FnMut(FlashingOption),
+    choices: &ProfileChoices<'_>,
     lang: &crate::utils::localization::Language,
 ) {
-    render_panel(ui, |ui| {
-        render_section_header(ui, translate(TextKey::Ch347Options, lang));
-        render_ch347_options(ui, on_select, lang);
-
-        ui.add_space(SECTION_BOTTOM_SPACE);
+    let accepts = |option: &FlashingOption| {
+        choices
+            .firmware
+            .is_none_or(|metadata| metadata.accepts(option))
+    };
+    let ch347: Vec<_> = CH347_FLASH_OPTIONS
+        .iter()
+        .filter(|(option, ..)| accepts(option))
+        .collect();
+    let rs232: Vec<_> = RS232_FLASH_OPTIONS
+        .iter()
+        .filter(|(option, ..)| accepts(option))
+        .collect();
 
-        render_section_header(ui, translate(TextKey::Rs232Options, lang));
-        render_rs232_options(ui, on_select, lang);
+    render_panel(ui, |ui| {
+        if !ch347.is_empty() {
+            render_section_header(ui, translate(TextKey::Ch347Options, lang));
+            render_flash_buttons(
+                ui,
+                &ch347,
+                egui_phosphor::regular::USB,
+                CH347_COLOR,
+                on_select,
+                choices.last_used,
+                lang,
+            );
+        }
+
+        if !ch347.is_empty() && !rs232.is_empty() {
+            ui.add_space(SECTION_BOTTOM_SPACE);
+        }
+
+        if !rs232.is_empty() {
+            render_section_header(ui, translate(TextKey::Rs232Options, lang));
+            render_flash_buttons(
+                ui,
+                &rs232,
+                egui_phosphor::regular::PLUG,
+                RS232_COLOR,
+                on_select,
+                choices.last_used,
+                lang,
+            );
+        }
     });
 }
 
 pub fn render_dna_section(
     ui: &mut Ui,
     on_select: &mut dyn FnMut(FlashingOption),
+    last_used: Option<&FlashingOption>,
     lang: &crate::utils::localization::Language,
 ) {
     render_panel(ui, |ui| {
-        render_colored_option_button(
-            ui,
-            translate(TextKey::Dna_Ch347_Label, lang),
-            translate(TextKey::Dna_Ch347_Desc, lang),
-            egui_phosphor::regular::FINGERPRINT,
-            CH347_COLOR,
-            || FlashingOption::DnaCH347,
-            on_select,
-        );
-
-        ui.add_space(BUTTON_SPACE);
-
-        render_colored_option_button(
-            ui,
-            translate(TextKey::Dna_Rs232_35T_Label, lang),
-            translate(TextKey::Dna_Rs232_35T_Desc, lang),
-            egui_phosphor::regular::FINGERPRINT,
-            RS232_COLOR,
-            || FlashingOption::DnaRS232_35T,
-            on_select,
-        );
-
-        ui.add_space(BUTTON_SPACE);
-
-        render_colored_option_button(
-            ui,
-            translate(TextKey::Dna_Rs232_75T_Label, lang),
-            translate(TextKey::Dna_Rs232_75T_Desc, lang),
-            egui_phosphor::regular::FINGERPRINT,
-            RS232_COLOR,
-            || FlashingOption::DnaRS232_75T,
-            on_select,
-        );
-
-        ui.add_space(BUTTON_SPACE);
-
-        render_colored_option_button(
-            ui,
-            translate(TextKey::Dna_Rs232_100T_Label, lang),
-            translate(TextKey::Dna_Rs232_100T_Desc, lang),
-            egui_phosphor::regular::FINGERPRINT,
-            RS232_COLOR,
-            || FlashingOption::DnaRS232_100T,
-            on_select,
-        );
+        for (index, (option, label, description, accent_color)) in DNA_OPTIONS.iter().enumerate() {
+            if index > 0 {
+                ui.add_space(BUTTON_SPACE);
+            }
+            let response = render_colored_option_button(
+                ui,
+                translate(*label, lang),
+                translate(*description, lang),
+                egui_phosphor::regular::FINGERPRINT,
+                *accent_color,
+                || option.clone(),
+                on_select,
+            );
+            if last_used == Some(option) {
+                draw_last_used_badge(ui, &response, translate(TextKey::LastUsed, lang));
+            }
+        }
     });
 }
 
@@ -110,82 +182,30 @@ fn render_section_header(ui: &mut Ui, title: &str) {
     ui.add_space(BUTTON_SPACE);
 }
 
-fn render_ch347_options(
+fn render_flash_buttons(
     ui: &mut Ui,
+    options: &[&(FlashingOption, TextKey, TextKey)],
+    icon: &str,
+    accent_color: egui::Color32,
     on_select: &mut dyn FnMut(FlashingOption),
+    last_used: Option<&FlashingOption>,
     lang: &crate::utils::localization::Language,
 ) {
-    render_colored_option_button(
-        ui,
-        translate(TextKey::Ch347_35T_Label, lang),
-        translate(TextKey::Ch347_35T_Desc, lang),
-        egui_phosphor::regular::USB,
-        CH347_COLOR,
-        || FlashingOption::CH347_35T,
-        on_select,
-    );
-
-    ui.add_space(BUTTON_SPACE);
-
-    render_colored_option_button(
-        ui,
-        translate(TextKey::Ch347_75T_Label, lang),
-        translate(TextKey::Ch347_75T_Desc, lang),
-        egui_phosphor::regular::USB,
-        CH347_COLOR,
-        || FlashingOption::CH347_75T,
-        on_select,
-    );
-
-    ui.add_space(BUTTON_SPACE);
-
-    render_colored_option_button(
-        ui,
-        translate(TextKey::Ch347_100T_Label, lang),
-        translate(TextKey::Ch347_100T_Desc, lang),
-        egui_phosphor::regular::USB,
-        CH347_COLOR,
-        || FlashingOption::CH347_100T,
-        on_select,
-    );
-}
-
-fn render_rs232_options(
-    ui: &mut Ui,
-    on_select: &mut dyn FnMut(FlashingOption),
-    lang: &crate::utils::localization::Language,
-) {
-    render_colored_option_button(
-        ui,
-        translate(TextKey::Rs232_35T_Label, lang),
-        translate(TextKey::Rs232_35T_Desc, lang),
-        egui_phosphor::regular::PLUG,
-        RS232_COLOR,
-        || FlashingOption::RS232_35T,
-        on_select,
-    );
-
-    ui.add_space(BUTTON_SPACE);
-
-    render_colored_option_button(
-        ui,
-        translate(TextKey::Rs232_75T_Label, lang),
-        translate(TextKey::Rs232_75T_Desc, lang),
-        egui_phosphor::regular::PLUG,
-        RS232_COLOR,
-        || FlashingOption::RS232_75T,
-        on_select,
-    );
-
-    ui.add_space(BUTTON_SPACE);
-
-    render_colored_option_button(
-        ui,
-        translate(TextKey::Rs232_100T_Label, lang),
-        translate(TextKey::Rs232_100T_Desc, lang),
-        egui_phosphor::regular::PLUG,
-        RS232_COLOR,
-        || FlashingOption::RS232_100T,
-        on_select,
-    );
+    for (index, (option, label, description)) in options.iter().enumerate() {
+        if index > 0 {
+            ui.add_space(BUTTON_SPACE);
+        }
+        let response = render_colored_option_button(
+            ui,
+            translate(*label, lang),
+            translate(*description, lang),
+            icon,
+            accent_color,
+            || option.clone(),
+            on_select,
+        );
+        if last_used == Some(option) {
+            draw_last_used_badge(ui, &response, translate(TextKey::LastUsed, lang));
+        }
+    }
 }
@@ -1,21 +1,57 @@
+use super::adapters::{AdapterPicker, render_adapter_picker};
+use super::dna_lock::render_dna_lock_picker;
 use super::panels::{render_dna_section, render_flash_section};
-use crate::device_programmer::FlashingOption;
+use super::signature::{SignaturePicker, render_signature_picker};
+use crate::device_programmer::{DnaLockPolicy, FlashingOption};
 use crate::ui::common;
-use crate::utils::localization::{TextKey, translate};
-use eframe::egui::{Ui, Vec2};
+use crate::ui::common::palette;
+use crate::utils::firmware_manifest::FirmwareMetadata;
+use crate::utils::localization::{TextKey, format_translation, translate};
+use eframe::egui::{RichText, Ui, Vec2};
 
 const MAIN_MENU_BUTTON_WIDTH: f32 = 200.0;
 const MAIN_MENU_BUTTON_HEIGHT: f32 = 30.0;
+const ADAPTER_PICKER_SPACE: f32 = 12.0;
+const FILTER_NOTE_SIZE: f32 = 14.0;
+
+/// Which profiles the flash screen offers and the one used last.
+pub struct ProfileChoices<'a> {
+    /// Offers only the profiles the firmware's manifest accepts.
+    pub firmware: Option<&'a FirmwareMetadata>,
+    pub last_used: Option<&'a FlashingOption>,
+}
 
 pub fn render_flash_options(
     ui: &mut Ui,
     on_select: &mut dyn FnMut(FlashingOption),
+    adapters: &mut AdapterPicker<'_>,
+    dna_lock: &mut DnaLockPolicy,
+    signature: &mut SignaturePicker<'_>,
+    choices: &ProfileChoices<'_>,
     lang: &crate::utils::localization::Language,
 ) {
     ui.vertical_centered(|ui| {
         ui.heading(translate(TextKey::SelectFlashingOption, lang));
+        if let Some(target) = choices.firmware.map(FirmwareMetadata::target_label)
+            && !target.is_empty()
+        {
+            ui.label(
+                RichText::new(format_translation(
+                    translate(TextKey::FirmwareTargetFilter, lang),
+                    &[&target],
+                ))
+                .size(FILTER_NOTE_SIZE)
+                .color(palette::TEXT_MUTED),
+            );
+        }
         ui.add_space(12.0);
-        render_flash_section(ui, on_select, lang);
+        render_adapter_picker(ui, adapters, lang);
+        ui.add_space(ADAPTER_PICKER_SPACE);
+        render_dna_lock_picker(ui, dna_lock, lang);
+        ui.add_space(ADAPTER_PICKER_SPACE);
+        render_signature_picker(ui, signature, lang);
+        ui.add_space(ADAPTER_PICKER_SPACE);
+        render_flash_section(ui, on_select, choices, lang);
     });
 }
 
@@ -23,12 +59,16 @@ pub fn render_dna_read_options(
     ui: &mut Ui,
     on_select: &mut dyn FnMut(FlashingOption),
     on_back: &mut dyn FnMut(),
+    adapters: &mut AdapterPicker<'_>,
+    last_used: Option<&FlashingOption>,
     lang: &crate::utils::localization::Language,
 ) {
     ui.vertical_centered(|ui| {
         ui.heading(translate(TextKey::SelectDnaReadOption, lang));
         ui.add_space(12.0);
-        render_dna_section(ui, on_select, lang);
+        render_adapter_picker(ui, adapters, lang);
+        ui.add_space(ADAPTER_PICKER_SPACE);
+        render_dna_section(ui, on_select, last_used, lang);
         ui.add_space(16.0);
         render_main_menu_button(ui, on_back, lang);
     });
@@ -0,0 +1,46 @@
+use crate::device_programmer::SignaturePolicy;
+use crate::ui::common::palette;
+use crate::utils::localization::{Language, TextKey, translate};
+use eframe::egui::{self, RichText, Ui};
+
+const COMBO_WIDTH: f32 = 120.0;
+const DESCRIPTION_SIZE: f32 = 13.0;
+
+/// The signature policy and whether the installation pins it.
+pub struct SignaturePicker<'a> {
+    pub policy: &'a mut SignaturePolicy,
+    pub pinned: bool,
+}
+
+pub(super) fn render_signature_picker(
+    ui: &mut Ui,
+    picker: &mut SignaturePicker<'_>,
+    lang: &Language,
+) {
+    ui.horizontal(|ui| {
+        ui.label(RichText::new(translate(TextKey::SignatureCheck, lang)).strong());
+        let policy = &mut *picker.policy;
+        ui.add_enabled_ui(!picker.pinned, |ui| {
+            egui::ComboBox::from_id_salt("signature_policy")
+                .width(COMBO_WIDTH)
+                .selected_text(translate(policy.label_key(), lang))
+                .show_ui(ui, |ui| {
+                    for option in SignaturePolicy::ALL {
+                        ui.selectable_value(policy, option, translate(option.label_key(), lang));
+                    }
+                });
+        })
+        .response
+        .on_disabled_hover_text(translate(TextKey::SignaturePinned, lang));
+        let description = if picker.pinned {
+            TextKey::SignaturePinned
+        } else {
+            TextKey::SignatureDescription
+        };
+        ui.label(
+            RichText::new(translate(description, lang))
+                .size(DESCRIPTION_SIZE)
+                .color(palette::TEXT_MUTED),
+        );
+    });
+}
@@ -1,6 +1,10 @@
-use crate::pcileech_test::PcileechTestState;
+use crate::pcileech_test::{
+    BENCHMARK_DURATIONS, MAX_FAILURE_REASONS, PcileechReport, PcileechTestSnapshot,
+    PcileechTestState, SOAK_GAPS, SOAK_LIMITS, SoakLimit, SoakPlan, SoakReport, TestMode,
+    TestProfile, Throughput, format_mbps,
+};
 use crate::ui::common::{self, palette};
-use crate::utils::localization::{TextKey, translate};
+use crate::utils::localization::{Language, TextKey, format_translation, translate};
 use eframe::egui::{self, Color32, RichText};
 
 const STATUS_CARD_WIDTH: f32 = 440.0;
@@ -16,28 +20,79 @@ const SUCCESS_COLOR: Color32 = palette::SUCCESS;
 const ERROR_COLOR: Color32 = palette::ERROR;
 const BODY_TEXT_COLOR: Color32 = palette::TEXT;
 const MUTED_TEXT_COLOR: Color32 = palette::TEXT_MUTED;
+const PROFILE_COMBO_WIDTH: f32 = 220.0;
+const PROFILE_FIELD_WIDTH: f32 = 300.0;
+const PROFILE_STATUS_SIZE: f32 = 13.5;
+const CHART_HEIGHT: f32 = 70.0;
+const CHART_STROKE_WIDTH: f32 = 1.5;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PcileechAction {
     Back,
     Retry,
+    /// Ends the soak test in progress and shows the attempts so far.
+    Stop,
+    SelectProfile(String),
+    SaveProfile,
+    DeleteProfile,
+    SetMode(TestMode),
+}
+
+/// Profile state owned by the app and edited in place by the screen.
+pub struct PcileechProfileView<'a> {
+    pub profiles: &'a [TestProfile],
+    pub selected: &'a str,
+    pub draft: &'a mut TestProfile,
+    pub editing: &'a mut bool,
+    pub status: Option<&'a Result<String, String>>,
 }
 
 pub fn render_pcileech_test(
     ui: &mut egui::Ui,
-    test_state: &PcileechTestState,
-    lang: &crate::utils::localization::Language,
+    snapshot: &PcileechTestSnapshot,
+    profiles: &mut PcileechProfileView<'_>,
+    lang: &Language,
 ) -> Option<PcileechAction> {
+    let mut action = None;
+
     ui.vertical_centered(|ui| {
         ui.heading(translate(TextKey::TestPcileechTitle, lang));
-        ui.add_space(30.0);
+        ui.add_space(16.0);
+        render_profile_picker(ui, profiles, lang, &mut action);
+        ui.add_space(8.0);
+        render_mode_picker(
+            ui,
+            snapshot.mode,
+            snapshot.benchmark_available,
+            lang,
+            &mut action,
+        );
+        ui.add_space(14.0);
+
+        // The editor takes the place of the status card to keep the window size.
+        if *profiles.editing {
+            render_profile_editor(ui, profiles, lang, &mut action);
+            return;
+        }
 
-        match test_state {
+        match &snapshot.state {
             PcileechTestState::Idle | PcileechTestState::Running => {
-                render_running_state(ui, lang);
+                match snapshot.mode {
+                    TestMode::Check => render_running_state(ui, lang),
+                    TestMode::Benchmark { .. } => {
+                        render_benchmark_running_state(ui, &snapshot.throughput, lang);
+                    }
+                    TestMode::Soak(_) => {
+                        render_soak_running_state(ui, &snapshot.soak, lang, &mut action);
+                    }
+                }
                 ui.ctx().request_repaint();
             }
-            PcileechTestState::Success(line) => render_success_state(ui, line, lang),
+            PcileechTestState::Success(report) => render_success_state(ui, report, lang),
+            PcileechTestState::Measured(throughput) => {
+                render_measured_state(ui, throughput, lang);
+            }
+            PcileechTestState::Soaked(report) => render_soaked_state(ui, report, lang),
             PcileechTestState::Failed(message) => render_error_state(ui, message, lang),
         }
     });
@@ -46,7 +101,6 @@ pub fn render_pcileech_test(
     ui.separator();
     ui.add_space(15.0);
 
-    let mut action = None;
     ui.horizontal(|ui| {
         let available_width = ui.available_width();
         let spacing = 12.0;
@@ -81,52 +135,556 @@ pub fn render_pcileech_test(
     action
 }
 
-fn render_running_state(ui: &mut egui::Ui, lang: &crate::utils::localization::Language) {
+fn render_profile_picker(
+    ui: &mut egui::Ui,
+    profiles: &mut PcileechProfileView<'_>,
+    lang: &Language,
+    action: &mut Option<PcileechAction>,
+) {
+    ui.horizontal(|ui| {
+        ui.label(translate(TextKey::PcileechProfile, lang));
+        egui::ComboBox::from_id_salt("pcileech_profile")
+            .width(PROFILE_COMBO_WIDTH)
+            .selected_text(profiles.selected)
+            .show_ui(ui, |ui| {
+                for profile in profiles.profiles {
+                    let is_selected = profile.name == profiles.selected;
+                    if ui.selectable_label(is_selected, &profile.name).clicked() && !is_selected {
+                        *action = Some(PcileechAction::SelectProfile(profile.name.clone()));
+                    }
+                }
+            });
+        ui.toggle_value(
+            profiles.editing,
+            translate(TextKey::PcileechProfileEdit, lang),
+        );
+    });
+
+    if let Some(status) = profiles.status {
+        let (text, color) = match status {
+            Ok(message) => (message.as_str(), SUCCESS_COLOR),
+            Err(message) => (message.as_str(), ERROR_COLOR),
+        };
+        ui.label(RichText::new(text).size(PROFILE_STATUS_SIZE).color(color));
+    }
+}
+
+fn render_profile_editor(
+    ui: &mut egui::Ui,
+    profiles: &mut PcileechProfileView<'_>,
+    lang: &Language,
+    action: &mut Option<PcileechAction>,
+) {
+    let draft = &mut *profiles.draft;
+    render_status_card(ui, palette::STROKE_SUBTLE, |ui| {
+        egui::Grid::new("pcileech_profile_editor")
+            .num_columns(2)
+            .spacing([12.0, 8.0])
+            .show(ui, |ui| {
+                for (key, value, hint) in [
+                    (TextKey::PcileechProfileName, &mut draft.name, None),
+                    (
+                        TextKey::PcileechProfileExecutable,
+                        &mut draft.executable,
+                        None,
+                    ),
+                    (
+                        TextKey::PcileechProfileConnector,
+                        &mut draft.connector,
+                        None,
+                    ),
+                    (
+                        TextKey::PcileechProfileConnectorArgs,
+                        &mut draft.connector_args,
+                        Some(TextKey::PcileechProfileConnectorArgsHint),
+                    ),
+                    (
+                        TextKey::PcileechProfileBenchmarkArgs,
+                        &mut draft.benchmark_args,
+                        None,
+                    ),
+                ] {
+                    ui.label(translate(key, lang));
+                    let mut field =
+                        egui::TextEdit::singleline(value).desired_width(PROFILE_FIELD_WIDTH);
+                    if let Some(hint) = hint {
+                        field = field.hint_text(translate(hint, lang));
+                    }
+                    ui.add(field);
+                    ui.end_row();
+                }
+            });
+
+        ui.add_space(12.0);
+        ui.horizontal(|ui| {
+            if ui
+                .button(translate(TextKey::PcileechProfileSave, lang))
+                .clicked()
+            {
+                *action = Some(PcileechAction::SaveProfile);
+            }
+            let can_delete = profiles.profiles.len() > 1;
+            if ui
+                .add_enabled(
+                    can_delete,
+                    egui::Button::new(translate(TextKey::PcileechProfileDelete, lang)),
+                )
+                .clicked()
+            {
+                *action = Some(PcileechAction::DeleteProfile);
+            }
+        });
+    });
+}
+
+fn render_mode_picker(
+    ui: &mut egui::Ui,
+    mode: TestMode,
+    benchmark_available: bool,
+    lang: &Language,
+    action: &mut Option<PcileechAction>,
+) {
+    ui.horizontal(|ui| {
+        let modes = [
+            (TestMode::Check, TextKey::PcileechModeCheck),
+            (
+                TestMode::Benchmark {
+                    seconds: BENCHMARK_DURATIONS[0],
+                },
+                TextKey::PcileechModeBenchmark,
+            ),
+            (
+                TestMode::Soak(SoakPlan::default()),
+                TextKey::PcileechModeSoak,
+            ),
+        ];
+        for (default_mode, key) in modes {
+            let is_current = std::mem::discriminant(&mode) == std::mem::discriminant(&default_mode);
+            let is_benchmark = matches!(default_mode, TestMode::Benchmark { .. });
+            let enabled = benchmark_available || !is_benchmark;
+            let mut label = ui.add_enabled(
+                enabled,
+                egui::Button::selectable(is_current, translate(key, lang)),
+            );
+            if !enabled {
+                label = label
+                    .on_disabled_hover_text(translate(TextKey::PcileechBenchmarkUnavailable, lang));
+            }
+            if label.clicked() && !is_current {
+                *action = Some(PcileechAction::SetMode(default_mode));
+            }
+        }
+    });
+
+    let chosen = match mode {
+        TestMode::Check => None,
+        TestMode::Benchmark { seconds } => {
+            ui.horizontal(|ui| {
+                ui.label(translate(TextKey::PcileechBenchmarkDuration, lang));
+                option_combo(
+                    ui,
+                    "pcileech_benchmark_duration",
+                    seconds,
+                    &BENCHMARK_DURATIONS,
+                    |value| seconds_text(value, lang),
+                )
+                .map(|seconds| TestMode::Benchmark { seconds })
+            })
+            .inner
+        }
+        TestMode::Soak(plan) => {
+            ui.horizontal(|ui| {
+                ui.label(translate(TextKey::PcileechSoakLength, lang));
+                let limit = option_combo(
+                    ui,
+                    "pcileech_soak_limit",
+                    plan.limit,
+                    &SOAK_LIMITS,
+                    |limit| soak_limit_text(limit, lang),
+                );
+                ui.add_space(8.0);
+                ui.label(translate(TextKey::PcileechSoakGap, lang));
+                let gap = option_combo(
+                    ui,
+                    "pcileech_soak_gap",
+                    plan.gap_seconds,
+                    &SOAK_GAPS,
+                    |gap| seconds_text(gap, lang),
+                );
+                (limit.is_some() || gap.is_some()).then(|| {
+                    TestMode::Soak(SoakPlan {
+                        limit: limit.unwrap_or(plan.limit),
+                        gap_seconds: gap.unwrap_or(plan.gap_seconds),
+                    })
+                })
+            })
+            .inner
+        }
+    };
+    if let Some(mode) = chosen {
+        *action = Some(PcileechAction::SetMode(mode));
+    }
+}
+
+/// A combo box over `options` that returns the newly chosen one, if any.
+fn option_combo<T: Copy + PartialEq>(
+    ui: &mut egui::Ui,
+    id: &str,
+    current: T,
+    options: &[T],
+    text: impl Fn(T) -> String,
+) -> Option<T> {
+    let mut chosen = None;
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(text(current))
+        .show_ui(ui, |ui| {
+            for option in options {
+                if ui
+                    .selectable_label(*option == current, text(*option))
+                    .clicked()
+                    && *option != current
+                {
+                    chosen = Some(*option);
+                }
+            }
+        });
+    chosen
+}
+
+fn seconds_text(seconds: u32, lang: &Language) -> String {
+    format_translation(
+        translate(TextKey::PcileechSeconds, lang),
+        &[&seconds.to_string()],
+    )
+}
+
+fn soak_limit_text(limit: SoakLimit, lang: &Language) -> String {
+    let (key, value) = match limit {
+        SoakLimit::Runs(runs) => (TextKey::PcileechSoakRuns, runs),
+        SoakLimit::Minutes(minutes) => (TextKey::PcileechSoakMinutes, minutes),
+    };
+    format_translation(translate(key, lang), &[&value.to_string()])
+}
+
+fn render_benchmark_running_state(ui: &mut egui::Ui, throughput: &Throughput, lang: &Language) {
     ui.add_space(10.0);
     render_status_card(ui, palette::INFO, |ui| {
         ui.vertical_centered(|ui| {
             ui.spinner();
             ui.add_space(12.0);
             ui.label(
-                RichText::new(translate(TextKey::TestingConnection, lang))
+                RichText::new(translate(TextKey::PcileechMeasuring, lang))
                     .size(STATUS_TEXT_SIZE + 1.0)
                     .color(BODY_TEXT_COLOR),
             );
+            if let Some(latest) = throughput.samples.last() {
+                ui.add_space(8.0);
+                ui.label(
+                    RichText::new(format_mbps(*latest))
+                        .monospace()
+                        .size(STATUS_TITLE_SIZE)
+                        .color(BODY_TEXT_COLOR),
+                );
+                ui.add_space(8.0);
+                render_throughput_chart(ui, throughput);
+            }
         });
     });
 }
 
-fn render_success_state(
-    ui: &mut egui::Ui,
-    line: &str,
-    lang: &crate::utils::localization::Language,
-) {
+fn render_measured_state(ui: &mut egui::Ui, throughput: &Throughput, lang: &Language) {
     ui.add_space(10.0);
     render_status_card(ui, SUCCESS_COLOR, |ui| {
         ui.vertical_centered(|ui| {
-            render_status_icon(ui, egui_phosphor::regular::CHECK_CIRCLE, SUCCESS_COLOR);
+            render_status_icon(ui, egui_phosphor::regular::GAUGE, SUCCESS_COLOR);
             ui.add_space(8.0);
             ui.label(
-                RichText::new(translate(TextKey::TestSuccess, lang))
+                RichText::new(translate(TextKey::PcileechBenchmarkDone, lang))
                     .strong()
                     .size(STATUS_TITLE_SIZE)
                     .color(SUCCESS_COLOR),
             );
             ui.add_space(12.0);
+
+            egui::Grid::new("pcileech_throughput")
+                .num_columns(3)
+                .spacing([24.0, 4.0])
+                .show(ui, |ui| {
+                    for key in [
+                        TextKey::PcileechThroughputMin,
+                        TextKey::PcileechThroughputAverage,
+                        TextKey::PcileechThroughputMax,
+                    ] {
+                        ui.label(
+                            RichText::new(translate(key, lang))
+                                .size(STATUS_TEXT_SIZE)
+                                .color(MUTED_TEXT_COLOR),
+                        );
+                    }
+                    ui.end_row();
+                    for value in [throughput.min(), throughput.average(), throughput.max()] {
+                        ui.label(
+                            RichText::new(format_mbps(value))
+                                .monospace()
+                                .size(STATUS_TEXT_SIZE)
+                                .color(BODY_TEXT_COLOR),
+                        );
+                    }
+                    ui.end_row();
+                });
+
+            ui.add_space(10.0);
+            render_throughput_chart(ui, throughput);
             ui.label(
-                RichText::new(line)
-                    .size(STATUS_TEXT_SIZE)
-                    .color(BODY_TEXT_COLOR),
+                RichText::new(format_translation(
+                    translate(TextKey::PcileechSamples, lang),
+                    &[&throughput.samples.len().to_string()],
+                ))
+                .size(PROFILE_STATUS_SIZE)
+                .color(MUTED_TEXT_COLOR),
             );
         });
     });
 }
 
-fn render_error_state(
+fn render_soak_running_state(
     ui: &mut egui::Ui,
-    message: &str,
-    lang: &crate::utils::localization::Language,
+    report: &SoakReport,
+    lang: &Language,
+    action: &mut Option<PcileechAction>,
 ) {
+    ui.add_space(10.0);
+    render_status_card(ui, palette::INFO, |ui| {
+        ui.vertical_centered(|ui| {
+            ui.spinner();
+            ui.add_space(12.0);
+            ui.label(
+                RichText::new(translate(TextKey::PcileechSoakRunning, lang))
+                    .size(STATUS_TEXT_SIZE + 1.0)
+                    .color(BODY_TEXT_COLOR),
+            );
+            ui.add_space(8.0);
+            ui.label(
+                RichText::new(soak_progress_text(report, lang))
+                    .size(STATUS_TEXT_SIZE)
+                    .color(MUTED_TEXT_COLOR),
+            );
+            ui.add_space(10.0);
+            if ui
+                .button(translate(TextKey::PcileechSoakStop, lang))
+                .clicked()
+            {
+                *action = Some(PcileechAction::Stop);
+            }
+        });
+    });
+}
+
+fn render_soaked_state(ui: &mut egui::Ui, report: &SoakReport, lang: &Language) {
+    let color = if report.failures() == 0 {
+        SUCCESS_COLOR
+    } else {
+        palette::WARNING
+    };
+
+    ui.add_space(10.0);
+    render_status_card(ui, color, |ui| {
+        ui.vertical_centered(|ui| {
+            ui.label(
+                RichText::new(translate(
+                    if report.cancelled {
+                        TextKey::PcileechSoakStopped
+                    } else {
+                        TextKey::PcileechSoakDone
+                    },
+                    lang,
+                ))
+                .strong()
+                .size(STATUS_TITLE_SIZE)
+                .color(color),
+            );
+            ui.add_space(8.0);
+            ui.label(
+                RichText::new(format_translation(
+                    translate(TextKey::PcileechSoakSuccessRate, lang),
+                    &[
+                        &format!("{:.1}", report.success_rate()),
+                        &report.successes().to_string(),
+                        &report.attempts.len().to_string(),
+                    ],
+                ))
+                .size(STATUS_TEXT_SIZE + 1.0)
+                .color(BODY_TEXT_COLOR),
+            );
+
+            if let Some(times) = report.success_times() {
+                ui.add_space(10.0);
+                ui.label(
+                    RichText::new(translate(TextKey::PcileechSoakTimeToSuccess, lang))
+                        .size(STATUS_TEXT_SIZE)
+                        .color(MUTED_TEXT_COLOR),
+                );
+                egui::Grid::new("pcileech_soak_times")
+                    .num_columns(4)
+                    .spacing([20.0, 4.0])
+                    .show(ui, |ui| {
+                        for label in ["p50", "p90", "p99", "max"] {
+                            ui.label(
+                                RichText::new(label)
+                                    .size(STATUS_TEXT_SIZE)
+                                    .color(MUTED_TEXT_COLOR),
+                            );
+                        }
+                        ui.end_row();
+                        for time in [times.p50, times.p90, times.p99, times.max] {
+                            ui.label(
+                                RichText::new(format!("{:.2} s", time.as_secs_f64()))
+                                    .monospace()
+                                    .size(STATUS_TEXT_SIZE)
+                                    .color(BODY_TEXT_COLOR),
+                            );
+                        }
+                        ui.end_row();
+                    });
+            }
+
+            let reasons = report.failure_reasons();
+            if !reasons.is_empty() {
+                ui.add_space(10.0);
+                ui.label(
+                    RichText::new(translate(TextKey::PcileechSoakFailures, lang))
+                        .size(STATUS_TEXT_SIZE)
+                        .color(MUTED_TEXT_COLOR),
+                );
+                for (reason, count) in reasons.iter().take(MAX_FAILURE_REASONS) {
+                    ui.label(
+                        RichText::new(format!("{count}\u{d7}  {reason}"))
+                            .size(PROFILE_STATUS_SIZE)
+                            .color(ERROR_COLOR),
+                    );
+                }
+                let other: usize = reasons
+                    .iter()
+                    .skip(MAX_FAILURE_REASONS)
+                    .map(|(_, count)| count)
+                    .sum();
+                if other > 0 {
+                    ui.label(
+                        RichText::new(format_translation(
+                            translate(TextKey::PcileechSoakOtherFailures, lang),
+                            &[&other.to_string()],
+                        ))
+                        .size(PROFILE_STATUS_SIZE)
+                        .color(MUTED_TEXT_COLOR),
+                    );
+                }
+            }
+        });
+    });
+}
+
+fn soak_progress_text(report: &SoakReport, lang: &Language) -> String {
+    format_translation(
+        translate(TextKey::PcileechSoakProgress, lang),
+        &[
+            &report.attempts.len().to_string(),
+            &report.successes().to_string(),
+            &report.failures().to_string(),
+        ],
+    )
+}
+
+/// Samples left to right, scaled so the fastest one reaches the top.
+fn render_throughput_chart(ui: &mut egui::Ui, throughput: &Throughput) {
+    let size = egui::vec2(ui.available_width(), CHART_HEIGHT);
+    let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, egui::CornerRadius::same(6), palette::SURFACE);
+
+    let max = throughput.max().max(1) as f32;
+    let last_index = throughput.samples.len().saturating_sub(1).max(1) as f32;
+    let area = rect.shrink(6.0);
+    let points: Vec<egui::Pos2> = throughput
+        .samples
+        .iter()
+        .enumerate()
+        .map(|(index, sample)| {
+            egui::pos2(
+                area.left() + area.width() * index as f32 / last_index,
+                area.bottom() - area.height() * *sample as f32 / max,
+            )
+        })
+        .collect();
+
+    let stroke = egui::Stroke::new(CHART_STROKE_WIDTH, palette::INFO);
+    match points.as_slice() {
+        [] => {}
+        [point] => {
+            painter.circle_filled(*point, CHART_STROKE_WIDTH * 1.5, palette::INFO);
+        }
+        _ => {
+            painter.add(egui::Shape::line(points, stroke));
+        }
+    }
+}
+
+fn render_running_state(ui: &mut egui::Ui, lang: &Language) {
+    ui.add_space(10.0);
+    render_status_card(ui, palette::INFO, |ui| {
+        ui.vertical_centered(|ui| {
+            ui.spinner();
+            ui.add_space(12.0);
+            ui.label(
+                RichText::new(translate(TextKey::TestingConnection, lang))
+                    .size(STATUS_TEXT_SIZE + 1.0)
+                    .color(BODY_TEXT_COLOR),
+            );
+        });
+    });
+}
+
+fn render_success_state(ui: &mut egui::Ui, report: &PcileechReport, lang: &Language) {
+    ui.add_space(10.0);
+    render_status_card(ui, SUCCESS_COLOR, |ui| {
+        ui.vertical_centered(|ui| {
+            render_status_icon(ui, egui_phosphor::regular::CHECK_CIRCLE, SUCCESS_COLOR);
+            ui.add_space(8.0);
+            ui.label(
+                RichText::new(translate(TextKey::TestSuccess, lang))
+                    .strong()
+                    .size(STATUS_TITLE_SIZE)
+                    .color(SUCCESS_COLOR),
+            );
+            ui.add_space(12.0);
+            render_report_table(ui, report, lang);
+        });
+    });
+}
+
+fn render_report_table(ui: &mut egui::Ui, report: &PcileechReport, lang: &Language) {
+    egui::Grid::new("pcileech_report")
+        .num_columns(2)
+        .striped(true)
+        .spacing([16.0, 4.0])
+        .show(ui, |ui| {
+            for (key, value) in report.rows() {
+                ui.label(
+                    RichText::new(translate(key, lang))
+                        .size(STATUS_TEXT_SIZE)
+                        .color(MUTED_TEXT_COLOR),
+                );
+                ui.label(
+                    RichText::new(value)
+                        .monospace()
+                        .size(STATUS_TEXT_SIZE)
+                        .color(BODY_TEXT_COLOR),
+                );
+                ui.end_row();
+            }
+        });
+}
+
+fn render_error_state(ui: &mut egui::Ui, message: &str, lang: &Language) {
     ui.add_space(10.0);
     render_status_card(ui, ERROR_COLOR, |ui| {
         ui.vertical_centered(|ui| {
@@ -190,7 +748,7 @@ fn render_error_message(ui: &mut egui::Ui, message: &str) {
         });
 }
 
-fn render_connection_fixes(ui: &mut egui::Ui, lang: &crate::utils::localization::Language) {
+fn render_connection_fixes(ui: &mut egui::Ui, lang: &Language) {
     ui.vertical_centered(|ui| {
         for (index, line) in translate(TextKey::ConnectionError, lang)
             .lines()
@@ -0,0 +1,16 @@
+use crate::device_programmer::OperationSnapshot;
+use crate::utils::localization::{Language, TextKey, translate};
+
+/// Adapter used by the operation, e.g. `Adapter: FTDI FT4ABC12`.
+pub(super) fn adapter_line(snapshot: &OperationSnapshot, lang: &Language) -> Option<String> {
+    let kind = snapshot.option.as_ref()?.adapter_kind();
+    let serial = snapshot
+        .adapter_serial
+        .as_deref()
+        .unwrap_or_else(|| translate(TextKey::AdapterAuto, lang));
+    Some(format!(
+        "{} {} {serial}",
+        translate(TextKey::AdapterLabel, lang),
+        kind.label()
+    ))
+}
@@ -0,0 +1,38 @@
+use crate::device_programmer::{FailureReason, WatchdogTimeout};
+use crate::utils::localization::{Language, TextKey, format_translation, translate};
+
+/// Localized explanation for a watchdog stop, including the exceeded limit.
+pub(super) fn timeout_message(timeout: &WatchdogTimeout, lang: &Language) -> String {
+    let key = match timeout {
+        WatchdogTimeout::NoOutput(_) => TextKey::AdapterNoOutputMsg,
+        WatchdogTimeout::TotalRuntime(_) => TextKey::AdapterRuntimeExceededMsg,
+    };
+    let seconds = timeout.limit().as_secs().to_string();
+    format_translation(translate(key, lang), &[&seconds])
+}
+
+/// Single-line failure text for status labels.
+pub(super) fn failure_summary(reason: &FailureReason, lang: &Language) -> String {
+    match reason {
+        FailureReason::Error(message) => message.clone(),
+        FailureReason::Timeout(_) => translate(TextKey::AdapterStoppedResponding, lang).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn timeout_message_contains_limit_without_placeholders() {
+        for lang in [Language::English, Language::Arabic] {
+            let message =
+                timeout_message(&WatchdogTimeout::NoOutput(Duration::from_secs(45)), &lang);
+
+            assert!(message.contains("45"));
+            assert!(!message.contains("{}"));
+            assert!(!message.contains("}{"));
+        }
+    }
+}
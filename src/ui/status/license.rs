@@ -0,0 +1,26 @@
+use crate::dna_allowlist::LicenseStatus;
+use crate::ui::common::palette;
+use crate::utils::localization::{Language, TextKey, format_translation, translate};
+use eframe::egui::Color32;
+
+/// Localized allowlist outcome and the color it is shown in.
+pub fn license_text(status: &LicenseStatus, lang: &Language) -> (String, Color32) {
+    match status {
+        LicenseStatus::Licensed(label) => (
+            format_translation(translate(TextKey::DnaLicensedTo, lang), &[label.as_str()]),
+            palette::SUCCESS,
+        ),
+        LicenseStatus::Unknown => (
+            translate(TextKey::DnaUnknownBoard, lang).to_string(),
+            palette::WARNING,
+        ),
+        LicenseStatus::Revoked(label) if label.is_empty() => (
+            translate(TextKey::DnaRevoked, lang).to_string(),
+            palette::ERROR,
+        ),
+        LicenseStatus::Revoked(label) => (
+            format_translation(translate(TextKey::DnaRevokedFrom, lang), &[label.as_str()]),
+            palette::ERROR,
+        ),
+    }
+}
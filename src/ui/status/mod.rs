@@ -1,7 +1,11 @@
+mod adapter;
+mod failure;
+mod license;
 mod progress;
 mod result;
 mod types;
 
+pub use license::license_text;
 pub use progress::render_flashing_progress;
 pub use result::render_result_screen;
 pub use types::ResultAction;
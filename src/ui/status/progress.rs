@@ -1,3 +1,5 @@
+use super::adapter::adapter_line;
+use super::failure::failure_summary;
 use crate::device_programmer::{
     CompletionStatus, FlashingOption, OperationSnapshot, OperationStage,
 };
@@ -51,7 +53,7 @@ pub fn render_flashing_progress(
 
             render_operation_info_frame(ui, is_dna_read, lang);
             ui.add_space(EXTRA_LARGE_SPACING);
-            render_technical_info_frame(ui, option, operation_name, lang);
+            render_technical_info_frame(ui, snapshot, option, operation_name, lang);
         });
     } else {
         ui.heading("Operation");
@@ -128,6 +130,7 @@ fn render_flashing_info(ui: &mut Ui, lang: &crate::utils::localization::Language
 
 fn render_technical_info_frame(
     ui: &mut Ui,
+    snapshot: &OperationSnapshot,
     option: &FlashingOption,
     _operation_name: &str,
     lang: &crate::utils::localization::Language,
@@ -149,6 +152,9 @@ fn render_technical_info_frame(
                     translate(TextKey::InterfaceLabel, lang),
                     option.get_driver_type()
                 ));
+                if let Some(adapter) = adapter_line(snapshot, lang) {
+                    ui.label(adapter);
+                }
 
                 let op_type_str = if option.is_dna_read() {
                     translate(TextKey::ReadingDeviceDna, lang)
@@ -226,16 +232,17 @@ fn dna_status_text(
             translate(TextKey::DnaReadSuccessStatus, lang).to_string()
         }
         CompletionStatus::Completed => translate(TextKey::DnaOperationCompleted, lang).to_string(),
-        CompletionStatus::Failed(error) => {
-            format_translation(translate(TextKey::DnaReadFailedStatus, lang), &[error])
-        }
+        CompletionStatus::Failed(reason) => format_translation(
+            translate(TextKey::DnaReadFailedStatus, lang),
+            &[&failure_summary(reason, lang)],
+        ),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::device_programmer::DnaInfo;
+    use crate::device_programmer::{DeviceDna, DnaInfo};
     use crate::utils::localization::Language;
 
     #[test]
@@ -246,8 +253,7 @@ mod tests {
             CompletionStatus::Completed,
             CompletionStatus::Failed("err".into()),
             CompletionStatus::DnaReadCompleted(DnaInfo {
-                dna_value: "0x1".into(),
-                dna_raw_value: "1".into(),
+                dna: DeviceDna::from_value(0x1).unwrap(),
                 device_type: "T".into(),
             }),
         ];
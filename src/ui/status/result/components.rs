@@ -1,4 +1,6 @@
+use super::super::failure::timeout_message;
 use super::super::types::ResultAction;
+use crate::device_programmer::WatchdogTimeout;
 use crate::ui::common::{self, palette};
 use crate::utils::localization::{TextKey, translate};
 use eframe::egui::{self, RichText, Ui};
@@ -99,6 +101,19 @@ pub(super) fn render_error(
     });
 }
 
+pub(super) fn render_timeout(
+    ui: &mut Ui,
+    timeout: &WatchdogTimeout,
+    lang: &crate::utils::localization::Language,
+) {
+    render_error(
+        ui,
+        translate(TextKey::AdapterStoppedResponding, lang),
+        &timeout_message(timeout, lang),
+        lang,
+    );
+}
+
 pub(super) fn render_success(ui: &mut Ui, lang: &crate::utils::localization::Language) {
     ui.vertical_centered(|ui| {
         ui.add_space(SPACING_LARGE);
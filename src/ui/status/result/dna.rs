@@ -1,27 +1,45 @@
+use super::super::types::ResultAction;
 use super::components::{
     DNA_VALUE_FONT_SIZE, SPACING_LARGE, SPACING_MEDIUM, SPACING_SMALL, SPACING_XLARGE,
     SUBTITLE_FONT_SIZE, SUCCESS_COLOR, TITLE_FONT_SIZE, render_error, render_framed_content,
-    render_icon,
+    render_icon, render_timeout,
 };
-use crate::device_programmer::{CompletionStatus, DnaInfo, OperationSnapshot};
-use crate::ui::common::palette;
+use crate::device_programmer::{
+    CompletionStatus, DnaFormat, DnaInfo, FailureReason, OperationSnapshot,
+};
+use crate::dna_allowlist::LicenseStatus;
+use crate::dna_history::ExportFormat;
+use crate::ui::common::{self, palette};
+use crate::ui::status::license_text;
 use crate::utils::localization::{TextKey, translate};
-use eframe::egui::{RichText, Ui};
+use eframe::egui::{self, RichText, Ui};
+
+const FORMAT_VALUE_SIZE: f32 = 12.5;
+const EXPORT_BUTTON_WIDTH: f32 = 150.0;
+const EXPORT_BUTTON_HEIGHT: f32 = 30.0;
 
 pub(super) fn render(
     ui: &mut Ui,
     snapshot: &OperationSnapshot,
+    license: Option<&LicenseStatus>,
+    export_status: Option<&Result<String, String>>,
+    on_action: &mut dyn FnMut(ResultAction),
     lang: &crate::utils::localization::Language,
 ) {
     match &snapshot.status {
-        CompletionStatus::DnaReadCompleted(dna_info) => render_success(ui, dna_info, lang),
+        CompletionStatus::DnaReadCompleted(dna_info) => {
+            render_success(ui, dna_info, license, export_status, on_action, lang);
+        }
         CompletionStatus::Completed => render_error(
             ui,
             translate(TextKey::DnaReadUnexpected, lang),
             translate(TextKey::DnaReadUnexpectedMsg, lang),
             lang,
         ),
-        CompletionStatus::Failed(error) => render_error(
+        CompletionStatus::Failed(FailureReason::Timeout(timeout)) => {
+            render_timeout(ui, timeout, lang);
+        }
+        CompletionStatus::Failed(FailureReason::Error(error)) => render_error(
             ui,
             translate(TextKey::DnaReadFailed, lang),
             &format!(
@@ -45,7 +63,15 @@ pub(super) fn render(
     }
 }
 
-fn render_success(ui: &mut Ui, dna_info: &DnaInfo, lang: &crate::utils::localization::Language) {
+fn render_success(
+    ui: &mut Ui,
+    dna_info: &DnaInfo,
+    license: Option<&LicenseStatus>,
+    export_status: Option<&Result<String, String>>,
+    on_action: &mut dyn FnMut(ResultAction),
+    lang: &crate::utils::localization::Language,
+) {
+    let hex = dna_info.dna.format(DnaFormat::Hex);
     ui.vertical_centered(|ui| {
         ui.add_space(SPACING_LARGE);
         render_icon(ui, egui_phosphor::regular::CHECK_CIRCLE, SUCCESS_COLOR);
@@ -68,7 +94,7 @@ fn render_success(ui: &mut Ui, dna_info: &DnaInfo, lang: &crate::utils::localiza
 
                 let response = ui.selectable_label(
                     false,
-                    RichText::new(&dna_info.dna_value)
+                    RichText::new(&hex)
                         .monospace()
                         .strong()
                         .size(DNA_VALUE_FONT_SIZE),
@@ -76,8 +102,8 @@ fn render_success(ui: &mut Ui, dna_info: &DnaInfo, lang: &crate::utils::localiza
 
                 if response.clicked() {
                     ui.ctx().copy_text(format!(
-                        "DNA RAW: {}\nDNA HEX: {}",
-                        dna_info.dna_raw_value, dna_info.dna_value
+                        "DNA RAW: {}\nDNA HEX: {hex}",
+                        dna_info.dna.format(DnaFormat::Binary)
                     ));
                 }
 
@@ -90,5 +116,100 @@ fn render_success(ui: &mut Ui, dna_info: &DnaInfo, lang: &crate::utils::localiza
                 );
             });
         });
+
+        if let Some(license) = license {
+            let (text, color) = license_text(license, lang);
+            ui.add_space(SPACING_SMALL);
+            ui.label(
+                RichText::new(text)
+                    .size(SUBTITLE_FONT_SIZE)
+                    .strong()
+                    .color(color),
+            );
+        }
+
+        ui.add_space(SPACING_MEDIUM);
+        render_formats(ui, dna_info, lang);
+        ui.add_space(SPACING_MEDIUM);
+        render_export_buttons(ui, on_action, lang);
+        if let Some(status) = export_status {
+            let (text, color) = match status {
+                Ok(message) => (message.as_str(), palette::SUCCESS),
+                Err(message) => (message.as_str(), palette::ERROR),
+            };
+            ui.add_space(SPACING_SMALL);
+            ui.label(RichText::new(text).size(14.0).color(color));
+        }
     });
 }
+
+fn render_formats(ui: &mut Ui, dna_info: &DnaInfo, lang: &crate::utils::localization::Language) {
+    ui.label(RichText::new(translate(TextKey::DnaFormatsHeader, lang)).size(SUBTITLE_FONT_SIZE));
+    ui.add_space(SPACING_SMALL);
+
+    egui::Grid::new("dna_formats")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            for format in DnaFormat::ALL {
+                let value = dna_info.dna.format(format);
+                ui.label(RichText::new(translate(format_label(format), lang)).strong());
+                ui.label(RichText::new(&value).monospace().size(FORMAT_VALUE_SIZE));
+                if ui
+                    .small_button(format!(
+                        "{} {}",
+                        egui_phosphor::regular::COPY,
+                        translate(TextKey::DnaCopyValue, lang)
+                    ))
+                    .clicked()
+                {
+                    ui.ctx().copy_text(value);
+                }
+                ui.end_row();
+            }
+        });
+}
+
+fn render_export_buttons(
+    ui: &mut Ui,
+    on_action: &mut dyn FnMut(ResultAction),
+    lang: &crate::utils::localization::Language,
+) {
+    let size = egui::vec2(EXPORT_BUTTON_WIDTH, EXPORT_BUTTON_HEIGHT);
+    let total_width = 2.0 * EXPORT_BUTTON_WIDTH + SPACING_MEDIUM;
+
+    ui.allocate_ui_with_layout(
+        egui::vec2(total_width, EXPORT_BUTTON_HEIGHT),
+        egui::Layout::left_to_right(egui::Align::Center),
+        |ui| {
+            for (format, key) in [
+                (ExportFormat::Csv, TextKey::DnaHistoryExportCsv),
+                (ExportFormat::Json, TextKey::DnaHistoryExportJson),
+            ] {
+                if common::secondary_icon_button(
+                    ui,
+                    Some(egui_phosphor::regular::EXPORT),
+                    translate(key, lang),
+                    size,
+                )
+                .clicked()
+                {
+                    on_action(ResultAction::ExportDna(format));
+                }
+                if format == ExportFormat::Csv {
+                    ui.add_space(SPACING_MEDIUM);
+                }
+            }
+        },
+    );
+}
+
+fn format_label(format: DnaFormat) -> TextKey {
+    match format {
+        DnaFormat::Hex => TextKey::DnaFormatHex,
+        DnaFormat::Hex57 => TextKey::DnaFormatHex57,
+        DnaFormat::Binary => TextKey::DnaFormatBinary,
+        DnaFormat::ByteReversed => TextKey::DnaFormatByteReversed,
+        DnaFormat::Base64 => TextKey::DnaFormatBase64,
+    }
+}
@@ -1,7 +1,9 @@
 use super::components::{
-    SPACING_SMALL, render_duration_if_meaningful, render_error, render_success,
+    SPACING_SMALL, render_duration_if_meaningful, render_error, render_success, render_timeout,
+};
+use crate::device_programmer::{
+    CompletionStatus, FailureReason, FlashAssessment, OperationSnapshot,
 };
-use crate::device_programmer::{CompletionStatus, FlashAssessment, OperationSnapshot};
 use crate::ui::common::palette;
 use crate::utils::localization::{TextKey, format_translation, translate};
 use eframe::egui::{RichText, Ui};
@@ -45,7 +47,10 @@ pub(super) fn render(
             translate(TextKey::UnexpectedStateMsg, lang),
             lang,
         ),
-        FlashAssessment::Failed(error) => render_error(
+        FlashAssessment::Failed(FailureReason::Timeout(timeout)) => {
+            render_timeout(ui, timeout, lang);
+        }
+        FlashAssessment::Failed(FailureReason::Error(error)) => render_error(
             ui,
             translate(TextKey::FlashingFailed, lang),
             &format!(
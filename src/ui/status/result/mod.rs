@@ -2,13 +2,18 @@ mod components;
 mod dna;
 mod flash;
 
+use super::adapter::adapter_line;
 use super::types::ResultAction;
-use crate::device_programmer::OperationSnapshot;
-use eframe::egui::Ui;
+use crate::device_programmer::{OperationSnapshot, SignatureOutcome};
+use crate::dna_allowlist::LicenseStatus;
+use crate::ui::common::palette;
+use eframe::egui::{RichText, Ui};
 
 pub fn render_result_screen(
     ui: &mut Ui,
     snapshot: &OperationSnapshot,
+    license: Option<&LicenseStatus>,
+    export_status: Option<&Result<String, String>>,
     on_action: &mut dyn FnMut(ResultAction),
     lang: &crate::utils::localization::Language,
 ) {
@@ -17,10 +22,40 @@ pub fn render_result_screen(
         .as_ref()
         .is_some_and(|option| option.is_dna_read())
     {
-        dna::render(ui, snapshot, lang);
+        dna::render(ui, snapshot, license, export_status, on_action, lang);
     } else {
         flash::render(ui, snapshot, lang);
     }
 
+    if let Some(adapter) = adapter_line(snapshot, lang) {
+        ui.vertical_centered(|ui| {
+            ui.label(RichText::new(adapter).size(14.0).color(palette::TEXT_MUTED));
+        });
+    }
+
+    if let Some(signature) = &snapshot.signature {
+        render_signature_line(ui, signature, lang);
+    }
+
     components::render_action_buttons(ui, on_action, lang, snapshot.safe_to_restart);
 }
+
+fn render_signature_line(
+    ui: &mut Ui,
+    signature: &SignatureOutcome,
+    lang: &crate::utils::localization::Language,
+) {
+    let (icon, color) = if signature.is_verified() {
+        (egui_phosphor::regular::SEAL_CHECK, palette::SUCCESS)
+    } else {
+        (egui_phosphor::regular::SEAL_WARNING, palette::WARNING)
+    };
+    ui.vertical_centered(|ui| {
+        ui.label(
+            RichText::new(format!("{icon} {}", signature.describe(lang)))
+                .size(14.0)
+                .strong()
+                .color(color),
+        );
+    });
+}
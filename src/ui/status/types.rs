@@ -1,5 +1,8 @@
+use crate::dna_history::ExportFormat;
+
 #[derive(Debug, Clone, Copy)]
 pub enum ResultAction {
     MainMenu,
     TryAgain,
+    ExportDna(ExportFormat),
 }
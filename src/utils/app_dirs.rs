@@ -0,0 +1,44 @@
+use std::env;
+use std::path::PathBuf;
+
+const APP_DIR_NAME: &str = "dma-tools";
+
+/// Per-user directory for data the app keeps between runs, e.g.
+/// `%APPDATA%\dma-tools` or `$XDG_DATA_HOME/dma-tools`.
+pub fn data_dir() -> Option<PathBuf> {
+    platform_data_root().map(|root| root.join(APP_DIR_NAME))
+}
+
+/// Per-user directory for preferences, e.g. `%APPDATA%\dma-tools` or
+/// `$XDG_CONFIG_HOME/dma-tools`.
+pub fn config_dir() -> Option<PathBuf> {
+    platform_config_root().map(|root| root.join(APP_DIR_NAME))
+}
+
+#[cfg(windows)]
+fn platform_data_root() -> Option<PathBuf> {
+    non_empty_env("APPDATA")
+}
+
+#[cfg(not(windows))]
+fn platform_data_root() -> Option<PathBuf> {
+    non_empty_env("XDG_DATA_HOME")
+        .or_else(|| non_empty_env("HOME").map(|home| home.join(".local").join("share")))
+}
+
+#[cfg(windows)]
+fn platform_config_root() -> Option<PathBuf> {
+    non_empty_env("APPDATA")
+}
+
+#[cfg(not(windows))]
+fn platform_config_root() -> Option<PathBuf> {
+    non_empty_env("XDG_CONFIG_HOME")
+        .or_else(|| non_empty_env("HOME").map(|home| home.join(".config")))
+}
+
+fn non_empty_env(name: &str) -> Option<PathBuf> {
+    env::var_os(name)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
@@ -1,13 +1,18 @@
-use crate::device_programmer::{CREATE_NO_WINDOW, DNA_OUTPUT_FILE, TEMP_FIRMWARE_FILE};
+use crate::device_programmer::TEMP_FIRMWARE_FILE;
 use crate::utils::logger::Logger;
+use crate::utils::process_job::hide_console;
 use std::fs;
-use std::os::windows::process::CommandExt;
 use std::path::Path;
 use std::process::Command;
 
+#[cfg(windows)]
 const OPENOCD_PROCESSES: [&str; 2] = ["openocd.exe", "openocd-347.exe"];
 
-const CLEANUP_FILES: &[&str] = &[TEMP_FIRMWARE_FILE, DNA_OUTPUT_FILE];
+// DNA reads now parse OpenOCD's output streams; the log file is only removed
+// when an older release left it behind.
+const LEGACY_DNA_OUTPUT_FILE: &str = "OpenOCD/openocd_output.log";
+
+const CLEANUP_FILES: &[&str] = &[TEMP_FIRMWARE_FILE, LEGACY_DNA_OUTPUT_FILE];
 
 pub fn perform_startup_cleanup(logger: &Logger) {
     logger.debug("Performing startup cleanup...");
@@ -19,18 +24,18 @@ pub fn perform_startup_cleanup(logger: &Logger) {
     logger.debug("Startup cleanup completed");
 }
 
+#[cfg(windows)]
 fn terminate_lingering_processes(logger: &Logger) {
     logger.debug("Checking for lingering processes...");
 
     for process_name in OPENOCD_PROCESSES.iter() {
         logger.debug(format!("Attempting to terminate {process_name}"));
 
-        let result = Command::new("taskkill")
-            .args(["/F", "/IM", process_name])
-            .creation_flags(CREATE_NO_WINDOW) // Hide the window
-            .output();
+        let mut command = Command::new("taskkill");
+        command.args(["/F", "/IM", process_name]);
+        hide_console(&mut command);
 
-        match result {
+        match command.output() {
             Ok(output) => {
                 let stdout = String::from_utf8_lossy(&output.stdout);
                 if !stdout.contains("ERROR") {
@@ -46,6 +51,36 @@ fn terminate_lingering_processes(logger: &Logger) {
     }
 }
 
+// Only the bundled binaries are matched, so an OpenOCD the user runs from
+// elsewhere is left alone.
+#[cfg(not(windows))]
+fn terminate_lingering_processes(logger: &Logger) {
+    use crate::device_programmer::types::{OPENOCD_CH347_PATH, OPENOCD_RS232_PATH};
+
+    logger.debug("Checking for lingering processes...");
+
+    for process_path in [OPENOCD_CH347_PATH, OPENOCD_RS232_PATH] {
+        logger.debug(format!("Attempting to terminate {process_path}"));
+
+        let mut command = Command::new("pkill");
+        command.args(["-KILL", "-f", process_path]);
+        hide_console(&mut command);
+
+        match command.output() {
+            // pkill exits with 1 when nothing matched.
+            Ok(output) if output.status.success() => {
+                logger.debug(format!("Terminated lingering {process_path} processes"));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                logger.debug(format!(
+                    "No {process_path} processes to terminate or error: {e}"
+                ));
+            }
+        }
+    }
+}
+
 fn cleanup_temp_files(logger: &Logger) {
     logger.debug("Cleaning up temporary files...");
 
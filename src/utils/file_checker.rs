@@ -1,17 +1,42 @@
+use crate::device_programmer::{FlashingOption, TEMP_FIRMWARE_FILE};
+use crate::utils::logger::Logger;
+use crate::utils::openocd_lint::{LintFinding, LintTarget, lint};
+use crate::utils::openocd_scripts::{DependencyGraph, ScriptLocation};
+use crate::utils::payload_manifest::{
+    PAYLOAD_MANIFEST_FILE, PayloadEntry, PayloadManifest, PayloadState,
+};
+use std::collections::HashSet;
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::Instant;
 
 pub const SUCCESS_TRANSITION_DELAY: u64 = 1;
+const OPENOCD_DIR: &str = "OpenOCD";
+/// Shown while the scripts are followed.
+const SCRIPTS_LABEL: &str = "OpenOCD/**/*.cfg";
+/// Upper bound on hashing threads; the payload is a few dozen files.
+const MAX_CHECK_THREADS: usize = 8;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FileCheckResult {
-    pub missing_files: Vec<String>,
+    pub problems: Vec<FileProblem>,
+    /// Mistakes found in the profiles' OpenOCD configs.
+    pub lint: Vec<LintFinding>,
     pub error_count: usize,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileProblem {
+    pub path: String,
+    pub state: PayloadState,
+    /// The script line that needs the file, when it was found by following
+    /// the OpenOCD scripts.
+    pub referenced_by: Option<ScriptLocation>,
+}
+
 /// Status of the file checking process
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CheckStatus {
@@ -42,13 +67,13 @@ impl FileChecker {
     }
 
     /// Starts file checking in a background thread to keep UI responsive
-    pub fn start_check(&self) {
+    pub fn start_check(&self, logger: Logger) {
         let status = Arc::clone(&self.status);
 
         thread::spawn(move || {
             log_execution_context();
 
-            let result = perform_file_check(&status);
+            let result = perform_file_check(&status, &logger);
 
             *status.lock().unwrap() = CheckStatus::Complete(result);
         });
@@ -66,76 +91,185 @@ fn log_execution_context() {
     }
 }
 
-fn perform_file_check(status: &Arc<Mutex<CheckStatus>>) -> FileCheckResult {
-    let mut missing_files = Vec::new();
-
-    let required_files = [
-        // Executables
-        "OpenOCD/openocd-347.exe",
-        "OpenOCD/openocd.exe",
-        // Libraries
-        "OpenOCD/cygwin1.dll",
-        "OpenOCD/cygusb-1.0.dll",
-        "OpenOCD/libftdi1.dll",
-        "OpenOCD/libgcc_s_sjlj-1.dll",
-        "OpenOCD/libhidapi-0.dll",
-        "OpenOCD/libusb-1.0.dll",
-        "OpenOCD/libwinpthread-1.dll",
-        // Configuration
-        "OpenOCD/libftdi1-config",
-        // Bitstream files
-        "OpenOCD/bit/bscan_spi_xc7a35t.bit",
-        "OpenOCD/bit/bscan_spi_xc7a75t.bit",
-        "OpenOCD/bit/bscan_spi_xc7a100t.bit",
-        // Flash configuration
-        "OpenOCD/flash/xc7a35T.cfg",
-        "OpenOCD/flash/xc7a75T.cfg",
-        "OpenOCD/flash/xc7a100T.cfg",
-        "OpenOCD/flash/xc7a35T_rs232.cfg",
-        "OpenOCD/flash/xc7a75T_rs232.cfg",
-        "OpenOCD/flash/xc7a100T_rs232.cfg",
-        // DNA configuration
-        "OpenOCD/DNA/init_347.cfg",
-        "OpenOCD/DNA/init_232_35t.cfg",
-        "OpenOCD/DNA/init_232_75t.cfg",
-        "OpenOCD/DNA/init_232_100t.cfg",
-        // FTDI driver
-        "tools/FTDIBUS3/FTDIBUS3.cat",
-        "tools/FTDIBUS3/ftdibus3.Inf",
-        "tools/FTDIBUS3/ftdibus3.PNF",
-        "tools/FTDIBUS3/FTDIBUS3.sys",
-        // Standalone driver tools
-        "tools/zadig-2.9.exe",
-        "tools/CH341PAR_USB_DRIVER.EXE",
-        // PCILeech test tool and runtime libraries
-        "tools/memflow-base/FTD3XX.dll",
-        "tools/memflow-base/memflow-base.exe",
-        "tools/memflow-base/memflow_pcileech.dll",
-        "tools/memflow-base/memflow_win32.dll",
-    ];
-
-    // Check each file with a small delay to show progress
-    for file in &required_files {
-        // Update status to show what we're currently checking
-        *status.lock().unwrap() = CheckStatus::Checking(file.to_string());
+// The profiles' scripts, bitstreams and other OpenOCD scripts are found by
+// following the entry scripts; see `required_by_scripts`.
+#[cfg(windows)]
+const PLATFORM_REQUIRED_FILES: &[&str] = &[
+    // Executables
+    "OpenOCD/openocd-347.exe",
+    "OpenOCD/openocd.exe",
+    // Libraries
+    "OpenOCD/cygwin1.dll",
+    "OpenOCD/cygusb-1.0.dll",
+    "OpenOCD/libftdi1.dll",
+    "OpenOCD/libgcc_s_sjlj-1.dll",
+    "OpenOCD/libhidapi-0.dll",
+    "OpenOCD/libusb-1.0.dll",
+    "OpenOCD/libwinpthread-1.dll",
+    // Configuration
+    "OpenOCD/libftdi1-config",
+    // FTDI driver
+    "tools/FTDIBUS3/FTDIBUS3.cat",
+    "tools/FTDIBUS3/ftdibus3.Inf",
+    "tools/FTDIBUS3/ftdibus3.PNF",
+    "tools/FTDIBUS3/FTDIBUS3.sys",
+    // Standalone driver tools
+    "tools/zadig-2.9.exe",
+    "tools/CH341PAR_USB_DRIVER.EXE",
+    // PCILeech test tool and runtime libraries
+    "tools/memflow-base/FTD3XX.dll",
+    "tools/memflow-base/memflow-base.exe",
+    "tools/memflow-base/memflow_pcileech.dll",
+    "tools/memflow-base/memflow_win32.dll",
+];
+
+// Native builds link against system libusb/libftdi and need no driver installers.
+#[cfg(not(windows))]
+const PLATFORM_REQUIRED_FILES: &[&str] = &["OpenOCD/openocd-347", "OpenOCD/openocd"];
 
-        // Allow UI to update and show progress
-        thread::sleep(Duration::from_millis(15));
+fn perform_file_check(status: &Arc<Mutex<CheckStatus>>, logger: &Logger) -> FileCheckResult {
+    let mut problems = Vec::new();
+    let mut verified = HashSet::new();
 
-        // Check if file exists in any of the possible locations
-        if !check_file_exists(file) {
-            missing_files.push(file.to_string());
+    // Without a usable manifest the remaining files can only be checked for
+    // presence. A damaged manifest is reported as a problem; a missing one is
+    // only logged, as source and development runs have none.
+    match locate(PAYLOAD_MANIFEST_FILE) {
+        Some(manifest_path) => match PayloadManifest::load(&manifest_path) {
+            Ok(manifest) => {
+                let root = manifest_path.parent().unwrap_or(Path::new("."));
+                let states = verify_entries(&manifest.files, root, status);
+                for (entry, state) in manifest.files.iter().zip(states) {
+                    verified.insert(entry.path.clone());
+                    if state != PayloadState::Intact {
+                        problems.push(FileProblem {
+                            path: entry.path.clone(),
+                            state,
+                            referenced_by: None,
+                        });
+                    }
+                }
+            }
+            Err(error) => {
+                logger.error(format!(
+                    "Cannot use {}: {error}; checking required files by presence only",
+                    manifest_path.display()
+                ));
+                problems.push(FileProblem {
+                    path: PAYLOAD_MANIFEST_FILE.to_string(),
+                    state: PayloadState::Corrupt,
+                    referenced_by: None,
+                });
+            }
+        },
+        None => {
+            logger.warning(format!(
+                "No {PAYLOAD_MANIFEST_FILE}; checking required files by presence only"
+            ));
+        }
+    }
+
+    // Files the app cannot run without are required even if the manifest
+    // omits them.
+    *status.lock().unwrap() = CheckStatus::Checking(SCRIPTS_LABEL.to_string());
+    for missing in required_by_scripts().missing {
+        match problems
+            .iter_mut()
+            .find(|problem| problem.path == missing.path)
+        {
+            Some(problem) => {
+                problem.referenced_by = problem.referenced_by.take().or(missing.location)
+            }
+            None => problems.push(FileProblem {
+                path: missing.path,
+                state: PayloadState::Missing,
+                referenced_by: missing.location,
+            }),
         }
     }
+    for file in PLATFORM_REQUIRED_FILES {
+        if verified.contains(*file) {
+            continue;
+        }
+        *status.lock().unwrap() = CheckStatus::Checking(file.to_string());
+        if locate(file).is_none() {
+            problems.push(FileProblem {
+                path: file.to_string(),
+                state: PayloadState::Missing,
+                referenced_by: None,
+            });
+        }
+    }
+
+    let lint = lint(&payload_root(), &LintTarget::bundled());
 
     FileCheckResult {
-        error_count: missing_files.len(),
-        missing_files,
+        error_count: problems.len() + lint.len(),
+        problems,
+        lint,
+    }
+}
+
+/// Follows every profile's entry script through the files it sources and
+/// loads. The flash image is written just before OpenOCD starts.
+fn required_by_scripts() -> DependencyGraph {
+    let root = payload_root();
+    let mut entries: Vec<&str> = FlashingOption::ALL
+        .iter()
+        .map(|option| option.get_command_args().1)
+        .collect();
+    entries.dedup();
+
+    let graph = DependencyGraph::scan(&root, &entries, &[TEMP_FIRMWARE_FILE]);
+    #[cfg(debug_assertions)]
+    for (script, edges) in &graph.edges {
+        for (kind, target) in edges {
+            println!("{script} -> {target} ({kind})");
+        }
     }
+    graph
 }
 
-/// Helper function to check multiple possible locations for a file
-fn check_file_exists(file_path: &str) -> bool {
+/// The folder holding `OpenOCD/`, which OpenOCD runs from.
+pub fn payload_root() -> PathBuf {
+    locate(OPENOCD_DIR)
+        .and_then(|dir| dir.parent().map(Path::to_path_buf))
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// Hashes the manifest's files on a few threads and returns their states in
+/// manifest order.
+fn verify_entries(
+    entries: &[PayloadEntry],
+    root: &Path,
+    status: &Arc<Mutex<CheckStatus>>,
+) -> Vec<PayloadState> {
+    let next = AtomicUsize::new(0);
+    let states = Mutex::new(vec![PayloadState::Intact; entries.len()]);
+    let threads = thread::available_parallelism()
+        .map_or(1, usize::from)
+        .clamp(1, MAX_CHECK_THREADS);
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(entry) = entries.get(index) else {
+                        break;
+                    };
+                    *status.lock().unwrap() = CheckStatus::Checking(entry.path.clone());
+                    let state = entry.verify(root);
+                    states.lock().unwrap()[index] = state;
+                }
+            });
+        }
+    });
+    states.into_inner().unwrap()
+}
+
+/// Finds `file_path` in the directories the payload may be extracted to.
+fn locate(file_path: &str) -> Option<PathBuf> {
     let mut base_paths = vec![PathBuf::from(".")];
 
     #[cfg(debug_assertions)]
@@ -164,23 +298,86 @@ fn check_file_exists(file_path: &str) -> bool {
         if full_path.exists() {
             #[cfg(debug_assertions)]
             println!("✓ Found: {}", full_path.display());
-            return true;
+            return Some(full_path);
         }
     }
 
     #[cfg(debug_assertions)]
     println!("✗ Not found: {file_path}");
 
-    false
+    None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::temp_dir::TempDir;
+    use std::fs;
 
     #[test]
     fn test_file_checker_initial_state() {
         let checker = FileChecker::new();
         assert_eq!(checker.get_status(), CheckStatus::NotStarted);
     }
+
+    #[test]
+    fn bundled_profiles_reference_only_bundled_files() {
+        let graph = required_by_scripts();
+
+        assert_eq!(graph.missing, []);
+        assert!(graph.edges["OpenOCD/flash/xc7a35T.cfg"].contains(&(
+            crate::utils::openocd_scripts::ReferenceKind::Bitstream,
+            "OpenOCD/bit/bscan_spi_xc7a35t.bit".to_string()
+        )));
+        assert!(
+            graph
+                .edges
+                .contains_key("OpenOCD/cpld/xilinx-dna-rs232.cfg")
+        );
+    }
+
+    #[test]
+    fn missing_manifest_is_logged_without_counting_as_a_problem() {
+        let logger = Logger::new("FileCheckTest");
+        let status = Arc::new(Mutex::new(CheckStatus::NotStarted));
+
+        let result = perform_file_check(&status, &logger);
+
+        assert!(
+            !result
+                .problems
+                .iter()
+                .any(|problem| problem.path == PAYLOAD_MANIFEST_FILE)
+        );
+        assert!(
+            logger
+                .get_entries()
+                .iter()
+                .any(|entry| entry.message.contains("by presence only"))
+        );
+    }
+
+    #[test]
+    fn parallel_verification_keeps_manifest_order() {
+        let dir = TempDir::new("file-check");
+        fs::create_dir_all(dir.join("OpenOCD")).unwrap();
+        for index in 0..20 {
+            fs::write(dir.join(format!("OpenOCD/{index:02}.cfg")), [index]).unwrap();
+        }
+        let mut entries = PayloadManifest::generate(dir.path()).unwrap().files;
+        entries[3].size += 1;
+        fs::remove_file(dir.join("OpenOCD/11.cfg")).unwrap();
+
+        let status = Arc::new(Mutex::new(CheckStatus::NotStarted));
+        let states = verify_entries(&entries, dir.path(), &status);
+
+        for (index, state) in states.into_iter().enumerate() {
+            let expected = match index {
+                3 => PayloadState::Corrupt,
+                11 => PayloadState::Missing,
+                _ => PayloadState::Intact,
+            };
+            assert_eq!(state, expected, "{}", entries[index].path);
+        }
+    }
 }
@@ -0,0 +1,230 @@
+use crate::utils::firmware_manifest::sha256_reader;
+use flate2::read::GzDecoder;
+use std::fs::{self, File};
+use std::io::{self, Cursor, Read};
+use std::path::{Path, PathBuf};
+use zip::ZipArchive;
+
+/// Larger images are refused, so a damaged or hostile archive cannot expand
+/// into gigabytes. The biggest supported flash (100T) holds far less.
+const MAX_IMAGE_SIZE: u64 = 64 * 1024 * 1024;
+/// Separates the archive from the image inside it in listings.
+const MEMBER_SEPARATOR: &str = " › ";
+
+/// Where the bytes of a listed firmware image are stored. An image inside a
+/// zip is listed under the virtual path `<archive>.zip/<entry>`.
+enum ImageSource<'a> {
+    File(&'a Path),
+    Gzip(&'a Path),
+    Zip { archive: &'a Path, entry: String },
+}
+
+impl<'a> ImageSource<'a> {
+    fn locate(path: &'a Path) -> Self {
+        if let Some(archive) = path
+            .ancestors()
+            .skip(1)
+            .find(|ancestor| has_extension(ancestor, "zip") && ancestor.is_file())
+            && let Ok(inner) = path.strip_prefix(archive)
+        {
+            let entry = inner
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            return Self::Zip { archive, entry };
+        }
+        if is_gzip_image(path) {
+            Self::Gzip(path)
+        } else {
+            Self::File(path)
+        }
+    }
+}
+
+/// True for files whose images are listed individually: `.zip` archives and
+/// gzip-compressed images (`.bin.gz`).
+pub fn is_archive(path: &Path) -> bool {
+    has_extension(path, "zip") || is_gzip_image(path)
+}
+
+/// True when `path` names an image inside a zip rather than a file on disk.
+pub fn is_archive_member(path: &Path) -> bool {
+    matches!(ImageSource::locate(path), ImageSource::Zip { .. })
+}
+
+/// The file on disk that holds the image: the archive for a zip member,
+/// otherwise `path` itself.
+pub fn backing_file(path: &Path) -> &Path {
+    match ImageSource::locate(path) {
+        ImageSource::Zip { archive, .. } => archive,
+        ImageSource::File(path) | ImageSource::Gzip(path) => path,
+    }
+}
+
+/// The `.bin` images an archive holds, as paths `read_image` understands.
+pub fn list_images(archive: &Path) -> io::Result<Vec<PathBuf>> {
+    if is_gzip_image(archive) {
+        return Ok(vec![archive.to_path_buf()]);
+    }
+
+    let mut zip = ZipArchive::new(File::open(archive)?).map_err(io::Error::other)?;
+    let mut images = Vec::new();
+    for index in 0..zip.len() {
+        let entry = zip.by_index_raw(index).map_err(io::Error::other)?;
+        // `enclosed_name` rejects absolute and `..` entries.
+        let Some(name) = entry.enclosed_name() else {
+            continue;
+        };
+        let is_resource_fork = name
+            .components()
+            .next()
+            .is_some_and(|first| first.as_os_str() == "__MACOSX");
+        if entry.is_file() && has_extension(&name, "bin") && !is_resource_fork {
+            images.push(archive.join(name));
+        }
+    }
+    images.sort();
+    Ok(images)
+}
+
+/// Reads a firmware image, decompressing it when it comes from an archive.
+pub fn read_image(path: &Path) -> io::Result<Vec<u8>> {
+    let mut image = Vec::new();
+    open_image(path)?
+        .take(MAX_IMAGE_SIZE + 1)
+        .read_to_end(&mut image)?;
+    if image.len() as u64 > MAX_IMAGE_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "image is larger than {} MiB",
+                MAX_IMAGE_SIZE / (1024 * 1024)
+            ),
+        ));
+    }
+    Ok(image)
+}
+
+/// Writes the image at `path` to `destination` as a plain `.bin`.
+pub fn extract_image(path: &Path, destination: &Path) -> io::Result<()> {
+    fs::write(destination, read_image(path)?)
+}
+
+/// Hex SHA-256 of the image's decompressed contents.
+pub fn sha256_image(path: &Path) -> io::Result<String> {
+    sha256_reader(open_image(path)?)
+}
+
+/// How the image is named in listings, e.g. `release.zip › 75T/pcileech.bin`.
+pub fn display_name(path: &Path) -> String {
+    match ImageSource::locate(path) {
+        ImageSource::Zip { archive, entry } => format!(
+            "{}{MEMBER_SEPARATOR}{entry}",
+            archive.file_name().unwrap_or_default().to_string_lossy()
+        ),
+        ImageSource::File(path) | ImageSource::Gzip(path) => path.file_name().map_or_else(
+            || path.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        ),
+    }
+}
+
+fn open_image(path: &Path) -> io::Result<Box<dyn Read>> {
+    match ImageSource::locate(path) {
+        ImageSource::File(path) => Ok(Box::new(File::open(path)?)),
+        ImageSource::Gzip(path) => Ok(Box::new(GzDecoder::new(File::open(path)?))),
+        ImageSource::Zip { archive, entry } => {
+            let mut zip = ZipArchive::new(File::open(archive)?).map_err(io::Error::other)?;
+            let file = zip.by_name(&entry).map_err(|error| {
+                io::Error::new(io::ErrorKind::NotFound, format!("{entry}: {error}"))
+            })?;
+            // Entries borrow the archive, so the image is read out before returning.
+            let mut image = Vec::new();
+            file.take(MAX_IMAGE_SIZE + 1).read_to_end(&mut image)?;
+            Ok(Box::new(Cursor::new(image)))
+        }
+    }
+}
+
+fn is_gzip_image(path: &Path) -> bool {
+    has_extension(path, "gz")
+        && path
+            .file_stem()
+            .is_some_and(|stem| has_extension(Path::new(stem), "bin"))
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .is_some_and(|actual| actual.eq_ignore_ascii_case(extension))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::temp_dir::TempDir;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+    use zip::write::{SimpleFileOptions, ZipWriter};
+
+    fn write_zip(path: &Path, entries: &[(&str, &[u8])]) {
+        let mut zip = ZipWriter::new(File::create(path).unwrap());
+        for (name, contents) in entries {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(contents).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn zip_images_are_listed_and_extracted() {
+        let dir = TempDir::new("archive");
+        let archive = dir.join("release.zip");
+        write_zip(
+            &archive,
+            &[
+                ("README.txt", b"read me"),
+                ("75T/pcileech.bin", b"seventy-five"),
+                ("35T/pcileech.bin", b"thirty-five"),
+                ("__MACOSX/75T/._pcileech.bin", b"fork"),
+            ],
+        );
+
+        let images = list_images(&archive).unwrap();
+        assert_eq!(
+            images,
+            [
+                archive.join("35T").join("pcileech.bin"),
+                archive.join("75T").join("pcileech.bin")
+            ]
+        );
+        let image = &images[1];
+        assert!(is_archive_member(image));
+        assert_eq!(backing_file(image), archive);
+        assert_eq!(display_name(image), "release.zip › 75T/pcileech.bin");
+
+        let destination = dir.join("FIRMWARE.bin");
+        extract_image(image, &destination).unwrap();
+        assert_eq!(fs::read(&destination).unwrap(), b"seventy-five");
+    }
+
+    #[test]
+    fn gzip_images_are_decompressed() {
+        let dir = TempDir::new("archive");
+        let path = dir.join("card.bin.gz");
+        let mut encoder = GzEncoder::new(File::create(&path).unwrap(), Compression::default());
+        encoder.write_all(b"compressed image").unwrap();
+        encoder.finish().unwrap();
+
+        assert!(is_archive(&path));
+        assert!(!is_archive(&dir.join("logs.gz")));
+        assert_eq!(list_images(&path).unwrap(), std::slice::from_ref(&path));
+        assert!(!is_archive_member(&path));
+        assert_eq!(read_image(&path).unwrap(), b"compressed image");
+        assert_eq!(
+            sha256_image(&path).unwrap(),
+            sha256_reader(&b"compressed image"[..]).unwrap()
+        );
+    }
+}
@@ -1,12 +1,39 @@
 use crate::device_programmer::TEMP_FIRMWARE_FILE;
+use crate::utils::firmware_archive;
+use crate::utils::firmware_hasher::FirmwareHasher;
+use crate::utils::firmware_locations::{FirmwareLocations, IgnorePatterns, SearchRoot};
+use crate::utils::firmware_manifest::{FirmwareMetadata, FolderManifest};
 use crate::utils::logger::Logger;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// Firmware found under one search root at its last scan.
+#[derive(Debug, Default)]
+struct DirListing {
+    files: Vec<PathBuf>,
+    metadata: HashMap<PathBuf, FirmwareMetadata>,
+}
+
 pub struct FirmwareManager {
+    builtin_dirs: Vec<PathBuf>,
+    /// The built-in folders followed by the user's search locations.
+    search_roots: Vec<SearchRoot>,
+    ignore: IgnorePatterns,
+    listings: HashMap<PathBuf, DirListing>,
+    /// Files the user picked or dropped onto the window, listed wherever they are.
+    picked: DirListing,
     firmware_files: Vec<PathBuf>,
+    metadata: HashMap<PathBuf, FirmwareMetadata>,
+    hasher: FirmwareHasher,
+    /// Listed file to the other files with identical content it stands for.
+    identical_copies: HashMap<PathBuf, Vec<PathBuf>>,
+    /// Manifest problems already logged, so rescans do not repeat them.
+    reported_warnings: HashSet<String>,
     selected_index: Option<usize>,
+    /// Selected when listed and nothing else is, e.g. the last firmware used.
+    preferred: Option<PathBuf>,
     scan_count: usize,
     logger: Logger,
     cleanup_enabled: bool,
@@ -14,37 +41,114 @@ pub struct FirmwareManager {
 
 impl FirmwareManager {
     pub fn new() -> Self {
+        let exe_path = env::current_exe().unwrap_or_else(|_| PathBuf::from("."));
+        let exe_dir = exe_path.parent().unwrap_or_else(|| Path::new("."));
+
+        let builtin_dirs = Self::create_search_dirs(exe_dir);
+        let logger = Logger::new("FirmwareDiscovery");
         Self {
+            search_roots: builtin_dirs.iter().cloned().map(SearchRoot::flat).collect(),
+            builtin_dirs,
+            ignore: IgnorePatterns::default(),
+            listings: HashMap::new(),
+            picked: DirListing::default(),
             firmware_files: Vec::new(),
+            metadata: HashMap::new(),
+            hasher: FirmwareHasher::start(logger.clone()),
+            identical_copies: HashMap::new(),
+            reported_warnings: HashSet::new(),
             selected_index: None,
+            preferred: None,
             scan_count: 0,
-            logger: Logger::new("FirmwareDiscovery"),
+            logger,
             cleanup_enabled: false,
         }
     }
 
-    pub fn scan_firmware_files(&mut self) {
+    /// Searches the user's locations after the built-in folders. Call
+    /// `scan_firmware_files` afterwards to list what they contain.
+    pub fn set_locations(&mut self, locations: &FirmwareLocations) {
+        self.search_roots = self
+            .builtin_dirs
+            .iter()
+            .cloned()
+            .map(SearchRoot::flat)
+            .chain(locations.roots().iter().cloned())
+            .collect();
+        self.ignore = IgnorePatterns::new(locations.ignore());
+        self.listings
+            .retain(|dir, _| self.search_roots.iter().any(|root| &root.path == dir));
+    }
+
+    /// Lists a firmware file from outside the search locations and selects
+    /// it; for an archive, every image inside is listed and the first one
+    /// selected. Returns false when `path` holds no firmware image.
+    pub fn add_file(&mut self, path: PathBuf) -> bool {
+        let images = if is_firmware_image(&path) {
+            vec![path]
+        } else if firmware_archive::is_archive(&path) {
+            match firmware_archive::list_images(&path) {
+                Ok(images) => images,
+                Err(e) => {
+                    self.logger
+                        .warning(format!("Ignoring archive {}: {e}", path.display()));
+                    return false;
+                }
+            }
+        } else {
+            return false;
+        };
+        let Some(first) = images.first().cloned() else {
+            return false;
+        };
+
+        for image in images {
+            if !self.picked.files.contains(&image) {
+                self.picked.files.push(image);
+            }
+        }
+        self.refresh_picked();
+        self.refresh_firmware_list(Some(&first));
+        self.selected_index.is_some()
+    }
+
+    /// Removes the copy a previous flash left behind, so it is not listed.
+    pub fn remove_temp_firmware(&self) {
         if let Err(e) = fs::remove_file(TEMP_FIRMWARE_FILE) {
             self.logger.info(format!(
                 "Note: Could not remove previous temp firmware file: {e}"
             ));
         }
+    }
 
-        let previous_selection = self.get_selected_firmware().cloned();
-        self.firmware_files.clear();
-
-        let exe_path = env::current_exe().unwrap_or_else(|_| PathBuf::from("."));
-        let exe_dir = exe_path.parent().unwrap_or_else(|| Path::new("."));
-
-        let search_dirs = self.create_search_dirs(exe_dir);
-
+    /// Rescans every search root
+    pub fn scan_firmware_files(&mut self) {
         #[cfg(debug_assertions)]
-        self.debug_print_search_dirs(&search_dirs);
+        self.debug_print_search_dirs();
+
+        let search_dirs: Vec<PathBuf> = self
+            .search_roots
+            .iter()
+            .map(|root| root.path.clone())
+            .collect();
+        self.rescan_dirs(&search_dirs);
+    }
 
-        self.collect_firmware_files(&search_dirs);
+    /// Rescans only the roots at `dirs`, keeping what the others last listed
+    pub fn rescan_dirs(&mut self, dirs: &[PathBuf]) {
+        let mut warnings = Vec::new();
+        for dir in dirs {
+            let Some(root) = self.search_roots.iter().find(|root| &root.path == dir) else {
+                continue;
+            };
+            let listing = Self::read_listing(root, &self.ignore, &mut warnings);
+            self.listings.insert(dir.clone(), listing);
+        }
+        self.report_warnings(warnings);
+        self.refresh_picked();
 
-        self.deduplicate_firmware_files();
-        self.restore_selection(previous_selection.as_deref());
+        let previous_selection = self.get_selected_firmware().cloned();
+        self.refresh_firmware_list(previous_selection.as_deref());
 
         self.scan_count += 1;
 
@@ -52,11 +156,53 @@ impl FirmwareManager {
         self.debug_print_results();
     }
 
+    /// Groups identical copies by the hashes finished since the last call.
+    /// Returns true when the list was updated.
+    pub fn receive_hashes(&mut self) -> bool {
+        if !self.hasher.receive() {
+            return false;
+        }
+        let previous_selection = self.get_selected_firmware().cloned();
+        self.refresh_firmware_list(previous_selection.as_deref());
+        true
+    }
+
+    /// True while listed files are still being hashed, so copies among them
+    /// may not be grouped yet
+    pub fn is_hashing(&self) -> bool {
+        self.hasher.is_hashing()
+    }
+
+    /// Returns the roots searched for firmware, in priority order
+    pub fn search_roots(&self) -> &[SearchRoot] {
+        &self.search_roots
+    }
+
     /// Returns a slice of all found firmware files
     pub fn get_firmware_files(&self) -> &[PathBuf] {
         &self.firmware_files
     }
 
+    /// Returns what the manifests declare about a discovered firmware file
+    pub fn metadata_for(&self, path: &Path) -> Option<&FirmwareMetadata> {
+        self.metadata.get(path)
+    }
+
+    /// Returns the unlisted files with the same content as a listed one
+    pub fn identical_copies(&self, path: &Path) -> &[PathBuf] {
+        self.identical_copies
+            .get(path)
+            .map_or(&[], |copies| copies.as_slice())
+    }
+
+    /// Returns true when another listed file has the same file name, so the
+    /// folder is needed to tell them apart
+    pub fn shares_name(&self, path: &Path) -> bool {
+        self.firmware_files
+            .iter()
+            .any(|other| other != path && other.file_name() == path.file_name())
+    }
+
     /// Selects a firmware file by index and returns it if valid
     pub fn select_firmware(&mut self, index: usize) -> Option<PathBuf> {
         if index < self.firmware_files.len() {
@@ -77,7 +223,7 @@ impl FirmwareManager {
         self.scan_count
     }
 
-    fn create_search_dirs(&self, exe_dir: &Path) -> Vec<PathBuf> {
+    fn create_search_dirs(exe_dir: &Path) -> Vec<PathBuf> {
         let mut search_dirs = vec![
             PathBuf::from("."),         // Current directory
             exe_dir.to_path_buf(),      // Executable directory
@@ -95,41 +241,181 @@ impl FirmwareManager {
         search_dirs
     }
 
-    fn collect_firmware_files(&mut self, search_dirs: &[PathBuf]) {
-        for dir in search_dirs {
-            if let Ok(entries) = fs::read_dir(dir) {
-                for entry in entries.filter_map(Result::ok) {
-                    let path = entry.path();
-                    if path.is_file() && path.extension().is_some_and(|ext| ext == "bin") {
-                        #[cfg(debug_assertions)]
-                        println!("Found firmware: {}", path.display());
+    fn read_listing(
+        root: &SearchRoot,
+        ignore: &IgnorePatterns,
+        warnings: &mut Vec<String>,
+    ) -> DirListing {
+        let mut listing = DirListing::default();
+        Self::read_dir_into(&root.path, root.depth(), ignore, &mut listing, warnings);
+        listing
+    }
 
-                        self.firmware_files.push(path);
-                    }
+    fn read_dir_into(
+        dir: &Path,
+        depth_left: usize,
+        ignore: &IgnorePatterns,
+        listing: &mut DirListing,
+        warnings: &mut Vec<String>,
+    ) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+
+        let folder = FolderManifest::load(dir, warnings);
+        let mut subdirs = Vec::new();
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            if ignore.matches(&path) {
+                continue;
+            }
+            // Symlinked folders are not followed so a link cycle cannot trap the scan.
+            if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+                subdirs.push(path);
+            } else if is_firmware_image(&path) {
+                #[cfg(debug_assertions)]
+                println!("Found firmware: {}", path.display());
+
+                if let Some(metadata) = FirmwareMetadata::for_image(&path, &folder, warnings) {
+                    listing.metadata.insert(path.clone(), metadata);
+                }
+                listing.files.push(path);
+            } else if firmware_archive::is_archive(&path) {
+                match firmware_archive::list_images(&path) {
+                    Ok(images) => listing
+                        .files
+                        .extend(images.into_iter().filter(|image| !ignore.matches(image))),
+                    Err(e) => warnings.push(format!("Ignoring archive {}: {e}", path.display())),
                 }
             }
         }
+
+        if depth_left > 0 {
+            subdirs.sort();
+            for subdir in subdirs {
+                Self::read_dir_into(&subdir, depth_left - 1, ignore, listing, warnings);
+            }
+        }
+    }
+
+    /// Re-reads the metadata of picked files and drops those that are gone.
+    fn refresh_picked(&mut self) {
+        let mut warnings = Vec::new();
+        self.picked
+            .files
+            .retain(|path| firmware_archive::backing_file(path).is_file());
+        self.picked.metadata.clear();
+        for path in &self.picked.files {
+            let dir = path.parent().unwrap_or_else(|| Path::new("."));
+            let folder = FolderManifest::load(dir, &mut warnings);
+            if let Some(metadata) = FirmwareMetadata::for_image(path, &folder, &mut warnings) {
+                self.picked.metadata.insert(path.clone(), metadata);
+            }
+        }
+        self.report_warnings(warnings);
+    }
+
+    fn refresh_firmware_list(&mut self, previous_selection: Option<&Path>) {
+        self.collect_firmware_files();
+        self.deduplicate_firmware_files();
+        self.restore_selection(previous_selection);
+    }
+
+    fn collect_firmware_files(&mut self) {
+        self.firmware_files.clear();
+        self.metadata.clear();
+        // Picked files come first so they stand for any identical copies the
+        // search locations hold.
+        for listing in std::iter::once(&self.picked).chain(
+            self.search_roots
+                .iter()
+                .filter_map(|root| self.listings.get(&root.path)),
+        ) {
+            self.firmware_files.extend(listing.files.iter().cloned());
+            self.metadata.extend(
+                listing
+                    .metadata
+                    .iter()
+                    .map(|(path, metadata)| (path.clone(), metadata.clone())),
+            );
+        }
+    }
+
+    fn report_warnings(&mut self, warnings: Vec<String>) {
+        for warning in warnings {
+            if self.reported_warnings.insert(warning.clone()) {
+                self.logger.warning(warning);
+            }
+        }
     }
 
+    /// Keeps one entry per distinct content, in search priority order. The
+    /// same file reached through two roots (e.g. `.` and the executable
+    /// directory) is dropped; a copy elsewhere is remembered under the entry.
+    /// Files not hashed yet are listed on their own until their hash arrives.
     fn deduplicate_firmware_files(&mut self) {
-        // Sort by filename for consistent ordering and deduplication
+        let files = std::mem::take(&mut self.firmware_files);
+        self.identical_copies.clear();
+        let mut seen_files = HashSet::new();
+        let mut by_content: HashMap<String, PathBuf> = HashMap::new();
+
+        for path in files {
+            let canonical = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+            if !seen_files.insert(canonical) {
+                continue;
+            }
+            // Unreadable files are listed on their own; flashing reports why.
+            let Some(sha256) = self.hasher.hash(&path) else {
+                self.firmware_files.push(path);
+                continue;
+            };
+            match by_content.get(&sha256) {
+                Some(listed) => self
+                    .identical_copies
+                    .entry(listed.clone())
+                    .or_default()
+                    .push(path),
+                None => {
+                    by_content.insert(sha256, path.clone());
+                    self.firmware_files.push(path);
+                }
+            }
+        }
+
+        let copies = &self.identical_copies;
+        let firmware_files = &self.firmware_files;
+        self.hasher.retain(|path| {
+            firmware_files.iter().any(|file| file == path)
+                || copies.values().flatten().any(|copy| copy == path)
+        });
+
+        // Sort by filename, then folder, for a stable order
         self.firmware_files.sort_by(|a, b| {
             a.file_name()
                 .unwrap_or_default()
                 .cmp(b.file_name().unwrap_or_default())
+                .then_with(|| a.cmp(b))
         });
+    }
 
-        // Remove duplicates based on filename
-        self.firmware_files.dedup_by(|a, b| {
-            a.file_name().unwrap_or_default() == b.file_name().unwrap_or_default()
-        });
+    /// Selects `path` once a scan lists it, unless another file is selected.
+    pub fn set_preferred(&mut self, path: Option<PathBuf>) {
+        self.preferred = path;
     }
 
     fn restore_selection(&mut self, previous_selection: Option<&Path>) {
+        let previous_selection = previous_selection.or(self.preferred.as_deref());
         self.selected_index = previous_selection.and_then(|selected| {
+            // A selected file that became a copy of another entry keeps that
+            // entry selected, since it flashes the same image.
+            let listed = self
+                .identical_copies
+                .iter()
+                .find(|(_, copies)| copies.iter().any(|copy| copy == selected))
+                .map_or(selected, |(listed, _)| listed.as_path());
             self.firmware_files
                 .iter()
-                .position(|candidate| candidate == selected)
+                .position(|candidate| candidate == listed)
         });
 
         // A single discovered file is unambiguous. With multiple files, never
@@ -140,11 +426,12 @@ impl FirmwareManager {
     }
 
     #[cfg(debug_assertions)]
-    fn debug_print_search_dirs(&self, search_dirs: &[PathBuf]) {
+    fn debug_print_search_dirs(&self) {
         println!("Searching for firmware in:");
-        for dir in search_dirs {
-            if let Ok(canonical) = fs::canonicalize(dir) {
-                println!("  - {}", canonical.display());
+        for root in &self.search_roots {
+            let dir = fs::canonicalize(&root.path).unwrap_or_else(|_| root.path.clone());
+            if root.recursive {
+                println!("  - {} (depth {})", dir.display(), root.depth());
             } else {
                 println!("  - {}", dir.display());
             }
@@ -168,9 +455,25 @@ impl FirmwareManager {
     }
 }
 
+fn is_firmware_image(path: &Path) -> bool {
+    path.is_file() && path.extension().is_some_and(|ext| ext == "bin")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::temp_dir::TempDir;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    /// Applies hashes as they arrive until none are outstanding.
+    fn wait_for_hashes(manager: &mut FirmwareManager) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while manager.is_hashing() && Instant::now() < deadline {
+            manager.receive_hashes();
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
 
     #[test]
     fn rescan_preserves_selection_by_exact_path_after_reordering() {
@@ -217,6 +520,24 @@ mod tests {
         assert!(manager.selected_index.is_none());
     }
 
+    #[test]
+    fn preferred_firmware_is_selected_once_listed() {
+        let mut manager = FirmwareManager::new();
+        manager.set_preferred(Some(PathBuf::from("firmware/b.bin")));
+        manager.firmware_files = vec![
+            PathBuf::from("firmware/a.bin"),
+            PathBuf::from("firmware/c.bin"),
+        ];
+        manager.restore_selection(None);
+        assert!(manager.selected_index.is_none());
+
+        manager
+            .firmware_files
+            .insert(1, PathBuf::from("firmware/b.bin"));
+        manager.restore_selection(None);
+        assert_eq!(manager.selected_index, Some(1));
+    }
+
     #[test]
     fn rescan_auto_selects_only_remaining_file_after_removal() {
         let mut manager = FirmwareManager::new();
@@ -236,4 +557,177 @@ mod tests {
         );
         assert_eq!(manager.selected_index, Some(0));
     }
+
+    #[test]
+    fn rescanning_one_directory_keeps_the_others_listing() {
+        let temp_first = TempDir::new("discovery-first");
+        let first = temp_first.path().to_path_buf();
+        let temp_second = TempDir::new("discovery-second");
+        let second = temp_second.path().to_path_buf();
+        fs::write(first.join("a.bin"), b"a").unwrap();
+        fs::write(second.join("b.bin"), b"b").unwrap();
+        let mut manager = FirmwareManager::new();
+        manager.search_roots = vec![SearchRoot::flat(&first), SearchRoot::flat(&second)];
+        manager.scan_firmware_files();
+
+        fs::remove_file(second.join("b.bin")).unwrap();
+        fs::write(first.join("c.bin"), b"c").unwrap();
+        manager.rescan_dirs(std::slice::from_ref(&first));
+
+        assert_eq!(
+            manager.get_firmware_files(),
+            [
+                first.join("a.bin"),
+                second.join("b.bin"),
+                first.join("c.bin")
+            ]
+        );
+
+        manager.rescan_dirs(std::slice::from_ref(&second));
+        assert_eq!(
+            manager.get_firmware_files(),
+            [first.join("a.bin"), first.join("c.bin")]
+        );
+    }
+
+    #[test]
+    fn recursive_roots_respect_depth_and_ignore_patterns() {
+        let temp_root = TempDir::new("discovery-recursive");
+        let root = temp_root.path().to_path_buf();
+        for dir in ["75T", "75T/nightly", "75T/nightly/deep", "old-builds"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for file in [
+            "top.bin",
+            "75T/card.bin",
+            "75T/card_debug.bin",
+            "75T/nightly/nightly.bin",
+            "75T/nightly/deep/deep.bin",
+            "old-builds/old.bin",
+        ] {
+            fs::write(root.join(file), file).unwrap();
+        }
+        let logger = Logger::new("FirmwareDiscoveryTest");
+        let mut locations = FirmwareLocations::in_memory(logger);
+        locations.add_root(root.clone());
+        locations.set_recursive(0, true, 2);
+        locations.set_ignore("old-*\n*_debug.bin");
+
+        let mut manager = FirmwareManager::new();
+        manager.builtin_dirs.clear();
+        manager.set_locations(&locations);
+        manager.scan_firmware_files();
+
+        assert_eq!(
+            manager.get_firmware_files(),
+            [
+                root.join("75T/card.bin"),
+                root.join("75T/nightly/nightly.bin"),
+                root.join("top.bin")
+            ]
+        );
+    }
+
+    #[test]
+    fn picked_files_are_listed_and_selected() {
+        let temp_dir = TempDir::new("discovery-picked");
+        let dir = temp_dir.path().to_path_buf();
+        let image = dir.join("picked.bin");
+        fs::write(&image, b"image").unwrap();
+        fs::write(dir.join("notes.txt"), b"notes").unwrap();
+        let mut manager = FirmwareManager::new();
+        manager.builtin_dirs.clear();
+        manager.search_roots.clear();
+
+        assert!(!manager.add_file(dir.join("notes.txt")));
+        assert!(manager.add_file(image.clone()));
+        assert_eq!(manager.get_selected_firmware(), Some(&image));
+
+        fs::remove_file(&image).unwrap();
+        manager.scan_firmware_files();
+        assert!(manager.get_firmware_files().is_empty());
+    }
+
+    #[test]
+    fn same_named_builds_are_kept_and_identical_content_is_grouped() {
+        let temp_firmware = TempDir::new("discovery-firmware");
+        let firmware = temp_firmware.path().to_path_buf();
+        let temp_fw = TempDir::new("discovery-fw");
+        let fw = temp_fw.path().to_path_buf();
+        let temp_backup = TempDir::new("discovery-backup");
+        let backup = temp_backup.path().to_path_buf();
+        fs::write(firmware.join("pcileech.bin"), b"build one").unwrap();
+        fs::write(fw.join("pcileech.bin"), b"build two!").unwrap();
+        fs::write(backup.join("pcileech-copy.bin"), b"build one").unwrap();
+        let mut manager = FirmwareManager::new();
+        manager.search_roots = [&firmware, &fw, &backup]
+            .into_iter()
+            .map(SearchRoot::flat)
+            .collect();
+        manager.scan_firmware_files();
+
+        // Listed at once, before any content is hashed.
+        assert_eq!(manager.get_firmware_files().len(), 3);
+        wait_for_hashes(&mut manager);
+
+        let first = firmware.join("pcileech.bin");
+        let second = fw.join("pcileech.bin");
+        let mut expected = vec![first.clone(), second.clone()];
+        expected.sort();
+        assert_eq!(manager.get_firmware_files(), expected);
+        assert!(manager.shares_name(&first));
+        assert_eq!(
+            manager.identical_copies(&first),
+            [backup.join("pcileech-copy.bin")]
+        );
+        assert!(manager.identical_copies(&second).is_empty());
+
+        fs::write(fw.join("pcileech.bin"), b"build one").unwrap();
+        manager.select_firmware(
+            manager
+                .get_firmware_files()
+                .iter()
+                .position(|p| p == &second)
+                .unwrap(),
+        );
+        manager.rescan_dirs(std::slice::from_ref(&fw));
+        wait_for_hashes(&mut manager);
+
+        assert_eq!(manager.get_firmware_files(), std::slice::from_ref(&first));
+        assert_eq!(manager.get_selected_firmware(), Some(&first));
+        assert!(!manager.shares_name(&first));
+    }
+
+    #[test]
+    fn images_inside_archives_are_listed() {
+        use std::io::Write;
+        use zip::write::{SimpleFileOptions, ZipWriter};
+
+        let temp_dir = TempDir::new("discovery-archives");
+        let dir = temp_dir.path().to_path_buf();
+        let archive = dir.join("release.zip");
+        let mut zip = ZipWriter::new(fs::File::create(&archive).unwrap());
+        for (name, contents) in [
+            ("readme.txt", &b"notes"[..]),
+            ("75T/pcileech.bin", b"seventy-five"),
+        ] {
+            zip.start_file(name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(contents).unwrap();
+        }
+        zip.finish().unwrap();
+        fs::write(dir.join("pcileech.bin"), b"seventy-five").unwrap();
+        let mut manager = FirmwareManager::new();
+        manager.search_roots = vec![SearchRoot::flat(&dir)];
+        manager.scan_firmware_files();
+        wait_for_hashes(&mut manager);
+
+        let member = archive.join("75T").join("pcileech.bin");
+        let listed = manager.get_firmware_files();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(manager.identical_copies(&listed[0]).len(), 1);
+
+        fs::remove_file(dir.join("pcileech.bin")).unwrap();
+        manager.scan_firmware_files();
+        assert_eq!(manager.get_firmware_files(), std::slice::from_ref(&member));
+    }
 }
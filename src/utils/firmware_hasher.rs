@@ -0,0 +1,167 @@
+use crate::utils::firmware_archive;
+use crate::utils::logger::Logger;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::SystemTime;
+
+/// The size and modification time of the file holding an image. A hash is
+/// reused only while both stay the same.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl FileStamp {
+    fn read(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(firmware_archive::backing_file(path)).ok()?;
+        Some(Self {
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+        })
+    }
+}
+
+#[derive(Debug)]
+struct HashResult {
+    path: PathBuf,
+    /// `None` when the file was gone before it could be hashed.
+    stamp: Option<FileStamp>,
+    /// `None` when the image could not be read.
+    sha256: Option<String>,
+}
+
+/// SHA-256 of firmware images, computed on a worker thread so a scan never
+/// waits on the contents of large archives or network folders.
+pub struct FirmwareHasher {
+    requests: Sender<PathBuf>,
+    results: Receiver<HashResult>,
+    hashes: HashMap<PathBuf, (FileStamp, Option<String>)>,
+    pending: HashSet<PathBuf>,
+}
+
+impl FirmwareHasher {
+    pub fn start(logger: Logger) -> Self {
+        let (requests, queued) = mpsc::channel::<PathBuf>();
+        let (finished, results) = mpsc::channel();
+        // The worker ends once the hasher, and with it the request sender, is dropped.
+        thread::spawn(move || {
+            for path in queued {
+                let stamp = FileStamp::read(&path);
+                let sha256 = stamp.and_then(|_| {
+                    firmware_archive::sha256_image(&path)
+                        .map_err(|e| {
+                            logger.debug(format!("Could not hash {}: {e}", path.display()))
+                        })
+                        .ok()
+                });
+                let result = HashResult {
+                    path,
+                    stamp,
+                    sha256,
+                };
+                if finished.send(result).is_err() {
+                    return;
+                }
+            }
+        });
+
+        Self {
+            requests,
+            results,
+            hashes: HashMap::new(),
+            pending: HashSet::new(),
+        }
+    }
+
+    /// The hash of `path` if it is known for the file as it is now. Otherwise
+    /// the file is queued for hashing and `None` returned until
+    /// `receive` has picked up the result.
+    pub fn hash(&mut self, path: &Path) -> Option<String> {
+        let stamp = FileStamp::read(path)?;
+        if let Some((hashed_stamp, sha256)) = self.hashes.get(path)
+            && *hashed_stamp == stamp
+        {
+            return sha256.clone();
+        }
+        if self.pending.insert(path.to_path_buf()) {
+            let _ = self.requests.send(path.to_path_buf());
+        }
+        None
+    }
+
+    /// Collects the hashes finished since the last call. Returns true when
+    /// there were any.
+    pub fn receive(&mut self) -> bool {
+        let mut received = false;
+        while let Ok(result) = self.results.try_recv() {
+            received = true;
+            self.pending.remove(&result.path);
+            if let Some(stamp) = result.stamp {
+                self.hashes.insert(result.path, (stamp, result.sha256));
+            }
+        }
+        received
+    }
+
+    /// True while files are queued or being hashed.
+    pub fn is_hashing(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Forgets the hashes of files `keep` rejects.
+    pub fn retain(&mut self, mut keep: impl FnMut(&Path) -> bool) {
+        self.hashes.retain(|path, _| keep(path));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::temp_dir::TempDir;
+    use std::time::{Duration, Instant};
+
+    fn wait_for(hasher: &mut FirmwareHasher, path: &Path) -> Option<String> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            if let Some(sha256) = hasher.hash(path) {
+                return Some(sha256);
+            }
+            hasher.receive();
+            if !hasher.is_hashing() || Instant::now() > deadline {
+                return hasher.hash(path);
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn hashes_arrive_later_and_are_redone_when_the_file_changes() {
+        let dir = TempDir::new("firmware-hasher");
+        let path = dir.join("card.bin");
+        fs::write(&path, b"first build").unwrap();
+        let mut hasher = FirmwareHasher::start(Logger::new("FirmwareHasherTest"));
+
+        assert_eq!(hasher.hash(&path), None);
+        assert!(hasher.is_hashing());
+        let first = wait_for(&mut hasher, &path).unwrap();
+        assert!(!hasher.is_hashing());
+
+        fs::write(&path, b"second, longer build").unwrap();
+        assert_eq!(hasher.hash(&path), None);
+        let second = wait_for(&mut hasher, &path).unwrap();
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn missing_files_are_not_queued() {
+        let dir = TempDir::new("firmware-hasher");
+        let mut hasher = FirmwareHasher::start(Logger::new("FirmwareHasherTest"));
+
+        assert_eq!(hasher.hash(&dir.join("gone.bin")), None);
+        assert!(!hasher.is_hashing());
+    }
+}
@@ -0,0 +1,264 @@
+use crate::utils::app_dirs;
+use crate::utils::json_store::JsonFormat;
+use crate::utils::logger::Logger;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const LOCATIONS_FILE: &str = "firmware_locations.json";
+const LOCATIONS_FORMAT: JsonFormat = JsonFormat {
+    label: "Firmware locations",
+    version: 1,
+    fallback: "searching the built-in folders only",
+};
+pub const DEFAULT_SCAN_DEPTH: usize = 4;
+pub const MAX_SCAN_DEPTH: usize = 16;
+
+/// A folder searched for firmware. Recursive roots also search subfolders
+/// down to `max_depth` levels below the root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchRoot {
+    pub path: PathBuf,
+    #[serde(default)]
+    pub recursive: bool,
+    #[serde(default = "default_scan_depth")]
+    pub max_depth: usize,
+}
+
+fn default_scan_depth() -> usize {
+    DEFAULT_SCAN_DEPTH
+}
+
+impl SearchRoot {
+    /// A root whose subfolders are not searched.
+    pub fn flat(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            recursive: false,
+            max_depth: DEFAULT_SCAN_DEPTH,
+        }
+    }
+
+    /// How many folder levels below the root are searched.
+    pub fn depth(&self) -> usize {
+        if self.recursive {
+            self.max_depth.min(MAX_SCAN_DEPTH)
+        } else {
+            0
+        }
+    }
+}
+
+/// Glob patterns (`*` and `?`) matched case-insensitively against the name of
+/// every file and folder met while scanning, e.g. `old*` or `*_debug.bin`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IgnorePatterns {
+    patterns: Vec<String>,
+}
+
+impl IgnorePatterns {
+    pub fn new(patterns: &[String]) -> Self {
+        Self {
+            patterns: patterns
+                .iter()
+                .map(|pattern| pattern.trim().to_lowercase())
+                .filter(|pattern| !pattern.is_empty())
+                .collect(),
+        }
+    }
+
+    pub fn matches(&self, path: &Path) -> bool {
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            return false;
+        };
+        let name = name.to_lowercase();
+        self.patterns
+            .iter()
+            .any(|pattern| glob_matches(pattern.as_bytes(), name.as_bytes()))
+    }
+}
+
+fn glob_matches(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // Where the last `*` was seen and the text position it currently covers up to.
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&byte) if byte == b'?' || byte == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&byte| byte == b'*')
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LocationsFile {
+    #[serde(default)]
+    roots: Vec<SearchRoot>,
+    #[serde(default)]
+    ignore: Vec<String>,
+}
+
+/// Firmware search roots and ignore patterns the user added, searched on top
+/// of the built-in folders.
+pub struct FirmwareLocations {
+    path: Option<PathBuf>,
+    roots: Vec<SearchRoot>,
+    ignore: Vec<String>,
+    logger: Logger,
+}
+
+impl FirmwareLocations {
+    /// Opens the locations in the per-user data directory, falling back to the
+    /// working directory when no data directory is available.
+    pub fn open_default(logger: Logger) -> Self {
+        let path = app_dirs::data_dir()
+            .map(|dir| dir.join(LOCATIONS_FILE))
+            .unwrap_or_else(|| PathBuf::from(LOCATIONS_FILE));
+        Self::open(path, logger)
+    }
+
+    pub fn open(path: PathBuf, logger: Logger) -> Self {
+        let (roots, ignore) = LOCATIONS_FORMAT
+            .load::<LocationsFile>(&path, &logger)
+            .map(|file| (file.roots, file.ignore))
+            .unwrap_or_default();
+        Self {
+            path: Some(path),
+            roots,
+            ignore,
+            logger,
+        }
+    }
+
+    /// Locations that are never written to disk.
+    #[cfg(test)]
+    pub fn in_memory(logger: Logger) -> Self {
+        Self {
+            path: None,
+            roots: Vec::new(),
+            ignore: Vec::new(),
+            logger,
+        }
+    }
+
+    pub fn roots(&self) -> &[SearchRoot] {
+        &self.roots
+    }
+
+    pub fn ignore(&self) -> &[String] {
+        &self.ignore
+    }
+
+    /// Adds a flat root unless the folder is already listed.
+    pub fn add_root(&mut self, path: PathBuf) -> bool {
+        if self.roots.iter().any(|root| root.path == path) {
+            return false;
+        }
+        self.roots.push(SearchRoot::flat(path));
+        self.save();
+        true
+    }
+
+    pub fn remove_root(&mut self, index: usize) -> bool {
+        if index >= self.roots.len() {
+            return false;
+        }
+        self.roots.remove(index);
+        self.save();
+        true
+    }
+
+    pub fn set_recursive(&mut self, index: usize, recursive: bool, max_depth: usize) {
+        let Some(root) = self.roots.get_mut(index) else {
+            return;
+        };
+        root.recursive = recursive;
+        root.max_depth = max_depth.clamp(1, MAX_SCAN_DEPTH);
+        self.save();
+    }
+
+    /// Replaces the ignore patterns with the non-empty lines of `text`.
+    pub fn set_ignore(&mut self, text: &str) {
+        self.ignore = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect();
+        self.save();
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let file = LocationsFile {
+            roots: self.roots.clone(),
+            ignore: self.ignore.clone(),
+        };
+        if let Err(error) = LOCATIONS_FORMAT.save(path, &file) {
+            self.logger.error(format!(
+                "Failed to save firmware locations to {}: {error}",
+                path.display()
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::temp_dir::TempDir;
+
+    #[test]
+    fn ignore_patterns_match_names_with_wildcards() {
+        let ignore = IgnorePatterns::new(&["old*".into(), "*_DEBUG.bin".into(), "v?".into()]);
+
+        assert!(ignore.matches(Path::new("builds/Old-2023")));
+        assert!(ignore.matches(Path::new("builds/card_debug.bin")));
+        assert!(ignore.matches(Path::new("builds/v2")));
+        assert!(!ignore.matches(Path::new("builds/v10")));
+        assert!(!ignore.matches(Path::new("builds/card.bin")));
+        assert!(!IgnorePatterns::default().matches(Path::new("old")));
+    }
+
+    #[test]
+    fn saved_roots_survive_a_restart() {
+        let dir = TempDir::new("locations");
+        let path = dir.join(LOCATIONS_FILE);
+        let logger = Logger::new("FirmwareLocationsTest");
+
+        let mut locations = FirmwareLocations::open(path.clone(), logger.clone());
+        assert!(locations.add_root(PathBuf::from("/srv/builds")));
+        assert!(!locations.add_root(PathBuf::from("/srv/builds")));
+        assert!(locations.add_root(PathBuf::from("/srv/old")));
+        locations.set_recursive(0, true, 99);
+        locations.set_ignore("archive*\n\n  *.tmp.bin ");
+        assert!(locations.remove_root(1));
+
+        let reopened = FirmwareLocations::open(path.clone(), logger);
+        assert_eq!(
+            reopened.roots(),
+            [SearchRoot {
+                path: PathBuf::from("/srv/builds"),
+                recursive: true,
+                max_depth: MAX_SCAN_DEPTH,
+            }]
+        );
+        assert_eq!(reopened.ignore(), ["archive*", "*.tmp.bin"]);
+    }
+}
@@ -0,0 +1,378 @@
+use crate::device_programmer::{AdapterKind, FlashingOption};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+const SIDECAR_EXTENSIONS: [&str; 2] = ["json", "toml"];
+const FOLDER_MANIFESTS: [&str; 2] = ["firmware.json", "firmware.toml"];
+const DENSITIES: [&str; 3] = ["35T", "75T", "100T"];
+const SHA256_HEX_LEN: usize = 64;
+
+/// What a manifest declares about one firmware image. Every field is optional.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FirmwareMetadata {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    /// One of the densities a `FlashingOption` targets, e.g. `75T`.
+    pub density: Option<&'static str>,
+    pub adapter: Option<AdapterKind>,
+    pub notes: Option<String>,
+    /// Lowercase hex digest the image must hash to before it is flashed.
+    pub sha256: Option<String>,
+    /// Why the manifest describing the image could not be read. Such an
+    /// image is not flashed, as its declared digest is unknown.
+    pub manifest_error: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RawMetadata {
+    name: Option<String>,
+    version: Option<String>,
+    density: Option<String>,
+    adapter: Option<String>,
+    #[serde(alias = "release_notes")]
+    notes: Option<String>,
+    sha256: Option<String>,
+}
+
+/// `firmware.json` / `firmware.toml` describing several images of a folder,
+/// keyed by file name under `firmware`.
+#[derive(Debug, Default, Deserialize)]
+struct RawFolderManifest {
+    #[serde(default)]
+    firmware: HashMap<String, RawMetadata>,
+}
+
+/// Metadata declared by the folder manifest of one directory.
+#[derive(Debug, Default)]
+pub struct FolderManifest {
+    entries: HashMap<String, FirmwareMetadata>,
+    /// Set when the folder manifest exists but could not be read; every image
+    /// of the folder without a sidecar then carries it.
+    error: Option<String>,
+}
+
+impl FolderManifest {
+    /// Reads the first folder manifest found in `dir`. Problems are returned
+    /// as warnings; one that leaves the manifest unreadable marks the images
+    /// of the folder as unverifiable.
+    pub fn load(dir: &Path, warnings: &mut Vec<String>) -> Self {
+        for file_name in FOLDER_MANIFESTS {
+            let path = dir.join(file_name);
+            if !path.is_file() {
+                continue;
+            }
+            let raw = match read_manifest::<RawFolderManifest>(&path) {
+                Ok(raw) => raw,
+                Err(error) => {
+                    warnings.push(error.clone());
+                    return Self {
+                        entries: HashMap::new(),
+                        error: Some(error),
+                    };
+                }
+            };
+            let entries = raw
+                .firmware
+                .into_iter()
+                .map(|(image, metadata)| {
+                    let source = format!("{} ({image})", path.display());
+                    (
+                        image,
+                        FirmwareMetadata::from_raw(metadata, &source, warnings),
+                    )
+                })
+                .collect();
+            return Self {
+                entries,
+                error: None,
+            };
+        }
+        Self::default()
+    }
+
+    fn get(&self, image: &Path) -> Option<FirmwareMetadata> {
+        if let Some(error) = &self.error {
+            return Some(FirmwareMetadata::unreadable(error.clone()));
+        }
+        let file_name = image.file_name()?.to_str()?;
+        self.entries.get(file_name).cloned()
+    }
+}
+
+impl FirmwareMetadata {
+    /// Metadata for `image`: its own sidecar (`<name>.bin.json` or
+    /// `<name>.bin.toml`) wins over an entry in the folder manifest.
+    pub fn for_image(
+        image: &Path,
+        folder: &FolderManifest,
+        warnings: &mut Vec<String>,
+    ) -> Option<Self> {
+        for path in sidecar_paths(image) {
+            if path.is_file() {
+                return Some(match read_manifest::<RawMetadata>(&path) {
+                    Ok(raw) => Self::from_raw(raw, &path.display().to_string(), warnings),
+                    Err(error) => {
+                        warnings.push(error.clone());
+                        Self::unreadable(error)
+                    }
+                });
+            }
+        }
+        folder.get(image)
+    }
+
+    fn unreadable(error: String) -> Self {
+        Self {
+            manifest_error: Some(error),
+            ..Self::default()
+        }
+    }
+
+    fn from_raw(raw: RawMetadata, source: &str, warnings: &mut Vec<String>) -> Self {
+        let density = raw.density.as_deref().and_then(|density| {
+            let parsed = parse_density(density);
+            if parsed.is_none() {
+                warnings.push(format!("{source}: unknown density `{density}`"));
+            }
+            parsed
+        });
+        let adapter = raw.adapter.as_deref().and_then(|adapter| {
+            let parsed = parse_adapter(adapter);
+            if parsed.is_none() {
+                warnings.push(format!("{source}: unknown adapter `{adapter}`"));
+            }
+            parsed
+        });
+        // A malformed digest is kept so the image fails verification instead
+        // of silently flashing unchecked.
+        let sha256 = raw.sha256.map(|digest| digest.trim().to_ascii_lowercase());
+        if let Some(digest) = &sha256
+            && !is_sha256_hex(digest)
+        {
+            warnings.push(format!("{source}: `{digest}` is not a SHA-256 digest"));
+        }
+
+        Self {
+            name: non_empty(raw.name),
+            version: non_empty(raw.version),
+            density,
+            adapter,
+            notes: non_empty(raw.notes),
+            sha256,
+            manifest_error: None,
+        }
+    }
+
+    /// True when the option is a flash profile this image was built for.
+    pub fn accepts(&self, option: &FlashingOption) -> bool {
+        self.density
+            .is_none_or(|density| option.density() == Some(density))
+            && self
+                .adapter
+                .is_none_or(|adapter| option.adapter_kind() == adapter)
+    }
+
+    /// Version, density and adapter joined for a one-line listing.
+    pub fn summary(&self) -> String {
+        let version = self.version.as_ref().map(|version| format!("v{version}"));
+        version
+            .into_iter()
+            .chain(self.target_parts())
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+
+    /// The declared density and adapter, e.g. `75T CH347`; empty when the
+    /// manifest declares neither.
+    pub fn target_label(&self) -> String {
+        self.target_parts().collect::<Vec<_>>().join(" ")
+    }
+
+    fn target_parts(&self) -> impl Iterator<Item = String> + '_ {
+        [
+            self.density.map(str::to_string),
+            self.adapter.map(|adapter| adapter.label().to_string()),
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+/// Hex SHA-256 of the file at `path`.
+pub fn sha256_file(path: &Path) -> io::Result<String> {
+    sha256_reader(File::open(path)?)
+}
+
+/// Hex SHA-256 of everything `reader` yields.
+pub fn sha256_reader(mut reader: impl Read) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+fn sidecar_paths(image: &Path) -> impl Iterator<Item = PathBuf> + '_ {
+    SIDECAR_EXTENSIONS.iter().map(move |extension| {
+        let mut path = image.as_os_str().to_os_string();
+        path.push(".");
+        path.push(extension);
+        PathBuf::from(path)
+    })
+}
+
+fn read_manifest<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|error| format!("Failed to read {}: {error}", path.display()))?;
+    let parsed = if path
+        .extension()
+        .is_some_and(|extension| extension == "toml")
+    {
+        toml::from_str(&contents).map_err(|error| error.to_string())
+    } else {
+        serde_json::from_str(&contents).map_err(|error| error.to_string())
+    };
+    parsed.map_err(|error| format!("Cannot parse {}: {error}", path.display()))
+}
+
+fn parse_density(text: &str) -> Option<&'static str> {
+    let text = text.trim();
+    DENSITIES
+        .into_iter()
+        .find(|density| density.eq_ignore_ascii_case(text))
+}
+
+fn parse_adapter(text: &str) -> Option<AdapterKind> {
+    match text.trim().to_ascii_lowercase().as_str() {
+        "ch347" => Some(AdapterKind::Ch347),
+        "rs232" | "ftdi" => Some(AdapterKind::Ftdi),
+        _ => None,
+    }
+}
+
+fn is_sha256_hex(text: &str) -> bool {
+    text.len() == SHA256_HEX_LEN && text.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+fn non_empty(text: Option<String>) -> Option<String> {
+    text.map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::temp_dir::TempDir;
+
+    #[test]
+    fn sidecar_wins_over_the_folder_manifest() {
+        let dir = TempDir::new("manifest");
+        let image = dir.join("card.bin");
+        fs::write(&image, b"image").unwrap();
+        fs::write(
+            dir.join("firmware.toml"),
+            "[firmware.\"card.bin\"]\nname = \"From folder\"\n\n[firmware.\"other.bin\"]\nversion = \"2\"\n",
+        )
+        .unwrap();
+        let mut warnings = Vec::new();
+        let folder = FolderManifest::load(dir.path(), &mut warnings);
+
+        assert_eq!(
+            FirmwareMetadata::for_image(&image, &folder, &mut warnings)
+                .unwrap()
+                .name
+                .as_deref(),
+            Some("From folder")
+        );
+
+        fs::write(
+            dir.join("card.bin.json"),
+            r#"{"name": "Card", "version": "1.4", "density": "75t", "adapter": "RS232",
+                "release_notes": "Fixes link training"}"#,
+        )
+        .unwrap();
+        let metadata = FirmwareMetadata::for_image(&image, &folder, &mut warnings).unwrap();
+
+        assert_eq!(metadata.name.as_deref(), Some("Card"));
+        assert_eq!(metadata.density, Some("75T"));
+        assert_eq!(metadata.adapter, Some(AdapterKind::Ftdi));
+        assert_eq!(metadata.notes.as_deref(), Some("Fixes link training"));
+        assert_eq!(metadata.summary(), "v1.4 · 75T · FTDI");
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn unreadable_folder_manifest_marks_its_images() {
+        let dir = TempDir::new("manifest");
+        let image = dir.join("card.bin");
+        fs::write(&image, b"image").unwrap();
+        fs::write(dir.join("firmware.toml"), "[firmware.\"card.bin\"\nname =").unwrap();
+        let mut warnings = Vec::new();
+        let folder = FolderManifest::load(dir.path(), &mut warnings);
+
+        let metadata = FirmwareMetadata::for_image(&image, &folder, &mut warnings).unwrap();
+
+        assert!(metadata.manifest_error.is_some());
+        assert_eq!(metadata.sha256, None);
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+    }
+
+    #[test]
+    fn declared_target_filters_flash_options() {
+        let metadata = FirmwareMetadata {
+            density: Some("35T"),
+            adapter: Some(AdapterKind::Ch347),
+            ..FirmwareMetadata::default()
+        };
+
+        assert!(metadata.accepts(&FlashingOption::CH347_35T));
+        assert!(!metadata.accepts(&FlashingOption::CH347_75T));
+        assert!(!metadata.accepts(&FlashingOption::RS232_35T));
+        assert!(FirmwareMetadata::default().accepts(&FlashingOption::RS232_100T));
+    }
+
+    #[test]
+    fn unusable_fields_are_reported() {
+        let mut warnings = Vec::new();
+        let metadata = FirmwareMetadata::from_raw(
+            RawMetadata {
+                density: Some("50T".into()),
+                adapter: Some("JLink".into()),
+                sha256: Some("ABC".into()),
+                ..RawMetadata::default()
+            },
+            "card.bin.json",
+            &mut warnings,
+        );
+
+        assert_eq!(metadata.density, None);
+        assert_eq!(metadata.adapter, None);
+        assert_eq!(metadata.sha256.as_deref(), Some("abc"));
+        assert_eq!(warnings.len(), 3, "{warnings:?}");
+    }
+
+    #[test]
+    fn hashes_file_contents() {
+        let dir = TempDir::new("manifest");
+        let path = dir.join("abc.bin");
+        fs::write(&path, b"abc").unwrap();
+
+        assert_eq!(
+            sha256_file(&path).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
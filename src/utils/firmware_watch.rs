@@ -0,0 +1,314 @@
+use crate::utils::firmware_locations::SearchRoot;
+use crate::utils::logger::Logger;
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+/// How long a directory must stay quiet before it is rescanned, so a large
+/// copy or a save that writes several files triggers one rescan.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+/// How often directories without notifications are rescanned.
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+/// Files whose changes can alter the firmware list: images, archives and manifests.
+const RELEVANT_EXTENSIONS: [&str; 5] = ["bin", "gz", "zip", "json", "toml"];
+
+/// Reports which firmware search roots changed. Directories are watched
+/// with filesystem notifications (inotify, ReadDirectoryChangesW, FSEvents);
+/// those that cannot be watched, e.g. because they do not exist yet, are
+/// polled instead and picked up for watching once they appear.
+pub struct FirmwareWatcher {
+    watcher: Option<RecommendedWatcher>,
+    events: Receiver<notify::Result<Event>>,
+    /// Canonical watched path to the search roots it stands for.
+    watched: HashMap<PathBuf, Vec<SearchRoot>>,
+    polled: Vec<SearchRoot>,
+    /// Search root paths with changes, and when the latest one was seen.
+    pending: HashMap<PathBuf, Instant>,
+    last_poll: Instant,
+    logger: Logger,
+}
+
+impl FirmwareWatcher {
+    pub fn start(search_roots: &[SearchRoot], logger: Logger) -> Self {
+        let (sender, events) = mpsc::channel();
+        let watcher = notify::recommended_watcher(move |event| {
+            let _ = sender.send(event);
+        })
+        .map_err(|error| {
+            logger.warning(format!(
+                "Firmware folder notifications unavailable, polling instead: {error}"
+            ));
+        })
+        .ok();
+
+        let mut firmware_watcher = Self {
+            watcher,
+            events,
+            watched: HashMap::new(),
+            polled: Vec::new(),
+            pending: HashMap::new(),
+            last_poll: Instant::now(),
+            logger,
+        };
+        for root in search_roots {
+            firmware_watcher.watch_or_poll(root.clone());
+        }
+        firmware_watcher
+    }
+
+    /// True when at least one directory is watched rather than polled.
+    #[cfg(test)]
+    pub fn is_watching(&self) -> bool {
+        !self.watched.is_empty()
+    }
+
+    /// Search root paths to rescan now: watched ones whose changes have
+    /// settled, and the polled ones once per poll interval.
+    pub fn changed_dirs(&mut self) -> Vec<PathBuf> {
+        self.drain_events();
+
+        let now = Instant::now();
+        let mut changed: Vec<PathBuf> = self
+            .pending
+            .iter()
+            .filter(|(_, seen)| now.duration_since(**seen) >= DEBOUNCE)
+            .map(|(dir, _)| dir.clone())
+            .collect();
+        for dir in &changed {
+            self.pending.remove(dir);
+        }
+
+        if now.duration_since(self.last_poll) >= POLL_INTERVAL {
+            self.last_poll = now;
+            for root in std::mem::take(&mut self.polled) {
+                if !changed.contains(&root.path) {
+                    changed.push(root.path.clone());
+                }
+                self.watch_or_poll(root);
+            }
+        }
+        changed
+    }
+
+    fn drain_events(&mut self) {
+        let mut lost = HashSet::new();
+        while let Ok(event) = self.events.try_recv() {
+            match event {
+                Ok(event) if is_relevant(&event) => {
+                    for path in &event.paths {
+                        self.mark_changed(path, &mut lost);
+                    }
+                }
+                Ok(_) => {}
+                Err(error) => {
+                    self.logger
+                        .debug(format!("Firmware folder notification error: {error}"));
+                    // Events may have been dropped; rescan everything watched.
+                    let all: Vec<PathBuf> = self
+                        .watched
+                        .values()
+                        .flatten()
+                        .map(|root| root.path.clone())
+                        .collect();
+                    let now = Instant::now();
+                    self.pending.extend(all.into_iter().map(|dir| (dir, now)));
+                }
+            }
+        }
+
+        // A watched directory that was removed or renamed falls back to polling.
+        for canonical in lost {
+            if let Some(roots) = self.watched.remove(&canonical) {
+                if let Some(watcher) = &mut self.watcher {
+                    let _ = watcher.unwatch(&canonical);
+                }
+                self.polled.extend(roots);
+            }
+        }
+    }
+
+    fn mark_changed(&mut self, path: &Path, lost: &mut HashSet<PathBuf>) {
+        let now = Instant::now();
+        if let Some(roots) = self.watched.get(path) {
+            // The root itself changed.
+            if !path.is_dir() {
+                lost.insert(path.to_path_buf());
+            }
+            self.pending
+                .extend(roots.iter().map(|root| (root.path.clone(), now)));
+            return;
+        }
+
+        // A change inside a root: a file directly in it, or anywhere below it
+        // for recursive roots, where an added or removed folder counts too.
+        let relevant_file = has_relevant_extension(path);
+        for (levels, ancestor) in path.ancestors().skip(1).enumerate() {
+            let Some(roots) = self.watched.get(ancestor) else {
+                continue;
+            };
+            let affected = roots.iter().filter(|root| {
+                if levels == 0 {
+                    relevant_file || root.recursive
+                } else {
+                    levels <= root.depth() && (relevant_file || path.extension().is_none())
+                }
+            });
+            self.pending
+                .extend(affected.map(|root| (root.path.clone(), now)));
+        }
+    }
+
+    fn watch_or_poll(&mut self, root: SearchRoot) {
+        let Some(canonical) = fs::canonicalize(&root.path)
+            .ok()
+            .filter(|path| path.is_dir())
+        else {
+            self.polled.push(root);
+            return;
+        };
+
+        if let Some(roots) = self.watched.get_mut(&canonical) {
+            // Widen the watch when a recursive root shares a flat root's folder.
+            if root.recursive
+                && !roots.iter().any(|existing| existing.recursive)
+                && let Some(watcher) = &mut self.watcher
+            {
+                let _ = watcher.watch(&canonical, RecursiveMode::Recursive);
+            }
+            roots.push(root);
+            return;
+        }
+
+        let Some(watcher) = &mut self.watcher else {
+            self.polled.push(root);
+            return;
+        };
+        let mode = if root.recursive {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        match watcher.watch(&canonical, mode) {
+            Ok(()) => {
+                self.watched.insert(canonical, vec![root]);
+            }
+            Err(error) => {
+                self.logger.debug(format!(
+                    "Polling {} for firmware: {error}",
+                    canonical.display()
+                ));
+                self.polled.push(root);
+            }
+        }
+    }
+}
+
+fn is_relevant(event: &Event) -> bool {
+    match event.kind {
+        EventKind::Create(_) | EventKind::Remove(_) | EventKind::Any | EventKind::Other => true,
+        EventKind::Modify(kind) => !matches!(kind, ModifyKind::Metadata(_)),
+        EventKind::Access(_) => false,
+    }
+}
+
+fn has_relevant_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            RELEVANT_EXTENSIONS
+                .iter()
+                .any(|relevant| extension.eq_ignore_ascii_case(relevant))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::temp_dir::TempDir;
+    use std::thread;
+
+    fn wait_for_change(watcher: &mut FirmwareWatcher) -> Vec<PathBuf> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            let changed = watcher.changed_dirs();
+            if !changed.is_empty() {
+                return changed;
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+        Vec::new()
+    }
+
+    #[test]
+    fn a_new_image_reports_its_directory_once_after_it_settles() {
+        let temp = TempDir::new("watch-new-image");
+        let dir = temp.path().to_path_buf();
+        let mut watcher = FirmwareWatcher::start(
+            &[SearchRoot::flat(&dir)],
+            Logger::new("FirmwareWatcherTest"),
+        );
+        if !watcher.is_watching() {
+            return;
+        }
+
+        for chunk in 0..3u8 {
+            fs::write(dir.join("card.bin"), [chunk; 16]).unwrap();
+        }
+
+        assert_eq!(wait_for_change(&mut watcher), std::slice::from_ref(&dir));
+        assert!(watcher.changed_dirs().is_empty());
+    }
+
+    #[test]
+    fn unrelated_files_do_not_trigger_a_rescan() {
+        let temp = TempDir::new("watch-unrelated");
+        let dir = temp.path().to_path_buf();
+        let mut watcher = FirmwareWatcher::start(
+            &[SearchRoot::flat(&dir)],
+            Logger::new("FirmwareWatcherTest"),
+        );
+
+        fs::write(dir.join("notes.txt"), b"notes").unwrap();
+        thread::sleep(DEBOUNCE * 2);
+
+        assert!(watcher.changed_dirs().is_empty());
+    }
+
+    #[test]
+    fn missing_directories_are_polled() {
+        let temp = TempDir::new("watch-missing");
+        let dir = temp.join("firmware");
+        let mut watcher = FirmwareWatcher::start(
+            &[SearchRoot::flat(&dir)],
+            Logger::new("FirmwareWatcherTest"),
+        );
+        assert!(!watcher.is_watching());
+
+        watcher.last_poll -= POLL_INTERVAL;
+        assert_eq!(watcher.changed_dirs(), std::slice::from_ref(&dir));
+        assert!(watcher.changed_dirs().is_empty());
+    }
+
+    #[test]
+    fn recursive_roots_report_changes_in_subfolders() {
+        let temp = TempDir::new("watch-recursive");
+        let dir = temp.path().to_path_buf();
+        fs::create_dir_all(dir.join("75T/nightly")).unwrap();
+        let root = SearchRoot {
+            recursive: true,
+            ..SearchRoot::flat(&dir)
+        };
+        let mut watcher = FirmwareWatcher::start(&[root], Logger::new("FirmwareWatcherTest"));
+        if !watcher.is_watching() {
+            return;
+        }
+
+        fs::write(dir.join("75T/nightly/card.bin"), b"image").unwrap();
+
+        assert_eq!(wait_for_change(&mut watcher), std::slice::from_ref(&dir));
+    }
+}
@@ -0,0 +1,180 @@
+use crate::utils::logger::Logger;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// The on-disk format of one of the per-user JSON stores: its fields plus a
+/// `version` that is bumped whenever an older build could misread them.
+pub struct JsonFormat {
+    /// What the file holds, for log messages, e.g. "DNA history".
+    pub label: &'static str,
+    pub version: u32,
+    /// What the store does instead when the file cannot be used.
+    pub fallback: &'static str,
+}
+
+#[derive(Serialize)]
+struct VersionedRef<'a, T> {
+    version: u32,
+    #[serde(flatten)]
+    contents: &'a T,
+}
+
+#[derive(Deserialize)]
+struct VersionOnly {
+    version: u32,
+}
+
+impl JsonFormat {
+    /// Reads the file at `path`. A missing file yields `None`; so does a
+    /// corrupt one or one written by a newer build, which is also moved to
+    /// `.json.bak` with a warning so the next save does not destroy it.
+    pub fn load<T: DeserializeOwned>(&self, path: &Path, logger: &Logger) -> Option<T> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(error) => {
+                logger.error(format!(
+                    "Failed to read {} from {}: {error}",
+                    self.label,
+                    path.display()
+                ));
+                return None;
+            }
+        };
+
+        // The version is checked on its own so a newer file is reported as
+        // such even when its fields no longer parse.
+        let parsed = serde_json::from_str::<VersionOnly>(&contents).and_then(|file| {
+            if file.version > self.version {
+                return Ok(Err(file.version));
+            }
+            serde_json::from_str::<T>(&contents).map(Ok)
+        });
+        match parsed {
+            Ok(Ok(contents)) => return Some(contents),
+            Ok(Err(version)) => logger.warning(format!(
+                "{} file {} uses newer format version {version}; {}",
+                self.label,
+                path.display(),
+                self.fallback
+            )),
+            Err(error) => logger.warning(format!(
+                "{} file {} is corrupt ({error}); {}",
+                self.label,
+                path.display(),
+                self.fallback
+            )),
+        }
+        self.preserve_unreadable(path, logger);
+        None
+    }
+
+    fn preserve_unreadable(&self, path: &Path, logger: &Logger) {
+        let backup = path.with_extension("json.bak");
+        match fs::rename(path, &backup) {
+            Ok(()) => logger.info(format!(
+                "Previous {} kept at {}",
+                self.label,
+                backup.display()
+            )),
+            Err(error) => logger.error(format!(
+                "Failed to move unreadable {} aside: {error}",
+                self.label
+            )),
+        }
+    }
+
+    /// Writes `contents` with the current version. The file is written to a
+    /// sibling first so a crash mid-write cannot truncate it.
+    pub fn save<T: Serialize>(&self, path: &Path, contents: &T) -> Result<(), String> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+        }
+
+        let file = VersionedRef {
+            version: self.version,
+            contents,
+        };
+        let contents = serde_json::to_string_pretty(&file).map_err(|error| error.to_string())?;
+
+        let temporary = path.with_extension("json.tmp");
+        fs::write(&temporary, contents).map_err(|error| error.to_string())?;
+        fs::rename(&temporary, path).map_err(|error| error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::temp_dir::TempDir;
+
+    const FORMAT: JsonFormat = JsonFormat {
+        label: "Test store",
+        version: 2,
+        fallback: "using the defaults",
+    };
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct Contents {
+        names: Vec<String>,
+    }
+
+    #[test]
+    fn saved_contents_are_read_back_with_their_version() {
+        let dir = TempDir::new("json-store");
+        let path = dir.join("store.json");
+        let logger = Logger::new("JsonStoreTest");
+        let contents = Contents {
+            names: vec!["a".to_string()],
+        };
+
+        FORMAT.save(&path, &contents).unwrap();
+
+        assert!(
+            fs::read_to_string(&path)
+                .unwrap()
+                .contains("\"version\": 2")
+        );
+        assert_eq!(FORMAT.load::<Contents>(&path, &logger), Some(contents));
+        assert_eq!(
+            FORMAT.load::<Contents>(&dir.join("missing.json"), &logger),
+            None
+        );
+    }
+
+    #[test]
+    fn unreadable_files_are_kept_aside_with_a_warning() {
+        let logger = Logger::new("JsonStoreTest");
+        for (name, contents, message) in [
+            (
+                "json-store-corrupt",
+                r#"{ "version": 1, "names": "#,
+                "is corrupt",
+            ),
+            (
+                "json-store-newer",
+                r#"{ "version": 3, "renamed": [] }"#,
+                "newer format version 3",
+            ),
+        ] {
+            let dir = TempDir::new(name);
+            let path = dir.join("store.json");
+            fs::write(&path, contents).unwrap();
+
+            assert_eq!(FORMAT.load::<Contents>(&path, &logger), None);
+            let backup = path.with_extension("json.bak");
+            assert_eq!(fs::read_to_string(&backup).unwrap(), contents);
+            assert!(
+                logger
+                    .get_entries()
+                    .iter()
+                    .any(|entry| entry.message.contains(message))
+            );
+        }
+    }
+}
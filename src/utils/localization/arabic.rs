@@ -13,6 +13,25 @@ pub fn get_text(key: TextKey) -> &'static str {
         TextKey::Copied => ":تم النسخ\n}{",
         TextKey::CheckingFiles => "...جارٍ فحص الملفات",
         TextKey::MissingFiles => "ملفات مفقودة",
+        TextKey::DamagedFiles => "ملفات مفقودة أو تالفة",
+        TextKey::FileStateMissing => "مفقود",
+        TextKey::FileStateCorrupt => "تالف",
+        TextKey::FileStateModified => "معدّل",
+        TextKey::ReferencedByScript => "}{ يحتاجه",
+        TextKey::ConfigWarnings => "تحذيرات إعداد OpenOCD",
+        TextKey::PresenceOnlyNote => {
+            "بدون ملف payload_manifest.json صالح تم التحقق من وجود الملفات الأخرى فقط وليس من سلامتها"
+        }
+        TextKey::LintAdapterSpeedConflict => "adapter speed }{ يتعارض مع }{ المحدد في }{",
+        TextKey::LintAdapterSpeedOutlier => {
+            "adapter speed }{ يختلف عن }{ المستخدم في ملفات }{ الأخرى"
+        }
+        TextKey::LintAdapterDriver => "adapter driver }{ لا يطابق ملف }{",
+        TextKey::LintUsbId => "معرف USB }{ ليس محول }{ معروفا",
+        TextKey::LintBitstreamDensity => "يحمّل }{ (}{) في إعداد }{",
+        TextKey::LintMissingExit => "لا ينتهي بـ exit أو shutdown",
+        TextKey::LintDuplicateProc => "proc }{ معرّف مسبقا في }{",
+        TextKey::LintUnreadableScript => "}{ :تعذرت قراءة الملف",
         TextKey::FileCheckSuccess => "!تم التحقق من جميع الملفات بنجاح",
         TextKey::SystemCheck => "فحص النظام",
         TextKey::WelcomeMessage => "}{ مرحبًا بك في أداة",
@@ -92,8 +111,10 @@ pub fn get_text(key: TextKey) -> &'static str {
         TextKey::Dna_Rs232_75T_Desc => "RS232 باستخدام واجهة 57T من لوحات AND قراءة",
         TextKey::Dna_Rs232_100T_Label => "RS232 - DNA :100T :قراءة",
         TextKey::Dna_Rs232_100T_Desc => "RS232 باستخدام واجهة 001T من لوحات AND قراءة",
+        TextKey::LastUsed => "آخر استخدام",
 
         TextKey::ClearLog => "مسح السجل",
+        TextKey::DebugLogging => "مخرجات التصحيح",
 
         TextKey::OperationTook => "استغرقت العملية",
         TextKey::NoteFewerSectors => {
@@ -167,14 +188,182 @@ pub fn get_text(key: TextKey) -> &'static str {
 
         TextKey::DnaInvalidOption => "DNA خيار غير صالح لقراءة",
         TextKey::DnaCommandFailed => "DNA فشل في تنفيذ أمر قراءة",
-        TextKey::DnaFileNotFound => "}{ بعد DNA لم يتم العثور على ملف إخراج محاولات",
         TextKey::DnaExtractFailed => "}{:DNA فشل في استخراج",
-        TextKey::DnaFileReadError => "}{:}{ في DNA فشل في قراءة ملف إخراج",
-        TextKey::DnaInfoNotFound => "في ملف الإخراج DNA فشل في العثور على معلومات",
+        TextKey::DnaInfoNotFound => "OpenOCD في إخراج DNA فشل في العثور على معلومات",
         TextKey::DnaWaitingStart => "...DNA انتظار بدء قراءة",
         TextKey::DnaRetrieving => "...DNA استرداد",
         TextKey::DnaReadSuccessStatus => "!DNA نجحت قراءة",
         TextKey::DnaOperationCompleted => "(DNA اكتملت العملية (غير",
         TextKey::DnaReadFailedStatus => "}{:DNA فشلت قراءة",
+
+        // Watchdog
+        TextKey::AdapterStoppedResponding => "توقف المحول عن الاستجابة",
+        TextKey::AdapterNoOutputMsg => {
+            ".ثانية وتم إيقافه }{ أي مخرجات لمدة OpenOCD لم ينتج\n\n.وتغذية المحول بالطاقة، ثم أعد توصيل المحول وحاول مرة أخرى JTAG تحقق من كابل"
+        }
+        TextKey::AdapterRuntimeExceededMsg => {
+            ".ثانية وتم إيقافها }{ لم تكتمل العملية خلال\n\n.وتغذية المحول بالطاقة، ثم أعد توصيل المحول وحاول مرة أخرى JTAG تحقق من كابل"
+        }
+
+        // Adapter selection
+        TextKey::AdapterSectionTitle => "USB محول",
+        TextKey::AdapterAuto => "(متصل محول أول) تلقائي",
+        TextKey::AdapterRefresh => "تحديث",
+        TextKey::AdapterScanning => "...المحولات عن البحث جارٍ",
+        TextKey::AdapterNoneFound => "محول أي اكتشاف يتم لم",
+        TextKey::AdapterScanFailed => "}{ :المحولات اكتشاف فشل",
+        TextKey::AdapterNoSerial => "(تسلسلي رقم بدون)",
+        TextKey::JtagSpeed => "JTAG",
+        TextKey::JtagSpeedProfileDefault => "سرعة الملف الشخصي",
+        TextKey::AdapterLabel => ":المحول",
+
+        // DNA history
+        TextKey::DnaHistory => "DNA سجل",
+        TextKey::DnaHistoryDesc => "سابقًا المقروء الجهاز DNA وتصدير بحث",
+        TextKey::DnaHistorySearchHint => "الست عشري DNA من جزء حسب بحث",
+        TextKey::DnaHistoryEmpty => "بعد DNA قراءة أي تسجيل يتم لم",
+        TextKey::DnaHistoryNoMatches => "البحث تطابق DNA قيمة توجد لا",
+        TextKey::DnaHistoryColDna => "DNA",
+        TextKey::DnaHistoryColDensity => "السعة",
+        TextKey::DnaHistoryColAdapter => "المحول",
+        TextKey::DnaHistoryColReads => "القراءات",
+        TextKey::DnaHistoryColLastRead => "(UTC) قراءة آخر",
+        TextKey::DnaHistoryFirstRead => ":(UTC) قراءة أول",
+        TextKey::DnaHistoryNote => ":ملاحظة",
+        TextKey::DnaHistorySaveNote => "الملاحظة حفظ",
+        TextKey::DnaHistoryNoteSaved => "الملاحظة حفظ تم",
+        TextKey::DnaHistoryExportCsv => "CSV تصدير",
+        TextKey::DnaHistoryExportJson => "JSON تصدير",
+        TextKey::DnaHistoryExported => "}{ إلى التصدير تم",
+        TextKey::DnaHistoryExportFailed => "}{ :التصدير فشل",
+
+        // DNA representations
+        TextKey::DnaFormatsHeader => "أخرى صيغ",
+        TextKey::DnaFormatHex => "عشري سداسي",
+        TextKey::DnaFormatHex57 => "بت 57 عشري سداسي",
+        TextKey::DnaFormatBinary => "ثنائي",
+        TextKey::DnaFormatByteReversed => "البايتات معكوس",
+        TextKey::DnaFormatBase64 => "Base64",
+        TextKey::DnaCopyValue => "نسخ",
+
+        // DNA allowlist
+        TextKey::DnaLicensedTo => "}{ مرخص لـ",
+        TextKey::DnaUnknownBoard => "غير معروفة لوحة",
+        TextKey::DnaRevoked => "ملغاة",
+        TextKey::DnaRevokedFrom => "}{ :ملغاة",
+
+        // DNA lock pre-flight
+        TextKey::DnaLockCheck => ":الثابت للبرنامج DNA قفل",
+        TextKey::DnaLockOff => "إيقاف",
+        TextKey::DnaLockWarn => "تحذير",
+        TextKey::DnaLockBlock => "منع",
+        TextKey::DnaLockDescription => {
+            "به مقفل الثابت البرنامج لوحة أي من ويتحقق أولاً اللوحة DNA يقرأ"
+        }
+        TextKey::DnaLockChecking => "...التفليش قبل اللوحة DNA قراءة جارٍ",
+        TextKey::DnaLockReadFailed => "}{ :التفليش قبل اللوحة DNA قراءة تعذرت",
+        TextKey::DnaLockMismatch => {
+            "}{ هي اللوحة هذه لكن ،(}{ الإزاحة عند }{) }{ DNA على مقفل الثابت البرنامج"
+        }
+        TextKey::DnaLockBoardNotFound => "الثابت البرنامج في }{ اللوحة هذه DNA على العثور يتم لم",
+
+        // Firmware manifests
+        TextKey::FirmwareTargetFilter => "}{ الثابت للبرنامج الخيارات عرض",
+
+        // PCILeech test profiles
+        TextKey::PcileechProfile => ":الملف",
+        TextKey::PcileechProfileEdit => "الملف تعديل",
+        TextKey::PcileechProfileName => "الاسم",
+        TextKey::PcileechProfileExecutable => "الاختبار أداة",
+        TextKey::PcileechProfileConnector => "الموصل",
+        TextKey::PcileechProfileConnectorArgs => "الموصل وسائط",
+        TextKey::PcileechProfileConnectorArgsHint => "device=FPGA :مثال",
+        TextKey::PcileechProfileBenchmarkArgs => "القياس وسائط",
+        TextKey::PcileechProfileSave => "الملف حفظ",
+        TextKey::PcileechProfileDelete => "الملف حذف",
+        TextKey::PcileechProfileSaved => "}{ الملف حفظ تم",
+        TextKey::PcileechProfileDeleted => "}{ الملف حذف تم",
+        TextKey::PcileechProfileMissingName => "للملف اسماً أدخل",
+        TextKey::PcileechProfileMissingExecutable => "الاختبار أداة مسار أدخل",
+        TextKey::PcileechProfileInvalidConnector => {
+            "':' أو مسافات بدون واحداً اسماً الموصل يكون أن يجب"
+        }
+        TextKey::PcileechProfileInvalidConnectorArgs => {
+            "device=<الاسم> بصيغة ،وسائط pcileech موصل يقبل فقط"
+        }
+
+        // PCILeech report
+        TextKey::PcileechFpgaDevice => "FPGA جهاز",
+        TextKey::PcileechFpgaId => "FPGA معرّف",
+        TextKey::PcileechFpgaVersion => "FPGA إصدار",
+        TextKey::PcileechDeviceId => "الجهاز معرّف",
+        TextKey::PcileechPcieLink => "PCIe رابط",
+        TextKey::PcileechTargetOs => "الهدف نظام",
+        TextKey::PcileechOsBuild => "النظام بناء",
+        TextKey::PcileechKernelBase => "النواة عنوان",
+        TextKey::PcileechDtb => "DTB",
+        TextKey::PcileechNtdllBase => "ntdll.dll عنوان",
+
+        // PCILeech benchmark
+        TextKey::PcileechModeCheck => "الاتصال فحص",
+        TextKey::PcileechModeBenchmark => "الإنتاجية قياس",
+        TextKey::PcileechBenchmarkUnavailable => {
+            "الملف في متكررة قراءة وضع لها اختبار أداة قياس وسائط عيّن"
+        }
+        TextKey::PcileechBenchmarkDuration => ":المدة",
+        TextKey::PcileechSeconds => "ث }{",
+        TextKey::PcileechMeasuring => "...القراءة إنتاجية قياس جارٍ",
+        TextKey::PcileechBenchmarkDone => "القياس اكتمل",
+        TextKey::PcileechThroughputMin => "الأدنى",
+        TextKey::PcileechThroughputAverage => "المتوسط",
+        TextKey::PcileechThroughputMax => "الأقصى",
+        TextKey::PcileechSamples => "عينة }{",
+
+        // PCILeech soak test
+        TextKey::PcileechModeSoak => "التحمل اختبار",
+        TextKey::PcileechSoakLength => ":الطول",
+        TextKey::PcileechSoakGap => ":الفاصل",
+        TextKey::PcileechSoakRuns => "مرة }{",
+        TextKey::PcileechSoakMinutes => "دقيقة }{",
+        TextKey::PcileechSoakRunning => "...التحمل اختبار تشغيل جارٍ",
+        TextKey::PcileechSoakProgress => "فشل }{ ،نجح }{ :}{ المحاولة",
+        TextKey::PcileechSoakStop => "إيقاف",
+        TextKey::PcileechSoakDone => "التحمل اختبار اكتمل",
+        TextKey::PcileechSoakStopped => "التحمل اختبار أُوقف",
+        TextKey::PcileechSoakSuccessRate => "(}{ من }{) %}{ :النجاح معدل",
+        TextKey::PcileechSoakTimeToSuccess => "النجاح حتى الوقت",
+        TextKey::PcileechSoakFailures => "الإخفاقات",
+        TextKey::PcileechSoakOtherFailures => "أخرى لأسباب إضافية }{",
+
+        // Firmware search locations
+        TextKey::BrowseFirmware => "...استعراض",
+        TextKey::FirmwareLocations => "البحث مواقع",
+        TextKey::DropFirmwareHint => "النافذة هذه على .bin ملف أسقط أو",
+        TextKey::FirmwareLocationsHint => "المدمجة المجلدات بعد المجلدات هذه في البحث يتم",
+        TextKey::FirmwareLocationsEmpty => "بعد مجلدات أي إضافة تتم لم",
+        TextKey::AddFirmwareFolder => "مجلد إضافة",
+        TextKey::RemoveFirmwareFolder => "إزالة",
+        TextKey::IncludeSubfolders => "الفرعية المجلدات تضمين",
+        TextKey::ScanDepth => ":العمق",
+        TextKey::IgnorePatternsLabel => ":(مسموحان ? و * ،سطر لكل واحد نمط) المطابقة الأسماء تجاهل",
+        TextKey::SaveIgnorePatterns => "الأنماط حفظ",
+        TextKey::IgnorePatternsSaved => "التجاهل أنماط حفظ تم",
+        TextKey::FirmwareFolderAdded => "}{ إضافة تمت",
+        TextKey::FirmwareFolderAlreadyListed => "بالفعل بحث موقع }{",
+        TextKey::PickedFileNotFirmware => "ثابت برنامج صورة على يحتوي لا }{",
+        TextKey::BackToFirmwareSelection => "رجوع",
+        TextKey::IdenticalCopies => ":متطابقة نسخ",
+        TextKey::SignatureCheck => ":الثابت البرنامج توقيع",
+        TextKey::SignatureDescription => "موثوقة مفاتيح مع للصورة Ed25519 توقيع من التحقق",
+        TextKey::SignaturePinned => "تغييره يمكن ولا التثبيت هذا من مضبوط",
+        TextKey::SignatureOff => "إيقاف",
+        TextKey::SignatureWarn => "تحذير",
+        TextKey::SignatureBlock => "منع",
+        TextKey::SignatureVerified => "(}{ المفتاح) }{ بواسطة موقّع :التوقيع من التحقق تم",
+        TextKey::SignatureMissing => "الصورة بجانب .sig ملف يوجد لا :موقّع غير الثابت البرنامج",
+        TextKey::SignatureInvalid => "}{ :صالح غير الثابت البرنامج توقيع",
+        TextKey::SignatureMalformed => "بايت 64 من Ed25519 توقيع ليس .sig ملف",
+        TextKey::SignatureNoTrustedKeys => "مثبتة موثوقة توقيع مفاتيح توجد لا",
+        TextKey::SignatureNoMatchingKey => "موثوق مفتاح أي بواسطة يُنشأ لم",
     }
 }
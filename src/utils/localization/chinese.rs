@@ -13,6 +13,23 @@ pub fn get_text(key: TextKey) -> &'static str {
         TextKey::Copied => "已复制：\n{}",
         TextKey::CheckingFiles => "正在检查文件...",
         TextKey::MissingFiles => "缺失文件",
+        TextKey::DamagedFiles => "缺失或损坏的文件",
+        TextKey::FileStateMissing => "缺失",
+        TextKey::FileStateCorrupt => "已损坏",
+        TextKey::FileStateModified => "已修改",
+        TextKey::ReferencedByScript => "被 {} 引用",
+        TextKey::ConfigWarnings => "OpenOCD 配置警告",
+        TextKey::PresenceOnlyNote => {
+            "没有可用的 payload_manifest.json，其他文件仅检查了是否存在，未检查是否损坏"
+        }
+        TextKey::LintAdapterSpeedConflict => "adapter speed {} 与 {} (设置于 {}) 冲突",
+        TextKey::LintAdapterSpeedOutlier => "adapter speed {} 不同于其他配置使用的 {} ({} 适配器)",
+        TextKey::LintAdapterDriver => "adapter driver {} 与 {} 配置不匹配",
+        TextKey::LintUsbId => "USB ID {} 不是已知的 {} 适配器",
+        TextKey::LintBitstreamDensity => "加载 {} ({})，但配置为 {}",
+        TextKey::LintMissingExit => "未以 exit 或 shutdown 结尾",
+        TextKey::LintDuplicateProc => "proc {} 已在 {} 定义",
+        TextKey::LintUnreadableScript => "无法读取：{}",
         TextKey::FileCheckSuccess => "所有文件验证成功！",
         TextKey::SystemCheck => "系统检查",
         TextKey::WelcomeMessage => "欢迎使用 {} 工具",
@@ -92,9 +109,11 @@ pub fn get_text(key: TextKey) -> &'static str {
         TextKey::Dna_Rs232_75T_Desc => "使用 RS232 接口从 75T 板卡读取 DNA",
         TextKey::Dna_Rs232_100T_Label => "RS232 - DNA 读取: 100T",
         TextKey::Dna_Rs232_100T_Desc => "使用 RS232 接口从 100T 板卡读取 DNA",
+        TextKey::LastUsed => "上次使用",
 
         // Log View
         TextKey::ClearLog => "清除日志",
+        TextKey::DebugLogging => "调试输出",
 
         // Result Extras
         TextKey::OperationTook => "操作耗时",
@@ -160,14 +179,176 @@ pub fn get_text(key: TextKey) -> &'static str {
         // DNA Backend & Status
         TextKey::DnaInvalidOption => "DNA 读取选项无效",
         TextKey::DnaCommandFailed => "执行 DNA 读取命令失败",
-        TextKey::DnaFileNotFound => "尝试 {} 次后仍未找到 DNA 输出文件",
         TextKey::DnaExtractFailed => "提取 DNA 失败：{}",
-        TextKey::DnaFileReadError => "读取位于 {} 的 DNA 输出文件失败：{}",
-        TextKey::DnaInfoNotFound => "在输出文件中未找到 DNA 信息",
+        TextKey::DnaInfoNotFound => "在 OpenOCD 输出中未找到 DNA 信息",
         TextKey::DnaWaitingStart => "等待开始读取 DNA...",
         TextKey::DnaRetrieving => "正在获取设备 DNA...",
         TextKey::DnaReadSuccessStatus => "DNA 读取成功！",
         TextKey::DnaOperationCompleted => "操作已完成（非 DNA）",
         TextKey::DnaReadFailedStatus => "DNA 读取失败：{}",
+
+        // Watchdog
+        TextKey::AdapterStoppedResponding => "适配器无响应",
+        TextKey::AdapterNoOutputMsg => {
+            "OpenOCD 在 {} 秒内没有任何输出，已被终止。\n\n请检查 JTAG 线缆和适配器供电，重新连接适配器后再试。"
+        }
+        TextKey::AdapterRuntimeExceededMsg => {
+            "操作未能在 {} 秒内完成，已被终止。\n\n请检查 JTAG 线缆和适配器供电，重新连接适配器后再试。"
+        }
+
+        // Adapter selection
+        TextKey::AdapterSectionTitle => "USB 适配器",
+        TextKey::AdapterAuto => "自动（首个检测到的）",
+        TextKey::AdapterRefresh => "刷新",
+        TextKey::AdapterScanning => "正在检测适配器...",
+        TextKey::AdapterNoneFound => "未检测到适配器",
+        TextKey::AdapterScanFailed => "适配器检测失败：{}",
+        TextKey::AdapterNoSerial => "（无序列号）",
+        TextKey::JtagSpeed => "JTAG",
+        TextKey::JtagSpeedProfileDefault => "配置默认速度",
+        TextKey::AdapterLabel => "适配器:",
+
+        // DNA history
+        TextKey::DnaHistory => "DNA 历史记录",
+        TextKey::DnaHistoryDesc => "搜索和导出之前读取的设备 DNA",
+        TextKey::DnaHistorySearchHint => "按部分 DNA 十六进制搜索",
+        TextKey::DnaHistoryEmpty => "尚无 DNA 读取记录",
+        TextKey::DnaHistoryNoMatches => "没有匹配搜索的 DNA 值",
+        TextKey::DnaHistoryColDna => "DNA",
+        TextKey::DnaHistoryColDensity => "容量",
+        TextKey::DnaHistoryColAdapter => "适配器",
+        TextKey::DnaHistoryColReads => "次数",
+        TextKey::DnaHistoryColLastRead => "最近读取 (UTC)",
+        TextKey::DnaHistoryFirstRead => "首次读取 (UTC):",
+        TextKey::DnaHistoryNote => "备注:",
+        TextKey::DnaHistorySaveNote => "保存备注",
+        TextKey::DnaHistoryNoteSaved => "备注已保存",
+        TextKey::DnaHistoryExportCsv => "导出 CSV",
+        TextKey::DnaHistoryExportJson => "导出 JSON",
+        TextKey::DnaHistoryExported => "已导出到 {}",
+        TextKey::DnaHistoryExportFailed => "导出失败：{}",
+
+        // DNA representations
+        TextKey::DnaFormatsHeader => "其他格式",
+        TextKey::DnaFormatHex => "十六进制",
+        TextKey::DnaFormatHex57 => "57 位十六进制",
+        TextKey::DnaFormatBinary => "二进制",
+        TextKey::DnaFormatByteReversed => "字节反序",
+        TextKey::DnaFormatBase64 => "Base64",
+        TextKey::DnaCopyValue => "复制",
+
+        // DNA allowlist
+        TextKey::DnaLicensedTo => "已授权给 {}",
+        TextKey::DnaUnknownBoard => "未知板卡",
+        TextKey::DnaRevoked => "已吊销",
+        TextKey::DnaRevokedFrom => "已吊销：{}",
+
+        // DNA lock pre-flight
+        TextKey::DnaLockCheck => "固件 DNA 锁定检查：",
+        TextKey::DnaLockOff => "关闭",
+        TextKey::DnaLockWarn => "警告",
+        TextKey::DnaLockBlock => "阻止",
+        TextKey::DnaLockDescription => "先读取板卡 DNA，并检查固件锁定的板卡",
+        TextKey::DnaLockChecking => "刷写前正在读取板卡 DNA...",
+        TextKey::DnaLockReadFailed => "刷写前无法读取板卡 DNA：{}",
+        TextKey::DnaLockMismatch => "固件已锁定到 DNA {}（{}，偏移 {}），但此板卡为 {}",
+        TextKey::DnaLockBoardNotFound => "固件中未找到此板卡的 DNA {}",
+
+        // Firmware manifests
+        TextKey::FirmwareTargetFilter => "仅显示适用于 {} 固件的选项",
+
+        // PCILeech test profiles
+        TextKey::PcileechProfile => "配置:",
+        TextKey::PcileechProfileEdit => "编辑配置",
+        TextKey::PcileechProfileName => "名称",
+        TextKey::PcileechProfileExecutable => "测试工具",
+        TextKey::PcileechProfileConnector => "连接器",
+        TextKey::PcileechProfileConnectorArgs => "连接器参数",
+        TextKey::PcileechProfileConnectorArgsHint => "例如 device=FPGA",
+        TextKey::PcileechProfileBenchmarkArgs => "基准测试参数",
+        TextKey::PcileechProfileSave => "保存配置",
+        TextKey::PcileechProfileDelete => "删除配置",
+        TextKey::PcileechProfileSaved => "配置 {} 已保存",
+        TextKey::PcileechProfileDeleted => "配置 {} 已删除",
+        TextKey::PcileechProfileMissingName => "请输入配置名称",
+        TextKey::PcileechProfileMissingExecutable => "请输入测试工具路径",
+        TextKey::PcileechProfileInvalidConnector => "连接器必须是不含空格或 ':' 的单个名称",
+        TextKey::PcileechProfileInvalidConnectorArgs => {
+            "只有 pcileech 连接器接受参数，格式为 device=<名称>"
+        }
+
+        // PCILeech report
+        TextKey::PcileechFpgaDevice => "FPGA 设备",
+        TextKey::PcileechFpgaId => "FPGA ID",
+        TextKey::PcileechFpgaVersion => "FPGA 版本",
+        TextKey::PcileechDeviceId => "设备 ID",
+        TextKey::PcileechPcieLink => "PCIe 链路",
+        TextKey::PcileechTargetOs => "目标系统",
+        TextKey::PcileechOsBuild => "系统版本号",
+        TextKey::PcileechKernelBase => "内核基址",
+        TextKey::PcileechDtb => "DTB",
+        TextKey::PcileechNtdllBase => "ntdll.dll 基址",
+
+        // PCILeech benchmark
+        TextKey::PcileechModeCheck => "连接检查",
+        TextKey::PcileechModeBenchmark => "吞吐量测试",
+        TextKey::PcileechBenchmarkUnavailable => {
+            "请在配置文件中设置支持循环读取模式的测试工具的基准测试参数"
+        }
+        TextKey::PcileechBenchmarkDuration => "时长:",
+        TextKey::PcileechSeconds => "{} 秒",
+        TextKey::PcileechMeasuring => "正在测量读取吞吐量...",
+        TextKey::PcileechBenchmarkDone => "测试完成",
+        TextKey::PcileechThroughputMin => "最小",
+        TextKey::PcileechThroughputAverage => "平均",
+        TextKey::PcileechThroughputMax => "最大",
+        TextKey::PcileechSamples => "{} 个样本",
+
+        // PCILeech soak test
+        TextKey::PcileechModeSoak => "稳定性测试",
+        TextKey::PcileechSoakLength => "长度:",
+        TextKey::PcileechSoakGap => "间隔:",
+        TextKey::PcileechSoakRuns => "{} 次",
+        TextKey::PcileechSoakMinutes => "{} 分钟",
+        TextKey::PcileechSoakRunning => "正在进行稳定性测试...",
+        TextKey::PcileechSoakProgress => "第 {} 次:{} 次通过,{} 次失败",
+        TextKey::PcileechSoakStop => "停止",
+        TextKey::PcileechSoakDone => "稳定性测试完成",
+        TextKey::PcileechSoakStopped => "稳定性测试已停止",
+        TextKey::PcileechSoakSuccessRate => "成功率:{}%({}/{})",
+        TextKey::PcileechSoakTimeToSuccess => "成功耗时",
+        TextKey::PcileechSoakFailures => "失败原因",
+        TextKey::PcileechSoakOtherFailures => "另有 {} 次其他原因",
+
+        // Firmware search locations
+        TextKey::BrowseFirmware => "浏览...",
+        TextKey::FirmwareLocations => "搜索位置",
+        TextKey::DropFirmwareHint => "或将 .bin 文件拖放到此窗口",
+        TextKey::FirmwareLocationsHint => "这些文件夹会在内置文件夹之后搜索",
+        TextKey::FirmwareLocationsEmpty => "尚未添加文件夹",
+        TextKey::AddFirmwareFolder => "添加文件夹",
+        TextKey::RemoveFirmwareFolder => "移除",
+        TextKey::IncludeSubfolders => "包含子文件夹",
+        TextKey::ScanDepth => "深度:",
+        TextKey::IgnorePatternsLabel => "忽略匹配的名称（每行一个模式，可使用 * 和 ?）：",
+        TextKey::SaveIgnorePatterns => "保存模式",
+        TextKey::IgnorePatternsSaved => "忽略模式已保存",
+        TextKey::FirmwareFolderAdded => "已添加 {}",
+        TextKey::FirmwareFolderAlreadyListed => "{} 已是搜索位置",
+        TextKey::PickedFileNotFirmware => "{} 不包含固件映像",
+        TextKey::BackToFirmwareSelection => "返回",
+        TextKey::IdenticalCopies => "相同内容的副本：",
+        TextKey::SignatureCheck => "固件签名：",
+        TextKey::SignatureDescription => "使用受信任的公钥验证映像的 Ed25519 签名",
+        TextKey::SignaturePinned => "由此安装固定，无法更改",
+        TextKey::SignatureOff => "关闭",
+        TextKey::SignatureWarn => "警告",
+        TextKey::SignatureBlock => "阻止",
+        TextKey::SignatureVerified => "签名已验证：由 {} 签名（密钥 {}）",
+        TextKey::SignatureMissing => "固件未签名：映像旁没有 .sig 文件",
+        TextKey::SignatureInvalid => "固件签名无效：{}",
+        TextKey::SignatureMalformed => ".sig 文件不是 64 字节的 Ed25519 签名",
+        TextKey::SignatureNoTrustedKeys => "未安装受信任的签名密钥",
+        TextKey::SignatureNoMatchingKey => "签名不是由任何受信任的密钥生成",
     }
 }
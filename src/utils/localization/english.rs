@@ -13,6 +13,25 @@ pub fn get_text(key: TextKey) -> &'static str {
         TextKey::Copied => "Copied:\n{}",
         TextKey::CheckingFiles => "Checking Files...",
         TextKey::MissingFiles => "Missing Files",
+        TextKey::DamagedFiles => "Missing or Damaged Files",
+        TextKey::FileStateMissing => "missing",
+        TextKey::FileStateCorrupt => "corrupt",
+        TextKey::FileStateModified => "modified",
+        TextKey::ReferencedByScript => "needed by {}",
+        TextKey::ConfigWarnings => "OpenOCD Config Warnings",
+        TextKey::PresenceOnlyNote => {
+            "Without a usable payload_manifest.json the other files were only checked for presence, not for damage"
+        }
+        TextKey::LintAdapterSpeedConflict => "adapter speed {} conflicts with {} set at {}",
+        TextKey::LintAdapterSpeedOutlier => {
+            "adapter speed {} differs from the {} the other {} profiles use"
+        }
+        TextKey::LintAdapterDriver => "adapter driver {} does not match the {} profile",
+        TextKey::LintUsbId => "USB ID {} is not a known {} adapter",
+        TextKey::LintBitstreamDensity => "loads {} ({}) in a {} config",
+        TextKey::LintMissingExit => "does not end with exit or shutdown",
+        TextKey::LintDuplicateProc => "proc {} is already defined at {}",
+        TextKey::LintUnreadableScript => "cannot be read: {}",
         TextKey::FileCheckSuccess => "All files validated successfully!",
         TextKey::SystemCheck => "System Check",
         TextKey::WelcomeMessage => "Welcome to the {} Tool",
@@ -93,9 +112,11 @@ pub fn get_text(key: TextKey) -> &'static str {
         TextKey::Dna_Rs232_75T_Desc => "Read DNA from 75T boards using RS232 interface",
         TextKey::Dna_Rs232_100T_Label => "RS232 - DNA Read: 100T",
         TextKey::Dna_Rs232_100T_Desc => "Read DNA from 100T boards using RS232 interface",
+        TextKey::LastUsed => "last used",
 
         // Log View
         TextKey::ClearLog => "Clear Log",
+        TextKey::DebugLogging => "Debug output",
 
         // Result Extras
         TextKey::OperationTook => "Operation took",
@@ -170,14 +191,186 @@ pub fn get_text(key: TextKey) -> &'static str {
         // DNA Backend & Status
         TextKey::DnaInvalidOption => "Invalid option for DNA read",
         TextKey::DnaCommandFailed => "Failed to execute DNA read command",
-        TextKey::DnaFileNotFound => "DNA output file not found after {} attempts",
         TextKey::DnaExtractFailed => "Failed to extract DNA: {}",
-        TextKey::DnaFileReadError => "Failed to read DNA output file at {}: {}",
-        TextKey::DnaInfoNotFound => "Could not find DNA information in the output file",
+        TextKey::DnaInfoNotFound => "Could not find DNA information in the OpenOCD output",
         TextKey::DnaWaitingStart => "Waiting to start DNA read...",
         TextKey::DnaRetrieving => "Retrieving device DNA...",
         TextKey::DnaReadSuccessStatus => "DNA read successful!",
         TextKey::DnaOperationCompleted => "Operation completed (Non-DNA)",
         TextKey::DnaReadFailedStatus => "DNA read failed: {}",
+
+        // Watchdog
+        TextKey::AdapterStoppedResponding => "ADAPTER STOPPED RESPONDING",
+        TextKey::AdapterNoOutputMsg => {
+            "OpenOCD produced no output for {} seconds and was stopped.\n\nCheck the JTAG cable and adapter power, reconnect the adapter, then try again."
+        }
+        TextKey::AdapterRuntimeExceededMsg => {
+            "The operation did not finish within {} seconds and was stopped.\n\nCheck the JTAG cable and adapter power, reconnect the adapter, then try again."
+        }
+
+        // Adapter selection
+        TextKey::AdapterSectionTitle => "USB Adapter",
+        TextKey::AdapterAuto => "Auto (first detected)",
+        TextKey::AdapterRefresh => "Refresh",
+        TextKey::AdapterScanning => "Detecting adapters...",
+        TextKey::AdapterNoneFound => "No adapters detected",
+        TextKey::AdapterScanFailed => "Adapter detection failed: {}",
+        TextKey::AdapterNoSerial => "(no serial)",
+        TextKey::JtagSpeed => "JTAG",
+        TextKey::JtagSpeedProfileDefault => "Profile speed",
+        TextKey::AdapterLabel => "Adapter:",
+
+        // DNA history
+        TextKey::DnaHistory => "DNA History",
+        TextKey::DnaHistoryDesc => "Search and export previously read device DNA",
+        TextKey::DnaHistorySearchHint => "Search by partial DNA hex",
+        TextKey::DnaHistoryEmpty => "No DNA reads recorded yet",
+        TextKey::DnaHistoryNoMatches => "No DNA values match the search",
+        TextKey::DnaHistoryColDna => "DNA",
+        TextKey::DnaHistoryColDensity => "Density",
+        TextKey::DnaHistoryColAdapter => "Adapter",
+        TextKey::DnaHistoryColReads => "Reads",
+        TextKey::DnaHistoryColLastRead => "Last read (UTC)",
+        TextKey::DnaHistoryFirstRead => "First read (UTC):",
+        TextKey::DnaHistoryNote => "Note:",
+        TextKey::DnaHistorySaveNote => "Save note",
+        TextKey::DnaHistoryNoteSaved => "Note saved",
+        TextKey::DnaHistoryExportCsv => "Export CSV",
+        TextKey::DnaHistoryExportJson => "Export JSON",
+        TextKey::DnaHistoryExported => "Exported to {}",
+        TextKey::DnaHistoryExportFailed => "Export failed: {}",
+
+        // DNA representations
+        TextKey::DnaFormatsHeader => "Other formats",
+        TextKey::DnaFormatHex => "Hex",
+        TextKey::DnaFormatHex57 => "57-bit hex",
+        TextKey::DnaFormatBinary => "Binary",
+        TextKey::DnaFormatByteReversed => "Byte-reversed",
+        TextKey::DnaFormatBase64 => "Base64",
+        TextKey::DnaCopyValue => "Copy",
+
+        // DNA allowlist
+        TextKey::DnaLicensedTo => "Licensed to {}",
+        TextKey::DnaUnknownBoard => "Unknown board",
+        TextKey::DnaRevoked => "Revoked",
+        TextKey::DnaRevokedFrom => "Revoked: {}",
+
+        // DNA lock pre-flight
+        TextKey::DnaLockCheck => "Firmware DNA lock:",
+        TextKey::DnaLockOff => "Off",
+        TextKey::DnaLockWarn => "Warn",
+        TextKey::DnaLockBlock => "Block",
+        TextKey::DnaLockDescription => {
+            "Reads the board DNA first and checks which board the firmware is locked to"
+        }
+        TextKey::DnaLockChecking => "Reading board DNA before flashing...",
+        TextKey::DnaLockReadFailed => "Could not read the board DNA before flashing: {}",
+        TextKey::DnaLockMismatch => {
+            "Firmware is locked to DNA {} ({} at offset {}), but this board is {}"
+        }
+        TextKey::DnaLockBoardNotFound => "This board's DNA {} was not found in the firmware",
+
+        // Firmware manifests
+        TextKey::FirmwareTargetFilter => "Showing options for {} firmware",
+
+        // PCILeech test profiles
+        TextKey::PcileechProfile => "Profile:",
+        TextKey::PcileechProfileEdit => "Edit profile",
+        TextKey::PcileechProfileName => "Name",
+        TextKey::PcileechProfileExecutable => "Test tool",
+        TextKey::PcileechProfileConnector => "Connector",
+        TextKey::PcileechProfileConnectorArgs => "Connector args",
+        TextKey::PcileechProfileConnectorArgsHint => "e.g. device=FPGA",
+        TextKey::PcileechProfileBenchmarkArgs => "Benchmark arguments",
+        TextKey::PcileechProfileSave => "Save profile",
+        TextKey::PcileechProfileDelete => "Delete profile",
+        TextKey::PcileechProfileSaved => "Profile {} saved",
+        TextKey::PcileechProfileDeleted => "Profile {} deleted",
+        TextKey::PcileechProfileMissingName => "Enter a profile name",
+        TextKey::PcileechProfileMissingExecutable => "Enter the path to the test tool",
+        TextKey::PcileechProfileInvalidConnector => {
+            "The connector must be a single name without spaces or ':'"
+        }
+        TextKey::PcileechProfileInvalidConnectorArgs => {
+            "Only the pcileech connector takes arguments, as device=<name>"
+        }
+
+        // PCILeech report
+        TextKey::PcileechFpgaDevice => "FPGA device",
+        TextKey::PcileechFpgaId => "FPGA ID",
+        TextKey::PcileechFpgaVersion => "FPGA version",
+        TextKey::PcileechDeviceId => "Device ID",
+        TextKey::PcileechPcieLink => "PCIe link",
+        TextKey::PcileechTargetOs => "Target OS",
+        TextKey::PcileechOsBuild => "OS build",
+        TextKey::PcileechKernelBase => "Kernel base",
+        TextKey::PcileechDtb => "DTB",
+        TextKey::PcileechNtdllBase => "ntdll.dll base",
+
+        // PCILeech benchmark
+        TextKey::PcileechModeCheck => "Connection check",
+        TextKey::PcileechModeBenchmark => "Throughput benchmark",
+        TextKey::PcileechBenchmarkUnavailable => {
+            "Set the benchmark arguments of a test tool with a read-loop mode in the profile"
+        }
+        TextKey::PcileechBenchmarkDuration => "Duration:",
+        TextKey::PcileechSeconds => "{} s",
+        TextKey::PcileechMeasuring => "Measuring read throughput...",
+        TextKey::PcileechBenchmarkDone => "Benchmark complete",
+        TextKey::PcileechThroughputMin => "Min",
+        TextKey::PcileechThroughputAverage => "Average",
+        TextKey::PcileechThroughputMax => "Max",
+        TextKey::PcileechSamples => "{} samples",
+
+        // PCILeech soak test
+        TextKey::PcileechModeSoak => "Soak test",
+        TextKey::PcileechSoakLength => "Length:",
+        TextKey::PcileechSoakGap => "Pause:",
+        TextKey::PcileechSoakRuns => "{} runs",
+        TextKey::PcileechSoakMinutes => "{} min",
+        TextKey::PcileechSoakRunning => "Running soak test...",
+        TextKey::PcileechSoakProgress => "Attempt {}: {} passed, {} failed",
+        TextKey::PcileechSoakStop => "Stop",
+        TextKey::PcileechSoakDone => "Soak test complete",
+        TextKey::PcileechSoakStopped => "Soak test stopped",
+        TextKey::PcileechSoakSuccessRate => "Success rate: {}% ({} of {})",
+        TextKey::PcileechSoakTimeToSuccess => "Time to success",
+        TextKey::PcileechSoakFailures => "Failures",
+        TextKey::PcileechSoakOtherFailures => "{} more with other reasons",
+
+        // Firmware search locations
+        TextKey::BrowseFirmware => "Browse...",
+        TextKey::FirmwareLocations => "Search locations",
+        TextKey::DropFirmwareHint => "Or drop a .bin file onto this window",
+        TextKey::FirmwareLocationsHint => "These folders are searched after the built-in ones",
+        TextKey::FirmwareLocationsEmpty => "No folders added yet",
+        TextKey::AddFirmwareFolder => "Add folder",
+        TextKey::RemoveFirmwareFolder => "Remove",
+        TextKey::IncludeSubfolders => "Include subfolders",
+        TextKey::ScanDepth => "Depth:",
+        TextKey::IgnorePatternsLabel => {
+            "Ignore names matching (one pattern per line, * and ? allowed):"
+        }
+        TextKey::SaveIgnorePatterns => "Save patterns",
+        TextKey::IgnorePatternsSaved => "Ignore patterns saved",
+        TextKey::FirmwareFolderAdded => "Added {}",
+        TextKey::FirmwareFolderAlreadyListed => "{} is already a search location",
+        TextKey::PickedFileNotFirmware => "{} does not contain a firmware image",
+        TextKey::BackToFirmwareSelection => "Back",
+        TextKey::IdenticalCopies => "Identical copies:",
+        TextKey::SignatureCheck => "Firmware signature:",
+        TextKey::SignatureDescription => {
+            "Verify the image's Ed25519 signature against trusted keys"
+        }
+        TextKey::SignaturePinned => "Set by this installation and cannot be changed",
+        TextKey::SignatureOff => "Off",
+        TextKey::SignatureWarn => "Warn",
+        TextKey::SignatureBlock => "Block",
+        TextKey::SignatureVerified => "Signature verified: signed by {} (key {})",
+        TextKey::SignatureMissing => "Firmware is not signed: no .sig file next to the image",
+        TextKey::SignatureInvalid => "Firmware signature is invalid: {}",
+        TextKey::SignatureMalformed => "the .sig file is not a 64-byte Ed25519 signature",
+        TextKey::SignatureNoTrustedKeys => "no trusted signing keys are installed",
+        TextKey::SignatureNoMatchingKey => "it was not made by any trusted key",
     }
 }
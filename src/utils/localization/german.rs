@@ -13,6 +13,25 @@ pub fn get_text(key: TextKey) -> &'static str {
         TextKey::Copied => "Kopiert:\n{}",
         TextKey::CheckingFiles => "Dateien werden überprüft...",
         TextKey::MissingFiles => "Fehlende Dateien",
+        TextKey::DamagedFiles => "Fehlende oder beschädigte Dateien",
+        TextKey::FileStateMissing => "fehlt",
+        TextKey::FileStateCorrupt => "beschädigt",
+        TextKey::FileStateModified => "verändert",
+        TextKey::ReferencedByScript => "benötigt von {}",
+        TextKey::ConfigWarnings => "OpenOCD-Konfigurationswarnungen",
+        TextKey::PresenceOnlyNote => {
+            "Ohne lesbare payload_manifest.json wurden die übrigen Dateien nur auf Vorhandensein geprüft, nicht auf Beschädigung"
+        }
+        TextKey::LintAdapterSpeedConflict => "adapter speed {} widerspricht {} aus {}",
+        TextKey::LintAdapterSpeedOutlier => {
+            "adapter speed {} weicht von {} der anderen {}-Profile ab"
+        }
+        TextKey::LintAdapterDriver => "adapter driver {} passt nicht zum {}-Profil",
+        TextKey::LintUsbId => "USB-ID {} ist kein bekannter {}-Adapter",
+        TextKey::LintBitstreamDensity => "lädt {} ({}) in einer {}-Konfiguration",
+        TextKey::LintMissingExit => "endet nicht mit exit oder shutdown",
+        TextKey::LintDuplicateProc => "proc {} ist bereits in {} definiert",
+        TextKey::LintUnreadableScript => "kann nicht gelesen werden: {}",
         TextKey::FileCheckSuccess => "Alle Dateien erfolgreich validiert!",
         TextKey::SystemCheck => "Systemprüfung",
         TextKey::WelcomeMessage => "Willkommen beim {} Tool",
@@ -94,8 +113,10 @@ pub fn get_text(key: TextKey) -> &'static str {
         TextKey::Dna_Rs232_75T_Desc => "DNA von 75T-Boards mit RS232-Schnittstelle lesen",
         TextKey::Dna_Rs232_100T_Label => "RS232 - DNA-Lesung: 100T",
         TextKey::Dna_Rs232_100T_Desc => "DNA von 100T-Boards mit RS232-Schnittstelle lesen",
+        TextKey::LastUsed => "zuletzt verwendet",
 
         TextKey::ClearLog => "Protokoll löschen",
+        TextKey::DebugLogging => "Debug-Ausgabe",
 
         TextKey::OperationTook => "Vorgang dauerte",
         TextKey::NoteFewerSectors => {
@@ -171,14 +192,190 @@ pub fn get_text(key: TextKey) -> &'static str {
 
         TextKey::DnaInvalidOption => "Ungültige Option für DNA-Lesung",
         TextKey::DnaCommandFailed => "Fehler beim Ausführen des DNA-Lesebefehls",
-        TextKey::DnaFileNotFound => "DNA-Ausgabedatei nach {} Versuchen nicht gefunden",
         TextKey::DnaExtractFailed => "Fehler beim Extrahieren der DNA: {}",
-        TextKey::DnaFileReadError => "Fehler beim Lesen der DNA-Ausgabedatei bei {}: {}",
-        TextKey::DnaInfoNotFound => "DNA-Informationen in der Ausgabedatei nicht gefunden",
+        TextKey::DnaInfoNotFound => "DNA-Informationen in der OpenOCD-Ausgabe nicht gefunden",
         TextKey::DnaWaitingStart => "Warten auf Start der DNA-Lesung...",
         TextKey::DnaRetrieving => "Geräte-DNA wird abgerufen...",
         TextKey::DnaReadSuccessStatus => "DNA-Lesung erfolgreich!",
         TextKey::DnaOperationCompleted => "Vorgang abgeschlossen (Nicht-DNA)",
         TextKey::DnaReadFailedStatus => "DNA-Lesung fehlgeschlagen: {}",
+
+        // Watchdog
+        TextKey::AdapterStoppedResponding => "ADAPTER REAGIERT NICHT MEHR",
+        TextKey::AdapterNoOutputMsg => {
+            "OpenOCD hat {} Sekunden lang keine Ausgabe erzeugt und wurde beendet.\n\nPrüfen Sie das JTAG-Kabel und die Stromversorgung des Adapters, stecken Sie den Adapter neu ein und versuchen Sie es erneut."
+        }
+        TextKey::AdapterRuntimeExceededMsg => {
+            "Der Vorgang wurde nicht innerhalb von {} Sekunden abgeschlossen und wurde beendet.\n\nPrüfen Sie das JTAG-Kabel und die Stromversorgung des Adapters, stecken Sie den Adapter neu ein und versuchen Sie es erneut."
+        }
+
+        // Adapter selection
+        TextKey::AdapterSectionTitle => "USB-Adapter",
+        TextKey::AdapterAuto => "Automatisch (erster gefundener)",
+        TextKey::AdapterRefresh => "Aktualisieren",
+        TextKey::AdapterScanning => "Adapter werden gesucht...",
+        TextKey::AdapterNoneFound => "Keine Adapter gefunden",
+        TextKey::AdapterScanFailed => "Adaptersuche fehlgeschlagen: {}",
+        TextKey::AdapterNoSerial => "(keine Seriennummer)",
+        TextKey::JtagSpeed => "JTAG",
+        TextKey::JtagSpeedProfileDefault => "Profilgeschwindigkeit",
+        TextKey::AdapterLabel => "Adapter:",
+
+        // DNA history
+        TextKey::DnaHistory => "DNA-Verlauf",
+        TextKey::DnaHistoryDesc => "Zuvor gelesene Geräte-DNA suchen und exportieren",
+        TextKey::DnaHistorySearchHint => "Nach Teil der DNA (Hex) suchen",
+        TextKey::DnaHistoryEmpty => "Noch keine DNA-Lesevorgänge gespeichert",
+        TextKey::DnaHistoryNoMatches => "Keine DNA-Werte entsprechen der Suche",
+        TextKey::DnaHistoryColDna => "DNA",
+        TextKey::DnaHistoryColDensity => "Größe",
+        TextKey::DnaHistoryColAdapter => "Adapter",
+        TextKey::DnaHistoryColReads => "Lesungen",
+        TextKey::DnaHistoryColLastRead => "Zuletzt gelesen (UTC)",
+        TextKey::DnaHistoryFirstRead => "Erstmals gelesen (UTC):",
+        TextKey::DnaHistoryNote => "Notiz:",
+        TextKey::DnaHistorySaveNote => "Notiz speichern",
+        TextKey::DnaHistoryNoteSaved => "Notiz gespeichert",
+        TextKey::DnaHistoryExportCsv => "CSV exportieren",
+        TextKey::DnaHistoryExportJson => "JSON exportieren",
+        TextKey::DnaHistoryExported => "Exportiert nach {}",
+        TextKey::DnaHistoryExportFailed => "Export fehlgeschlagen: {}",
+
+        // DNA representations
+        TextKey::DnaFormatsHeader => "Weitere Formate",
+        TextKey::DnaFormatHex => "Hex",
+        TextKey::DnaFormatHex57 => "57-Bit-Hex",
+        TextKey::DnaFormatBinary => "Binär",
+        TextKey::DnaFormatByteReversed => "Bytes umgekehrt",
+        TextKey::DnaFormatBase64 => "Base64",
+        TextKey::DnaCopyValue => "Kopieren",
+
+        // DNA allowlist
+        TextKey::DnaLicensedTo => "Lizenziert für {}",
+        TextKey::DnaUnknownBoard => "Unbekanntes Board",
+        TextKey::DnaRevoked => "Widerrufen",
+        TextKey::DnaRevokedFrom => "Widerrufen: {}",
+
+        // DNA lock pre-flight
+        TextKey::DnaLockCheck => "Firmware-DNA-Sperre:",
+        TextKey::DnaLockOff => "Aus",
+        TextKey::DnaLockWarn => "Warnen",
+        TextKey::DnaLockBlock => "Blockieren",
+        TextKey::DnaLockDescription => {
+            "Liest zuerst die Board-DNA und prüft, an welches Board die Firmware gebunden ist"
+        }
+        TextKey::DnaLockChecking => "Board-DNA wird vor dem Flashen gelesen...",
+        TextKey::DnaLockReadFailed => "Board-DNA konnte vor dem Flashen nicht gelesen werden: {}",
+        TextKey::DnaLockMismatch => {
+            "Firmware ist an DNA {} gebunden ({} bei Offset {}), dieses Board ist aber {}"
+        }
+        TextKey::DnaLockBoardNotFound => {
+            "Die DNA {} dieses Boards wurde in der Firmware nicht gefunden"
+        }
+
+        // Firmware manifests
+        TextKey::FirmwareTargetFilter => "Optionen für {}-Firmware",
+
+        // PCILeech test profiles
+        TextKey::PcileechProfile => "Profil:",
+        TextKey::PcileechProfileEdit => "Profil bearbeiten",
+        TextKey::PcileechProfileName => "Name",
+        TextKey::PcileechProfileExecutable => "Testwerkzeug",
+        TextKey::PcileechProfileConnector => "Konnektor",
+        TextKey::PcileechProfileConnectorArgs => "Konnektor-Argumente",
+        TextKey::PcileechProfileConnectorArgsHint => "z. B. device=FPGA",
+        TextKey::PcileechProfileBenchmarkArgs => "Benchmark-Argumente",
+        TextKey::PcileechProfileSave => "Profil speichern",
+        TextKey::PcileechProfileDelete => "Profil löschen",
+        TextKey::PcileechProfileSaved => "Profil {} gespeichert",
+        TextKey::PcileechProfileDeleted => "Profil {} gelöscht",
+        TextKey::PcileechProfileMissingName => "Profilnamen eingeben",
+        TextKey::PcileechProfileMissingExecutable => "Pfad zum Testwerkzeug eingeben",
+        TextKey::PcileechProfileInvalidConnector => {
+            "Der Konnektor muss ein einzelner Name ohne Leerzeichen oder ':' sein"
+        }
+        TextKey::PcileechProfileInvalidConnectorArgs => {
+            "Nur der pcileech-Konnektor nimmt Argumente, als device=<Name>"
+        }
+
+        // PCILeech report
+        TextKey::PcileechFpgaDevice => "FPGA-Gerät",
+        TextKey::PcileechFpgaId => "FPGA-ID",
+        TextKey::PcileechFpgaVersion => "FPGA-Version",
+        TextKey::PcileechDeviceId => "Geräte-ID",
+        TextKey::PcileechPcieLink => "PCIe-Verbindung",
+        TextKey::PcileechTargetOs => "Zielsystem",
+        TextKey::PcileechOsBuild => "OS-Build",
+        TextKey::PcileechKernelBase => "Kernel-Basis",
+        TextKey::PcileechDtb => "DTB",
+        TextKey::PcileechNtdllBase => "ntdll.dll-Basis",
+
+        // PCILeech benchmark
+        TextKey::PcileechModeCheck => "Verbindungstest",
+        TextKey::PcileechModeBenchmark => "Durchsatz-Benchmark",
+        TextKey::PcileechBenchmarkUnavailable => {
+            "Im Profil die Benchmark-Argumente eines Testwerkzeugs mit Lese-Schleife festlegen"
+        }
+        TextKey::PcileechBenchmarkDuration => "Dauer:",
+        TextKey::PcileechSeconds => "{} s",
+        TextKey::PcileechMeasuring => "Lesedurchsatz wird gemessen...",
+        TextKey::PcileechBenchmarkDone => "Benchmark abgeschlossen",
+        TextKey::PcileechThroughputMin => "Min.",
+        TextKey::PcileechThroughputAverage => "Durchschnitt",
+        TextKey::PcileechThroughputMax => "Max.",
+        TextKey::PcileechSamples => "{} Messwerte",
+
+        // PCILeech soak test
+        TextKey::PcileechModeSoak => "Dauertest",
+        TextKey::PcileechSoakLength => "Umfang:",
+        TextKey::PcileechSoakGap => "Pause:",
+        TextKey::PcileechSoakRuns => "{} Durchläufe",
+        TextKey::PcileechSoakMinutes => "{} Min.",
+        TextKey::PcileechSoakRunning => "Dauertest läuft...",
+        TextKey::PcileechSoakProgress => "Versuch {}: {} bestanden, {} fehlgeschlagen",
+        TextKey::PcileechSoakStop => "Stoppen",
+        TextKey::PcileechSoakDone => "Dauertest abgeschlossen",
+        TextKey::PcileechSoakStopped => "Dauertest gestoppt",
+        TextKey::PcileechSoakSuccessRate => "Erfolgsquote: {} % ({} von {})",
+        TextKey::PcileechSoakTimeToSuccess => "Zeit bis zum Erfolg",
+        TextKey::PcileechSoakFailures => "Fehler",
+        TextKey::PcileechSoakOtherFailures => "{} weitere aus anderen Gründen",
+
+        // Firmware search locations
+        TextKey::BrowseFirmware => "Durchsuchen...",
+        TextKey::FirmwareLocations => "Suchorte",
+        TextKey::DropFirmwareHint => "Oder eine .bin-Datei auf dieses Fenster ziehen",
+        TextKey::FirmwareLocationsHint => "Diese Ordner werden nach den eingebauten durchsucht",
+        TextKey::FirmwareLocationsEmpty => "Noch keine Ordner hinzugefügt",
+        TextKey::AddFirmwareFolder => "Ordner hinzufügen",
+        TextKey::RemoveFirmwareFolder => "Entfernen",
+        TextKey::IncludeSubfolders => "Unterordner einbeziehen",
+        TextKey::ScanDepth => "Tiefe:",
+        TextKey::IgnorePatternsLabel => {
+            "Namen ignorieren, die passen (ein Muster pro Zeile, * und ? erlaubt):"
+        }
+        TextKey::SaveIgnorePatterns => "Muster speichern",
+        TextKey::IgnorePatternsSaved => "Ignoriermuster gespeichert",
+        TextKey::FirmwareFolderAdded => "{} hinzugefügt",
+        TextKey::FirmwareFolderAlreadyListed => "{} ist bereits ein Suchort",
+        TextKey::PickedFileNotFirmware => "{} enthält kein Firmware-Image",
+        TextKey::BackToFirmwareSelection => "Zurück",
+        TextKey::IdenticalCopies => "Identische Kopien:",
+        TextKey::SignatureCheck => "Firmware-Signatur:",
+        TextKey::SignatureDescription => {
+            "Ed25519-Signatur des Images mit vertrauenswürdigen Schlüsseln prüfen"
+        }
+        TextKey::SignaturePinned => "Von dieser Installation festgelegt und nicht änderbar",
+        TextKey::SignatureOff => "Aus",
+        TextKey::SignatureWarn => "Warnen",
+        TextKey::SignatureBlock => "Blockieren",
+        TextKey::SignatureVerified => "Signatur geprüft: signiert von {} (Schlüssel {})",
+        TextKey::SignatureMissing => {
+            "Firmware ist nicht signiert: keine .sig-Datei neben dem Image"
+        }
+        TextKey::SignatureInvalid => "Firmware-Signatur ist ungültig: {}",
+        TextKey::SignatureMalformed => "die .sig-Datei ist keine 64-Byte-Ed25519-Signatur",
+        TextKey::SignatureNoTrustedKeys => "keine vertrauenswürdigen Signaturschlüssel installiert",
+        TextKey::SignatureNoMatchingKey => "sie stammt von keinem vertrauenswürdigen Schlüssel",
     }
 }
@@ -5,11 +5,12 @@ pub mod german;
 pub mod portuguese;
 pub mod reshaper;
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Mutex;
 use std::sync::OnceLock;
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum Language {
     English,
     Chinese,
@@ -32,6 +33,21 @@ pub enum TextKey {
     Copied,
     CheckingFiles,
     MissingFiles,
+    DamagedFiles,
+    FileStateMissing,
+    FileStateCorrupt,
+    FileStateModified,
+    ReferencedByScript,
+    ConfigWarnings,
+    PresenceOnlyNote,
+    LintAdapterSpeedConflict,
+    LintAdapterSpeedOutlier,
+    LintAdapterDriver,
+    LintUsbId,
+    LintBitstreamDensity,
+    LintMissingExit,
+    LintDuplicateProc,
+    LintUnreadableScript,
     FileCheckSuccess,
     SystemCheck,
     WelcomeMessage,
@@ -107,8 +123,10 @@ pub enum TextKey {
     Dna_Rs232_75T_Desc,
     Dna_Rs232_100T_Label,
     Dna_Rs232_100T_Desc,
+    LastUsed,
     // Log View
     ClearLog,
+    DebugLogging,
 
     // Result Extras
     OperationTook,
@@ -166,15 +184,170 @@ pub enum TextKey {
     // DNA Backend & Status
     DnaInvalidOption,
     DnaCommandFailed,
-    DnaFileNotFound,
     DnaExtractFailed,
-    DnaFileReadError,
     DnaInfoNotFound,
     DnaWaitingStart,
     DnaRetrieving,
     DnaReadSuccessStatus,
     DnaOperationCompleted,
     DnaReadFailedStatus,
+
+    // Watchdog
+    AdapterStoppedResponding,
+    AdapterNoOutputMsg,
+    AdapterRuntimeExceededMsg,
+
+    // Adapter selection
+    AdapterSectionTitle,
+    AdapterAuto,
+    AdapterRefresh,
+    AdapterScanning,
+    AdapterNoneFound,
+    AdapterScanFailed,
+    AdapterNoSerial,
+    JtagSpeed,
+    JtagSpeedProfileDefault,
+    AdapterLabel,
+
+    // DNA history
+    DnaHistory,
+    DnaHistoryDesc,
+    DnaHistorySearchHint,
+    DnaHistoryEmpty,
+    DnaHistoryNoMatches,
+    DnaHistoryColDna,
+    DnaHistoryColDensity,
+    DnaHistoryColAdapter,
+    DnaHistoryColReads,
+    DnaHistoryColLastRead,
+    DnaHistoryFirstRead,
+    DnaHistoryNote,
+    DnaHistorySaveNote,
+    DnaHistoryNoteSaved,
+    DnaHistoryExportCsv,
+    DnaHistoryExportJson,
+    DnaHistoryExported,
+    DnaHistoryExportFailed,
+
+    // DNA representations
+    DnaFormatsHeader,
+    DnaFormatHex,
+    DnaFormatHex57,
+    DnaFormatBinary,
+    DnaFormatByteReversed,
+    DnaFormatBase64,
+    DnaCopyValue,
+
+    // DNA allowlist
+    DnaLicensedTo,
+    DnaUnknownBoard,
+    DnaRevoked,
+    DnaRevokedFrom,
+
+    // DNA lock pre-flight
+    DnaLockCheck,
+    DnaLockOff,
+    DnaLockWarn,
+    DnaLockBlock,
+    DnaLockDescription,
+    DnaLockChecking,
+    DnaLockReadFailed,
+    DnaLockMismatch,
+    DnaLockBoardNotFound,
+
+    // Firmware manifests
+    FirmwareTargetFilter,
+
+    // PCILeech test profiles
+    PcileechProfile,
+    PcileechProfileEdit,
+    PcileechProfileName,
+    PcileechProfileExecutable,
+    PcileechProfileConnector,
+    PcileechProfileConnectorArgs,
+    PcileechProfileConnectorArgsHint,
+    PcileechProfileBenchmarkArgs,
+    PcileechProfileSave,
+    PcileechProfileDelete,
+    PcileechProfileSaved,
+    PcileechProfileDeleted,
+    PcileechProfileMissingName,
+    PcileechProfileMissingExecutable,
+    PcileechProfileInvalidConnector,
+    PcileechProfileInvalidConnectorArgs,
+
+    // PCILeech report
+    PcileechFpgaDevice,
+    PcileechFpgaId,
+    PcileechFpgaVersion,
+    PcileechDeviceId,
+    PcileechPcieLink,
+    PcileechTargetOs,
+    PcileechOsBuild,
+    PcileechKernelBase,
+    PcileechDtb,
+    PcileechNtdllBase,
+
+    // PCILeech benchmark
+    PcileechModeCheck,
+    PcileechModeBenchmark,
+    PcileechBenchmarkUnavailable,
+    PcileechBenchmarkDuration,
+    PcileechSeconds,
+    PcileechMeasuring,
+    PcileechBenchmarkDone,
+    PcileechThroughputMin,
+    PcileechThroughputAverage,
+    PcileechThroughputMax,
+    PcileechSamples,
+
+    // PCILeech soak test
+    PcileechModeSoak,
+    PcileechSoakLength,
+    PcileechSoakGap,
+    PcileechSoakRuns,
+    PcileechSoakMinutes,
+    PcileechSoakRunning,
+    PcileechSoakProgress,
+    PcileechSoakStop,
+    PcileechSoakDone,
+    PcileechSoakStopped,
+    PcileechSoakSuccessRate,
+    PcileechSoakTimeToSuccess,
+    PcileechSoakFailures,
+    PcileechSoakOtherFailures,
+
+    // Firmware search locations
+    BrowseFirmware,
+    FirmwareLocations,
+    DropFirmwareHint,
+    FirmwareLocationsHint,
+    FirmwareLocationsEmpty,
+    AddFirmwareFolder,
+    RemoveFirmwareFolder,
+    IncludeSubfolders,
+    ScanDepth,
+    IgnorePatternsLabel,
+    SaveIgnorePatterns,
+    IgnorePatternsSaved,
+    FirmwareFolderAdded,
+    FirmwareFolderAlreadyListed,
+    PickedFileNotFirmware,
+    BackToFirmwareSelection,
+    IdenticalCopies,
+    // Firmware signatures
+    SignatureCheck,
+    SignatureDescription,
+    SignaturePinned,
+    SignatureOff,
+    SignatureWarn,
+    SignatureBlock,
+    SignatureVerified,
+    SignatureMissing,
+    SignatureInvalid,
+    SignatureMalformed,
+    SignatureNoTrustedKeys,
+    SignatureNoMatchingKey,
 }
 
 // Use a static cache to store reshaped Arabic strings so we can return &'static str.
@@ -13,6 +13,25 @@ pub fn get_text(key: TextKey) -> &'static str {
         TextKey::Copied => "Copiado:\n{}",
         TextKey::CheckingFiles => "Verificando arquivos...",
         TextKey::MissingFiles => "Arquivos Ausentes",
+        TextKey::DamagedFiles => "Arquivos ausentes ou danificados",
+        TextKey::FileStateMissing => "ausente",
+        TextKey::FileStateCorrupt => "corrompido",
+        TextKey::FileStateModified => "modificado",
+        TextKey::ReferencedByScript => "necessário para {}",
+        TextKey::ConfigWarnings => "Avisos de configuração do OpenOCD",
+        TextKey::PresenceOnlyNote => {
+            "Sem um payload_manifest.json utilizável, os outros arquivos foram verificados apenas quanto à presença, não quanto a danos"
+        }
+        TextKey::LintAdapterSpeedConflict => "adapter speed {} conflita com {} definido em {}",
+        TextKey::LintAdapterSpeedOutlier => {
+            "adapter speed {} difere de {} usado pelos outros perfis {}"
+        }
+        TextKey::LintAdapterDriver => "adapter driver {} não corresponde ao perfil {}",
+        TextKey::LintUsbId => "ID USB {} não é um adaptador {} conhecido",
+        TextKey::LintBitstreamDensity => "carrega {} ({}) em uma configuração {}",
+        TextKey::LintMissingExit => "não termina com exit ou shutdown",
+        TextKey::LintDuplicateProc => "proc {} já está definido em {}",
+        TextKey::LintUnreadableScript => "não pode ser lido: {}",
         TextKey::FileCheckSuccess => "Todos os arquivos validados com sucesso!",
         TextKey::SystemCheck => "Verificação do Sistema",
         TextKey::WelcomeMessage => "Bem-vindo à Ferramenta {}",
@@ -96,8 +115,10 @@ pub fn get_text(key: TextKey) -> &'static str {
         TextKey::Dna_Rs232_75T_Desc => "Ler DNA de placas 75T usando interface RS232",
         TextKey::Dna_Rs232_100T_Label => "RS232 - Leitura de DNA: 100T",
         TextKey::Dna_Rs232_100T_Desc => "Ler DNA de placas 100T usando interface RS232",
+        TextKey::LastUsed => "último usado",
 
         TextKey::ClearLog => "Limpar Registro",
+        TextKey::DebugLogging => "Saída de depuração",
 
         TextKey::OperationTook => "Operação levou",
         TextKey::NoteFewerSectors => {
@@ -173,16 +194,190 @@ pub fn get_text(key: TextKey) -> &'static str {
 
         TextKey::DnaInvalidOption => "Opção inválida para leitura de DNA",
         TextKey::DnaCommandFailed => "Falha ao executar comando de leitura de DNA",
-        TextKey::DnaFileNotFound => "Arquivo de saída de DNA não encontrado após {} tentativas",
         TextKey::DnaExtractFailed => "Falha ao extrair DNA: {}",
-        TextKey::DnaFileReadError => "Falha ao ler arquivo de saída de DNA em {}: {}",
         TextKey::DnaInfoNotFound => {
-            "Não foi possível encontrar informações de DNA no arquivo de saída"
+            "Não foi possível encontrar informações de DNA na saída do OpenOCD"
         }
         TextKey::DnaWaitingStart => "Aguardando início da leitura de DNA...",
         TextKey::DnaRetrieving => "Recuperando DNA do dispositivo...",
         TextKey::DnaReadSuccessStatus => "Leitura de DNA bem-sucedida!",
         TextKey::DnaOperationCompleted => "Operação concluída (Não-DNA)",
         TextKey::DnaReadFailedStatus => "Leitura de DNA falhou: {}",
+
+        // Watchdog
+        TextKey::AdapterStoppedResponding => "O ADAPTADOR PAROU DE RESPONDER",
+        TextKey::AdapterNoOutputMsg => {
+            "O OpenOCD não produziu saída por {} segundos e foi interrompido.\n\nVerifique o cabo JTAG e a alimentação do adaptador, reconecte o adaptador e tente novamente."
+        }
+        TextKey::AdapterRuntimeExceededMsg => {
+            "A operação não terminou em {} segundos e foi interrompida.\n\nVerifique o cabo JTAG e a alimentação do adaptador, reconecte o adaptador e tente novamente."
+        }
+
+        // Adapter selection
+        TextKey::AdapterSectionTitle => "Adaptador USB",
+        TextKey::AdapterAuto => "Automático (primeiro detectado)",
+        TextKey::AdapterRefresh => "Atualizar",
+        TextKey::AdapterScanning => "Detectando adaptadores...",
+        TextKey::AdapterNoneFound => "Nenhum adaptador detectado",
+        TextKey::AdapterScanFailed => "Falha na detecção de adaptadores: {}",
+        TextKey::AdapterNoSerial => "(sem número de série)",
+        TextKey::JtagSpeed => "JTAG",
+        TextKey::JtagSpeedProfileDefault => "Velocidade do perfil",
+        TextKey::AdapterLabel => "Adaptador:",
+
+        // DNA history
+        TextKey::DnaHistory => "Histórico de DNA",
+        TextKey::DnaHistoryDesc => "Pesquisar e exportar DNA de dispositivos lidos anteriormente",
+        TextKey::DnaHistorySearchHint => "Pesquisar por parte do DNA em hex",
+        TextKey::DnaHistoryEmpty => "Nenhuma leitura de DNA registrada ainda",
+        TextKey::DnaHistoryNoMatches => "Nenhum valor de DNA corresponde à pesquisa",
+        TextKey::DnaHistoryColDna => "DNA",
+        TextKey::DnaHistoryColDensity => "Densidade",
+        TextKey::DnaHistoryColAdapter => "Adaptador",
+        TextKey::DnaHistoryColReads => "Leituras",
+        TextKey::DnaHistoryColLastRead => "Última leitura (UTC)",
+        TextKey::DnaHistoryFirstRead => "Primeira leitura (UTC):",
+        TextKey::DnaHistoryNote => "Nota:",
+        TextKey::DnaHistorySaveNote => "Salvar nota",
+        TextKey::DnaHistoryNoteSaved => "Nota salva",
+        TextKey::DnaHistoryExportCsv => "Exportar CSV",
+        TextKey::DnaHistoryExportJson => "Exportar JSON",
+        TextKey::DnaHistoryExported => "Exportado para {}",
+        TextKey::DnaHistoryExportFailed => "Falha na exportação: {}",
+
+        // DNA representations
+        TextKey::DnaFormatsHeader => "Outros formatos",
+        TextKey::DnaFormatHex => "Hex",
+        TextKey::DnaFormatHex57 => "Hex de 57 bits",
+        TextKey::DnaFormatBinary => "Binário",
+        TextKey::DnaFormatByteReversed => "Bytes invertidos",
+        TextKey::DnaFormatBase64 => "Base64",
+        TextKey::DnaCopyValue => "Copiar",
+
+        // DNA allowlist
+        TextKey::DnaLicensedTo => "Licenciado para {}",
+        TextKey::DnaUnknownBoard => "Placa desconhecida",
+        TextKey::DnaRevoked => "Revogado",
+        TextKey::DnaRevokedFrom => "Revogado: {}",
+
+        // DNA lock pre-flight
+        TextKey::DnaLockCheck => "Bloqueio de DNA do firmware:",
+        TextKey::DnaLockOff => "Desligado",
+        TextKey::DnaLockWarn => "Avisar",
+        TextKey::DnaLockBlock => "Bloquear",
+        TextKey::DnaLockDescription => {
+            "Lê o DNA da placa primeiro e verifica a qual placa o firmware está bloqueado"
+        }
+        TextKey::DnaLockChecking => "Lendo o DNA da placa antes da gravação...",
+        TextKey::DnaLockReadFailed => "Não foi possível ler o DNA da placa antes da gravação: {}",
+        TextKey::DnaLockMismatch => {
+            "O firmware está bloqueado ao DNA {} ({} no deslocamento {}), mas esta placa é {}"
+        }
+        TextKey::DnaLockBoardNotFound => "O DNA {} desta placa não foi encontrado no firmware",
+
+        // Firmware manifests
+        TextKey::FirmwareTargetFilter => "Mostrando opções para firmware {}",
+
+        // PCILeech test profiles
+        TextKey::PcileechProfile => "Perfil:",
+        TextKey::PcileechProfileEdit => "Editar perfil",
+        TextKey::PcileechProfileName => "Nome",
+        TextKey::PcileechProfileExecutable => "Ferramenta de teste",
+        TextKey::PcileechProfileConnector => "Conector",
+        TextKey::PcileechProfileConnectorArgs => "Argumentos do conector",
+        TextKey::PcileechProfileConnectorArgsHint => "ex.: device=FPGA",
+        TextKey::PcileechProfileBenchmarkArgs => "Argumentos do benchmark",
+        TextKey::PcileechProfileSave => "Salvar perfil",
+        TextKey::PcileechProfileDelete => "Excluir perfil",
+        TextKey::PcileechProfileSaved => "Perfil {} salvo",
+        TextKey::PcileechProfileDeleted => "Perfil {} excluído",
+        TextKey::PcileechProfileMissingName => "Informe um nome de perfil",
+        TextKey::PcileechProfileMissingExecutable => "Informe o caminho da ferramenta de teste",
+        TextKey::PcileechProfileInvalidConnector => {
+            "O conector deve ser um único nome sem espaços ou ':'"
+        }
+        TextKey::PcileechProfileInvalidConnectorArgs => {
+            "Apenas o conector pcileech aceita argumentos, como device=<nome>"
+        }
+
+        // PCILeech report
+        TextKey::PcileechFpgaDevice => "Dispositivo FPGA",
+        TextKey::PcileechFpgaId => "ID do FPGA",
+        TextKey::PcileechFpgaVersion => "Versão do FPGA",
+        TextKey::PcileechDeviceId => "ID do dispositivo",
+        TextKey::PcileechPcieLink => "Link PCIe",
+        TextKey::PcileechTargetOs => "SO de destino",
+        TextKey::PcileechOsBuild => "Build do SO",
+        TextKey::PcileechKernelBase => "Base do kernel",
+        TextKey::PcileechDtb => "DTB",
+        TextKey::PcileechNtdllBase => "Base do ntdll.dll",
+
+        // PCILeech benchmark
+        TextKey::PcileechModeCheck => "Verificação de conexão",
+        TextKey::PcileechModeBenchmark => "Teste de vazão",
+        TextKey::PcileechBenchmarkUnavailable => {
+            "Defina no perfil os argumentos de benchmark de uma ferramenta com modo de leitura contínua"
+        }
+        TextKey::PcileechBenchmarkDuration => "Duração:",
+        TextKey::PcileechSeconds => "{} s",
+        TextKey::PcileechMeasuring => "Medindo a vazão de leitura...",
+        TextKey::PcileechBenchmarkDone => "Teste concluído",
+        TextKey::PcileechThroughputMin => "Mín.",
+        TextKey::PcileechThroughputAverage => "Média",
+        TextKey::PcileechThroughputMax => "Máx.",
+        TextKey::PcileechSamples => "{} amostras",
+
+        // PCILeech soak test
+        TextKey::PcileechModeSoak => "Teste de estabilidade",
+        TextKey::PcileechSoakLength => "Duração:",
+        TextKey::PcileechSoakGap => "Pausa:",
+        TextKey::PcileechSoakRuns => "{} execuções",
+        TextKey::PcileechSoakMinutes => "{} min",
+        TextKey::PcileechSoakRunning => "Executando teste de estabilidade...",
+        TextKey::PcileechSoakProgress => "Tentativa {}: {} aprovadas, {} falharam",
+        TextKey::PcileechSoakStop => "Parar",
+        TextKey::PcileechSoakDone => "Teste de estabilidade concluído",
+        TextKey::PcileechSoakStopped => "Teste de estabilidade interrompido",
+        TextKey::PcileechSoakSuccessRate => "Taxa de sucesso: {}% ({} de {})",
+        TextKey::PcileechSoakTimeToSuccess => "Tempo até o sucesso",
+        TextKey::PcileechSoakFailures => "Falhas",
+        TextKey::PcileechSoakOtherFailures => "Mais {} por outros motivos",
+
+        // Firmware search locations
+        TextKey::BrowseFirmware => "Procurar...",
+        TextKey::FirmwareLocations => "Locais de busca",
+        TextKey::DropFirmwareHint => "Ou arraste um arquivo .bin para esta janela",
+        TextKey::FirmwareLocationsHint => "Estas pastas são pesquisadas após as integradas",
+        TextKey::FirmwareLocationsEmpty => "Nenhuma pasta adicionada ainda",
+        TextKey::AddFirmwareFolder => "Adicionar pasta",
+        TextKey::RemoveFirmwareFolder => "Remover",
+        TextKey::IncludeSubfolders => "Incluir subpastas",
+        TextKey::ScanDepth => "Profundidade:",
+        TextKey::IgnorePatternsLabel => {
+            "Ignorar nomes correspondentes (um padrão por linha, * e ? permitidos):"
+        }
+        TextKey::SaveIgnorePatterns => "Salvar padrões",
+        TextKey::IgnorePatternsSaved => "Padrões de exclusão salvos",
+        TextKey::FirmwareFolderAdded => "{} adicionada",
+        TextKey::FirmwareFolderAlreadyListed => "{} já é um local de busca",
+        TextKey::PickedFileNotFirmware => "{} não contém uma imagem de firmware",
+        TextKey::BackToFirmwareSelection => "Voltar",
+        TextKey::IdenticalCopies => "Cópias idênticas:",
+        TextKey::SignatureCheck => "Assinatura do firmware:",
+        TextKey::SignatureDescription => {
+            "Verificar a assinatura Ed25519 da imagem com chaves confiáveis"
+        }
+        TextKey::SignaturePinned => "Definido por esta instalação e não pode ser alterado",
+        TextKey::SignatureOff => "Desligado",
+        TextKey::SignatureWarn => "Avisar",
+        TextKey::SignatureBlock => "Bloquear",
+        TextKey::SignatureVerified => "Assinatura verificada: assinado por {} (chave {})",
+        TextKey::SignatureMissing => {
+            "Firmware não está assinado: nenhum arquivo .sig junto à imagem"
+        }
+        TextKey::SignatureInvalid => "Assinatura do firmware inválida: {}",
+        TextKey::SignatureMalformed => "o arquivo .sig não é uma assinatura Ed25519 de 64 bytes",
+        TextKey::SignatureNoTrustedKeys => "nenhuma chave de assinatura confiável instalada",
+        TextKey::SignatureNoMatchingKey => "ela não foi feita por nenhuma chave confiável",
     }
 }
@@ -132,11 +132,14 @@ impl Logger {
         format!("{:.2}s", elapsed.as_secs_f32())
     }
 
-    #[cfg(debug_assertions)]
     pub fn set_debug_mode(&self, debug: bool) {
         self.debug_mode.store(debug, Ordering::SeqCst);
     }
 
+    pub fn debug_mode(&self) -> bool {
+        self.debug_mode.load(Ordering::SeqCst)
+    }
+
     /// Debug-level logging — only outputs when debug mode is enabled.
     pub fn debug(&self, message: impl Into<String>) {
         // Only log if debug mode is enabled AND logger is enabled
@@ -1,10 +1,24 @@
+pub mod app_dirs;
 pub mod cleanup;
 #[cfg(not(feature = "branding"))]
 pub mod contact;
 pub mod file_checker;
+pub mod firmware_archive;
 pub mod firmware_discovery;
+pub mod firmware_hasher;
+pub mod firmware_locations;
+pub mod firmware_manifest;
+pub mod firmware_watch;
+pub mod json_store;
 pub mod localization;
 pub mod logger;
+pub mod openocd_lint;
+pub mod openocd_scripts;
+pub mod payload_manifest;
 pub mod process_job;
+pub mod settings;
+#[cfg(test)]
+pub(crate) mod temp_dir;
+#[cfg(windows)]
 pub mod win_utils;
 pub mod window;
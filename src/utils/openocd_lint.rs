@@ -0,0 +1,553 @@
+use crate::device_programmer::{AdapterKind, FlashingOption, TEMP_FIRMWARE_FILE};
+use crate::utils::localization::{Language, TextKey, format_translation, translate};
+use crate::utils::openocd_scripts::{
+    DependencyGraph, ScriptCommand, ScriptLocation, parse_commands,
+};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// What a lint finding is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintRule {
+    AdapterSpeedConflict,
+    AdapterSpeedOutlier,
+    AdapterDriver,
+    UsbId,
+    BitstreamDensity,
+    MissingExit,
+    DuplicateProc,
+    /// The entry script cannot be read, e.g. because it does not exist.
+    UnreadableScript,
+}
+
+impl LintRule {
+    fn text_key(&self) -> TextKey {
+        match self {
+            Self::AdapterSpeedConflict => TextKey::LintAdapterSpeedConflict,
+            Self::AdapterSpeedOutlier => TextKey::LintAdapterSpeedOutlier,
+            Self::AdapterDriver => TextKey::LintAdapterDriver,
+            Self::UsbId => TextKey::LintUsbId,
+            Self::BitstreamDensity => TextKey::LintBitstreamDensity,
+            Self::MissingExit => TextKey::LintMissingExit,
+            Self::DuplicateProc => TextKey::LintDuplicateProc,
+            Self::UnreadableScript => TextKey::LintUnreadableScript,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LintFinding {
+    pub location: ScriptLocation,
+    pub rule: LintRule,
+    /// Filled into the rule's message.
+    pub args: Vec<String>,
+}
+
+impl LintFinding {
+    pub fn describe(&self, lang: &Language) -> String {
+        let args: Vec<&str> = self.args.iter().map(String::as_str).collect();
+        format_translation(translate(self.rule.text_key(), lang), &args)
+    }
+}
+
+/// An entry script and what its profile expects of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintTarget {
+    pub script: String,
+    /// `None` takes the adapter from the script's `adapter driver`.
+    pub adapter: Option<AdapterKind>,
+    /// FPGA density the bitstream must match, e.g. `75T`.
+    pub density: Option<String>,
+}
+
+impl LintTarget {
+    /// The entry script of every profile. The density comes from the script
+    /// name, so a config pasted under the wrong name is caught.
+    pub fn bundled() -> Vec<Self> {
+        let mut targets: Vec<Self> = Vec::new();
+        for option in FlashingOption::ALL {
+            let script = option.get_command_args().1;
+            if targets.iter().any(|target| target.script == script) {
+                continue;
+            }
+            targets.push(Self {
+                script: script.to_string(),
+                adapter: Some(option.adapter_kind()),
+                density: density_in_name(script).or_else(|| option.density().map(str::to_string)),
+            });
+        }
+        targets
+    }
+
+    /// A config supplied by the user; only its name says what it targets.
+    pub fn user(script: &str) -> Self {
+        Self {
+            script: script.to_string(),
+            adapter: None,
+            density: density_in_name(script),
+        }
+    }
+}
+
+/// A script reached from a target, parsed once.
+struct ParsedScript {
+    name: String,
+    commands: Vec<ScriptCommand>,
+}
+
+/// Checks each target and the scripts it sources. Scripts shared between
+/// targets are reported once.
+pub fn lint(root: &Path, targets: &[LintTarget]) -> Vec<LintFinding> {
+    let mut findings = Vec::new();
+    let mut speeds: Vec<(Option<AdapterKind>, ScriptLocation, String)> = Vec::new();
+
+    for target in targets {
+        let scripts = match parse_target(root, target) {
+            Ok(scripts) => scripts,
+            Err(error) => {
+                findings.push(LintFinding {
+                    location: ScriptLocation {
+                        script: target.script.clone(),
+                        line: 1,
+                    },
+                    rule: LintRule::UnreadableScript,
+                    args: vec![error],
+                });
+                continue;
+            }
+        };
+        let entry = &scripts[0];
+        let adapter = target.adapter.or_else(|| declared_driver(entry));
+
+        if let Some(speed) = check_adapter_speed(&scripts, &mut findings) {
+            speeds.push((adapter, speed.0, speed.1));
+        }
+        check_usb_ids(&scripts, target.adapter, adapter, &mut findings);
+        if let Some(density) = &target.density {
+            check_bitstream_density(&scripts, density, &mut findings);
+        }
+        check_exit(entry, &mut findings);
+        check_duplicate_procs(&scripts, &mut findings);
+    }
+    check_speed_outliers(&speeds, &mut findings);
+
+    findings.sort();
+    findings.dedup();
+    findings
+}
+
+/// The entry script first, then every script it sources. Fails when the
+/// entry script itself cannot be read.
+fn parse_target(root: &Path, target: &LintTarget) -> Result<Vec<ParsedScript>, String> {
+    let graph = DependencyGraph::scan(root, &[&target.script], &[TEMP_FIRMWARE_FILE]);
+    let entry = graph
+        .edges
+        .keys()
+        .find(|script| Path::new(script.as_str()) == Path::new(&target.script))
+        .or_else(|| graph.edges.keys().next())
+        .cloned()
+        .unwrap_or_else(|| target.script.clone());
+
+    let mut names = vec![entry.clone()];
+    names.extend(graph.edges.keys().filter(|name| **name != entry).cloned());
+    names
+        .into_iter()
+        .map(|name| {
+            let text = match fs::read(root.join(&name)) {
+                Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+                Err(error) if name == entry => return Err(error.to_string()),
+                // A missing sourced script is reported by the file check.
+                Err(_) => String::new(),
+            };
+            Ok(ParsedScript {
+                commands: parse_commands(&text),
+                name,
+            })
+        })
+        .collect()
+}
+
+fn location(script: &ParsedScript, command: &ScriptCommand) -> ScriptLocation {
+    ScriptLocation {
+        script: script.name.clone(),
+        line: command.line,
+    }
+}
+
+fn top_level_commands(
+    scripts: &[ParsedScript],
+) -> impl Iterator<Item = (&ParsedScript, &ScriptCommand)> {
+    scripts.iter().flat_map(|script| {
+        script
+            .commands
+            .iter()
+            .filter(|command| command.top_level)
+            .map(move |command| (script, command))
+    })
+}
+
+fn words(command: &ScriptCommand) -> Vec<&str> {
+    command.words.iter().map(String::as_str).collect()
+}
+
+fn declared_driver(entry: &ParsedScript) -> Option<AdapterKind> {
+    entry
+        .commands
+        .iter()
+        .filter(|command| command.top_level)
+        .find_map(|command| match words(command).as_slice() {
+            ["adapter", "driver", driver, ..] | ["interface", driver, ..] => {
+                AdapterKind::from_openocd_driver(driver)
+            }
+            _ => None,
+        })
+}
+
+/// Flags a speed set differently later in the same profile and returns the
+/// speed the profile ends up with.
+fn check_adapter_speed(
+    scripts: &[ParsedScript],
+    findings: &mut Vec<LintFinding>,
+) -> Option<(ScriptLocation, String)> {
+    let mut first: Option<(ScriptLocation, String)> = None;
+    let mut last = None;
+    for (script, command) in top_level_commands(scripts) {
+        let speed = match words(command).as_slice() {
+            ["adapter", "speed", speed, ..] | ["adapter_khz", speed, ..] => speed.to_string(),
+            _ => continue,
+        };
+        let here = location(script, command);
+        match &first {
+            None => first = Some((here.clone(), speed.clone())),
+            Some((first_at, first_speed)) if *first_speed != speed => {
+                findings.push(LintFinding {
+                    location: here.clone(),
+                    rule: LintRule::AdapterSpeedConflict,
+                    args: vec![speed.clone(), first_speed.clone(), first_at.to_string()],
+                });
+            }
+            Some(_) => {}
+        }
+        last = Some((here, speed));
+    }
+    last
+}
+
+/// Flags profiles whose speed differs from the one most profiles of the same
+/// adapter use.
+fn check_speed_outliers(
+    speeds: &[(Option<AdapterKind>, ScriptLocation, String)],
+    findings: &mut Vec<LintFinding>,
+) {
+    let mut counts: HashMap<(Option<AdapterKind>, &str), usize> = HashMap::new();
+    for (adapter, _, speed) in speeds {
+        *counts.entry((*adapter, speed.as_str())).or_default() += 1;
+    }
+    for (adapter, at, speed) in speeds {
+        let Some((common, &count)) = counts
+            .iter()
+            .filter(|((kind, _), _)| kind == adapter)
+            .max_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then(b.1.cmp(a.1)))
+            .map(|((_, common), count)| (*common, count))
+        else {
+            continue;
+        };
+        if common != speed && count > 1 {
+            findings.push(LintFinding {
+                location: at.clone(),
+                rule: LintRule::AdapterSpeedOutlier,
+                args: vec![
+                    speed.clone(),
+                    common.to_string(),
+                    adapter.map_or("OpenOCD", |kind| kind.label()).to_string(),
+                ],
+            });
+        }
+    }
+}
+
+fn check_usb_ids(
+    scripts: &[ParsedScript],
+    expected: Option<AdapterKind>,
+    adapter: Option<AdapterKind>,
+    findings: &mut Vec<LintFinding>,
+) {
+    for (script, command) in top_level_commands(scripts) {
+        let words = words(command);
+        let ids = match words.as_slice() {
+            ["adapter", "driver", driver, ..] => {
+                if let Some(expected) = expected
+                    && *driver != expected.openocd_driver()
+                {
+                    findings.push(LintFinding {
+                        location: location(script, command),
+                        rule: LintRule::AdapterDriver,
+                        args: vec![driver.to_string(), expected.label().to_string()],
+                    });
+                }
+                continue;
+            }
+            ["ch347" | "ftdi", "vid_pid", ids @ ..] | ["ftdi_vid_pid", ids @ ..] => ids,
+            _ => continue,
+        };
+        let Some(adapter) = adapter else {
+            continue;
+        };
+        for [vendor_id, product_id] in ids.as_chunks::<2>().0 {
+            let (Some(vendor_id), Some(product_id)) = (parse_hex(vendor_id), parse_hex(product_id))
+            else {
+                continue;
+            };
+            if !adapter.is_known_usb_id(vendor_id, product_id) {
+                findings.push(LintFinding {
+                    location: location(script, command),
+                    rule: LintRule::UsbId,
+                    args: vec![
+                        format!("{vendor_id:04x}:{product_id:04x}"),
+                        adapter.label().to_string(),
+                    ],
+                });
+            }
+        }
+    }
+}
+
+fn check_bitstream_density(
+    scripts: &[ParsedScript],
+    density: &str,
+    findings: &mut Vec<LintFinding>,
+) {
+    for script in scripts {
+        for command in &script.commands {
+            let words = words(command);
+            let ["pld", "load", _, bitstream, ..] = words.as_slice() else {
+                continue;
+            };
+            let Some(loaded) = density_in_name(bitstream) else {
+                continue;
+            };
+            if !loaded.eq_ignore_ascii_case(density) {
+                findings.push(LintFinding {
+                    location: location(script, command),
+                    rule: LintRule::BitstreamDensity,
+                    args: vec![bitstream.to_string(), loaded, density.to_string()],
+                });
+            }
+        }
+    }
+}
+
+fn check_exit(entry: &ParsedScript, findings: &mut Vec<LintFinding>) {
+    let ends = entry.commands.iter().any(|command| {
+        command.top_level && matches!(command.words[0].as_str(), "exit" | "shutdown")
+    });
+    if !ends {
+        findings.push(LintFinding {
+            location: ScriptLocation {
+                script: entry.name.clone(),
+                line: entry.commands.last().map_or(1, |command| command.line),
+            },
+            rule: LintRule::MissingExit,
+            args: Vec::new(),
+        });
+    }
+}
+
+fn check_duplicate_procs(scripts: &[ParsedScript], findings: &mut Vec<LintFinding>) {
+    let mut defined: HashMap<&str, ScriptLocation> = HashMap::new();
+    for (script, command) in top_level_commands(scripts) {
+        let [keyword, name, ..] = command.words.as_slice() else {
+            continue;
+        };
+        if keyword != "proc" {
+            continue;
+        }
+        let here = location(script, command);
+        match defined.get(name.as_str()) {
+            Some(first) => findings.push(LintFinding {
+                location: here,
+                rule: LintRule::DuplicateProc,
+                args: vec![name.clone(), first.to_string()],
+            }),
+            None => {
+                defined.insert(name.as_str(), here);
+            }
+        }
+    }
+}
+
+/// `75T` for names like `xc7a75T.cfg` or `bscan_spi_xc7a75t.bit`.
+fn density_in_name(name: &str) -> Option<String> {
+    let file = Path::new(name)
+        .file_name()?
+        .to_string_lossy()
+        .to_ascii_lowercase();
+    let digits: String = file
+        .split("xc7a")
+        .nth(1)?
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    let rest = &file[file.find("xc7a")? + 4 + digits.len()..];
+    (!digits.is_empty() && rest.starts_with('t')).then(|| format!("{digits}T"))
+}
+
+fn parse_hex(word: &str) -> Option<u16> {
+    let digits = word
+        .strip_prefix("0x")
+        .or_else(|| word.strip_prefix("0X"))
+        .unwrap_or(word);
+    u16::from_str_radix(digits, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::temp_dir::TempDir;
+
+    fn temporary_root() -> TempDir {
+        let root = TempDir::new("lint");
+        fs::create_dir_all(root.join("OpenOCD/flash")).unwrap();
+        fs::create_dir_all(root.join("OpenOCD/cpld")).unwrap();
+        fs::create_dir_all(root.join("OpenOCD/bit")).unwrap();
+        fs::write(root.join("OpenOCD/bit/bscan_spi_xc7a35t.bit"), b"bit").unwrap();
+        root
+    }
+
+    fn rules(findings: &[LintFinding]) -> Vec<(LintRule, usize)> {
+        findings
+            .iter()
+            .map(|finding| (finding.rule, finding.location.line))
+            .collect()
+    }
+
+    #[test]
+    fn bundled_configs_are_clean() {
+        assert_eq!(lint(Path::new("."), &LintTarget::bundled()), []);
+    }
+
+    #[test]
+    fn mispasted_flash_config_is_flagged() {
+        let root = temporary_root();
+        fs::write(
+            root.join("OpenOCD/cpld/xilinx-xc7.cfg"),
+            "proc fpga_program {} {\n    echo in\n}\nadapter speed 5000\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("OpenOCD/flash/xc7a75T.cfg"),
+            "adapter driver ch347\n\
+             ch347 vid_pid 0x0403 0x6014\n\
+             adapter speed 10000\n\
+             source OpenOCD/cpld/xilinx-xc7.cfg\n\
+             proc fpga_program {} {\n\
+             \x20   pld load 0 $proxy_bit\n\
+             }\n\
+             pld load 0 OpenOCD/bit/bscan_spi_xc7a35t.bit\n\
+             flash write_image erase FIRMWARE.bin 0x0 bin\n",
+        )
+        .unwrap();
+
+        let findings = lint(
+            root.path(),
+            &[LintTarget {
+                script: "OpenOCD/flash/xc7a75T.cfg".into(),
+                adapter: Some(AdapterKind::Ch347),
+                density: Some("75T".into()),
+            }],
+        );
+        let summary: Vec<_> = findings
+            .iter()
+            .map(|finding| {
+                (
+                    finding.rule,
+                    finding.location.script.as_str(),
+                    finding.location.line,
+                )
+            })
+            .collect();
+        let entry = "OpenOCD/flash/xc7a75T.cfg";
+        let sourced = "OpenOCD/cpld/xilinx-xc7.cfg";
+        assert_eq!(
+            summary,
+            [
+                (LintRule::DuplicateProc, sourced, 1),
+                (LintRule::AdapterSpeedConflict, sourced, 4),
+                (LintRule::UsbId, entry, 2),
+                (LintRule::BitstreamDensity, entry, 8),
+                (LintRule::MissingExit, entry, 9),
+            ]
+        );
+
+        let density = findings
+            .iter()
+            .find(|finding| finding.rule == LintRule::BitstreamDensity)
+            .unwrap();
+        assert_eq!(
+            density.describe(&Language::English),
+            "loads OpenOCD/bit/bscan_spi_xc7a35t.bit (35T) in a 75T config"
+        );
+    }
+
+    #[test]
+    fn user_config_takes_its_adapter_from_the_driver() {
+        let root = temporary_root();
+        let script = root.join("custom_xc7a35t.cfg");
+        fs::write(
+            &script,
+            "adapter driver ftdi\nftdi vid_pid 0x1a86 0x55dd\npld load 0 OpenOCD/bit/bscan_spi_xc7a35t.bit\nshutdown\n",
+        )
+        .unwrap();
+
+        let target = LintTarget::user(&script.to_string_lossy());
+        assert_eq!(target.density.as_deref(), Some("35T"));
+        assert_eq!(rules(&lint(root.path(), &[target])), [(LintRule::UsbId, 2)]);
+    }
+
+    #[test]
+    fn unreadable_entry_scripts_are_flagged() {
+        let root = temporary_root();
+
+        let findings = lint(
+            root.path(),
+            &[
+                LintTarget::user("does/not/exist.cfg"),
+                LintTarget::user("."),
+            ],
+        );
+        assert_eq!(
+            rules(&findings),
+            [
+                (LintRule::UnreadableScript, 1),
+                (LintRule::UnreadableScript, 1)
+            ]
+        );
+        assert_eq!(findings[0].location.script, ".");
+        assert_eq!(findings[1].location.script, "does/not/exist.cfg");
+    }
+
+    #[test]
+    fn outlying_speeds_are_flagged_against_the_other_profiles() {
+        let root = temporary_root();
+        for (name, speed) in [("a", 10000), ("b", 10000), ("c", 4000)] {
+            fs::write(
+                root.join(format!("OpenOCD/flash/{name}.cfg")),
+                format!("adapter speed {speed}\nexit\n"),
+            )
+            .unwrap();
+        }
+        let targets: Vec<LintTarget> = ["a", "b", "c"]
+            .iter()
+            .map(|name| LintTarget {
+                script: format!("OpenOCD/flash/{name}.cfg"),
+                adapter: Some(AdapterKind::Ftdi),
+                density: None,
+            })
+            .collect();
+
+        let findings = lint(root.path(), &targets);
+        assert_eq!(rules(&findings), [(LintRule::AdapterSpeedOutlier, 1)]);
+        assert_eq!(findings[0].location.script, "OpenOCD/flash/c.cfg");
+    }
+}
@@ -0,0 +1,357 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// What an OpenOCD directive pulls in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReferenceKind {
+    /// `source [find] <script>`
+    Source,
+    /// `pld load <device> <bitstream>`
+    Bitstream,
+    /// `flash write_image [erase] [unlock] <image> ...`
+    FlashImage,
+    /// `log_output <file>`; only its folder has to exist.
+    LogOutput,
+}
+
+impl fmt::Display for ReferenceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Source => "source",
+            Self::Bitstream => "pld load",
+            Self::FlashImage => "flash write_image",
+            Self::LogOutput => "log_output",
+        })
+    }
+}
+
+/// A line of a script, as `OpenOCD/flash/xc7a35T.cfg:20`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ScriptLocation {
+    pub script: String,
+    pub line: usize,
+}
+
+impl fmt::Display for ScriptLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.script, self.line)
+    }
+}
+
+/// A file named by a directive, as written in the script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptReference {
+    pub kind: ReferenceKind,
+    pub target: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingReference {
+    /// Relative to the payload root, with `/` separators.
+    pub path: String,
+    pub kind: ReferenceKind,
+    /// `None` for an entry script that does not exist.
+    pub location: Option<ScriptLocation>,
+}
+
+/// Every script reachable from the profiles' entry scripts and the files
+/// they reference. Paths are relative to the payload root.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DependencyGraph {
+    pub edges: BTreeMap<String, Vec<(ReferenceKind, String)>>,
+    pub missing: Vec<MissingReference>,
+}
+
+impl DependencyGraph {
+    /// Follows the entry scripts under `root`. References to `generated`
+    /// files, which the app writes before OpenOCD starts, and ones computed
+    /// at runtime (`$var`, `[cmd]`) are not checked.
+    pub fn scan(root: &Path, entries: &[&str], generated: &[&str]) -> Self {
+        let mut graph = Self::default();
+        let mut pending: Vec<String> = Vec::new();
+        for entry in entries {
+            let path = normalize(Path::new(entry));
+            if root.join(&path).is_file() {
+                pending.push(path);
+            } else {
+                graph.report(path, ReferenceKind::Source, None);
+            }
+        }
+
+        while let Some(script) = pending.pop() {
+            if graph.edges.contains_key(&script) {
+                continue;
+            }
+            let text = fs::read(root.join(&script))
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                .unwrap_or_default();
+            let mut edges = Vec::new();
+
+            for reference in parse_references(&text) {
+                if is_computed(&reference.target) || generated.contains(&reference.target.as_str())
+                {
+                    continue;
+                }
+                let location = ScriptLocation {
+                    script: script.clone(),
+                    line: reference.line,
+                };
+                let Some(path) = resolve(root, &script, &reference) else {
+                    let path = normalize(Path::new(&reference.target));
+                    graph.report(path, reference.kind, Some(location));
+                    continue;
+                };
+                if reference.kind == ReferenceKind::Source {
+                    pending.push(path.clone());
+                }
+                edges.push((reference.kind, path));
+            }
+            graph.edges.insert(script, edges);
+        }
+        graph.missing.sort_by(|a, b| a.location.cmp(&b.location));
+        graph
+    }
+
+    fn report(&mut self, path: String, kind: ReferenceKind, location: Option<ScriptLocation>) {
+        self.missing.push(MissingReference {
+            path,
+            kind,
+            location,
+        });
+    }
+}
+
+/// One command of a script, split into words with quotes and braces
+/// removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptCommand {
+    /// 1-based line the command starts on.
+    pub line: usize,
+    pub words: Vec<String>,
+    /// False inside a `proc` body or other braced block.
+    pub top_level: bool,
+}
+
+/// The commands of a script. Comments are skipped, `\` continuations
+/// joined and `;` separated commands split.
+pub fn parse_commands(text: &str) -> Vec<ScriptCommand> {
+    let mut commands = Vec::new();
+    let mut pending = String::new();
+    let mut start_line = 0;
+    let mut depth = 0usize;
+
+    for (index, line) in text.lines().enumerate() {
+        if pending.is_empty() {
+            start_line = index + 1;
+        }
+        if let Some(continued) = line.strip_suffix('\\') {
+            pending.push_str(continued);
+            pending.push(' ');
+            continue;
+        }
+        pending.push_str(line);
+        let joined = std::mem::take(&mut pending);
+        for part in joined.split(';') {
+            let part = part.trim();
+            if part.starts_with('#') {
+                continue;
+            }
+            let top_level = depth == 0;
+            for c in part.chars() {
+                match c {
+                    '{' => depth += 1,
+                    '}' => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+            }
+            let words: Vec<String> = part
+                .split_whitespace()
+                .map(|word| word.trim_matches(|c| matches!(c, '"' | '{' | '}' | '[' | ']')))
+                .filter(|word| !word.is_empty())
+                .map(str::to_string)
+                .collect();
+            if !words.is_empty() {
+                commands.push(ScriptCommand {
+                    line: start_line,
+                    words,
+                    top_level,
+                });
+            }
+        }
+    }
+    commands
+}
+
+/// The file references in a script, one per directive.
+pub fn parse_references(text: &str) -> Vec<ScriptReference> {
+    parse_commands(text)
+        .iter()
+        .filter_map(reference_of)
+        .collect()
+}
+
+fn reference_of(command: &ScriptCommand) -> Option<ScriptReference> {
+    let words: Vec<&str> = command.words.iter().map(String::as_str).collect();
+    let (kind, target) = match words.as_slice() {
+        ["source", "find", target, ..] | ["source", target, ..] => (ReferenceKind::Source, *target),
+        ["pld", "load", _device, target, ..] => (ReferenceKind::Bitstream, *target),
+        ["flash", "write_image", rest @ ..] => (
+            ReferenceKind::FlashImage,
+            *rest
+                .iter()
+                .find(|word| !matches!(**word, "erase" | "unlock"))?,
+        ),
+        ["log_output", target, ..] => (ReferenceKind::LogOutput, *target),
+        _ => return None,
+    };
+    Some(ScriptReference {
+        kind,
+        target: target.to_string(),
+        line: command.line,
+    })
+}
+
+fn is_computed(target: &str) -> bool {
+    target.contains(['$', '['])
+}
+
+/// Resolves a reference against the payload root, where OpenOCD runs, then
+/// against the folder of the script that makes it.
+fn resolve(root: &Path, script: &str, reference: &ScriptReference) -> Option<String> {
+    let target = Path::new(&reference.target);
+    let script_dir = Path::new(script).parent().unwrap_or(Path::new(""));
+    let candidates = [PathBuf::from(target), script_dir.join(target)];
+
+    candidates.iter().map(|path| normalize(path)).find(|path| {
+        let full = root.join(path);
+        match reference.kind {
+            ReferenceKind::LogOutput => full
+                .parent()
+                .is_none_or(|dir| dir.as_os_str().is_empty() || dir.is_dir()),
+            _ => full.is_file(),
+        }
+    })
+}
+
+/// `a/./b/../c` becomes `a/c`, with `/` separators. Absolute paths stay
+/// absolute.
+fn normalize(path: &Path) -> String {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::temp_dir::TempDir;
+
+    #[test]
+    fn directives_are_parsed_with_their_lines() {
+        let script = "\
+# source OpenOCD/old.cfg
+source [find OpenOCD/cpld/xilinx-xc7.cfg]
+pld load 0 \"OpenOCD/bit/bscan_spi_xc7a35t.bit\"
+flash write_image erase unlock FIRMWARE.bin 0x0 bin
+proc load {} { pld load $chain_id $proxy_bit }
+log_output \\
+    logs/openocd.log; echo done
+";
+        let references = parse_references(script);
+        let summary: Vec<(ReferenceKind, &str, usize)> = references
+            .iter()
+            .map(|reference| (reference.kind, reference.target.as_str(), reference.line))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (ReferenceKind::Source, "OpenOCD/cpld/xilinx-xc7.cfg", 2),
+                (
+                    ReferenceKind::Bitstream,
+                    "OpenOCD/bit/bscan_spi_xc7a35t.bit",
+                    3
+                ),
+                (ReferenceKind::FlashImage, "FIRMWARE.bin", 4),
+                (ReferenceKind::LogOutput, "logs/openocd.log", 6),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_references_are_reported_where_they_are_made() {
+        let root = TempDir::new("scripts");
+        fs::create_dir_all(root.join("OpenOCD/flash")).unwrap();
+        fs::create_dir_all(root.join("OpenOCD/cpld")).unwrap();
+        fs::write(
+            root.join("OpenOCD/flash/xc7a35T.cfg"),
+            "source OpenOCD/cpld/xilinx-xc7.cfg\n\
+             source OpenOCD/cpld/jtagspi.cfg\n\
+             pld load 0 OpenOCD/bit/bscan_spi_xc7a35t.bit\n\
+             flash write_image erase FIRMWARE.bin 0x0 bin\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("OpenOCD/cpld/xilinx-xc7.cfg"),
+            "source ../cpld/jtagspi.cfg\n",
+        )
+        .unwrap();
+        fs::write(root.join("OpenOCD/cpld/jtagspi.cfg"), "# proxy\n").unwrap();
+
+        let graph = DependencyGraph::scan(
+            root.path(),
+            &["OpenOCD/flash/xc7a35T.cfg", "OpenOCD/DNA/init_347.cfg"],
+            &["FIRMWARE.bin"],
+        );
+
+        assert_eq!(
+            graph.edges.keys().collect::<Vec<_>>(),
+            [
+                "OpenOCD/cpld/jtagspi.cfg",
+                "OpenOCD/cpld/xilinx-xc7.cfg",
+                "OpenOCD/flash/xc7a35T.cfg"
+            ]
+        );
+        // `../cpld/jtagspi.cfg` resolves against the sourcing script's folder.
+        assert_eq!(
+            graph.edges["OpenOCD/cpld/xilinx-xc7.cfg"],
+            [(
+                ReferenceKind::Source,
+                "OpenOCD/cpld/jtagspi.cfg".to_string()
+            )]
+        );
+        assert_eq!(
+            graph.missing,
+            [
+                MissingReference {
+                    path: "OpenOCD/DNA/init_347.cfg".into(),
+                    kind: ReferenceKind::Source,
+                    location: None,
+                },
+                MissingReference {
+                    path: "OpenOCD/bit/bscan_spi_xc7a35t.bit".into(),
+                    kind: ReferenceKind::Bitstream,
+                    location: Some(ScriptLocation {
+                        script: "OpenOCD/flash/xc7a35T.cfg".into(),
+                        line: 3,
+                    }),
+                },
+            ]
+        );
+    }
+}
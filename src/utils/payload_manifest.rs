@@ -0,0 +1,174 @@
+use crate::utils::firmware_manifest::sha256_file;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Written next to the executable when a release is packaged.
+pub const PAYLOAD_MANIFEST_FILE: &str = "payload_manifest.json";
+/// Folders whose every file is listed in the manifest.
+pub const PAYLOAD_DIRS: [&str; 2] = ["OpenOCD", "tools"];
+const MANIFEST_VERSION: u32 = 1;
+
+/// Expected size and SHA-256 of every file in the bundled payload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayloadManifest {
+    pub version: u32,
+    pub files: Vec<PayloadEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayloadEntry {
+    /// Relative to the manifest, with `/` separators.
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// How a payload file differs from its manifest entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadState {
+    Intact,
+    Missing,
+    /// Truncated, padded or unreadable.
+    Corrupt,
+    /// The right size, but different content.
+    Modified,
+}
+
+impl PayloadManifest {
+    /// Lists every file under the payload folders of `root`; a folder that
+    /// does not exist is skipped.
+    pub fn generate(root: &Path) -> io::Result<Self> {
+        let mut files = Vec::new();
+        for dir in PAYLOAD_DIRS {
+            let dir = root.join(dir);
+            if dir.is_dir() {
+                collect_entries(root, &dir, &mut files)?;
+            }
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(Self {
+            version: MANIFEST_VERSION,
+            files,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path).map_err(|error| error.to_string())?;
+        let manifest: Self = serde_json::from_str(&contents).map_err(|error| error.to_string())?;
+        if manifest.version > MANIFEST_VERSION {
+            return Err(format!(
+                "manifest version {} is newer than this build supports ({MANIFEST_VERSION})",
+                manifest.version
+            ));
+        }
+        Ok(manifest)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, json)
+    }
+}
+
+impl PayloadEntry {
+    /// Compares the file under `root` with this entry. The size is checked
+    /// first, so a truncated file is reported without hashing it.
+    pub fn verify(&self, root: &Path) -> PayloadState {
+        let path = root.join(&self.path);
+        let size = match fs::metadata(&path) {
+            Ok(metadata) => metadata.len(),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                return PayloadState::Missing;
+            }
+            Err(_) => return PayloadState::Corrupt,
+        };
+        if size != self.size {
+            return PayloadState::Corrupt;
+        }
+        match sha256_file(&path) {
+            Ok(actual) if actual.eq_ignore_ascii_case(&self.sha256) => PayloadState::Intact,
+            Ok(_) => PayloadState::Modified,
+            Err(_) => PayloadState::Corrupt,
+        }
+    }
+}
+
+fn collect_entries(root: &Path, dir: &Path, files: &mut Vec<PayloadEntry>) -> io::Result<()> {
+    let mut entries: Vec<_> = fs::read_dir(dir)?.collect::<Result<_, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_entries(root, &path, files)?;
+        } else if file_type.is_file() {
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            files.push(PayloadEntry {
+                path: relative
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/"),
+                size: entry.metadata()?.len(),
+                sha256: sha256_file(&path)?,
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::temp_dir::TempDir;
+
+    #[test]
+    fn damaged_payload_files_are_told_apart() {
+        let root = TempDir::new("payload");
+        fs::create_dir_all(root.join("OpenOCD/bit")).unwrap();
+        fs::create_dir_all(root.join("tools")).unwrap();
+        fs::write(root.join("OpenOCD/openocd.exe"), b"openocd").unwrap();
+        fs::write(root.join("OpenOCD/bit/bscan_spi_xc7a35t.bit"), b"bitstream").unwrap();
+        fs::write(root.join("tools/zadig.exe"), b"zadig").unwrap();
+        fs::write(root.join("tools/readme.txt"), b"readme").unwrap();
+
+        let manifest = PayloadManifest::generate(root.path()).unwrap();
+        let paths: Vec<&str> = manifest
+            .files
+            .iter()
+            .map(|file| file.path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "OpenOCD/bit/bscan_spi_xc7a35t.bit",
+                "OpenOCD/openocd.exe",
+                "tools/readme.txt",
+                "tools/zadig.exe"
+            ]
+        );
+        let manifest_path = root.join(PAYLOAD_MANIFEST_FILE);
+        manifest.save(&manifest_path).unwrap();
+        assert_eq!(PayloadManifest::load(&manifest_path).unwrap(), manifest);
+
+        fs::write(root.join("OpenOCD/bit/bscan_spi_xc7a35t.bit"), b"bits").unwrap();
+        fs::write(root.join("OpenOCD/openocd.exe"), b"OPENOCD").unwrap();
+        fs::remove_file(root.join("tools/readme.txt")).unwrap();
+        let states: Vec<PayloadState> = manifest
+            .files
+            .iter()
+            .map(|file| file.verify(root.path()))
+            .collect();
+        assert_eq!(
+            states,
+            [
+                PayloadState::Corrupt,
+                PayloadState::Modified,
+                PayloadState::Missing,
+                PayloadState::Intact
+            ]
+        );
+    }
+}
@@ -0,0 +1,15 @@
+//! Ownership of the process trees started for one operation.
+//!
+//! Spawn with [`contain_command`], then hand the child to
+//! [`ProcessJob::assign_and_resume`]. Every descendant stays in the job and
+//! can be terminated together, including when the job is dropped.
+
+#[cfg(unix)]
+mod unix;
+#[cfg(windows)]
+mod windows;
+
+#[cfg(unix)]
+pub use unix::{ProcessJob, contain_command, hide_console};
+#[cfg(windows)]
+pub use windows::{ProcessJob, contain_command, hide_console};
@@ -0,0 +1,254 @@
+use std::io;
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Console tools open no window of their own on Unix.
+pub fn hide_console(_command: &mut Command) {}
+
+/// Starts the process as the leader of a new process group. The group is set
+/// up during spawn, so the process never runs outside its [`ProcessJob`].
+pub fn contain_command(command: &mut Command) {
+    command.process_group(0);
+}
+
+/// Owns the process groups of the processes assigned to it and kills every
+/// member when dropped, like a kill-on-close Windows Job Object.
+///
+/// Descendants inherit the group unless they deliberately call `setsid` or
+/// `setpgid`, which OpenOCD and memflow do not.
+pub struct ProcessJob {
+    groups: Mutex<Vec<libc::pid_t>>,
+}
+
+impl ProcessJob {
+    pub fn new_kill_on_close() -> Result<Self, String> {
+        Ok(Self {
+            groups: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Takes ownership of the group led by a child spawned through
+    /// [`contain_command`]. Unix processes are never created suspended, so
+    /// there is nothing to resume.
+    pub fn assign_and_resume(&self, child: &Child) -> Result<(), String> {
+        let pid = libc::pid_t::try_from(child.id())
+            .map_err(|_| format!("Process ID {} is out of range", child.id()))?;
+        // SAFETY: getpgid takes a plain process ID. The child is not reaped
+        // yet, so the ID cannot refer to another process.
+        let group = unsafe { libc::getpgid(pid) };
+        if group == -1 {
+            return Err(format!(
+                "Failed to inspect process group: {}",
+                io::Error::last_os_error()
+            ));
+        }
+        if group != pid {
+            return Err(format!(
+                "Process {pid} was not started in its own process group"
+            ));
+        }
+
+        self.groups.lock().unwrap().push(group);
+        Ok(())
+    }
+
+    /// Confirms that no member of an owned group is still running, forcibly
+    /// terminating any descendants that outlived the direct child.
+    pub fn terminate_remaining_and_wait(
+        &self,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<(), String> {
+        if !self.has_running_members()? {
+            return Ok(());
+        }
+
+        self.terminate_and_wait(timeout, poll_interval)
+    }
+
+    /// Kills every owned group and confirms that no member is still running.
+    pub fn terminate_and_wait(
+        &self,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<(), String> {
+        self.signal_groups(libc::SIGKILL)?;
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            if !self.has_running_members()? {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err(format!(
+                    "Process group did not terminate within {} seconds",
+                    timeout.as_secs_f32()
+                ));
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            thread::sleep(poll_interval.max(Duration::from_millis(1)).min(remaining));
+        }
+    }
+
+    fn signal_groups(&self, signal: libc::c_int) -> Result<(), String> {
+        for &group in self.groups.lock().unwrap().iter() {
+            // SAFETY: killpg takes plain integers and touches no memory.
+            if unsafe { libc::killpg(group, signal) } == 0 {
+                continue;
+            }
+            let error = io::Error::last_os_error();
+            if error.raw_os_error() != Some(libc::ESRCH) {
+                return Err(format!(
+                    "Failed to terminate process group {group}: {error}"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Forgets groups without a running member, so an ID the system reuses
+    /// later is never signalled.
+    fn has_running_members(&self) -> Result<bool, String> {
+        let mut groups = self.groups.lock().unwrap();
+        let mut inspection_error = None;
+        groups.retain(|&group| {
+            group_is_running(group).unwrap_or_else(|error| {
+                inspection_error.get_or_insert(error);
+                true
+            })
+        });
+
+        match inspection_error {
+            Some(error) => Err(error),
+            None => Ok(!groups.is_empty()),
+        }
+    }
+}
+
+impl Drop for ProcessJob {
+    fn drop(&mut self) {
+        let _ = self.signal_groups(libc::SIGKILL);
+    }
+}
+
+/// Whether any member of `group` can still run. A terminated direct child
+/// stays in its group as a zombie until its owner reaps it, so zombies are not
+/// counted, matching a Job Object's active process count.
+#[cfg(target_os = "linux")]
+fn group_is_running(group: libc::pid_t) -> Result<bool, String> {
+    let entries =
+        std::fs::read_dir("/proc").map_err(|error| format!("Failed to list processes: {error}"))?;
+
+    for entry in entries.flatten() {
+        let is_process = entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| name.bytes().all(|byte| byte.is_ascii_digit()));
+        if !is_process {
+            continue;
+        }
+        // The process may exit between listing and reading.
+        let Ok(stat) = std::fs::read_to_string(entry.path().join("stat")) else {
+            continue;
+        };
+        if parse_stat(&stat).is_some_and(|(state, pgrp)| pgrp == group && !is_dead(state)) {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Without `/proc` an unreaped direct child still counts as running, so
+/// owners must reap it before the group can be confirmed empty.
+#[cfg(not(target_os = "linux"))]
+fn group_is_running(group: libc::pid_t) -> Result<bool, String> {
+    // SAFETY: Signal 0 only probes for existence and touches no memory.
+    if unsafe { libc::killpg(group, 0) } == 0 {
+        return Ok(true);
+    }
+
+    let error = io::Error::last_os_error();
+    match error.raw_os_error() {
+        Some(libc::ESRCH) => Ok(false),
+        Some(libc::EPERM) => Ok(true),
+        _ => Err(format!("Failed to inspect process group {group}: {error}")),
+    }
+}
+
+/// Reads the state and process group from `/proc/<pid>/stat`.
+#[cfg(target_os = "linux")]
+fn parse_stat(stat: &str) -> Option<(char, libc::pid_t)> {
+    // The command name is parenthesized and may itself contain spaces or
+    // parentheses, so fields are counted from the last `)`.
+    let (_, fields) = stat.rsplit_once(')')?;
+    let mut fields = fields.split_whitespace();
+    let state = fields.next()?.chars().next()?;
+    let _parent = fields.next()?;
+    let group = fields.next()?.parse().ok()?;
+    Some((state, group))
+}
+
+#[cfg(target_os = "linux")]
+fn is_dead(state: char) -> bool {
+    matches!(state, 'Z' | 'X' | 'x')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Stdio;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn stat_fields_are_read_after_the_command_name() {
+        assert_eq!(
+            parse_stat("4242 (open) ocd) S 1 4242 4242 0 -1 4194560"),
+            Some(('S', 4242))
+        );
+        assert_eq!(parse_stat("4242 (truncated"), None);
+    }
+
+    #[test]
+    fn terminating_the_job_kills_background_descendants() {
+        let mut command = Command::new("sh");
+        command
+            .args(["-c", "sleep 30 & sleep 30"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        contain_command(&mut command);
+        let mut child = command.spawn().unwrap();
+        let job = ProcessJob::new_kill_on_close().unwrap();
+        job.assign_and_resume(&child).unwrap();
+        let group = libc::pid_t::try_from(child.id()).unwrap();
+
+        let started_at = Instant::now();
+        job.terminate_and_wait(Duration::from_secs(2), Duration::from_millis(10))
+            .unwrap();
+        child.wait().unwrap();
+
+        assert!(started_at.elapsed() < Duration::from_secs(3));
+        assert!(!group_is_running(group).unwrap());
+        assert!(!job.has_running_members().unwrap());
+    }
+
+    #[test]
+    fn uncontained_children_are_rejected() {
+        let mut child = Command::new("sleep").arg("30").spawn().unwrap();
+
+        let error = ProcessJob::new_kill_on_close()
+            .unwrap()
+            .assign_and_resume(&child)
+            .unwrap_err();
+
+        assert!(error.contains("own process group"), "{error}");
+        child.kill().unwrap();
+        child.wait().unwrap();
+    }
+}
@@ -1,7 +1,8 @@
 use std::io;
 use std::mem::{size_of, zeroed};
 use std::os::windows::io::{AsRawHandle, FromRawHandle, OwnedHandle};
-use std::process::Child;
+use std::os::windows::process::CommandExt;
+use std::process::{Child, Command};
 use std::ptr;
 use std::thread;
 use std::time::{Duration, Instant};
@@ -21,10 +22,23 @@ use winapi::um::winnt::{
     JobObjectExtendedLimitInformation, THREAD_SUSPEND_RESUME,
 };
 
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
 /// Prevents a newly created process from executing until its primary thread is
 /// explicitly resumed. This gives callers time to assign it to a Job Object
 /// before it can touch hardware or create descendants.
-pub const CREATE_SUSPENDED: u32 = 0x0000_0004;
+const CREATE_SUSPENDED: u32 = 0x0000_0004;
+
+/// Keeps a console tool from opening its own window.
+pub fn hide_console(command: &mut Command) {
+    command.creation_flags(CREATE_NO_WINDOW);
+}
+
+/// Creates the process suspended and windowless so it can be assigned to a
+/// [`ProcessJob`] before it runs.
+pub fn contain_command(command: &mut Command) {
+    command.creation_flags(CREATE_NO_WINDOW | CREATE_SUSPENDED);
+}
 
 /// Owns a Windows Job Object that terminates every assigned process when the
 /// final job handle is closed, including during application shutdown.
@@ -0,0 +1,200 @@
+use crate::device_programmer::{FlashingOption, SignaturePolicy, WatchdogConfig};
+use crate::utils::app_dirs;
+use crate::utils::json_store::JsonFormat;
+use crate::utils::localization::Language;
+use crate::utils::logger::Logger;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+const SETTINGS_FILE: &str = "settings.json";
+const SETTINGS_FORMAT: JsonFormat = JsonFormat {
+    label: "Settings",
+    version: 1,
+    fallback: "using the defaults",
+};
+
+/// Preferences restored on the next launch. Fields missing from the file,
+/// e.g. ones added after it was written, take their default.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub language: Language,
+    /// Profile of the last flash or DNA read.
+    pub last_option: Option<FlashingOption>,
+    pub last_firmware: Option<PathBuf>,
+    pub cleanup_enabled: bool,
+    pub debug_mode: bool,
+    pub log_expanded: bool,
+    /// `None` keeps each profile's own `adapter speed`.
+    pub jtag_speed_khz: Option<u32>,
+    /// Name of the selected PCILeech test profile.
+    pub pcileech_profile: Option<String>,
+    /// `None` until the user picks one; the default then follows whether
+    /// trusted keys are installed.
+    pub signature_policy: Option<SignaturePolicy>,
+    /// Idle and total time limits of flash and DNA read runs.
+    pub watchdog: WatchdogConfig,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            language: Language::English,
+            last_option: None,
+            last_firmware: None,
+            cleanup_enabled: false,
+            debug_mode: false,
+            log_expanded: false,
+            jtag_speed_khz: None,
+            pcileech_profile: None,
+            signature_policy: None,
+            watchdog: WatchdogConfig::default(),
+        }
+    }
+}
+
+/// The user's settings, written back whenever they change.
+pub struct SettingsStore {
+    path: Option<PathBuf>,
+    settings: Settings,
+    logger: Logger,
+}
+
+impl SettingsStore {
+    /// Opens the settings in the per-user config directory, falling back to
+    /// the working directory when no config directory is available.
+    pub fn open_default(logger: Logger) -> Self {
+        let path = app_dirs::config_dir()
+            .map(|dir| dir.join(SETTINGS_FILE))
+            .unwrap_or_else(|| PathBuf::from(SETTINGS_FILE));
+        Self::open(path, logger)
+    }
+
+    pub fn open(path: PathBuf, logger: Logger) -> Self {
+        let settings = SETTINGS_FORMAT.load(&path, &logger).unwrap_or_default();
+        Self {
+            path: Some(path),
+            settings,
+            logger,
+        }
+    }
+
+    /// Settings that are never written to disk.
+    #[cfg(test)]
+    pub fn in_memory(logger: Logger) -> Self {
+        Self {
+            path: None,
+            settings: Settings::default(),
+            logger,
+        }
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// Replaces the settings, saving them if anything changed.
+    pub fn update(&mut self, settings: Settings) {
+        if settings == self.settings {
+            return;
+        }
+        self.settings = settings;
+        self.save();
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if let Err(error) = SETTINGS_FORMAT.save(path, &self.settings) {
+            self.logger.error(format!(
+                "Failed to save settings to {}: {error}",
+                path.display()
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device_programmer::WatchdogBudget;
+    use crate::utils::temp_dir::TempDir;
+    use std::fs;
+    use std::time::Duration;
+
+    #[test]
+    fn saved_settings_survive_a_restart() {
+        let dir = TempDir::new("settings");
+        let path = dir.join(SETTINGS_FILE);
+        let logger = Logger::new("SettingsTest");
+
+        let mut store = SettingsStore::open(path.clone(), logger.clone());
+        assert_eq!(store.settings(), &Settings::default());
+        let settings = Settings {
+            language: Language::German,
+            last_option: Some(FlashingOption::RS232_75T),
+            last_firmware: Some(PathBuf::from("firmware/card.bin")),
+            cleanup_enabled: true,
+            debug_mode: true,
+            log_expanded: true,
+            jtag_speed_khz: Some(4000),
+            pcileech_profile: Some("Lab".to_string()),
+            signature_policy: Some(SignaturePolicy::Block),
+            watchdog: WatchdogConfig {
+                flash: WatchdogBudget {
+                    no_output: Some(Duration::from_secs(300)),
+                    total_runtime: None,
+                },
+                ..WatchdogConfig::default()
+            },
+        };
+        store.update(settings.clone());
+
+        let reopened = SettingsStore::open(path.clone(), logger);
+        assert_eq!(reopened.settings(), &settings);
+    }
+
+    #[test]
+    fn older_files_fill_in_defaults() {
+        let dir = TempDir::new("settings-old");
+        let path = dir.join(SETTINGS_FILE);
+        fs::write(&path, r#"{ "version": 1, "language": "Arabic" }"#).unwrap();
+
+        let store = SettingsStore::open(path.clone(), Logger::new("SettingsTest"));
+        assert_eq!(
+            store.settings(),
+            &Settings {
+                language: Language::Arabic,
+                ..Settings::default()
+            }
+        );
+    }
+
+    #[test]
+    fn unreadable_settings_fall_back_to_defaults_and_are_kept() {
+        let logger = Logger::new("SettingsTest");
+        for (name, contents) in [
+            ("settings-corrupt", r#"{ "version": 1, "language": "#),
+            (
+                "settings-newer",
+                r#"{ "version": 99, "language": "German" }"#,
+            ),
+        ] {
+            let dir = TempDir::new(name);
+            let path = dir.join(SETTINGS_FILE);
+            fs::write(&path, contents).unwrap();
+
+            let store = SettingsStore::open(path.clone(), logger.clone());
+            assert_eq!(store.settings(), &Settings::default());
+            let backup = path.with_extension("json.bak");
+            assert_eq!(fs::read_to_string(&backup).unwrap(), contents);
+        }
+        assert!(
+            logger
+                .get_entries()
+                .iter()
+                .any(|entry| entry.message.contains("is corrupt"))
+        );
+    }
+}
@@ -6,6 +6,7 @@ use winapi::ctypes::c_void;
 use winapi::shared::minwindef::{BOOL, LPARAM, TRUE};
 use winapi::shared::windef::HWND;
 use winapi::um::dwmapi::DwmSetWindowAttribute;
+use winapi::um::wincon::{ATTACH_PARENT_PROCESS, AttachConsole};
 use winapi::um::winuser::{
     EnumWindows, GWL_STYLE, GetWindowLongA, GetWindowTextA, SWP_FRAMECHANGED, SWP_NOMOVE,
     SWP_NOSIZE, SWP_NOZORDER, SetWindowLongA, SetWindowPos, WS_MAXIMIZEBOX,
@@ -156,3 +157,14 @@ pub fn setup_window_controls() {
         }
     });
 }
+
+/// Attaches to the console of the process that started the app, so the
+/// command-line modes can print from a release build, which has no console
+/// of its own. Does nothing when started without a console, e.g. from Explorer.
+pub fn attach_parent_console() {
+    // SAFETY: AttachConsole takes no pointers and fails harmlessly when the
+    // parent has no console or this process already has one.
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
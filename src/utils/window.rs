@@ -6,12 +6,15 @@ pub enum WindowSizeType {
     MissingFiles,
     OperationSelection,
     FileSelection,
+    FirmwareLocations,
     FlashOptionSelection,
     ReadOptionSelection,
     FlashingProgress { log_expanded: bool },
     OperationResult { log_expanded: bool },
+    DnaResult { log_expanded: bool },
     Drivers,
     PcileechTest,
+    DnaHistory,
 }
 
 pub const WINDOW_WIDTH: f32 = 600.0;
@@ -20,25 +23,29 @@ pub const WINDOW_HEIGHT_INITIAL: f32 = 250.0;
 pub const WINDOW_HEIGHT_FILE_CHECK: f32 = 330.0;
 pub const WINDOW_HEIGHT_MISSING_FILES: f32 = 600.0;
 
-pub const WINDOW_HEIGHT_OPERATION_SELECT: f32 = 670.0;
+pub const WINDOW_HEIGHT_OPERATION_SELECT: f32 = 790.0;
 
-pub const WINDOW_HEIGHT_FLASH_FILE_SELECT: f32 = 360.0;
+pub const WINDOW_HEIGHT_FLASH_FILE_SELECT: f32 = 420.0;
+pub const WINDOW_HEIGHT_FIRMWARE_LOCATIONS: f32 = 560.0;
 
-pub const WINDOW_HEIGHT_FLASH_OPTION_SELECT: f32 = 820.0;
-pub const WINDOW_HEIGHT_READ_OPTION_SELECT: f32 = 530.0;
+pub const WINDOW_HEIGHT_FLASH_OPTION_SELECT: f32 = 1064.0;
+pub const WINDOW_HEIGHT_READ_OPTION_SELECT: f32 = 704.0;
 
 // The progress screen (spinner + technical info) is tall on its own, so it
 // keeps the old combined-with-log height even with the log collapsed.
-pub const WINDOW_HEIGHT_FLASHING_PROGRESS: f32 = 725.0;
+pub const WINDOW_HEIGHT_FLASHING_PROGRESS: f32 = 750.0;
 // Result screens are shorter on average (icon + message + action buttons),
 // so this can sit lower than the progress screen once the log is collapsed.
-pub const WINDOW_HEIGHT_OPERATION_RESULT: f32 = 625.0;
+pub const WINDOW_HEIGHT_OPERATION_RESULT: f32 = 645.0;
+// DNA results add the representation table and export buttons.
+pub const WINDOW_HEIGHT_DNA_RESULT: f32 = 860.0;
 // Extra room needed to fit the toggle button, scrollable entries, and clear
 // button once the operation log is expanded.
 pub const LOG_EXPANDED_EXTRA_HEIGHT: f32 = 220.0;
 
 pub const WINDOW_HEIGHT_DRIVERS: f32 = 470.0;
-pub const WINDOW_HEIGHT_PCILEECH_TEST: f32 = 575.0;
+pub const WINDOW_HEIGHT_PCILEECH_TEST: f32 = 740.0;
+pub const WINDOW_HEIGHT_DNA_HISTORY: f32 = 640.0;
 
 pub struct WindowManager {
     previous_height: Option<f32>,
@@ -227,6 +234,7 @@ impl WindowManager {
             WindowSizeType::MissingFiles => WINDOW_HEIGHT_MISSING_FILES,
             WindowSizeType::OperationSelection => WINDOW_HEIGHT_OPERATION_SELECT,
             WindowSizeType::FileSelection => WINDOW_HEIGHT_FLASH_FILE_SELECT,
+            WindowSizeType::FirmwareLocations => WINDOW_HEIGHT_FIRMWARE_LOCATIONS,
             WindowSizeType::FlashOptionSelection => WINDOW_HEIGHT_FLASH_OPTION_SELECT,
             WindowSizeType::ReadOptionSelection => WINDOW_HEIGHT_READ_OPTION_SELECT,
             WindowSizeType::FlashingProgress { log_expanded } => {
@@ -243,8 +251,16 @@ impl WindowManager {
                     WINDOW_HEIGHT_OPERATION_RESULT
                 }
             }
+            WindowSizeType::DnaResult { log_expanded } => {
+                if log_expanded {
+                    WINDOW_HEIGHT_DNA_RESULT + LOG_EXPANDED_EXTRA_HEIGHT
+                } else {
+                    WINDOW_HEIGHT_DNA_RESULT
+                }
+            }
             WindowSizeType::Drivers => WINDOW_HEIGHT_DRIVERS,
             WindowSizeType::PcileechTest => WINDOW_HEIGHT_PCILEECH_TEST,
+            WindowSizeType::DnaHistory => WINDOW_HEIGHT_DNA_HISTORY,
         }
     }
 